@@ -0,0 +1,114 @@
+use crate::*;
+
+// Activity totals of a brand during one epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochStats {
+    pub issued: i64,
+    pub redeemed: i64,
+    // Brand points given up in exchanges to other brands
+    pub exchanged_out: i64,
+    // Brand points received in exchanges from other brands
+    pub exchanged_in: i64,
+}
+
+// Mapping for activity totals: (Brand, Epoch) -> EpochStats
+#[contracttype]
+pub enum AnalyticsBook {
+    Stats(u64, u32),
+}
+
+// Length of an analytics epoch (about one day)
+pub const EPOCH_LEDGERS: u32 = 17_280;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View the activity totals of a brand during an epoch
+    /// Epochs with no activity report zeros
+    pub fn epoch_stats(env: Env, brand_id: u64, epoch: u32) -> EpochStats {
+        env.storage()
+            .instance()
+            .get(&AnalyticsBook::Stats(brand_id, epoch))
+            .unwrap_or(EpochStats {
+                issued: 0,
+                redeemed: 0,
+                exchanged_out: 0,
+                exchanged_in: 0,
+            })
+    }
+
+    /// View the epoch the current ledger falls in
+    pub fn current_epoch(env: Env) -> u32 {
+        env.ledger().sequence() / EPOCH_LEDGERS
+    }
+}
+
+pub(crate) fn track_issued(env: &Env, brand_id: u64, amount: i64) {
+    update_stats(env, brand_id, |stats| stats.issued += amount);
+}
+
+pub(crate) fn track_redeemed(env: &Env, brand_id: u64, amount: i64) {
+    update_stats(env, brand_id, |stats| stats.redeemed += amount);
+}
+
+pub(crate) fn track_exchanged(
+    env: &Env,
+    from_brand: u64,
+    to_brand: u64,
+    amount_in: i64,
+    amount_out: i64,
+) {
+    update_stats(env, from_brand, |stats| stats.exchanged_out += amount_in);
+    update_stats(env, to_brand, |stats| stats.exchanged_in += amount_out);
+}
+
+// Apply a change to a brand's totals for the current epoch
+fn update_stats(env: &Env, brand_id: u64, apply: impl FnOnce(&mut EpochStats)) {
+    let epoch = LoyaltyTokenExchange::current_epoch(env.clone());
+    let mut stats = LoyaltyTokenExchange::epoch_stats(env.clone(), brand_id, epoch);
+    apply(&mut stats);
+    env.storage()
+        .instance()
+        .set(&AnalyticsBook::Stats(brand_id, epoch), &stats);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_epoch_stats_bucket_activity_by_day() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let bakery = client.register_brand(&String::from_str(&env, "Bakery"));
+        client.set_brand_admin(&cafe, &Address::generate(&env));
+        let reward_id = client.add_reward(&cafe, &String::from_str(&env, "Latte"), &50, &10);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &cafe, &300, &None);
+        client.redeem_reward(&user, &reward_id, &None);
+        client.exchange_tokens(&user, &cafe, &bakery, &100);
+
+        env.ledger().set_sequence_number(EPOCH_LEDGERS);
+        assert_eq!(client.current_epoch(), 1);
+        client.issue_tokens(&user, &cafe, &40, &None);
+
+        let day0 = client.epoch_stats(&cafe, &0);
+        assert_eq!(
+            (day0.issued, day0.redeemed, day0.exchanged_out),
+            (300, 50, 100)
+        );
+        assert_eq!(client.epoch_stats(&bakery, &0).exchanged_in, 100);
+        assert_eq!(client.epoch_stats(&cafe, &1).issued, 40);
+        assert_eq!(client.epoch_stats(&cafe, &2).issued, 0);
+    }
+}
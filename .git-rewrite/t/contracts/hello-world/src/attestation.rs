@@ -0,0 +1,58 @@
+use crate::*;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Check whether a user holds at least `min_amount` points of a brand
+    /// Intended for partner contracts gating features on loyalty holdings
+    pub fn attest_balance(env: Env, user: Address, brand_id: u64, min_amount: i64) -> bool {
+        read_balance(&env, &user, brand_id) >= min_amount
+    }
+
+    /// Same as `attest_balance`, but also publishes the result as an event
+    /// attributed to `requester`, giving partners an on-chain audit trail
+    pub fn attest_balance_emit(
+        env: Env,
+        requester: Address,
+        user: Address,
+        brand_id: u64,
+        min_amount: i64,
+    ) -> bool {
+        requester.require_auth();
+
+        let attested = read_balance(&env, &user, brand_id) >= min_amount;
+        BalanceAttested {
+            brand_id,
+            user,
+            requester,
+            min_amount,
+            attested,
+        }
+        .publish(&env);
+        attested
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_attest_balance() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let partner = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Theatre"));
+        client.issue_tokens(&user, &brand_id, &500, &None);
+
+        assert!(client.attest_balance(&user, &brand_id, &500));
+        assert!(!client.attest_balance(&user, &brand_id, &501));
+        assert!(client.attest_balance_emit(&partner, &user, &brand_id, &100));
+        assert!(!client.attest_balance_emit(&partner, &user, &brand_id, &1000));
+    }
+}
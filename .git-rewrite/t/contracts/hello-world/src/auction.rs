@@ -0,0 +1,295 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a bid escrowed in an auction
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionBid {
+    pub bidder: Address,
+    pub amount: i64,
+}
+
+// Structure to store an auction of scarce reward stock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub auction_id: u64,
+    pub reward_id: u64,
+    pub brand_id: u64,
+    pub quantity: u32,
+    pub min_bid: i64,
+    // Bids are accepted from start_ledger up to (excluding) end_ledger
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub is_closed: bool,
+    pub winners: Vec<Address>,
+}
+
+// Mapping for auctions
+#[contracttype]
+pub enum AuctionBook {
+    Auction(u64),
+    // Bids ordered from highest to lowest, earlier bids first on ties
+    Bids(u64),
+}
+
+// Counter for auctions
+const AUCTION_COUNT: Symbol = symbol_short!("A_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Auction `quantity` units of a reward during a ledger window (brand admin only)
+    /// The units are taken out of the reward's stock until the auction closes
+    /// Returns the auction_id of the new auction
+    pub fn start_auction(
+        env: Env,
+        reward_id: u64,
+        quantity: u32,
+        min_bid: i64,
+        start_ledger: u32,
+        end_ledger: u32,
+    ) -> u64 {
+        let mut reward = load_reward(&env, reward_id);
+        require_brand_admin(&env, reward.brand_id);
+
+        if quantity == 0 {
+            panic!("Quantity must be positive");
+        }
+        if min_bid <= 0 {
+            panic!("Minimum bid must be positive");
+        }
+        if end_ledger <= start_ledger || end_ledger <= env.ledger().sequence() {
+            panic!("Invalid auction window");
+        }
+        if reward.stock < quantity {
+            panic!("Insufficient reward stock");
+        }
+
+        reward.stock -= quantity;
+        save_reward(&env, &reward);
+
+        let mut auction_count: u64 = env.storage().instance().get(&AUCTION_COUNT).unwrap_or(0);
+        auction_count += 1;
+
+        let auction = Auction {
+            auction_id: auction_count,
+            reward_id,
+            brand_id: reward.brand_id,
+            quantity,
+            min_bid,
+            start_ledger,
+            end_ledger,
+            is_closed: false,
+            winners: Vec::new(&env),
+        };
+        env.storage()
+            .instance()
+            .set(&AuctionBook::Auction(auction_count), &auction);
+        env.storage().instance().set(&AUCTION_COUNT, &auction_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Auction {} started for {} units of reward {}",
+            auction_count,
+            quantity,
+            reward_id
+        );
+        auction_count
+    }
+
+    /// Bid points on an open auction; the points are escrowed until close
+    /// Bidding again replaces the previous bid and only escrows the difference
+    pub fn place_bid(env: Env, bidder: Address, auction_id: u64, amount: i64) {
+        bidder.require_auth();
+
+        let auction = load_auction(&env, auction_id);
+        let sequence = env.ledger().sequence();
+        if auction.is_closed || sequence < auction.start_ledger || sequence >= auction.end_ledger {
+            panic!("Auction is not open");
+        }
+        if amount < auction.min_bid {
+            panic!("Bid below minimum");
+        }
+
+        let mut bids = Self::view_auction_bids(env.clone(), auction_id);
+        let mut escrow = amount;
+        if let Some(index) = bids.iter().position(|bid| bid.bidder == bidder) {
+            let previous = bids.get(index as u32).unwrap();
+            if amount <= previous.amount {
+                panic!("Bid must exceed your previous bid");
+            }
+            escrow -= previous.amount;
+            bids.remove(index as u32);
+        }
+        debit(&env, &bidder, auction.brand_id, escrow);
+
+        // Keep bids sorted so the winners are the first `quantity` entries
+        let position = bids
+            .iter()
+            .position(|bid| bid.amount < amount)
+            .unwrap_or(bids.len() as usize);
+        bids.insert(position as u32, AuctionBid { bidder, amount });
+        env.storage()
+            .instance()
+            .set(&AuctionBook::Bids(auction_id), &bids);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Bid of {} placed on auction {}",
+            amount,
+            auction_id
+        );
+    }
+
+    /// Close an auction once its window has ended; anyone may call this
+    /// The highest bids win the auctioned units, losing bids are refunded and
+    /// unsold units return to the reward's stock
+    /// Returns the winning bidders
+    pub fn close_auction(env: Env, auction_id: u64) -> Vec<Address> {
+        let mut auction = load_auction(&env, auction_id);
+        if auction.is_closed {
+            panic!("Auction already closed");
+        }
+        if env.ledger().sequence() < auction.end_ledger {
+            panic!("Auction is still running");
+        }
+
+        let bids = Self::view_auction_bids(env.clone(), auction_id);
+        for bid in bids.iter() {
+            if auction.winners.len() < auction.quantity {
+                // The escrowed points pay for the reward
+                auction.winners.push_back(bid.bidder);
+            } else {
+                credit(&env, &bid.bidder, auction.brand_id, bid.amount);
+            }
+        }
+
+        let unsold = auction.quantity - auction.winners.len();
+        if unsold > 0 {
+            let mut reward = load_reward(&env, auction.reward_id);
+            reward.stock += unsold;
+            save_reward(&env, &reward);
+        }
+
+        auction.is_closed = true;
+        env.storage()
+            .instance()
+            .set(&AuctionBook::Auction(auction_id), &auction);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Auction {} closed with {} winners",
+            auction_id,
+            auction.winners.len()
+        );
+        auction.winners
+    }
+
+    /// View auction details by auction_id
+    pub fn view_auction(env: Env, auction_id: u64) -> Auction {
+        load_auction(&env, auction_id)
+    }
+
+    /// View the bids of an auction, highest first
+    pub fn view_auction_bids(env: Env, auction_id: u64) -> Vec<AuctionBid> {
+        env.storage()
+            .instance()
+            .get(&AuctionBook::Bids(auction_id))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+fn load_auction(env: &Env, auction_id: u64) -> Auction {
+    env.storage()
+        .instance()
+        .get(&AuctionBook::Auction(auction_id))
+        .unwrap_or_else(|| panic!("Auction not found"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64) {
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let brand_id = client.register_brand(&String::from_str(env, "Stadium"));
+        client.set_brand_admin(&brand_id, &Address::generate(env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(env, "Final"), &500, &3);
+        (client, brand_id, reward_id)
+    }
+
+    #[test]
+    fn test_auction_highest_bids_win_and_losers_are_refunded() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, reward_id) = setup(&env);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        for user in [&alice, &bob, &carol] {
+            client.issue_tokens(user, &brand_id, &1000, &None);
+        }
+
+        let auction_id = client.start_auction(&reward_id, &2, &100, &10, &20);
+        assert_eq!(client.view_reward(&reward_id).stock, 1);
+
+        env.ledger().set_sequence_number(10);
+        client.place_bid(&alice, &auction_id, &300);
+        client.place_bid(&bob, &auction_id, &200);
+        client.place_bid(&carol, &auction_id, &250);
+        client.place_bid(&bob, &auction_id, &400);
+        assert_eq!(client.view_user_balance(&bob, &brand_id), 600);
+
+        env.ledger().set_sequence_number(20);
+        let winners = client.close_auction(&auction_id);
+        assert_eq!(winners, vec![&env, bob.clone(), alice.clone()]);
+
+        assert_eq!(client.view_user_balance(&alice, &brand_id), 700);
+        assert_eq!(client.view_user_balance(&bob, &brand_id), 600);
+        assert_eq!(client.view_user_balance(&carol, &brand_id), 1000);
+        assert!(client.view_auction(&auction_id).is_closed);
+    }
+
+    #[test]
+    fn test_unsold_auction_units_return_to_stock() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, reward_id) = setup(&env);
+
+        let alice = Address::generate(&env);
+        client.issue_tokens(&alice, &brand_id, &1000, &None);
+
+        let auction_id = client.start_auction(&reward_id, &3, &100, &0, &5);
+        client.place_bid(&alice, &auction_id, &150);
+
+        env.ledger().set_sequence_number(5);
+        client.close_auction(&auction_id);
+        assert_eq!(client.view_reward(&reward_id).stock, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Auction is not open")]
+    fn test_bid_after_window_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, reward_id) = setup(&env);
+
+        let alice = Address::generate(&env);
+        client.issue_tokens(&alice, &brand_id, &1000, &None);
+
+        let auction_id = client.start_auction(&reward_id, &1, &100, &0, &5);
+        env.ledger().set_sequence_number(5);
+        client.place_bid(&alice, &auction_id, &150);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::*;
+use soroban_sdk::{Map, Vec};
+
+// Balances live in one persistent entry per user (brand -> balance) rather
+// than one instance entry per (user, brand). Entries written in the legacy
+// layout stay readable and move over on their next write or through
+// `migrate_balances`
+
+// State of a user's balance as seen from the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BalanceState {
+    Empty,
+    Active(i64),
+    // The balance entry's TTL expired and it must be restored before use
+    Archived,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Move users' legacy balance entries into their persistent balance map
+    /// Anyone may call this since balances are unchanged
+    /// Returns the number of legacy entries migrated
+    pub fn migrate_balances(env: Env, users: Vec<Address>) -> u32 {
+        let brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+        let mut migrated: u32 = 0;
+
+        for user in users.iter() {
+            let mut balances = load_balances(&env, &user);
+            let mut changed = false;
+            for brand_id in 1..=brand_count {
+                let legacy_key = UserBalance::Balance(user.clone(), brand_id);
+                let legacy: Option<i64> = env.storage().instance().get(&legacy_key);
+                if let Some(balance) = legacy {
+                    if !balances.contains_key(brand_id) && balance != 0 {
+                        balances.set(brand_id, balance);
+                    }
+                    env.storage().instance().remove(&legacy_key);
+                    migrated += 1;
+                    changed = true;
+                }
+            }
+            if changed {
+                save_balances(&env, &user, &balances);
+            }
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Migrated {} balance entries", migrated);
+        migrated
+    }
+
+    /// Extend the storage TTL of customers' balance entries on behalf of a brand
+    /// (brand admin only), so inactive customers' balances are not archived
+    /// Only users holding the brand's points are bumped
+    /// Returns the number of entries extended
+    pub fn sponsor_ttl(env: Env, brand_id: u64, users: Vec<Address>) -> u32 {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let mut extended: u32 = 0;
+        for user in users.iter() {
+            if is_archived(&env, &user) || !load_balances(&env, &user).contains_key(brand_id) {
+                continue;
+            }
+            extend_balances_ttl(&env, &user);
+            extended += 1;
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} extended {} balance entries",
+            brand_id,
+            extended
+        );
+        extended
+    }
+
+    /// Extend the storage TTL of a user's balance entry; anyone may call this
+    /// Also refreshes the entry's status after it was restored from the archive
+    pub fn extend_balance_ttl(env: Env, user: Address) {
+        let key = UserBalance::Balances(user.clone());
+        if !env.storage().persistent().has(&key) {
+            panic!("No balance entry");
+        }
+        extend_balances_ttl(&env, &user);
+        env.storage().instance().extend_ttl(100000, 100000);
+    }
+
+    /// View a user's balance of a brand, telling an archived entry apart from a zero balance
+    pub fn view_balance_state(env: Env, user: Address, brand_id: u64) -> BalanceState {
+        if is_archived(&env, &user) {
+            return BalanceState::Archived;
+        }
+        match stored_balance(&env, &user, brand_id) {
+            0 => BalanceState::Empty,
+            balance => BalanceState::Active(balance),
+        }
+    }
+
+    /// Ledger keys of the contract's persistent storage that must be restored
+    /// (RestoreFootprint) before the user's balance of a brand can be used
+    /// Empty when nothing needs restoring
+    pub fn restore_hint(env: Env, user: Address, brand_id: u64) -> Vec<UserBalance> {
+        let mut keys = Vec::new(&env);
+        let legacy = env
+            .storage()
+            .instance()
+            .has(&UserBalance::Balance(user.clone(), brand_id));
+        if !legacy && is_archived(&env, &user) {
+            keys.push_back(UserBalance::Balances(user));
+        }
+        keys
+    }
+
+    /// View every brand balance of a user, including legacy entries not yet migrated
+    pub fn view_balances(env: Env, user: Address) -> Map<u64, i64> {
+        let mut balances = load_balances(&env, &user);
+        let brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+        for brand_id in 1..=brand_count {
+            if balances.contains_key(brand_id) {
+                continue;
+            }
+            let legacy: Option<i64> = env
+                .storage()
+                .instance()
+                .get(&UserBalance::Balance(user.clone(), brand_id));
+            if let Some(balance) = legacy.filter(|balance| *balance != 0) {
+                balances.set(brand_id, balance);
+            }
+        }
+        balances
+    }
+}
+
+// Whether the user has ever been credited in the persistent layout
+pub(crate) fn has_balance_entry(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&UserBalance::Balances(user.clone()))
+}
+
+// Read a balance from the persistent map, falling back to the legacy entry
+pub(crate) fn stored_balance(env: &Env, user: &Address, brand_id: u64) -> i64 {
+    if let Some(balance) = load_balances(env, user).get(brand_id) {
+        return balance;
+    }
+    env.storage()
+        .instance()
+        .get(&UserBalance::Balance(user.clone(), brand_id))
+        .unwrap_or(0)
+}
+
+// Write a balance in the persistent layout and drop any legacy entry
+pub(crate) fn write_balance(env: &Env, user: &Address, brand_id: u64, balance: i64) {
+    let mut balances = load_balances(env, user);
+    if balance == 0 {
+        balances.remove(brand_id);
+    } else {
+        balances.set(brand_id, balance);
+    }
+    save_balances(env, user, &balances);
+    env.storage()
+        .instance()
+        .remove(&UserBalance::Balance(user.clone(), brand_id));
+}
+
+fn load_balances(env: &Env, user: &Address) -> Map<u64, i64> {
+    env.storage()
+        .persistent()
+        .get(&UserBalance::Balances(user.clone()))
+        .unwrap_or(Map::new(env))
+}
+
+fn save_balances(env: &Env, user: &Address, balances: &Map<u64, i64>) {
+    env.storage()
+        .persistent()
+        .set(&UserBalance::Balances(user.clone()), balances);
+    extend_balances_ttl(env, user);
+}
+
+// Extend a balance entry and remember until when it is live, so views can
+// report it as archived without touching the expired entry
+fn extend_balances_ttl(env: &Env, user: &Address) {
+    let key = UserBalance::Balances(user.clone());
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+
+    let live_until = env.ledger().sequence() + 100000;
+    let tracked_key = UserBalance::LiveUntil(user.clone());
+    let tracked: u32 = env.storage().instance().get(&tracked_key).unwrap_or(0);
+    if live_until > tracked {
+        env.storage().instance().set(&tracked_key, &live_until);
+    }
+}
+
+fn is_archived(env: &Env, user: &Address) -> bool {
+    let live_until: Option<u32> = env
+        .storage()
+        .instance()
+        .get(&UserBalance::LiveUntil(user.clone()));
+    live_until.is_some_and(|live_until| live_until < env.ledger().sequence())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{storage::Persistent as _, Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    #[test]
+    fn test_legacy_balances_are_read_and_migrated() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        // Balances written by a deployment using the per-entry layout
+        env.as_contract(&contract_id, || {
+            for (user, brand_id) in [(&user1, hotel), (&user1, airline), (&user2, hotel)] {
+                env.storage()
+                    .instance()
+                    .set(&UserBalance::Balance(user.clone(), brand_id), &100_i64);
+            }
+        });
+        assert_eq!(client.view_user_balance(&user1, &hotel), 100);
+        assert_eq!(client.view_balances(&user1).len(), 2);
+
+        // A write moves the touched entry to the new layout
+        client.issue_tokens(&user1, &hotel, &50, &None);
+        assert_eq!(client.view_user_balance(&user1, &hotel), 150);
+
+        assert_eq!(
+            client.migrate_balances(&vec![&env, user1.clone(), user2.clone()]),
+            2
+        );
+        assert_eq!(client.view_user_balance(&user1, &airline), 100);
+        assert_eq!(client.view_user_balance(&user2, &hotel), 100);
+        env.as_contract(&contract_id, || {
+            assert!(!env
+                .storage()
+                .instance()
+                .has(&UserBalance::Balance(user2.clone(), hotel)));
+        });
+    }
+
+    #[test]
+    fn test_brand_sponsors_customer_ttls() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        let customer = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        client.issue_tokens(&customer, &hotel, &100, &None);
+
+        env.ledger().set_sequence_number(60_000);
+        let ttl = || {
+            env.as_contract(&contract_id, || {
+                env.storage()
+                    .persistent()
+                    .get_ttl(&UserBalance::Balances(customer.clone()))
+            })
+        };
+        assert_eq!(ttl(), 40_000);
+
+        let extended = client.sponsor_ttl(&hotel, &vec![&env, customer.clone(), stranger]);
+        assert_eq!(extended, 1);
+        assert_eq!(ttl(), 100_000);
+    }
+
+    #[test]
+    fn test_archived_balance_is_reported_with_restore_keys() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let dormant = Address::generate(&env);
+        let active = Address::generate(&env);
+        client.issue_tokens(&dormant, &hotel, &100, &None);
+        assert_eq!(
+            client.view_balance_state(&dormant, &hotel),
+            BalanceState::Active(100)
+        );
+        assert_eq!(
+            client.view_balance_state(&active, &hotel),
+            BalanceState::Empty
+        );
+
+        // Keep the contract live while the dormant user's entry expires
+        env.ledger().set_sequence_number(60_000);
+        client.issue_tokens(&active, &hotel, &100, &None);
+        env.ledger().set_sequence_number(120_000);
+
+        assert_eq!(
+            client.view_balance_state(&dormant, &hotel),
+            BalanceState::Archived
+        );
+        assert_eq!(
+            client.restore_hint(&dormant, &hotel),
+            vec![&env, UserBalance::Balances(dormant.clone())]
+        );
+        assert_eq!(client.restore_hint(&active, &hotel).len(), 0);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure describing one brand's share of a basket unit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BasketComponent {
+    pub brand_id: u64,
+    // Points of the brand locked per basket unit
+    pub weight: i64,
+}
+
+// Structure to store a multi-brand basket
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Basket {
+    pub basket_id: u64,
+    pub name: String,
+    pub components: Vec<BasketComponent>,
+    pub total_units: i64,
+    pub is_active: bool,
+}
+
+// Mapping for baskets and basket holdings
+#[contracttype]
+pub enum BasketBook {
+    Basket(u64),
+    // (User, Basket) -> Units held
+    Holding(Address, u64),
+}
+
+// Counter for baskets
+const BASKET_COUNT: Symbol = symbol_short!("BK_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Define a basket of weighted brand points (platform admin only)
+    /// Returns the basket_id of the new basket
+    pub fn create_basket(env: Env, name: String, components: Vec<BasketComponent>) -> u64 {
+        require_platform_admin(&env);
+
+        if components.is_empty() {
+            panic!("Basket needs at least one component");
+        }
+        let mut seen: Vec<u64> = Vec::new(&env);
+        for component in components.iter() {
+            if component.weight <= 0 {
+                panic!("Weight must be positive");
+            }
+            if seen.contains(component.brand_id) {
+                panic!("Duplicate basket component");
+            }
+            let brand = load_brand(&env, component.brand_id);
+            if !brand.is_active {
+                panic!("Brand is not active");
+            }
+            seen.push_back(component.brand_id);
+        }
+
+        let mut basket_count: u64 = env.storage().instance().get(&BASKET_COUNT).unwrap_or(0);
+        basket_count += 1;
+
+        let basket = Basket {
+            basket_id: basket_count,
+            name,
+            components,
+            total_units: 0,
+            is_active: true,
+        };
+        save_basket(&env, &basket);
+        env.storage().instance().set(&BASKET_COUNT, &basket_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Basket registered with ID: {}", basket_count);
+        basket_count
+    }
+
+    /// Deposit the constituent points of `units` basket units and receive the units
+    pub fn wrap_basket(env: Env, user: Address, basket_id: u64, units: i64) {
+        user.require_auth();
+
+        let mut basket = load_basket(&env, basket_id);
+        if !basket.is_active {
+            panic!("Basket is not active");
+        }
+        if units <= 0 {
+            panic!("Units must be positive");
+        }
+
+        for component in basket.components.iter() {
+            debit(&env, &user, component.brand_id, component.weight * units);
+        }
+        adjust_holding(&env, &user, basket_id, units);
+        basket.total_units += units;
+        save_basket(&env, &basket);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Wrapped {} units of basket {}", units, basket_id);
+    }
+
+    /// Return `units` basket units and get the constituent points back
+    pub fn unwrap_basket(env: Env, user: Address, basket_id: u64, units: i64) {
+        user.require_auth();
+
+        let mut basket = load_basket(&env, basket_id);
+        if units <= 0 {
+            panic!("Units must be positive");
+        }
+        if Self::view_basket_balance(env.clone(), user.clone(), basket_id) < units {
+            panic!("Insufficient basket balance");
+        }
+
+        adjust_holding(&env, &user, basket_id, -units);
+        for component in basket.components.iter() {
+            credit(&env, &user, component.brand_id, component.weight * units);
+        }
+        basket.total_units -= units;
+        save_basket(&env, &basket);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Unwrapped {} units of basket {}", units, basket_id);
+    }
+
+    /// View basket details by basket_id
+    pub fn view_basket(env: Env, basket_id: u64) -> Basket {
+        load_basket(&env, basket_id)
+    }
+
+    /// View the basket units held by a user
+    pub fn view_basket_balance(env: Env, user: Address, basket_id: u64) -> i64 {
+        env.storage()
+            .instance()
+            .get(&BasketBook::Holding(user, basket_id))
+            .unwrap_or(0)
+    }
+}
+
+fn load_basket(env: &Env, basket_id: u64) -> Basket {
+    env.storage()
+        .instance()
+        .get(&BasketBook::Basket(basket_id))
+        .unwrap_or_else(|| panic!("Basket not found"))
+}
+
+fn save_basket(env: &Env, basket: &Basket) {
+    env.storage()
+        .instance()
+        .set(&BasketBook::Basket(basket.basket_id), basket);
+}
+
+fn adjust_holding(env: &Env, user: &Address, basket_id: u64, delta: i64) {
+    let key = BasketBook::Holding(user.clone(), basket_id);
+    let units: i64 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage().instance().set(&key, &(units + delta));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    #[test]
+    fn test_wrap_and_unwrap_basket() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let components = vec![
+            &env,
+            BasketComponent {
+                brand_id: airline,
+                weight: 100,
+            },
+            BasketComponent {
+                brand_id: hotel,
+                weight: 50,
+            },
+        ];
+        let basket_id = client.create_basket(&String::from_str(&env, "Travel"), &components);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &airline, &1000, &None);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        client.wrap_basket(&user, &basket_id, &3);
+        assert_eq!(client.view_basket_balance(&user, &basket_id), 3);
+        assert_eq!(client.view_user_balance(&user, &airline), 700);
+        assert_eq!(client.view_user_balance(&user, &hotel), 850);
+        assert_eq!(client.view_basket(&basket_id).total_units, 3);
+
+        client.unwrap_basket(&user, &basket_id, &2);
+        assert_eq!(client.view_basket_balance(&user, &basket_id), 1);
+        assert_eq!(client.view_user_balance(&user, &airline), 900);
+        assert_eq!(client.view_user_balance(&user, &hotel), 950);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient balance")]
+    fn test_wrap_basket_requires_every_component() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let components = vec![
+            &env,
+            BasketComponent {
+                brand_id: airline,
+                weight: 100,
+            },
+            BasketComponent {
+                brand_id: hotel,
+                weight: 50,
+            },
+        ];
+        let basket_id = client.create_basket(&String::from_str(&env, "Travel"), &components);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &airline, &1000, &None);
+        client.wrap_basket(&user, &basket_id, &1);
+    }
+}
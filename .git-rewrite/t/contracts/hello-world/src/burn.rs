@@ -0,0 +1,113 @@
+use crate::*;
+
+// Structure reporting a brand's supply figures
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupplyStats {
+    pub brand_id: u64,
+    pub circulating: i64,
+    pub burned: i64,
+    pub burn_bps: u32,
+}
+
+// Mapping for burn-on-exchange policies and totals
+#[contracttype]
+pub enum BurnBook {
+    // Brand -> Share of outbound exchanges burned, in basis points
+    Rate(u64),
+    // Brand -> Total points burned by exchanges
+    Burned(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the share of every outbound exchange that is burned (brand admin only)
+    pub fn set_exchange_burn(env: Env, brand_id: u64, burn_bps: u32) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if burn_bps as i64 > RATE_DENOMINATOR {
+            panic!("Burn cannot exceed 100%");
+        }
+
+        env.storage()
+            .instance()
+            .set(&BurnBook::Rate(brand_id), &burn_bps);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Exchange burn for brand {} set to {} bps",
+            brand_id,
+            burn_bps
+        );
+    }
+
+    /// View circulating supply, total burned and burn policy of a brand
+    pub fn view_supply_stats(env: Env, brand_id: u64) -> SupplyStats {
+        SupplyStats {
+            brand_id,
+            circulating: Self::view_brand_supply(env.clone(), brand_id),
+            burned: env
+                .storage()
+                .instance()
+                .get(&BurnBook::Burned(brand_id))
+                .unwrap_or(0),
+            burn_bps: exchange_burn_bps(&env, brand_id),
+        }
+    }
+}
+
+pub(crate) fn exchange_burn_bps(env: &Env, brand_id: u64) -> u32 {
+    env.storage()
+        .instance()
+        .get(&BurnBook::Rate(brand_id))
+        .unwrap_or(0)
+}
+
+pub(crate) fn record_burn(env: &Env, brand_id: u64, amount: i64) {
+    if amount == 0 {
+        return;
+    }
+    let key = BurnBook::Burned(brand_id);
+    let burned: i64 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage().instance().set(&key, &(burned + amount));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_exchange_burns_configured_share() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let grocer = client.register_brand(&String::from_str(&env, "Grocer"));
+        let fuel = client.register_brand(&String::from_str(&env, "Fuel"));
+        client.set_brand_admin(&grocer, &Address::generate(&env));
+        client.set_exchange_burn(&grocer, &1000);
+
+        let quote = client.quote_exchange(&grocer, &fuel, &500);
+        assert_eq!(quote.burned, 50);
+        assert_eq!(quote.amount_out, 450);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &grocer, &500, &None);
+        client.exchange_tokens(&user, &grocer, &fuel, &500);
+        assert_eq!(client.view_user_balance(&user, &fuel), 450);
+
+        let stats = client.view_supply_stats(&grocer);
+        assert_eq!(stats.circulating, 0);
+        assert_eq!(stats.burned, 50);
+        assert_eq!(stats.burn_bps, 1000);
+
+        // Inbound exchanges into the grocer are not burned
+        client.exchange_tokens(&user, &fuel, &grocer, &450);
+        assert_eq!(client.view_user_balance(&user, &grocer), 450);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store points issued to an address that has not enrolled yet
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claimable {
+    pub claim_id: u64,
+    pub user: Address,
+    pub brand_id: u64,
+    pub amount: i64,
+    // Issuance the points come from
+    pub op_id: u64,
+    pub expires_at: u32,
+}
+
+// Mapping for claimable balances
+#[contracttype]
+pub enum ClaimBook {
+    Claim(u64),
+    // User -> claim_ids waiting for the user
+    User(Address),
+    // Brand -> Ledgers a new user has to claim issued points
+    Window(u64),
+}
+
+// Counter for claimable balances
+const CLAIM_COUNT: Symbol = symbol_short!("CL_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Hold points issued to addresses without a balance entry until the user
+    /// claims them within `window_ledgers` (brand admin only); 0 turns it off
+    pub fn set_claim_window(env: Env, brand_id: u64, window_ledgers: u32) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        if window_ledgers == 0 {
+            env.storage()
+                .instance()
+                .remove(&ClaimBook::Window(brand_id));
+        } else {
+            env.storage()
+                .instance()
+                .set(&ClaimBook::Window(brand_id), &window_ledgers);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Claim window for brand {} set to {}",
+            brand_id,
+            window_ledgers
+        );
+    }
+
+    /// Claim points held for the user, crediting their balance
+    /// Returns the amount credited
+    pub fn claim(env: Env, user: Address, claim_id: u64) -> i64 {
+        user.require_auth();
+
+        let claimable = load_claimable(&env, claim_id);
+        if claimable.user != user {
+            panic!("Not the claimant");
+        }
+        if env.ledger().sequence() > claimable.expires_at {
+            panic!("Claim expired");
+        }
+
+        credit(&env, &user, claimable.brand_id, claimable.amount);
+        remove_claimable(&env, &claimable);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Claimed {} tokens of brand {}",
+            claimable.amount,
+            claimable.brand_id
+        );
+        claimable.amount
+    }
+
+    /// Return an expired, unclaimed balance to the brand; anyone may call this
+    /// The points go to the brand's treasury reserve
+    pub fn expire_claimable(env: Env, claim_id: u64) {
+        let claimable = load_claimable(&env, claim_id);
+        if env.ledger().sequence() <= claimable.expires_at {
+            panic!("Claim has not expired");
+        }
+
+        deposit_treasury(
+            &env,
+            claimable.brand_id,
+            TreasurySource::Clawback,
+            claimable.amount,
+        );
+        remove_claimable(&env, &claimable);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Claimable {} returned to brand", claim_id);
+    }
+
+    /// View the claim window of a brand, if claimable issuance is on
+    pub fn view_claim_window(env: Env, brand_id: u64) -> Option<u32> {
+        env.storage().instance().get(&ClaimBook::Window(brand_id))
+    }
+
+    /// View the balances waiting to be claimed by a user
+    pub fn view_claimables(env: Env, user: Address) -> Vec<Claimable> {
+        let mut claimables = Vec::new(&env);
+        for claim_id in user_claims(&env, &user).iter() {
+            claimables.push_back(load_claimable(&env, claim_id));
+        }
+        claimables
+    }
+}
+
+// Claim window to hold an issuance in, when the user has no balance entry and
+// the brand has claimable issuance on
+pub(crate) fn parking_window(env: &Env, user: &Address, brand_id: u64) -> Option<u32> {
+    let window: Option<u32> = env.storage().instance().get(&ClaimBook::Window(brand_id));
+    window.filter(|_| !has_balance_entry(env, user))
+}
+
+pub(crate) fn park_claimable(
+    env: &Env,
+    user: &Address,
+    brand_id: u64,
+    amount: i64,
+    op_id: u64,
+    window_ledgers: u32,
+) {
+    let mut claim_count: u64 = env.storage().instance().get(&CLAIM_COUNT).unwrap_or(0);
+    claim_count += 1;
+
+    let claimable = Claimable {
+        claim_id: claim_count,
+        user: user.clone(),
+        brand_id,
+        amount,
+        op_id,
+        expires_at: env.ledger().sequence() + window_ledgers,
+    };
+    env.storage()
+        .instance()
+        .set(&ClaimBook::Claim(claim_count), &claimable);
+    let mut claims = user_claims(env, user);
+    claims.push_back(claim_count);
+    env.storage()
+        .instance()
+        .set(&ClaimBook::User(user.clone()), &claims);
+    env.storage().instance().set(&CLAIM_COUNT, &claim_count);
+
+    log!(env, "✅ Issuance held as claimable {}", claim_count);
+}
+
+fn load_claimable(env: &Env, claim_id: u64) -> Claimable {
+    env.storage()
+        .instance()
+        .get(&ClaimBook::Claim(claim_id))
+        .unwrap_or_else(|| panic!("Claimable not found"))
+}
+
+fn remove_claimable(env: &Env, claimable: &Claimable) {
+    env.storage()
+        .instance()
+        .remove(&ClaimBook::Claim(claimable.claim_id));
+    let mut claims = user_claims(env, &claimable.user);
+    if let Some(index) = claims.first_index_of(claimable.claim_id) {
+        claims.remove(index);
+    }
+    env.storage()
+        .instance()
+        .set(&ClaimBook::User(claimable.user.clone()), &claims);
+}
+
+fn user_claims(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&ClaimBook::User(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_new_users_claim_issued_points() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let issuer = Address::generate(&env);
+        client.add_issuer(&brand_id, &issuer);
+        client.set_claim_window(&brand_id, &100);
+
+        let newcomer = Address::generate(&env);
+        client.issue_as(&issuer, &newcomer, &brand_id, &80, &None);
+        assert_eq!(client.view_user_balance(&newcomer, &brand_id), 0);
+        assert_eq!(client.view_brand_supply(&brand_id), 0);
+
+        let claimables = client.view_claimables(&newcomer);
+        assert_eq!(claimables.len(), 1);
+        let claim_id = claimables.get(0).unwrap().claim_id;
+        assert!(client
+            .try_claim(&Address::generate(&env), &claim_id)
+            .is_err());
+        assert_eq!(client.claim(&newcomer, &claim_id), 80);
+        assert_eq!(client.view_user_balance(&newcomer, &brand_id), 80);
+        assert_eq!(client.view_claimables(&newcomer).len(), 0);
+
+        // Once enrolled, issuances are credited directly
+        client.issue_as(&issuer, &newcomer, &brand_id, &20, &None);
+        assert_eq!(client.view_user_balance(&newcomer, &brand_id), 100);
+    }
+
+    #[test]
+    fn test_unclaimed_points_return_to_brand() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        client.set_claim_window(&brand_id, &100);
+
+        let newcomer = Address::generate(&env);
+        client.issue_tokens(&newcomer, &brand_id, &50, &None);
+        let claim_id = client.view_claimables(&newcomer).get(0).unwrap().claim_id;
+        assert!(client.try_expire_claimable(&claim_id).is_err());
+
+        env.ledger().set_sequence_number(101);
+        assert!(client.try_claim(&newcomer, &claim_id).is_err());
+        client.expire_claimable(&claim_id);
+        assert_eq!(client.view_treasury_reserve(&brand_id), 50);
+        assert_eq!(client.view_claimables(&newcomer).len(), 0);
+    }
+}
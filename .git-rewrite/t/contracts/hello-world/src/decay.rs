@@ -0,0 +1,188 @@
+use crate::*;
+
+// Structure storing a brand's inactivity decay policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecayPolicy {
+    // Ledgers without activity after which a balance decays
+    pub inactive_ledgers: u32,
+    // Share of the balance lost once inactive, in basis points
+    pub decay_bps: u32,
+    // Ledger the policy was set; balances untouched since count as active then
+    pub since: u32,
+}
+
+// Structure showing a balance before and after pending decay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecayQuote {
+    pub stored: i64,
+    pub decay: i64,
+    pub balance: i64,
+    // Ledger from which the stored balance decays; None without a policy
+    pub decays_at: Option<u32>,
+}
+
+// Mapping for decay policies and balance activity
+#[contracttype]
+pub enum DecayBook {
+    Policy(u64),
+    // (User, Brand) -> Ledger the balance last changed
+    LastActive(Address, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Make balances untouched for `inactive_ledgers` lose `decay_bps` of their
+    /// value on their next interaction (brand admin only)
+    pub fn set_decay_policy(env: Env, brand_id: u64, inactive_ledgers: u32, decay_bps: u32) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if inactive_ledgers == 0 {
+            panic!("Inactivity period must be positive");
+        }
+        if decay_bps == 0 || decay_bps as i64 > RATE_DENOMINATOR {
+            panic!("Invalid decay rate");
+        }
+
+        let policy = DecayPolicy {
+            inactive_ledgers,
+            decay_bps,
+            since: env.ledger().sequence(),
+        };
+        env.storage()
+            .instance()
+            .set(&DecayBook::Policy(brand_id), &policy);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Decay of {} bps after {} ledgers set for brand {}",
+            decay_bps,
+            inactive_ledgers,
+            brand_id
+        );
+    }
+
+    /// Stop decaying a brand's inactive balances (brand admin only)
+    pub fn remove_decay_policy(env: Env, brand_id: u64) {
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .remove(&DecayBook::Policy(brand_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Decay removed for brand {}", brand_id);
+    }
+
+    /// View the decay policy of a brand, if any
+    pub fn view_decay_policy(env: Env, brand_id: u64) -> Option<DecayPolicy> {
+        env.storage().instance().get(&DecayBook::Policy(brand_id))
+    }
+
+    /// View a user's balance before and after any pending decay
+    pub fn quote_decay(env: Env, user: Address, brand_id: u64) -> DecayQuote {
+        let stored = stored_balance(&env, &user, brand_id);
+        let decay = pending_decay(&env, &user, brand_id);
+        let decays_at = Self::view_decay_policy(env.clone(), brand_id)
+            .map(|policy| last_active(&env, &user, brand_id, &policy) + policy.inactive_ledgers);
+        DecayQuote {
+            stored,
+            decay,
+            balance: stored - decay,
+            decays_at,
+        }
+    }
+}
+
+// Points a balance would lose if it were touched now
+pub(crate) fn pending_decay(env: &Env, user: &Address, brand_id: u64) -> i64 {
+    let Some(policy) = LoyaltyTokenExchange::view_decay_policy(env.clone(), brand_id) else {
+        return 0;
+    };
+    let stored = stored_balance(env, user, brand_id);
+    let inactive_since = last_active(env, user, brand_id, &policy);
+    if stored <= 0 || env.ledger().sequence() < inactive_since + policy.inactive_ledgers {
+        return 0;
+    }
+    apply_bps(stored, policy.decay_bps)
+}
+
+// Apply pending decay to a balance and restart its inactivity clock
+// Called before every balance change
+pub(crate) fn settle_decay(env: &Env, user: &Address, brand_id: u64) {
+    if LoyaltyTokenExchange::view_decay_policy(env.clone(), brand_id).is_none() {
+        return;
+    }
+
+    let decay = pending_decay(env, user, brand_id);
+    if decay > 0 {
+        let stored = stored_balance(env, user, brand_id);
+        write_balance(env, user, brand_id, stored - decay);
+        adjust_supply(env, brand_id, -decay);
+        track_votes(env, user, brand_id, -decay);
+        log!(
+            env,
+            "⚠️ {} inactive tokens of brand {} decayed",
+            decay,
+            brand_id
+        );
+    }
+    env.storage().instance().set(
+        &DecayBook::LastActive(user.clone(), brand_id),
+        &env.ledger().sequence(),
+    );
+}
+
+fn last_active(env: &Env, user: &Address, brand_id: u64, policy: &DecayPolicy) -> u32 {
+    let active: u32 = env
+        .storage()
+        .instance()
+        .get(&DecayBook::LastActive(user.clone(), brand_id))
+        .unwrap_or(0);
+    active.max(policy.since)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_inactive_balance_decays_on_next_interaction() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        client.set_decay_policy(&brand_id, &1000, &1000);
+
+        let user = Address::generate(&env);
+        let friend = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        assert_eq!(client.quote_decay(&user, &brand_id).decays_at, Some(1000));
+
+        env.ledger().set_sequence_number(999);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 500);
+
+        env.ledger().set_sequence_number(1000);
+        let quote = client.quote_decay(&user, &brand_id);
+        assert_eq!((quote.stored, quote.decay, quote.balance), (500, 50, 450));
+        assert_eq!(client.view_user_balance(&user, &brand_id), 450);
+
+        client.transfer_tokens(&user, &friend, &brand_id, &100, &None);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 350);
+        assert_eq!(client.view_brand_supply(&brand_id), 450);
+
+        // The transfer restarted the clock
+        let quote = client.quote_decay(&user, &brand_id);
+        assert_eq!((quote.decay, quote.decays_at), (0, Some(2000)));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::*;
+
+// Lifecycle of a dispute raised over a redemption
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    // The brand is found to have delivered the reward
+    Fulfilled,
+    // The brand is found to have failed to deliver the reward
+    Unfulfilled,
+}
+
+// Structure to store a user's dispute over a redemption
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub dispute_id: u64,
+    pub op_id: u64,
+    pub user: Address,
+    pub brand_id: u64,
+    pub opened_at: u32,
+    pub status: DisputeStatus,
+}
+
+// Mapping for disputes
+#[contracttype]
+pub enum DisputeBook {
+    Dispute(u64),
+    // Redemption op_id -> dispute_id
+    DisputedOp(u64),
+}
+
+// Counter for disputes
+const DISPUTE_COUNT: Symbol = symbol_short!("D_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Dispute one of the user's own redemptions, e.g. when the reward was never delivered
+    /// Returns the dispute_id
+    pub fn open_dispute(env: Env, user: Address, op_id: u64) -> u64 {
+        user.require_auth();
+
+        let record = Self::view_operation(env.clone(), op_id);
+        if record.kind != OperationKind::Redeem {
+            panic!("Only redemptions can be disputed");
+        }
+        if record.user != user {
+            panic!("Not the redeeming user");
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&DisputeBook::DisputedOp(op_id))
+        {
+            panic!("Redemption already disputed");
+        }
+
+        let mut dispute_count: u64 = env.storage().instance().get(&DISPUTE_COUNT).unwrap_or(0);
+        dispute_count += 1;
+
+        let dispute = Dispute {
+            dispute_id: dispute_count,
+            op_id,
+            user,
+            brand_id: record.brand_id,
+            opened_at: env.ledger().sequence(),
+            status: DisputeStatus::Open,
+        };
+        env.storage()
+            .instance()
+            .set(&DisputeBook::Dispute(dispute_count), &dispute);
+        env.storage()
+            .instance()
+            .set(&DisputeBook::DisputedOp(op_id), &dispute_count);
+        env.storage().instance().set(&DISPUTE_COUNT, &dispute_count);
+        record_reputation_dispute(&env, record.brand_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Dispute {} opened for operation {}",
+            dispute_count,
+            op_id
+        );
+        dispute_count
+    }
+
+    /// Resolve an open dispute (platform admin only)
+    /// `fulfilled` records whether the brand had delivered the redeemed reward
+    pub fn resolve_dispute(env: Env, dispute_id: u64, fulfilled: bool) {
+        require_platform_admin(&env);
+
+        let mut dispute = Self::view_dispute(env.clone(), dispute_id);
+        if dispute.status != DisputeStatus::Open {
+            panic!("Dispute already resolved");
+        }
+        dispute.status = if fulfilled {
+            DisputeStatus::Fulfilled
+        } else {
+            DisputeStatus::Unfulfilled
+        };
+
+        env.storage()
+            .instance()
+            .set(&DisputeBook::Dispute(dispute_id), &dispute);
+        record_reputation_resolution(&env, dispute.brand_id, fulfilled);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Dispute {} resolved", dispute_id);
+    }
+
+    /// View a dispute by dispute_id
+    pub fn view_dispute(env: Env, dispute_id: u64) -> Dispute {
+        env.storage()
+            .instance()
+            .get(&DisputeBook::Dispute(dispute_id))
+            .unwrap_or_else(|| panic!("Dispute not found"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_only_own_redemptions_can_be_disputed_once() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Latte"), &100, &10);
+        let user = Address::generate(&env);
+        let issue_op = client.issue_tokens(&user, &brand_id, &500, &None);
+        let redeem_op = client.redeem_reward(&user, &reward_id, &None);
+
+        assert!(client.try_open_dispute(&user, &issue_op).is_err());
+        assert!(client
+            .try_open_dispute(&Address::generate(&env), &redeem_op)
+            .is_err());
+
+        let dispute_id = client.open_dispute(&user, &redeem_op);
+        assert!(client.try_open_dispute(&user, &redeem_op).is_err());
+
+        client.resolve_dispute(&dispute_id, &true);
+        assert_eq!(
+            client.view_dispute(&dispute_id).status,
+            DisputeStatus::Fulfilled
+        );
+        assert!(client.try_resolve_dispute(&dispute_id, &false).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Param, TreasurySource};
+use soroban_sdk::{contractevent, Address, BytesN};
+
+// Every event carries the brand it concerns as its first topic after the
+// event name, so RPC filters can subscribe to a single brand's events
+
+// Brand topic of events that concern the whole platform rather than one brand
+pub const PLATFORM_BRAND_ID: u64 = 0;
+
+// Emitted when a brand issues tokens to a user
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokensIssued {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i64,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when tokens move between two users
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokensTransferred {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i64,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when a user redeems a reward
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardRedeemed {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub reward_id: u64,
+    pub cost: i64,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when a partner contract records a balance attestation
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceAttested {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub requester: Address,
+    pub min_amount: i64,
+    pub attested: bool,
+}
+
+// Emitted when a parameter change is queued behind the timelock
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeScheduled {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub change_id: u64,
+    pub param: Param,
+    pub value: i64,
+    pub eta: u32,
+}
+
+// Emitted when a queued parameter change takes effect
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeExecuted {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub change_id: u64,
+    pub param: Param,
+    pub value: i64,
+}
+
+// Emitted when a queued parameter change is withdrawn
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeCancelled {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub change_id: u64,
+    pub param: Param,
+}
+
+// Emitted for each brand balance guardians move to a user's new address
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalancesRecovered {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub new_address: Address,
+    pub amount: i64,
+}
+
+// Emitted when an exchange route is paused or resumed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoutePauseChanged {
+    #[topic]
+    pub from_brand: u64,
+    #[topic]
+    pub to_brand: u64,
+    pub paused: bool,
+    pub caller: Address,
+}
+
+// Emitted when a user spends points directly, without a catalog reward
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsRedeemed {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i64,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when an exchange wins a surprise bonus from a brand's pool
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardDropped {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub bonus: i64,
+    pub pool_balance: i64,
+}
+
+// Emitted when a brand admin points the brand treasury at a new address
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySet {
+    #[topic]
+    pub brand_id: u64,
+    pub treasury: Address,
+}
+
+// Emitted when points are added to a brand's treasury reserve
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryDeposited {
+    #[topic]
+    pub brand_id: u64,
+    pub source: TreasurySource,
+    pub amount: i64,
+    pub reserve: i64,
+}
+
+// Emitted when a brand admin pays out of the treasury reserve
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawn {
+    #[topic]
+    pub brand_id: u64,
+    pub treasury: Address,
+    pub amount: i64,
+    pub reserve: i64,
+}
@@ -0,0 +1,213 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure describing one fee discount tier
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscountTier {
+    // Trailing exchange volume needed to reach the tier
+    pub min_volume: i64,
+    // Share of the exchange fee waived, in basis points
+    pub discount_bps: u32,
+}
+
+// Structure storing a user's exchange volume for one day
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyVolume {
+    pub day: u32,
+    pub volume: i64,
+}
+
+// Mapping for exchange volumes: User -> Daily volumes within the window, oldest first
+#[contracttype]
+pub enum VolumeBook {
+    Volume(Address),
+}
+
+// Fee discount tiers, ascending by volume
+const DISCOUNT_TIERS: Symbol = symbol_short!("D_TIERS");
+
+// Length of a volume bucket (about one day)
+pub const VOLUME_DAY_LEDGERS: u32 = 17_280;
+
+// Number of daily buckets in the trailing volume window
+pub const VOLUME_WINDOW_DAYS: u32 = 30;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the fee discount schedule (platform admin only)
+    /// Tiers must be ascending in both volume and discount
+    pub fn set_fee_discount_schedule(env: Env, tiers: Vec<DiscountTier>) {
+        require_platform_admin(&env);
+
+        let mut previous: Option<DiscountTier> = None;
+        for tier in tiers.iter() {
+            if tier.min_volume <= 0 || tier.discount_bps > RATE_DENOMINATOR as u32 {
+                panic!("Invalid discount tier");
+            }
+            if let Some(previous) = previous {
+                if tier.min_volume <= previous.min_volume
+                    || tier.discount_bps <= previous.discount_bps
+                {
+                    panic!("Tiers must be ascending");
+                }
+            }
+            previous = Some(tier);
+        }
+
+        env.storage().instance().set(&DISCOUNT_TIERS, &tiers);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Fee discount schedule set with {} tiers",
+            tiers.len()
+        );
+    }
+
+    /// View the fee discount schedule
+    pub fn view_fee_discount_schedule(env: Env) -> Vec<DiscountTier> {
+        env.storage()
+            .instance()
+            .get(&DISCOUNT_TIERS)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View a user's exchange volume over the trailing VOLUME_WINDOW_DAYS
+    pub fn view_exchange_volume(env: Env, user: Address) -> i64 {
+        trailing_volume(&env, &user)
+    }
+
+    /// View the discount tier a user currently qualifies for, if any
+    pub fn view_fee_tier(env: Env, user: Address) -> Option<DiscountTier> {
+        current_tier(&env, &user)
+    }
+}
+
+// Reduce a route's fee by the user's discount tier
+pub(crate) fn discounted_fee_bps(env: &Env, user: &Address, fee_bps: u32) -> u32 {
+    match current_tier(env, user) {
+        Some(tier) => fee_bps - apply_bps(fee_bps as i64, tier.discount_bps) as u32,
+        None => fee_bps,
+    }
+}
+
+// Add an exchange to the user's volume, dropping days outside the window
+pub(crate) fn record_exchange_volume(env: &Env, user: &Address, amount: i64) {
+    let today = env.ledger().sequence() / VOLUME_DAY_LEDGERS;
+    let mut volumes = window_volumes(env, user);
+    match volumes.last() {
+        Some(entry) if entry.day == today => volumes.set(
+            volumes.len() - 1,
+            DailyVolume {
+                day: today,
+                volume: entry.volume + amount,
+            },
+        ),
+        _ => volumes.push_back(DailyVolume {
+            day: today,
+            volume: amount,
+        }),
+    }
+    env.storage()
+        .instance()
+        .set(&VolumeBook::Volume(user.clone()), &volumes);
+}
+
+fn current_tier(env: &Env, user: &Address) -> Option<DiscountTier> {
+    let volume = trailing_volume(env, user);
+    let mut reached = None;
+    for tier in LoyaltyTokenExchange::view_fee_discount_schedule(env.clone()).iter() {
+        if volume >= tier.min_volume {
+            reached = Some(tier);
+        }
+    }
+    reached
+}
+
+fn trailing_volume(env: &Env, user: &Address) -> i64 {
+    window_volumes(env, user)
+        .iter()
+        .map(|entry| entry.volume)
+        .sum()
+}
+
+// Stored daily volumes still inside the trailing window
+fn window_volumes(env: &Env, user: &Address) -> Vec<DailyVolume> {
+    let today = env.ledger().sequence() / VOLUME_DAY_LEDGERS;
+    let stored: Vec<DailyVolume> = env
+        .storage()
+        .instance()
+        .get(&VolumeBook::Volume(user.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut volumes = Vec::new(env);
+    for entry in stored.iter() {
+        if entry.day + VOLUME_WINDOW_DAYS > today {
+            volumes.push_back(entry);
+        }
+    }
+    volumes
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    #[test]
+    fn test_volume_tiers_discount_exchange_fees() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+
+        // 1% exchange fee, halved from 1000 points of volume
+        let change_id = client.schedule_change(&Param::ExchangeFeeBps, &100);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        client.set_fee_discount_schedule(&vec![
+            &env,
+            DiscountTier {
+                min_volume: 1000,
+                discount_bps: 5000,
+            },
+        ]);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &5000, &None);
+        assert_eq!(
+            client
+                .quote_exchange_for(&user, &hotel, &airline, &1000)
+                .fee,
+            10
+        );
+
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_exchange_volume(&user), 1000);
+        assert_eq!(client.view_fee_tier(&user).unwrap().discount_bps, 5000);
+        assert_eq!(
+            client
+                .quote_exchange_for(&user, &hotel, &airline, &1000)
+                .fee,
+            5
+        );
+
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_collected_fees(&hotel), 15);
+
+        // The volume leaves the window after 30 days
+        env.ledger()
+            .set_sequence_number(TIMELOCK_LEDGERS + VOLUME_WINDOW_DAYS * VOLUME_DAY_LEDGERS);
+        assert_eq!(client.view_exchange_volume(&user), 0);
+        assert_eq!(client.view_fee_tier(&user), None);
+    }
+}
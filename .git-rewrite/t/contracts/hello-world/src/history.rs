@@ -0,0 +1,211 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Kinds of balance-changing operations kept in the history
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationKind {
+    Issue,
+    Transfer,
+    Redeem,
+    Exchange,
+    Sell,
+}
+
+// Structure to store one executed operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationRecord {
+    pub op_id: u64,
+    pub kind: OperationKind,
+    pub user: Address,
+    pub brand_id: u64,
+    pub amount: i64,
+    // Receiving user of a transfer
+    pub counterparty: Option<Address>,
+    // Destination brand of an exchange
+    pub to_brand: Option<u64>,
+    // Issuer that performed an issuance on the brand's behalf
+    pub issuer: Option<Address>,
+    pub memo: Option<BytesN<32>>,
+    pub ledger: u32,
+}
+
+// Mapping for operation history
+#[contracttype]
+pub enum HistoryBook {
+    Operation(u64),
+    // User -> op_ids involving the user, oldest first
+    UserOps(Address),
+    // Memo -> op_id of the latest operation carrying it
+    Reference(BytesN<32>),
+}
+
+// Counter for operations
+const OP_COUNT: Symbol = symbol_short!("OP_COUNT");
+
+impl OperationRecord {
+    // Start a record for the current ledger with a freshly allocated op_id
+    pub(crate) fn new(
+        env: &Env,
+        kind: OperationKind,
+        user: &Address,
+        brand_id: u64,
+        amount: i64,
+    ) -> Self {
+        let mut op_count: u64 = env.storage().instance().get(&OP_COUNT).unwrap_or(0);
+        op_count += 1;
+        env.storage().instance().set(&OP_COUNT, &op_count);
+
+        OperationRecord {
+            op_id: op_count,
+            kind,
+            user: user.clone(),
+            brand_id,
+            amount,
+            counterparty: None,
+            to_brand: None,
+            issuer: None,
+            memo: None,
+            ledger: env.ledger().sequence(),
+        }
+    }
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View an operation by op_id
+    pub fn view_operation(env: Env, op_id: u64) -> OperationRecord {
+        env.storage()
+            .instance()
+            .get(&HistoryBook::Operation(op_id))
+            .unwrap_or_else(|| panic!("Operation not found"))
+    }
+
+    /// Find the latest operation submitted with `reference` as its memo
+    pub fn find_operation(env: Env, reference: BytesN<32>) -> OperationRecord {
+        let op_id: u64 = env
+            .storage()
+            .instance()
+            .get(&HistoryBook::Reference(reference))
+            .unwrap_or_else(|| panic!("Operation not found"));
+        Self::view_operation(env, op_id)
+    }
+
+    /// View a page of the operations involving a user, oldest first
+    pub fn view_user_history(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<OperationRecord> {
+        let op_ids = user_op_ids(&env, &user);
+        let mut records = Vec::new(&env);
+        let end = op_ids.len().min(start.saturating_add(limit));
+        for index in start..end {
+            let op_id = op_ids.get(index).unwrap();
+            records.push_back(Self::view_operation(env.clone(), op_id));
+        }
+        records
+    }
+}
+
+// Store a record and index it under every user it involves
+pub(crate) fn save_operation(env: &Env, record: &OperationRecord) {
+    env.storage()
+        .instance()
+        .set(&HistoryBook::Operation(record.op_id), record);
+
+    index_operation(env, &record.user, record.op_id);
+    if let Some(counterparty) = &record.counterparty {
+        index_operation(env, counterparty, record.op_id);
+    }
+    if let Some(memo) = &record.memo {
+        env.storage()
+            .instance()
+            .set(&HistoryBook::Reference(memo.clone()), &record.op_id);
+    }
+}
+
+fn user_op_ids(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&HistoryBook::UserOps(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn index_operation(env: &Env, user: &Address, op_id: u64) {
+    let mut op_ids = user_op_ids(env, user);
+    op_ids.push_back(op_id);
+    env.storage()
+        .instance()
+        .set(&HistoryBook::UserOps(user.clone()), &op_ids);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+    #[test]
+    fn test_memos_are_stored_in_history() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Bakery"));
+
+        let order = BytesN::from_array(&env, &[7; 32]);
+        let issue_op = client.issue_tokens(&alice, &brand_id, &300, &Some(order.clone()));
+        let transfer_op = client.transfer_tokens(&alice, &bob, &brand_id, &100, &None);
+
+        assert_eq!(client.view_user_balance(&alice, &brand_id), 200);
+        assert_eq!(client.view_user_balance(&bob, &brand_id), 100);
+
+        let issued = client.view_operation(&issue_op);
+        assert_eq!(issued.kind, OperationKind::Issue);
+        assert_eq!(issued.memo, Some(order));
+
+        let transfer = client.view_operation(&transfer_op);
+        assert_eq!(transfer.counterparty, Some(bob.clone()));
+        assert_eq!(transfer.memo, None);
+
+        assert_eq!(client.view_user_history(&alice, &0, &10).len(), 2);
+        let bob_history = client.view_user_history(&bob, &0, &10);
+        assert_eq!(bob_history.len(), 1);
+        assert_eq!(bob_history.get(0).unwrap().op_id, transfer_op);
+    }
+
+    #[test]
+    fn test_find_operation_by_reference() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        client.set_platform_admin(&Address::generate(&env));
+
+        let user = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Bakery"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Cake"), &50, &5);
+
+        let order = BytesN::from_array(&env, &[1; 32]);
+        let receipt = BytesN::from_array(&env, &[2; 32]);
+        client.issue_tokens(&user, &brand_id, &100, &Some(order.clone()));
+        let redeem_op = client.redeem_reward(&user, &reward_id, &Some(receipt.clone()));
+
+        let found = client.find_operation(&receipt);
+        assert_eq!(found.op_id, redeem_op);
+        assert_eq!(found.kind, OperationKind::Redeem);
+        assert_eq!(client.find_operation(&order).kind, OperationKind::Issue);
+
+        let unknown = BytesN::from_array(&env, &[3; 32]);
+        assert!(client.try_find_operation(&unknown).is_err());
+    }
+}
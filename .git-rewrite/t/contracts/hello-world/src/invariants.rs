@@ -0,0 +1,105 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Invariant checks compiled in with the `debug-assertions` feature
+// Every balance change re-verifies the touched brand, so a new feature that
+// mints, burns or moves points incorrectly fails the test that exercises it
+
+// Mapping for every address that ever held a brand's points
+#[contracttype]
+pub enum InvariantBook {
+    Holders(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Verify supply conservation and non-negative balances for every brand
+    pub fn check_invariants(env: Env) {
+        let brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+        for brand_id in 1..=brand_count {
+            check_brand(&env, brand_id);
+        }
+    }
+}
+
+// Remember a holder so its balance is included in the brand's checks
+pub(crate) fn note_holder(env: &Env, user: &Address, brand_id: u64) {
+    let mut holders = holders(env, brand_id);
+    if !holders.contains(user) {
+        holders.push_back(user.clone());
+        env.storage()
+            .instance()
+            .set(&InvariantBook::Holders(brand_id), &holders);
+    }
+}
+
+// A brand's supply equals the sum of its holders' balances and no balance is negative
+pub(crate) fn check_brand(env: &Env, brand_id: u64) {
+    let mut total: i64 = 0;
+    for holder in holders(env, brand_id).iter() {
+        let balance = stored_balance(env, &holder, brand_id);
+        if balance < 0 {
+            panic!("Invariant violated: negative balance");
+        }
+        total += balance;
+    }
+
+    let supply: i64 = env
+        .storage()
+        .instance()
+        .get(&BrandSupply::Supply(brand_id))
+        .unwrap_or(0);
+    if supply != total {
+        panic!("Invariant violated: supply does not match balances");
+    }
+}
+
+fn holders(env: &Env, brand_id: u64) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&InvariantBook::Holders(brand_id))
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_invariants_hold_across_operations() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        client.issue_tokens(&user1, &hotel, &1000, &None);
+        client.transfer_tokens(&user1, &user2, &hotel, &300, &None);
+        client.exchange_tokens(&user2, &hotel, &airline, &200);
+        client.check_invariants();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invariant violated: supply does not match balances")]
+    fn test_supply_drift_is_detected() {
+        let env = Env::default();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        client.issue_tokens(&Address::generate(&env), &hotel, &100, &None);
+
+        // Simulate a buggy feature minting supply without crediting anyone
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .set(&BrandSupply::Supply(hotel), &150_i64);
+        });
+        client.check_invariants();
+    }
+}
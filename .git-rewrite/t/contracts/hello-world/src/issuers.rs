@@ -0,0 +1,179 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store an address allowed to issue a brand's points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerRecord {
+    pub brand_id: u64,
+    pub issuer: Address,
+    pub added_at: u32,
+    // Set once the issuer is rotated out; revoked issuers cannot be re-added
+    pub revoked_at: Option<u32>,
+}
+
+// Mapping for brand issuers and the issuances attributed to them
+#[contracttype]
+pub enum IssuerBook {
+    Issuer(u64, Address),
+    // (Issuer, Brand) -> op_ids of the issuer's issuances, oldest first
+    Issuances(Address, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Authorize an issuer key for a brand (brand admin only)
+    pub fn add_issuer(env: Env, brand_id: u64, issuer: Address) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = IssuerBook::Issuer(brand_id, issuer.clone());
+        if let Some(existing) = env.storage().instance().get::<_, IssuerRecord>(&key) {
+            if existing.revoked_at.is_some() {
+                panic!("Issuer was revoked");
+            }
+            panic!("Issuer already added");
+        }
+
+        let record = IssuerRecord {
+            brand_id,
+            issuer,
+            added_at: env.ledger().sequence(),
+            revoked_at: None,
+        };
+        env.storage().instance().set(&key, &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Issuer added for brand {}", brand_id);
+    }
+
+    /// Revoke an issuer key (brand admin only); its past issuances stay attributed to it
+    pub fn revoke_issuer(env: Env, brand_id: u64, issuer: Address) {
+        require_brand_admin(&env, brand_id);
+
+        let mut record = Self::view_issuer(env.clone(), brand_id, issuer.clone())
+            .unwrap_or_else(|| panic!("Issuer not found"));
+        if record.revoked_at.is_some() {
+            panic!("Issuer already revoked");
+        }
+        record.revoked_at = Some(env.ledger().sequence());
+        env.storage()
+            .instance()
+            .set(&IssuerBook::Issuer(brand_id, issuer), &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Issuer revoked for brand {}", brand_id);
+    }
+
+    /// Issue points to a user as one of the brand's issuers
+    /// Returns the op_id of the recorded issuance
+    pub fn issue_as(
+        env: Env,
+        issuer: Address,
+        user: Address,
+        brand_id: u64,
+        amount: i64,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        issuer.require_auth();
+
+        let record = Self::view_issuer(env.clone(), brand_id, issuer.clone())
+            .unwrap_or_else(|| panic!("Not an issuer of this brand"));
+        if record.revoked_at.is_some() {
+            panic!("Issuer was revoked");
+        }
+        issue(&env, Some(issuer), user, brand_id, amount, memo)
+    }
+
+    /// View an issuer of a brand, including revoked ones
+    pub fn view_issuer(env: Env, brand_id: u64, issuer: Address) -> Option<IssuerRecord> {
+        env.storage()
+            .instance()
+            .get(&IssuerBook::Issuer(brand_id, issuer))
+    }
+
+    /// View a page of the issuances an issuer performed for a brand, oldest first
+    pub fn list_issuances_by(
+        env: Env,
+        issuer: Address,
+        brand_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<OperationRecord> {
+        let op_ids = issuance_ids(&env, &issuer, brand_id);
+        let mut records = Vec::new(&env);
+        let end = op_ids.len().min(start.saturating_add(limit));
+        for index in start..end {
+            let op_id = op_ids.get(index).unwrap();
+            records.push_back(Self::view_operation(env.clone(), op_id));
+        }
+        records
+    }
+}
+
+// Attribute an issuance to the issuer that performed it
+pub(crate) fn index_issuance(env: &Env, issuer: &Address, brand_id: u64, op_id: u64) {
+    let mut op_ids = issuance_ids(env, issuer, brand_id);
+    op_ids.push_back(op_id);
+    env.storage()
+        .instance()
+        .set(&IssuerBook::Issuances(issuer.clone(), brand_id), &op_ids);
+}
+
+fn issuance_ids(env: &Env, issuer: &Address, brand_id: u64) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&IssuerBook::Issuances(issuer.clone(), brand_id))
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_rotated_issuer_keeps_attribution() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let old_key = Address::generate(&env);
+        let new_key = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        assert!(client
+            .try_issue_as(&old_key, &user, &brand_id, &10, &None)
+            .is_err());
+        client.add_issuer(&brand_id, &old_key);
+        client.issue_as(&old_key, &user, &brand_id, &100, &None);
+        client.issue_as(&old_key, &user, &brand_id, &50, &None);
+
+        // Rotate: the new key takes over and the old one is blocked
+        client.add_issuer(&brand_id, &new_key);
+        client.revoke_issuer(&brand_id, &old_key);
+        assert!(client
+            .try_issue_as(&old_key, &user, &brand_id, &10, &None)
+            .is_err());
+        assert!(client.try_add_issuer(&brand_id, &old_key).is_err());
+        let op_id = client.issue_as(&new_key, &user, &brand_id, &25, &None);
+        assert_eq!(client.view_operation(&op_id).issuer, Some(new_key.clone()));
+
+        let old_issuances = client.list_issuances_by(&old_key, &brand_id, &0, &10);
+        assert_eq!(old_issuances.len(), 2);
+        assert_eq!(old_issuances.get(1).unwrap().amount, 50);
+        assert_eq!(
+            client.list_issuances_by(&old_key, &brand_id, &1, &10).len(),
+            1
+        );
+        assert_eq!(
+            client.list_issuances_by(&new_key, &brand_id, &0, &10).len(),
+            1
+        );
+        assert_eq!(client.view_user_balance(&user, &brand_id), 175);
+    }
+}
@@ -0,0 +1,646 @@
+#![no_std]
+mod analytics;
+mod api_keys;
+mod attestation;
+mod auction;
+mod balances;
+mod basket;
+mod bonding_curve;
+mod burn;
+mod circuit_breaker;
+mod claimable;
+mod coalition;
+mod decay;
+mod disputes;
+mod events;
+mod fee_discounts;
+mod history;
+#[cfg(feature = "debug-assertions")]
+mod interfaces;
+mod invariants;
+mod issuers;
+mod merge;
+mod params;
+mod pending_redemption;
+mod quick_pay;
+mod rate_history;
+mod receiver_whitelist;
+mod recovery;
+mod reference_value;
+mod reputation;
+mod reservation;
+mod revenue_share;
+mod reward_drops;
+mod rewards;
+mod routes;
+mod settlement;
+mod standing_order;
+mod sub_brand;
+mod subscription;
+mod treasury;
+mod validation;
+mod voting;
+
+pub use analytics::*;
+pub use api_keys::*;
+pub use attestation::*;
+pub use auction::*;
+pub use balances::*;
+pub use basket::*;
+pub use bonding_curve::*;
+pub use burn::*;
+pub use circuit_breaker::*;
+pub use claimable::*;
+pub use coalition::*;
+pub use decay::*;
+pub use disputes::*;
+pub use events::*;
+pub use fee_discounts::*;
+pub use history::*;
+pub use interfaces::*;
+pub use issuers::*;
+pub use merge::*;
+pub use params::*;
+pub use pending_redemption::*;
+pub use quick_pay::*;
+pub use rate_history::*;
+pub use receiver_whitelist::*;
+pub use recovery::*;
+pub use reference_value::*;
+pub use reputation::*;
+pub use reservation::*;
+pub use revenue_share::*;
+pub use reward_drops::*;
+pub use rewards::*;
+pub use routes::*;
+pub use settlement::*;
+pub use standing_order::*;
+pub use sub_brand::*;
+pub use subscription::*;
+pub use treasury::*;
+pub use validation::*;
+pub use voting::*;
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, log, symbol_short, Address, BytesN, Env, String, Symbol,
+};
+
+// Structure to store brand information
+#[contracttype]
+#[derive(Clone)]
+pub struct Brand {
+    pub brand_id: u64,
+    pub brand_name: String,
+    pub is_active: bool,
+}
+
+// Mapping for brands
+#[contracttype]
+pub enum BrandBook {
+    Brand(u64),
+}
+
+// Counter for brands
+const BRAND_COUNT: Symbol = symbol_short!("B_COUNT");
+
+// Platform administrator address
+const PLATFORM_ADMIN: Symbol = symbol_short!("P_ADMIN");
+
+// Denominator for rates expressed in basis points (10_000 = 1:1)
+pub const RATE_DENOMINATOR: i64 = 10_000;
+
+// Mapping for user balances
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UserBalance {
+    // (User, Brand) -> Balance; legacy layout in instance storage
+    Balance(Address, u64),
+    // User -> (Brand -> Balance); current layout in persistent storage
+    Balances(Address),
+    // User -> Last ledger the user's balance entry is known to be live until
+    LiveUntil(Address),
+}
+
+// Mapping for circulating supply: Brand -> Total balances held by users
+#[contracttype]
+pub enum BrandSupply {
+    Supply(u64),
+}
+
+// Mapping for brand administrators: Brand -> Admin
+#[contracttype]
+pub enum BrandAdmin {
+    Admin(u64),
+}
+
+#[contract]
+pub struct LoyaltyTokenExchange;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Register a new brand in the exchange platform
+    /// Returns the brand_id of the newly registered brand
+    pub fn register_brand(env: Env, brand_name: String) -> u64 {
+        create_brand(&env, brand_name)
+    }
+
+    /// Issue loyalty tokens to a user from a specific brand
+    /// `memo` carries an optional external reference such as a POS order id
+    /// Returns the op_id of the recorded operation
+    pub fn issue_tokens(
+        env: Env,
+        user: Address,
+        brand_id: u64,
+        amount: i64,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        user.require_auth();
+        issue(&env, None, user, brand_id, amount, memo)
+    }
+
+    /// Transfer tokens of a brand from one user to another
+    /// Returns the op_id of the recorded operation
+    pub fn transfer_tokens(
+        env: Env,
+        from: Address,
+        to: Address,
+        brand_id: u64,
+        amount: i64,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        from.require_auth();
+
+        let brand = Self::view_brand(env.clone(), brand_id);
+        if !brand.is_active {
+            panic!("Brand is not active");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if from == to {
+            panic!("Cannot transfer to yourself");
+        }
+        if !Self::can_receive(env.clone(), brand_id, to.clone()) {
+            panic!("Receiver is not whitelisted");
+        }
+
+        debit(&env, &from, brand_id, amount);
+        credit(&env, &to, brand_id, amount);
+
+        let mut record =
+            OperationRecord::new(&env, OperationKind::Transfer, &from, brand_id, amount);
+        record.counterparty = Some(to.clone());
+        record.memo = memo.clone();
+        save_operation(&env, &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        TokensTransferred {
+            brand_id,
+            from,
+            to,
+            amount,
+            op_id: record.op_id,
+            memo,
+        }
+        .publish(&env);
+
+        log!(
+            &env,
+            "✅ Transferred {} tokens of brand {}",
+            amount,
+            brand_id
+        );
+        record.op_id
+    }
+
+    /// Exchange tokens between two brands as priced by `quote_exchange`
+    /// Deprecated: forwards to `exchange_tokens_v2` with no minimum output,
+    /// no deadline and no memo
+    pub fn exchange_tokens(env: Env, user: Address, from_brand: u64, to_brand: u64, amount: i64) {
+        Self::exchange_tokens_v2(env, user, from_brand, to_brand, amount, 0, u32::MAX, None);
+    }
+
+    /// Exchange tokens between two brands as priced by `quote_exchange`
+    /// Fails if the output is below `min_out` or the ledger is past `deadline`
+    /// Returns the op_id of the recorded operation
+    #[allow(clippy::too_many_arguments)]
+    pub fn exchange_tokens_v2(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i64,
+        min_out: i64,
+        deadline: u32,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        user.require_auth();
+
+        if env.ledger().sequence() > deadline {
+            panic!("Deadline has passed");
+        }
+        exchange(&env, &user, from_brand, to_brand, amount, min_out, memo)
+    }
+
+    /// View user's token balance
+    pub fn view_user_balance(env: Env, user: Address, brand_id: u64) -> i64 {
+        read_balance(&env, &user, brand_id)
+    }
+
+    /// View the circulating supply of a brand
+    pub fn view_brand_supply(env: Env, brand_id: u64) -> i64 {
+        env.storage()
+            .instance()
+            .get(&BrandSupply::Supply(brand_id))
+            .unwrap_or(0)
+    }
+
+    /// View brand details by brand_id
+    pub fn view_brand(env: Env, brand_id: u64) -> Brand {
+        let key = BrandBook::Brand(brand_id);
+        env.storage().instance().get(&key).unwrap_or(Brand {
+            brand_id: 0,
+            brand_name: String::from_str(&env, "Not_Found"),
+            is_active: false,
+        })
+    }
+
+    /// Get total number of registered brands
+    pub fn get_brand_count(env: Env) -> u64 {
+        env.storage().instance().get(&BRAND_COUNT).unwrap_or(0)
+    }
+
+    /// Set the platform admin
+    /// The first call bootstraps the admin; later calls must be authorized by the current admin
+    pub fn set_platform_admin(env: Env, new_admin: Address) {
+        match env.storage().instance().get::<_, Address>(&PLATFORM_ADMIN) {
+            Some(admin) => admin.require_auth(),
+            None => new_admin.require_auth(),
+        }
+
+        env.storage().instance().set(&PLATFORM_ADMIN, &new_admin);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Platform admin updated");
+    }
+
+    /// View the platform admin, if one has been set
+    pub fn get_platform_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&PLATFORM_ADMIN)
+    }
+
+    /// Set the admin of a brand
+    /// Must be authorized by the current brand admin, or by the platform admin
+    /// when the brand has no admin yet
+    pub fn set_brand_admin(env: Env, brand_id: u64, new_admin: Address) {
+        load_brand(&env, brand_id);
+        match brand_admin(&env, brand_id) {
+            Some(admin) => admin.require_auth(),
+            None => {
+                require_platform_admin(&env);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .set(&BrandAdmin::Admin(brand_id), &new_admin);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Admin updated for brand {}", brand_id);
+    }
+
+    /// View the admin of a brand, including one inherited from a parent brand
+    pub fn get_brand_admin(env: Env, brand_id: u64) -> Option<Address> {
+        brand_admin(&env, brand_id)
+    }
+}
+
+// Validate and credit an issuance, record it with its issuer and announce it
+fn issue(
+    env: &Env,
+    issuer: Option<Address>,
+    user: Address,
+    brand_id: u64,
+    amount: i64,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    // Verify brand is active and amount is valid
+    if let Err(failure) = check_issue(env, brand_id, amount) {
+        failure.fail();
+    }
+
+    // Update user balance, or hold the points until a new user claims them
+    let claim_window = parking_window(env, &user, brand_id);
+    if claim_window.is_none() {
+        credit(env, &user, brand_id, amount);
+    }
+    record_brand_activity(env, brand_id);
+    track_issued(env, brand_id, amount);
+
+    let mut record = OperationRecord::new(env, OperationKind::Issue, &user, brand_id, amount);
+    record.memo = memo.clone();
+    record.issuer = issuer.clone();
+    save_operation(env, &record);
+    if let Some(issuer) = issuer {
+        index_issuance(env, &issuer, brand_id, record.op_id);
+    }
+    if let Some(window) = claim_window {
+        park_claimable(env, &user, brand_id, amount, record.op_id, window);
+    }
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    TokensIssued {
+        brand_id,
+        user,
+        amount,
+        op_id: record.op_id,
+        memo,
+    }
+    .publish(env);
+
+    log!(
+        env,
+        "✅ Issued {} tokens from brand {} to user",
+        amount,
+        brand_id
+    );
+    record.op_id
+}
+
+// Validate and settle an exchange at the current quote and record it
+fn exchange(
+    env: &Env,
+    user: &Address,
+    from_brand: u64,
+    to_brand: u64,
+    amount: i64,
+    min_out: i64,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    // Check amount, both brands and the source balance
+    if let Err(failure) = check_exchange(env, user, from_brand, to_brand, amount) {
+        failure.fail();
+    }
+
+    let quote = quote(env, Some(user), from_brand, to_brand, amount);
+    if quote.amount_out < min_out {
+        panic!("Output below minimum");
+    }
+
+    // Deduct from source balance
+    debit(env, user, from_brand, amount);
+    record_burn(env, from_brand, quote.burned);
+    settle_fee(env, &quote);
+    record_exchange_volume(env, user, amount);
+    record_rate_snapshot(env, from_brand, to_brand, quote.rate);
+    track_route_volume(env, from_brand, to_brand, amount);
+    track_exchanged(env, from_brand, to_brand, amount, quote.amount_out);
+
+    // Add to destination
+    credit(env, user, to_brand, quote.amount_out);
+    roll_reward_drop(env, user, to_brand);
+
+    let mut record = OperationRecord::new(env, OperationKind::Exchange, user, from_brand, amount);
+    record.to_brand = Some(to_brand);
+    record.memo = memo;
+    save_operation(env, &record);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(
+        env,
+        "✅ Exchanged {} tokens from brand {} → {} tokens of brand {}",
+        amount,
+        from_brand,
+        quote.amount_out,
+        to_brand
+    );
+    record.op_id
+}
+
+// Store a new active brand and return its id
+fn create_brand(env: &Env, brand_name: String) -> u64 {
+    // Get current brand count or start from 0
+    let mut brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+    brand_count += 1;
+
+    // Create new brand instance
+    let new_brand = Brand {
+        brand_id: brand_count,
+        brand_name,
+        is_active: true,
+    };
+
+    // Store the brand
+    env.storage()
+        .instance()
+        .set(&BrandBook::Brand(brand_count), &new_brand);
+    env.storage().instance().set(&BRAND_COUNT, &brand_count);
+    init_reputation(env, brand_count);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Brand registered with ID: {}", brand_count);
+    brand_count
+}
+
+// Require the platform admin's authorization and return its address
+fn require_platform_admin(env: &Env) -> Address {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&PLATFORM_ADMIN)
+        .unwrap_or_else(|| panic!("Platform admin not set"));
+    admin.require_auth();
+    admin
+}
+
+// Load a brand, panicking if it was never registered
+fn load_brand(env: &Env, brand_id: u64) -> Brand {
+    env.storage()
+        .instance()
+        .get(&BrandBook::Brand(brand_id))
+        .unwrap_or_else(|| panic!("Brand not found"))
+}
+
+// Resolve a brand's admin; sub-brands without their own admin use the parent's
+fn brand_admin(env: &Env, brand_id: u64) -> Option<Address> {
+    let admin: Option<Address> = env.storage().instance().get(&BrandAdmin::Admin(brand_id));
+    if admin.is_some() {
+        return admin;
+    }
+    let parent: Option<u64> = env
+        .storage()
+        .instance()
+        .get(&SubBrandBook::Parent(brand_id));
+    parent.and_then(|parent_id| brand_admin(env, parent_id))
+}
+
+// Require the brand admin's authorization and return its address
+fn require_brand_admin(env: &Env, brand_id: u64) -> Address {
+    let admin = brand_admin(env, brand_id).unwrap_or_else(|| panic!("Brand admin not set"));
+    admin.require_auth();
+    admin
+}
+
+// Spendable balance, net of any decay pending on an inactive balance
+fn read_balance(env: &Env, user: &Address, brand_id: u64) -> i64 {
+    stored_balance(env, user, brand_id) - pending_decay(env, user, brand_id)
+}
+
+// Add to a user's balance and to the brand's circulating supply
+fn credit(env: &Env, user: &Address, brand_id: u64, amount: i64) {
+    settle_decay(env, user, brand_id);
+    let balance = read_balance(env, user, brand_id);
+    write_balance(env, user, brand_id, balance + amount);
+    adjust_supply(env, brand_id, amount);
+    track_votes(env, user, brand_id, amount);
+
+    #[cfg(feature = "debug-assertions")]
+    {
+        invariants::note_holder(env, user, brand_id);
+        invariants::check_brand(env, brand_id);
+    }
+}
+
+// Remove from a user's balance and from the brand's circulating supply
+fn debit(env: &Env, user: &Address, brand_id: u64, amount: i64) {
+    settle_decay(env, user, brand_id);
+    let balance = read_balance(env, user, brand_id);
+    if balance < amount {
+        panic!("Insufficient balance");
+    }
+    write_balance(env, user, brand_id, balance - amount);
+    adjust_supply(env, brand_id, -amount);
+    track_votes(env, user, brand_id, -amount);
+
+    #[cfg(feature = "debug-assertions")]
+    invariants::check_brand(env, brand_id);
+}
+
+fn adjust_supply(env: &Env, brand_id: u64, delta: i64) {
+    let supply_key = BrandSupply::Supply(brand_id);
+    let supply: i64 = env.storage().instance().get(&supply_key).unwrap_or(0);
+    env.storage().instance().set(&supply_key, &(supply + delta));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_register_brand() {
+        let env = Env::default();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let brand_name = String::from_str(&env, "Starbucks");
+        let brand_id = client.register_brand(&brand_name);
+
+        assert_eq!(brand_id, 1);
+        let brand = client.view_brand(&brand_id);
+        assert_eq!(brand.brand_name, brand_name);
+        assert!(brand.is_active);
+    }
+
+    #[test]
+    fn test_issue_and_view_tokens() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let brand_name = String::from_str(&env, "Nike");
+        let brand_id = client.register_brand(&brand_name);
+
+        client.issue_tokens(&user, &brand_id, &1000, &None);
+        let balance = client.view_user_balance(&user, &brand_id);
+        assert_eq!(balance, 1000);
+    }
+
+    #[test]
+    fn test_exchange_tokens() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+
+        let brand1 = String::from_str(&env, "Amazon");
+        let brand2 = String::from_str(&env, "Apple");
+
+        let brand_id_1 = client.register_brand(&brand1);
+        let brand_id_2 = client.register_brand(&brand2);
+
+        client.issue_tokens(&user, &brand_id_1, &1000, &None);
+        client.exchange_tokens(&user, &brand_id_1, &brand_id_2, &500);
+
+        let balance1 = client.view_user_balance(&user, &brand_id_1);
+        let balance2 = client.view_user_balance(&user, &brand_id_2);
+
+        assert_eq!(balance1, 500);
+        assert_eq!(balance2, 500);
+    }
+
+    #[test]
+    fn test_brand_supply_follows_exchanges() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let brand_id_1 = client.register_brand(&String::from_str(&env, "Adidas"));
+        let brand_id_2 = client.register_brand(&String::from_str(&env, "Puma"));
+
+        client.issue_tokens(&user, &brand_id_1, &1000, &None);
+        client.exchange_tokens(&user, &brand_id_1, &brand_id_2, &400);
+
+        assert_eq!(client.view_brand_supply(&brand_id_1), 600);
+        assert_eq!(client.view_brand_supply(&brand_id_2), 400);
+    }
+
+    #[test]
+    fn test_set_brand_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let platform_admin = Address::generate(&env);
+        let brand_admin = Address::generate(&env);
+        client.set_platform_admin(&platform_admin);
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Lego"));
+        assert_eq!(client.get_brand_admin(&brand_id), None);
+
+        client.set_brand_admin(&brand_id, &brand_admin);
+        assert_eq!(client.get_brand_admin(&brand_id), Some(brand_admin));
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient balance")]
+    fn test_exchange_insufficient_balance() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+
+        let brand1 = String::from_str(&env, "Tesla");
+        let brand2 = String::from_str(&env, "SpaceX");
+
+        let brand_id_1 = client.register_brand(&brand1);
+        let brand_id_2 = client.register_brand(&brand2);
+
+        client.issue_tokens(&user, &brand_id_1, &100, &None);
+        client.exchange_tokens(&user, &brand_id_1, &brand_id_2, &500);
+    }
+}
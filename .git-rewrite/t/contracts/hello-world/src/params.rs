@@ -0,0 +1,267 @@
+use crate::*;
+
+// Parameters that can only change through the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Param {
+    // Platform fee on every exchange, in basis points
+    ExchangeFeeBps,
+    // (From, To) -> Rate overriding the coalition or parity rate
+    RouteRate(u64, u64),
+    // Brand -> Maximum circulating supply reachable through issuance
+    SupplyCap(u64),
+}
+
+impl Param {
+    // Brand a parameter belongs to; the source brand for route rates
+    pub fn brand_id(&self) -> u64 {
+        match self {
+            Param::ExchangeFeeBps => PLATFORM_BRAND_ID,
+            Param::RouteRate(from_brand, _) => *from_brand,
+            Param::SupplyCap(brand_id) => *brand_id,
+        }
+    }
+}
+
+// Structure to store a queued parameter change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledChange {
+    pub change_id: u64,
+    pub param: Param,
+    pub value: i64,
+    // First ledger at which the change can be executed
+    pub eta: u32,
+}
+
+// Mapping for parameters and their queued changes
+#[contracttype]
+pub enum ParamBook {
+    Value(Param),
+    Change(u64),
+}
+
+// Counter for scheduled changes
+const CHANGE_COUNT: Symbol = symbol_short!("CH_COUNT");
+
+// Delay between scheduling and executing a change (about one day)
+pub const TIMELOCK_LEDGERS: u32 = 17_280;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Queue a parameter change that can be executed after TIMELOCK_LEDGERS
+    /// Fees and route rates are managed by the platform admin, supply caps by the brand admin
+    /// Returns the change_id of the queued change
+    pub fn schedule_change(env: Env, param: Param, value: i64) -> u64 {
+        require_param_admin(&env, &param);
+        validate_param(&param, value);
+
+        let mut change_count: u64 = env.storage().instance().get(&CHANGE_COUNT).unwrap_or(0);
+        change_count += 1;
+
+        let change = ScheduledChange {
+            change_id: change_count,
+            param: param.clone(),
+            value,
+            eta: env.ledger().sequence() + TIMELOCK_LEDGERS,
+        };
+        env.storage()
+            .instance()
+            .set(&ParamBook::Change(change_count), &change);
+        env.storage().instance().set(&CHANGE_COUNT, &change_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        ChangeScheduled {
+            brand_id: param.brand_id(),
+            change_id: change_count,
+            param,
+            value,
+            eta: change.eta,
+        }
+        .publish(&env);
+        change_count
+    }
+
+    /// Apply a queued change once its timelock has passed; anyone may call this
+    pub fn execute_change(env: Env, change_id: u64) {
+        let change = load_change(&env, change_id);
+        if env.ledger().sequence() < change.eta {
+            panic!("Timelock has not expired");
+        }
+
+        env.storage()
+            .instance()
+            .set(&ParamBook::Value(change.param.clone()), &change.value);
+        env.storage()
+            .instance()
+            .remove(&ParamBook::Change(change_id));
+        if let Param::RouteRate(from_brand, to_brand) = change.param {
+            record_rate_snapshot(&env, from_brand, to_brand, change.value as u32);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        ChangeExecuted {
+            brand_id: change.param.brand_id(),
+            change_id,
+            param: change.param,
+            value: change.value,
+        }
+        .publish(&env);
+    }
+
+    /// Withdraw a queued change (same admin as for scheduling)
+    pub fn cancel_change(env: Env, change_id: u64) {
+        let change = load_change(&env, change_id);
+        require_param_admin(&env, &change.param);
+
+        env.storage()
+            .instance()
+            .remove(&ParamBook::Change(change_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        ChangeCancelled {
+            brand_id: change.param.brand_id(),
+            change_id,
+            param: change.param,
+        }
+        .publish(&env);
+    }
+
+    /// View a queued change, if it is still pending
+    pub fn view_scheduled_change(env: Env, change_id: u64) -> Option<ScheduledChange> {
+        env.storage().instance().get(&ParamBook::Change(change_id))
+    }
+
+    /// View the current value of a parameter, if it has been set
+    pub fn view_param(env: Env, param: Param) -> Option<i64> {
+        param_value(&env, &param)
+    }
+}
+
+pub(crate) fn param_value(env: &Env, param: &Param) -> Option<i64> {
+    env.storage()
+        .instance()
+        .get(&ParamBook::Value(param.clone()))
+}
+
+fn require_param_admin(env: &Env, param: &Param) {
+    match param {
+        Param::SupplyCap(brand_id) => {
+            load_brand(env, *brand_id);
+            require_brand_admin(env, *brand_id);
+        }
+        Param::ExchangeFeeBps | Param::RouteRate(..) => {
+            require_platform_admin(env);
+        }
+    }
+}
+
+fn validate_param(param: &Param, value: i64) {
+    match param {
+        Param::ExchangeFeeBps => {
+            if !(0..=RATE_DENOMINATOR).contains(&value) {
+                panic!("Fee must be between 0 and 10000 bps");
+            }
+        }
+        Param::RouteRate(from_brand, to_brand) => {
+            if from_brand == to_brand {
+                panic!("Cannot exchange to the same brand");
+            }
+            if value <= 0 || value > u32::MAX as i64 {
+                panic!("Rate out of range");
+            }
+        }
+        Param::SupplyCap(_) => {
+            if value < 0 {
+                panic!("Cap cannot be negative");
+            }
+        }
+    }
+}
+
+fn load_change(env: &Env, change_id: u64) -> ScheduledChange {
+    env.storage()
+        .instance()
+        .get(&ParamBook::Change(change_id))
+        .unwrap_or_else(|| panic!("Scheduled change not found"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64) {
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let hotel = client.register_brand(&String::from_str(env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(env, "Airline"));
+        (client, hotel, airline)
+    }
+
+    #[test]
+    fn test_fee_and_rate_changes_wait_for_timelock() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, airline) = setup(&env);
+
+        let fee_change = client.schedule_change(&Param::ExchangeFeeBps, &100);
+        let rate_change = client.schedule_change(&Param::RouteRate(hotel, airline), &20000);
+        assert!(client.try_execute_change(&fee_change).is_err());
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&fee_change);
+        client.execute_change(&rate_change);
+        assert_eq!(client.view_param(&Param::ExchangeFeeBps), Some(100));
+        assert_eq!(client.view_scheduled_change(&fee_change), None);
+
+        // 1% fee on 1000 hotel points, the rest converts at 2:1
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!(quote.fee, 10);
+        assert_eq!(quote.amount_out, 1980);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_user_balance(&user, &airline), 1980);
+        assert_eq!(client.view_collected_fees(&hotel), 10);
+    }
+
+    #[test]
+    fn test_supply_cap_limits_issuance() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, _) = setup(&env);
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+
+        let change_id = client.schedule_change(&Param::SupplyCap(hotel), &500);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+        assert_eq!(
+            client.can_issue(&hotel, &1),
+            Some(CheckFailure::SupplyCapExceeded)
+        );
+    }
+
+    #[test]
+    fn test_cancelled_change_cannot_execute() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, _) = setup(&env);
+
+        let change_id = client.schedule_change(&Param::ExchangeFeeBps, &50);
+        client.cancel_change(&change_id);
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        assert!(client.try_execute_change(&change_id).is_err());
+        assert_eq!(client.view_param(&Param::ExchangeFeeBps), None);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::*;
+
+// Structure to store a redemption whose point cost is locked during checkout
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingRedemption {
+    pub pending_id: u64,
+    pub user: Address,
+    pub reward_id: u64,
+    pub brand_id: u64,
+    pub locked_cost: i64,
+    pub expires_at: u32,
+}
+
+// Mapping for pending redemptions
+#[contracttype]
+pub enum PendingRedemptionBook {
+    Pending(u64),
+}
+
+// Counter for pending redemptions
+const PENDING_COUNT: Symbol = symbol_short!("PR_COUNT");
+
+// Number of ledgers a started redemption keeps its locked cost
+pub const REDEMPTION_LOCK_LEDGERS: u32 = 720;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Start a redemption, locking the reward's current cost and reserving one
+    /// unit of stock for REDEMPTION_LOCK_LEDGERS ledgers
+    /// Returns the pending_id to finalize or cancel
+    pub fn start_redemption(env: Env, user: Address, reward_id: u64) -> u64 {
+        user.require_auth();
+
+        let mut reward = load_reward(&env, reward_id);
+        if !reward.is_active {
+            panic!("Reward is not active");
+        }
+        if available_stock(&env, &reward) == 0 {
+            panic!("Reward is out of stock");
+        }
+        let brand = load_brand(&env, reward.brand_id);
+        if !brand.is_active {
+            panic!("Brand is not active");
+        }
+
+        reward.stock -= 1;
+        save_reward(&env, &reward);
+
+        let mut pending_count: u64 = env.storage().instance().get(&PENDING_COUNT).unwrap_or(0);
+        pending_count += 1;
+
+        let pending = PendingRedemption {
+            pending_id: pending_count,
+            user,
+            reward_id,
+            brand_id: reward.brand_id,
+            locked_cost: reward.cost,
+            expires_at: env.ledger().sequence() + REDEMPTION_LOCK_LEDGERS,
+        };
+        env.storage()
+            .instance()
+            .set(&PendingRedemptionBook::Pending(pending_count), &pending);
+        env.storage().instance().set(&PENDING_COUNT, &pending_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Redemption {} started at locked cost {}",
+            pending_count,
+            reward.cost
+        );
+        pending_count
+    }
+
+    /// Finalize a pending redemption, paying the locked cost
+    pub fn finalize_redemption(env: Env, user: Address, pending_id: u64) {
+        user.require_auth();
+
+        let pending = load_pending(&env, pending_id);
+        if pending.user != user {
+            panic!("Not the redeemer");
+        }
+        if env.ledger().sequence() > pending.expires_at {
+            panic!("Price lock expired");
+        }
+
+        debit(&env, &user, pending.brand_id, pending.locked_cost);
+        let reward = load_reward(&env, pending.reward_id);
+        record_redemption(&env, &user, &reward, pending.locked_cost, None);
+        env.storage()
+            .instance()
+            .remove(&PendingRedemptionBook::Pending(pending_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Redemption {} finalized for {} tokens",
+            pending_id,
+            pending.locked_cost
+        );
+    }
+
+    /// Cancel a pending redemption and release its reserved stock
+    pub fn cancel_redemption(env: Env, user: Address, pending_id: u64) {
+        user.require_auth();
+
+        let pending = load_pending(&env, pending_id);
+        if pending.user != user {
+            panic!("Not the redeemer");
+        }
+        release_pending(&env, &pending);
+
+        log!(&env, "✅ Redemption {} cancelled", pending_id);
+    }
+
+    /// Release the reserved stock of an expired pending redemption; anyone may call this
+    pub fn release_expired_redemption(env: Env, pending_id: u64) {
+        let pending = load_pending(&env, pending_id);
+        if env.ledger().sequence() <= pending.expires_at {
+            panic!("Price lock has not expired");
+        }
+        release_pending(&env, &pending);
+
+        log!(&env, "✅ Expired redemption {} released", pending_id);
+    }
+
+    /// View a pending redemption, if it is still open
+    pub fn view_pending_redemption(env: Env, pending_id: u64) -> Option<PendingRedemption> {
+        env.storage()
+            .instance()
+            .get(&PendingRedemptionBook::Pending(pending_id))
+    }
+}
+
+fn load_pending(env: &Env, pending_id: u64) -> PendingRedemption {
+    env.storage()
+        .instance()
+        .get(&PendingRedemptionBook::Pending(pending_id))
+        .unwrap_or_else(|| panic!("Pending redemption not found"))
+}
+
+// Return the reserved unit to the reward's stock and drop the record
+fn release_pending(env: &Env, pending: &PendingRedemption) {
+    let mut reward = load_reward(env, pending.reward_id);
+    reward.stock += 1;
+    save_reward(env, &reward);
+
+    env.storage()
+        .instance()
+        .remove(&PendingRedemptionBook::Pending(pending.pending_id));
+    env.storage().instance().extend_ttl(100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64, Address) {
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let brand_id = client.register_brand(&String::from_str(env, "Books"));
+        client.set_brand_admin(&brand_id, &Address::generate(env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(env, "Novel"), &100, &1);
+
+        let user = Address::generate(env);
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        (client, brand_id, reward_id, user)
+    }
+
+    #[test]
+    fn test_locked_cost_survives_repricing() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, reward_id, user) = setup(&env);
+
+        let pending_id = client.start_redemption(&user, &reward_id);
+        assert_eq!(client.view_reward(&reward_id).stock, 0);
+
+        client.set_reward_cost(&reward_id, &300);
+        client.finalize_redemption(&user, &pending_id);
+
+        assert_eq!(client.view_user_balance(&user, &brand_id), 400);
+        assert_eq!(client.view_pending_redemption(&pending_id), None);
+    }
+
+    #[test]
+    fn test_cancel_and_expiry_release_stock() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, reward_id, user) = setup(&env);
+
+        let pending_id = client.start_redemption(&user, &reward_id);
+        client.cancel_redemption(&user, &pending_id);
+        assert_eq!(client.view_reward(&reward_id).stock, 1);
+
+        let pending_id = client.start_redemption(&user, &reward_id);
+        env.ledger()
+            .set_sequence_number(REDEMPTION_LOCK_LEDGERS + 1);
+        assert!(client.try_finalize_redemption(&user, &pending_id).is_err());
+
+        client.release_expired_redemption(&pending_id);
+        assert_eq!(client.view_reward(&reward_id).stock, 1);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure storing the guardians a user trusts to recover their balances
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianSet {
+    pub guardians: Vec<Address>,
+    // Number of guardian approvals needed to recover
+    pub threshold: u32,
+}
+
+// Structure to store an in-progress recovery of a user's balances
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub user: Address,
+    pub new_address: Address,
+    pub approvals: Vec<Address>,
+    // Ledger from which the recovery can be executed; 0 until the threshold is met
+    pub ready_at: u32,
+}
+
+// Mapping for guardians and pending recoveries, keyed by the user being recovered
+#[contracttype]
+pub enum RecoveryBook {
+    Guardians(Address),
+    Request(Address),
+}
+
+// Waiting period after guardians approve, during which the user can veto (about 3 days)
+pub const RECOVERY_DELAY_LEDGERS: u32 = 51_840;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Opt in to social recovery by naming guardians and an approval threshold
+    /// Calling it again replaces the guardian set
+    pub fn set_guardians(env: Env, user: Address, guardians: Vec<Address>, threshold: u32) {
+        user.require_auth();
+
+        if threshold == 0 || threshold > guardians.len() {
+            panic!("Invalid guardian threshold");
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardian == user {
+                panic!("User cannot guard themselves");
+            }
+            if guardians.first_index_of(&guardian) != Some(i as u32) {
+                panic!("Duplicate guardian");
+            }
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&RecoveryBook::Request(user.clone()))
+        {
+            panic!("Recovery in progress");
+        }
+
+        env.storage().instance().set(
+            &RecoveryBook::Guardians(user),
+            &GuardianSet {
+                guardians,
+                threshold,
+            },
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Guardians set with threshold {}", threshold);
+    }
+
+    /// Start recovering `user`'s balances to `new_address` (guardian only)
+    /// The initiating guardian's approval is counted
+    pub fn initiate_recovery(env: Env, guardian: Address, user: Address, new_address: Address) {
+        guardian.require_auth();
+        require_guardian(&env, &user, &guardian);
+
+        if new_address == user {
+            panic!("New address must differ from the user");
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&RecoveryBook::Request(user.clone()))
+        {
+            panic!("Recovery in progress");
+        }
+
+        let request = RecoveryRequest {
+            user: user.clone(),
+            new_address,
+            approvals: Vec::new(&env),
+            ready_at: 0,
+        };
+        add_approval(&env, request, guardian);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Recovery initiated");
+    }
+
+    /// Approve a pending recovery (guardian only)
+    /// Reaching the threshold starts the veto window
+    pub fn approve_recovery(env: Env, guardian: Address, user: Address) {
+        guardian.require_auth();
+        require_guardian(&env, &user, &guardian);
+
+        let request = load_recovery(&env, &user);
+        if request.approvals.contains(&guardian) {
+            panic!("Guardian already approved");
+        }
+        add_approval(&env, request, guardian);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Recovery approved");
+    }
+
+    /// Veto a pending recovery with the original key
+    pub fn veto_recovery(env: Env, user: Address) {
+        user.require_auth();
+        load_recovery(&env, &user);
+
+        env.storage()
+            .instance()
+            .remove(&RecoveryBook::Request(user));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Recovery vetoed");
+    }
+
+    /// Move every brand balance of `user` to the approved new address once the
+    /// veto window has passed; anyone may call this
+    /// The guardian set is cleared and must be configured again for the new address
+    pub fn execute_recovery(env: Env, user: Address) -> Address {
+        let request = load_recovery(&env, &user);
+        if request.ready_at == 0 {
+            panic!("Not enough guardian approvals");
+        }
+        if env.ledger().sequence() < request.ready_at {
+            panic!("Veto window has not passed");
+        }
+
+        let brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+        for brand_id in 1..=brand_count {
+            let balance = read_balance(&env, &user, brand_id);
+            if balance > 0 {
+                debit(&env, &user, brand_id, balance);
+                credit(&env, &request.new_address, brand_id, balance);
+                BalancesRecovered {
+                    brand_id,
+                    user: user.clone(),
+                    new_address: request.new_address.clone(),
+                    amount: balance,
+                }
+                .publish(&env);
+            }
+        }
+
+        env.storage()
+            .instance()
+            .remove(&RecoveryBook::Request(user.clone()));
+        env.storage()
+            .instance()
+            .remove(&RecoveryBook::Guardians(user.clone()));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Balances recovered");
+        request.new_address
+    }
+
+    /// View the guardian set of a user, if they opted in
+    pub fn view_guardians(env: Env, user: Address) -> Option<GuardianSet> {
+        env.storage().instance().get(&RecoveryBook::Guardians(user))
+    }
+
+    /// View the pending recovery of a user, if any
+    pub fn view_recovery(env: Env, user: Address) -> Option<RecoveryRequest> {
+        env.storage().instance().get(&RecoveryBook::Request(user))
+    }
+}
+
+fn require_guardian(env: &Env, user: &Address, guardian: &Address) -> GuardianSet {
+    let set: GuardianSet = env
+        .storage()
+        .instance()
+        .get(&RecoveryBook::Guardians(user.clone()))
+        .unwrap_or_else(|| panic!("User has no guardians"));
+    if !set.guardians.contains(guardian) {
+        panic!("Not a guardian");
+    }
+    set
+}
+
+fn load_recovery(env: &Env, user: &Address) -> RecoveryRequest {
+    env.storage()
+        .instance()
+        .get(&RecoveryBook::Request(user.clone()))
+        .unwrap_or_else(|| panic!("No recovery in progress"))
+}
+
+// Record a guardian approval and start the veto window once the threshold is met
+fn add_approval(env: &Env, mut request: RecoveryRequest, guardian: Address) {
+    let set = require_guardian(env, &request.user, &guardian);
+    request.approvals.push_back(guardian);
+    if request.ready_at == 0 && request.approvals.len() >= set.threshold {
+        request.ready_at = env.ledger().sequence() + RECOVERY_DELAY_LEDGERS;
+    }
+    env.storage()
+        .instance()
+        .set(&RecoveryBook::Request(request.user.clone()), &request);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    #[test]
+    fn test_guardians_recover_balances_after_delay() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &400, &None);
+        client.issue_tokens(&user, &airline, &250, &None);
+
+        let g1 = Address::generate(&env);
+        let g2 = Address::generate(&env);
+        let g3 = Address::generate(&env);
+        client.set_guardians(&user, &vec![&env, g1.clone(), g2.clone(), g3], &2);
+
+        let new_address = Address::generate(&env);
+        client.initiate_recovery(&g1, &user, &new_address);
+        assert!(client.try_execute_recovery(&user).is_err());
+
+        client.approve_recovery(&g2, &user);
+        assert!(client.try_execute_recovery(&user).is_err());
+
+        env.ledger().set_sequence_number(RECOVERY_DELAY_LEDGERS);
+        assert_eq!(client.execute_recovery(&user), new_address);
+        assert_eq!(client.view_user_balance(&user, &hotel), 0);
+        assert_eq!(client.view_user_balance(&new_address, &hotel), 400);
+        assert_eq!(client.view_user_balance(&new_address, &airline), 250);
+        assert_eq!(client.view_brand_supply(&hotel), 400);
+        assert_eq!(client.view_guardians(&user), None);
+    }
+
+    #[test]
+    fn test_user_can_veto_recovery() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let guardian = Address::generate(&env);
+        client.set_guardians(&user, &vec![&env, guardian.clone()], &1);
+        client.initiate_recovery(&guardian, &user, &Address::generate(&env));
+        assert!(client.view_recovery(&user).unwrap().ready_at > 0);
+
+        client.veto_recovery(&user);
+        assert_eq!(client.view_recovery(&user), None);
+
+        env.ledger().set_sequence_number(RECOVERY_DELAY_LEDGERS);
+        assert!(client.try_execute_recovery(&user).is_err());
+        assert!(client
+            .try_initiate_recovery(&Address::generate(&env), &user, &guardian)
+            .is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::*;
+
+// Structure comparing an exchange quote with the fair value of the points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FairValueComparison {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub amount_in: i64,
+    // Reference units the input points are worth
+    pub value_in: i64,
+    // Destination points worth the same as the input at reference values
+    pub fair_amount_out: i64,
+    // Destination points the exchange would actually pay
+    pub quoted_amount_out: i64,
+}
+
+// Mapping for brand reference values: Brand -> Points per reference unit
+#[contracttype]
+pub enum ReferenceValueBook {
+    PointsPerUnit(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set how many points of a brand are worth one reference unit, e.g. one cent
+    /// (brand admin only); used for display only, never for settlement
+    pub fn set_reference_value(env: Env, brand_id: u64, points_per_unit: i64) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if points_per_unit <= 0 {
+            panic!("Points per unit must be positive");
+        }
+
+        env.storage().instance().set(
+            &ReferenceValueBook::PointsPerUnit(brand_id),
+            &points_per_unit,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} valued at {} points per unit",
+            brand_id,
+            points_per_unit
+        );
+    }
+
+    /// View how many points of a brand are worth one reference unit, if set
+    pub fn view_reference_value(env: Env, brand_id: u64) -> Option<i64> {
+        env.storage()
+            .instance()
+            .get(&ReferenceValueBook::PointsPerUnit(brand_id))
+    }
+
+    /// Approximate worth of `amount` points in reference units, rounded down
+    pub fn value_of(env: Env, brand_id: u64, amount: i64) -> i64 {
+        amount / points_per_unit(&env, brand_id)
+    }
+
+    /// Compare what an exchange would pay with what the points are worth at
+    /// the two brands' reference values
+    pub fn compare_fair_value(
+        env: Env,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i64,
+    ) -> FairValueComparison {
+        let from_points = points_per_unit(&env, from_brand);
+        let to_points = points_per_unit(&env, to_brand);
+        let quote = Self::quote_exchange(env.clone(), from_brand, to_brand, amount);
+
+        FairValueComparison {
+            from_brand,
+            to_brand,
+            amount_in: amount,
+            value_in: amount / from_points,
+            fair_amount_out: (amount as i128 * to_points as i128 / from_points as i128) as i64,
+            quoted_amount_out: quote.amount_out,
+        }
+    }
+}
+
+fn points_per_unit(env: &Env, brand_id: u64) -> i64 {
+    env.storage()
+        .instance()
+        .get(&ReferenceValueBook::PointsPerUnit(brand_id))
+        .unwrap_or_else(|| panic!("Reference value not set"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_value_and_fair_value_comparison() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        client.set_brand_admin(&airline, &Address::generate(&env));
+        assert!(client.try_value_of(&hotel, &100).is_err());
+
+        // A hotel point is worth half a cent, an airline point one cent
+        client.set_reference_value(&hotel, &2);
+        client.set_reference_value(&airline, &1);
+        assert_eq!(client.value_of(&hotel, &1001), 500);
+
+        let comparison = client.compare_fair_value(&hotel, &airline, &1000);
+        assert_eq!(comparison.value_in, 500);
+        assert_eq!(comparison.fair_amount_out, 500);
+        assert_eq!(comparison.quoted_amount_out, 1000);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a user's place in line for an out-of-stock reward
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    pub reservation_id: u64,
+    pub user: Address,
+    pub reward_id: u64,
+    pub brand_id: u64,
+    // Points taken from the user when joining the queue
+    pub locked_cost: i64,
+    pub created_at: u32,
+}
+
+// Mapping for reservations
+#[contracttype]
+pub enum ReservationBook {
+    Reservation(u64),
+    // Reward -> reservation_ids waiting for stock, oldest first
+    Queue(u64),
+}
+
+// Counter for reservations
+const RESERVATION_COUNT: Symbol = symbol_short!("RV_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Join the reservation queue of an out-of-stock reward, locking its current cost
+    /// Returns the reservation_id
+    pub fn reserve_reward(env: Env, user: Address, reward_id: u64) -> u64 {
+        user.require_auth();
+
+        let reward = load_reward(&env, reward_id);
+        if !reward.is_active {
+            panic!("Reward is not active");
+        }
+        if available_stock(&env, &reward) > 0 {
+            panic!("Reward is in stock");
+        }
+        if !load_brand(&env, reward.brand_id).is_active {
+            panic!("Brand is not active");
+        }
+
+        debit(&env, &user, reward.brand_id, reward.cost);
+
+        let mut reservation_count: u64 = env
+            .storage()
+            .instance()
+            .get(&RESERVATION_COUNT)
+            .unwrap_or(0);
+        reservation_count += 1;
+
+        let reservation = Reservation {
+            reservation_id: reservation_count,
+            user,
+            reward_id,
+            brand_id: reward.brand_id,
+            locked_cost: reward.cost,
+            created_at: env.ledger().sequence(),
+        };
+        env.storage().instance().set(
+            &ReservationBook::Reservation(reservation_count),
+            &reservation,
+        );
+        let mut queue = reservation_queue(&env, reward_id);
+        queue.push_back(reservation_count);
+        save_queue(&env, reward_id, &queue);
+        env.storage()
+            .instance()
+            .set(&RESERVATION_COUNT, &reservation_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Reservation {} queued for reward {}",
+            reservation_count,
+            reward_id
+        );
+        reservation_count
+    }
+
+    /// Fulfill the oldest reservation of a reward from its stock; anyone may call this
+    /// Returns the op_id of the recorded redemption
+    pub fn fulfill_next(env: Env, reward_id: u64) -> u64 {
+        let mut reward = load_reward(&env, reward_id);
+        if reward.stock == 0 {
+            panic!("Reward is out of stock");
+        }
+
+        let mut queue = reservation_queue(&env, reward_id);
+        let reservation_id = queue
+            .pop_front()
+            .unwrap_or_else(|| panic!("No reservations waiting"));
+        let reservation = load_reservation(&env, reservation_id);
+
+        reward.stock -= 1;
+        save_reward(&env, &reward);
+        let op_id = record_redemption(
+            &env,
+            &reservation.user,
+            &reward,
+            reservation.locked_cost,
+            None,
+        );
+
+        save_queue(&env, reward_id, &queue);
+        env.storage()
+            .instance()
+            .remove(&ReservationBook::Reservation(reservation_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Reservation {} fulfilled", reservation_id);
+        op_id
+    }
+
+    /// Leave the queue and get the locked points back (reserving user only)
+    pub fn cancel_reservation(env: Env, user: Address, reservation_id: u64) {
+        user.require_auth();
+
+        let reservation = load_reservation(&env, reservation_id);
+        if reservation.user != user {
+            panic!("Not the reserving user");
+        }
+
+        credit(&env, &user, reservation.brand_id, reservation.locked_cost);
+        let mut queue = reservation_queue(&env, reservation.reward_id);
+        if let Some(index) = queue.first_index_of(reservation_id) {
+            queue.remove(index);
+        }
+        save_queue(&env, reservation.reward_id, &queue);
+        env.storage()
+            .instance()
+            .remove(&ReservationBook::Reservation(reservation_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Reservation {} cancelled", reservation_id);
+    }
+
+    /// View a reservation, if it is still waiting
+    pub fn view_reservation(env: Env, reservation_id: u64) -> Option<Reservation> {
+        env.storage()
+            .instance()
+            .get(&ReservationBook::Reservation(reservation_id))
+    }
+
+    /// View the reservations waiting for a reward, oldest first
+    pub fn view_reservation_queue(env: Env, reward_id: u64) -> Vec<u64> {
+        reservation_queue(&env, reward_id)
+    }
+}
+
+// Stock left for direct redemptions once waiting reservations are served
+pub(crate) fn available_stock(env: &Env, reward: &Reward) -> u32 {
+    reward
+        .stock
+        .saturating_sub(reservation_queue(env, reward.reward_id).len())
+}
+
+fn reservation_queue(env: &Env, reward_id: u64) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&ReservationBook::Queue(reward_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_queue(env: &Env, reward_id: u64, queue: &Vec<u64>) {
+    env.storage()
+        .instance()
+        .set(&ReservationBook::Queue(reward_id), queue);
+}
+
+fn load_reservation(env: &Env, reservation_id: u64) -> Reservation {
+    env.storage()
+        .instance()
+        .get(&ReservationBook::Reservation(reservation_id))
+        .unwrap_or_else(|| panic!("Reservation not found"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    #[test]
+    fn test_reservations_are_fulfilled_in_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Gadgets"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id =
+            client.add_reward(&brand_id, &String::from_str(&env, "Headphones"), &300, &0);
+
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let walk_in = Address::generate(&env);
+        for user in [&first, &second, &walk_in] {
+            client.issue_tokens(user, &brand_id, &500, &None);
+        }
+
+        let r1 = client.reserve_reward(&first, &reward_id);
+        let r2 = client.reserve_reward(&second, &reward_id);
+        assert_eq!(client.view_user_balance(&first, &brand_id), 200);
+        assert_eq!(
+            client.view_reservation_queue(&reward_id),
+            vec![&env, r1, r2]
+        );
+        assert!(client.try_fulfill_next(&reward_id).is_err());
+
+        // Restocked units go to the queue before walk-in redemptions
+        client.restock_reward(&reward_id, &1);
+        assert!(client
+            .try_redeem_reward(&walk_in, &reward_id, &None)
+            .is_err());
+        let op_id = client.fulfill_next(&reward_id);
+        assert_eq!(client.view_operation(&op_id).user, first);
+        assert_eq!(client.view_reservation(&r1), None);
+        assert_eq!(client.view_reservation_queue(&reward_id), vec![&env, r2]);
+
+        client.cancel_reservation(&second, &r2);
+        assert_eq!(client.view_user_balance(&second, &brand_id), 500);
+        assert_eq!(client.view_reservation_queue(&reward_id).len(), 0);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::*;
+
+// Mapping for partner revenue sharing on conversions
+#[contracttype]
+pub enum RevenueBook {
+    // (From, To) -> Share of the platform fee paid to the source brand, in basis points
+    Share(u64, u64),
+    // Brand -> Total fee share received, in the brand's points
+    Shared(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Pay `share_bps` of the platform fee on conversions from `from_brand` to
+    /// `to_brand` into the source brand's treasury (platform admin only)
+    /// A share of 0 keeps the whole fee for the platform
+    pub fn set_revenue_share(env: Env, from_brand: u64, to_brand: u64, share_bps: u32) {
+        require_platform_admin(&env);
+        load_brand(&env, from_brand);
+        load_brand(&env, to_brand);
+        if share_bps as i64 > RATE_DENOMINATOR {
+            panic!("Share cannot exceed 100%");
+        }
+
+        let key = RevenueBook::Share(from_brand, to_brand);
+        if share_bps == 0 {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &share_bps);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Revenue share on route {} -> {} set to {} bps",
+            from_brand,
+            to_brand,
+            share_bps
+        );
+    }
+
+    /// View the fee share paid to the source brand on a route
+    pub fn view_revenue_share(env: Env, from_brand: u64, to_brand: u64) -> u32 {
+        env.storage()
+            .instance()
+            .get(&RevenueBook::Share(from_brand, to_brand))
+            .unwrap_or(0)
+    }
+
+    /// View the total fee share a brand has received from its partners
+    pub fn view_shared_revenue(env: Env, brand_id: u64) -> i64 {
+        env.storage()
+            .instance()
+            .get(&RevenueBook::Shared(brand_id))
+            .unwrap_or(0)
+    }
+}
+
+// Pay a source brand its share of an exchange fee
+pub(crate) fn share_revenue(env: &Env, brand_id: u64, share: i64) {
+    if share <= 0 {
+        return;
+    }
+    let shared = LoyaltyTokenExchange::view_shared_revenue(env.clone(), brand_id) + share;
+    env.storage()
+        .instance()
+        .set(&RevenueBook::Shared(brand_id), &shared);
+    deposit_treasury(env, brand_id, TreasurySource::RevenueShare, share);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_source_brand_receives_fee_share() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        let change = client.schedule_change(&Param::ExchangeFeeBps, &200);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change);
+        client.set_revenue_share(&hotel, &airline, &2500);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &2000, &None);
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!((quote.fee, quote.partner_share), (20, 5));
+
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_treasury_reserve(&hotel), 5);
+        assert_eq!(client.view_shared_revenue(&hotel), 5);
+        assert_eq!(client.view_collected_fees(&hotel), 15);
+
+        // With the fee taken in destination points the share is still paid in
+        // source points
+        client.set_route_fee_payer(&hotel, &airline, &FeePayer::Output);
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_treasury_reserve(&hotel), 10);
+        assert_eq!(client.view_collected_fees(&airline), 15);
+
+        // The reverse route has no share configured
+        client.exchange_tokens(&user, &airline, &hotel, &100);
+        assert_eq!(client.view_shared_revenue(&airline), 0);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::*;
+
+// Structure to store a brand's surprise bonus pool and its accounting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DropPool {
+    pub brand_id: u64,
+    // Chance, in basis points, that an exchange into the brand wins a bonus
+    pub probability_bps: u32,
+    pub bonus: i64,
+    // Points currently available for bonuses
+    pub balance: i64,
+    pub funded: i64,
+    pub paid_out: i64,
+    pub withdrawn: i64,
+    pub drops: u32,
+}
+
+// Mapping for bonus pools: Brand -> Pool
+#[contracttype]
+pub enum DropBook {
+    Pool(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Configure surprise bonuses for exchanges into a brand (brand admin only)
+    /// A probability of 0 turns the drops off
+    pub fn set_reward_drop(env: Env, brand_id: u64, probability_bps: u32, bonus: i64) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if probability_bps > RATE_DENOMINATOR as u32 {
+            panic!("Probability must be at most 10000 bps");
+        }
+        if bonus <= 0 {
+            panic!("Bonus must be positive");
+        }
+
+        let mut pool = load_pool(&env, brand_id);
+        pool.probability_bps = probability_bps;
+        pool.bonus = bonus;
+        save_pool(&env, &pool);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Reward drop for brand {} set to {} bps",
+            brand_id,
+            probability_bps
+        );
+    }
+
+    /// Move points of a brand from `funder` into the brand's bonus pool
+    pub fn fund_drop_pool(env: Env, funder: Address, brand_id: u64, amount: i64) {
+        funder.require_auth();
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        debit(&env, &funder, brand_id, amount);
+        let mut pool = load_pool(&env, brand_id);
+        pool.balance += amount;
+        pool.funded += amount;
+        save_pool(&env, &pool);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Drop pool of brand {} funded with {}",
+            brand_id,
+            amount
+        );
+    }
+
+    /// Take unused points out of a brand's bonus pool (brand admin only)
+    pub fn withdraw_drop_pool(env: Env, brand_id: u64, to: Address, amount: i64) {
+        require_brand_admin(&env, brand_id);
+
+        let mut pool = load_pool(&env, brand_id);
+        if amount <= 0 || amount > pool.balance {
+            panic!("Invalid withdrawal amount");
+        }
+        pool.balance -= amount;
+        pool.withdrawn += amount;
+        save_pool(&env, &pool);
+        credit(&env, &to, brand_id, amount);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Withdrew {} from drop pool of brand {}",
+            amount,
+            brand_id
+        );
+    }
+
+    /// View a brand's bonus pool and its drawdown
+    pub fn view_drop_pool(env: Env, brand_id: u64) -> DropPool {
+        load_pool(&env, brand_id)
+    }
+}
+
+// Roll for a surprise bonus after an exchange into `brand_id`
+// Uses the host PRNG, which is seeded per transaction by the network and
+// cannot be chosen by the user; bonuses only pay out while the pool covers them
+pub(crate) fn roll_reward_drop(env: &Env, user: &Address, brand_id: u64) {
+    let mut pool = load_pool(env, brand_id);
+    if pool.probability_bps == 0 || pool.balance < pool.bonus {
+        return;
+    }
+    let roll: u64 = env.prng().gen_range(0..RATE_DENOMINATOR as u64);
+    if roll >= pool.probability_bps as u64 {
+        return;
+    }
+
+    pool.balance -= pool.bonus;
+    pool.paid_out += pool.bonus;
+    pool.drops += 1;
+    save_pool(env, &pool);
+    credit(env, user, brand_id, pool.bonus);
+
+    RewardDropped {
+        brand_id,
+        user: user.clone(),
+        bonus: pool.bonus,
+        pool_balance: pool.balance,
+    }
+    .publish(env);
+}
+
+fn load_pool(env: &Env, brand_id: u64) -> DropPool {
+    env.storage()
+        .instance()
+        .get(&DropBook::Pool(brand_id))
+        .unwrap_or(DropPool {
+            brand_id,
+            probability_bps: 0,
+            bonus: 0,
+            balance: 0,
+            funded: 0,
+            paid_out: 0,
+            withdrawn: 0,
+            drops: 0,
+        })
+}
+
+fn save_pool(env: &Env, pool: &DropPool) {
+    env.storage()
+        .instance()
+        .set(&DropBook::Pool(pool.brand_id), pool);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_drops_draw_down_the_pool() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let airline_admin = Address::generate(&env);
+        client.set_brand_admin(&airline, &airline_admin);
+        client.issue_tokens(&airline_admin, &airline, &150, &None);
+
+        // Certain drops of 100 points, funded for a single bonus
+        client.set_reward_drop(&airline, &10000, &100);
+        client.fund_drop_pool(&airline_admin, &airline, &150);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        assert_eq!(client.view_user_balance(&user, &airline), 300);
+
+        let pool = client.view_drop_pool(&airline);
+        assert_eq!(pool.drops, 1);
+        assert_eq!(pool.paid_out, 100);
+        assert_eq!(pool.balance, 50);
+
+        client.withdraw_drop_pool(&airline, &airline_admin, &50);
+        let pool = client.view_drop_pool(&airline);
+        assert_eq!(pool.funded, pool.paid_out + pool.withdrawn + pool.balance);
+        assert_eq!(client.view_user_balance(&airline_admin, &airline), 50);
+    }
+
+    #[test]
+    fn test_no_drops_at_zero_probability() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let airline_admin = Address::generate(&env);
+        client.set_brand_admin(&airline, &airline_admin);
+        client.issue_tokens(&airline_admin, &airline, &500, &None);
+        client.set_reward_drop(&airline, &0, &100);
+        client.fund_drop_pool(&airline_admin, &airline, &500);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        for _ in 0..5 {
+            client.exchange_tokens(&user, &hotel, &airline, &100);
+        }
+        assert_eq!(client.view_user_balance(&user, &airline), 500);
+        assert_eq!(client.view_drop_pool(&airline).drops, 0);
+    }
+}
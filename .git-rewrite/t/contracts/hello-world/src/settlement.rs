@@ -0,0 +1,159 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Direction of an offline point-of-sale event
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SettlementKind {
+    Earn,
+    Burn,
+}
+
+// Structure describing one offline event recorded by a store
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementEntry {
+    pub user: Address,
+    pub kind: SettlementKind,
+    pub amount: i64,
+    // Unique id assigned by the store, used to detect re-uploads
+    pub reference: BytesN<32>,
+}
+
+// Mapping for settled offline events: (Brand, Reference) -> op_id
+#[contracttype]
+pub enum SettlementBook {
+    Settled(u64, BytesN<32>),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Apply a batch of offline earn/burn events uploaded by a brand (brand admin only)
+    /// Entries whose reference was already settled are skipped, so a batch can
+    /// safely be uploaded again; any other failing entry aborts the whole batch
+    /// Returns the number of entries applied
+    pub fn settle_batch(env: Env, brand_id: u64, entries: Vec<SettlementEntry>) -> u32 {
+        load_brand(&env, brand_id);
+        let admin = require_brand_admin(&env, brand_id);
+
+        let mut applied: u32 = 0;
+        for entry in entries.iter() {
+            let key = SettlementBook::Settled(brand_id, entry.reference.clone());
+            if env.storage().instance().has(&key) {
+                continue;
+            }
+
+            let memo = Some(entry.reference.clone());
+            let op_id = match entry.kind {
+                SettlementKind::Earn => issue(
+                    &env,
+                    Some(admin.clone()),
+                    entry.user,
+                    brand_id,
+                    entry.amount,
+                    memo,
+                ),
+                SettlementKind::Burn => {
+                    redeem_points(&env, entry.user, brand_id, entry.amount, memo)
+                }
+            };
+            env.storage().instance().set(&key, &op_id);
+            applied += 1;
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Settled {} of {} offline entries for brand {}",
+            applied,
+            entries.len(),
+            brand_id
+        );
+        applied
+    }
+
+    /// View the op_id an offline event was settled as, if it was settled
+    pub fn view_settlement(env: Env, brand_id: u64, reference: BytesN<32>) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&SettlementBook::Settled(brand_id, reference))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+    fn entry(
+        env: &Env,
+        user: &Address,
+        kind: SettlementKind,
+        amount: i64,
+        id: u8,
+    ) -> SettlementEntry {
+        SettlementEntry {
+            user: user.clone(),
+            kind,
+            amount,
+            reference: BytesN::from_array(env, &[id; 32]),
+        }
+    }
+
+    #[test]
+    fn test_batch_skips_already_settled_references() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Grocer"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let user = Address::generate(&env);
+
+        let batch = vec![
+            &env,
+            entry(&env, &user, SettlementKind::Earn, 300, 1),
+            entry(&env, &user, SettlementKind::Burn, 120, 2),
+            entry(&env, &user, SettlementKind::Burn, 120, 2),
+        ];
+        assert_eq!(client.settle_batch(&brand_id, &batch), 2);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 180);
+
+        // Re-uploading after a dropped connection only applies the new entry
+        let mut retry = batch.clone();
+        retry.push_back(entry(&env, &user, SettlementKind::Earn, 20, 3));
+        assert_eq!(client.settle_batch(&brand_id, &retry), 1);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 200);
+
+        let reference = BytesN::from_array(&env, &[2; 32]);
+        let op_id = client.view_settlement(&brand_id, &reference).unwrap();
+        assert_eq!(client.view_operation(&op_id).kind, OperationKind::Redeem);
+    }
+
+    #[test]
+    fn test_failing_entry_aborts_whole_batch() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Grocer"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let user = Address::generate(&env);
+
+        let batch = vec![
+            &env,
+            entry(&env, &user, SettlementKind::Earn, 100, 1),
+            entry(&env, &user, SettlementKind::Burn, 500, 2),
+        ];
+        assert!(client.try_settle_batch(&brand_id, &batch).is_err());
+        assert_eq!(client.view_user_balance(&user, &brand_id), 0);
+        assert_eq!(
+            client.view_settlement(&brand_id, &BytesN::from_array(&env, &[1; 32])),
+            None
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Mapping for brand hierarchy
+#[contracttype]
+pub enum SubBrandBook {
+    // Sub-brand -> Parent brand
+    Parent(u64),
+    // Parent brand -> Sub-brands
+    Children(u64),
+}
+
+// Structure reporting supply aggregated over a brand and its sub-brands
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramSupply {
+    pub parent_id: u64,
+    pub parent_supply: i64,
+    pub sub_brand_count: u32,
+    pub total_supply: i64,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Create a regional sub-program under `parent_id` (parent brand admin only)
+    /// The sub-brand inherits the parent's admin until it is given its own
+    /// Returns the brand_id of the new sub-brand
+    pub fn create_sub_brand(env: Env, parent_id: u64, name: String) -> u64 {
+        let parent = load_brand(&env, parent_id);
+        if !parent.is_active {
+            panic!("Brand is not active");
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&SubBrandBook::Parent(parent_id))
+        {
+            panic!("Sub-brands cannot have sub-brands");
+        }
+        require_brand_admin(&env, parent_id);
+
+        let sub_brand_id = create_brand(&env, name);
+        env.storage()
+            .instance()
+            .set(&SubBrandBook::Parent(sub_brand_id), &parent_id);
+
+        let mut children = Self::view_sub_brands(env.clone(), parent_id);
+        children.push_back(sub_brand_id);
+        env.storage()
+            .instance()
+            .set(&SubBrandBook::Children(parent_id), &children);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Sub-brand {} created under brand {}",
+            sub_brand_id,
+            parent_id
+        );
+        sub_brand_id
+    }
+
+    /// View the sub-brands of a brand
+    pub fn view_sub_brands(env: Env, parent_id: u64) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&SubBrandBook::Children(parent_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View the parent of a sub-brand, if any
+    pub fn view_parent_brand(env: Env, brand_id: u64) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&SubBrandBook::Parent(brand_id))
+    }
+
+    /// View the supply of a brand aggregated with all of its sub-brands
+    pub fn view_program_supply(env: Env, parent_id: u64) -> ProgramSupply {
+        let parent_supply = Self::view_brand_supply(env.clone(), parent_id);
+        let children = Self::view_sub_brands(env.clone(), parent_id);
+
+        let mut total_supply = parent_supply;
+        for sub_brand_id in children.iter() {
+            total_supply += Self::view_brand_supply(env.clone(), sub_brand_id);
+        }
+
+        ProgramSupply {
+            parent_id,
+            parent_supply,
+            sub_brand_count: children.len(),
+            total_supply,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, Address, u64) {
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+
+        let platform_admin = Address::generate(env);
+        let brand_admin = Address::generate(env);
+        client.set_platform_admin(&platform_admin);
+
+        let parent_id = client.register_brand(&String::from_str(env, "Coffee Co"));
+        client.set_brand_admin(&parent_id, &brand_admin);
+        (client, brand_admin, parent_id)
+    }
+
+    #[test]
+    fn test_sub_brand_inherits_admin_and_rolls_up_supply() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_admin, parent_id) = setup(&env);
+
+        let east = client.create_sub_brand(&parent_id, &String::from_str(&env, "Coffee Co East"));
+        let west = client.create_sub_brand(&parent_id, &String::from_str(&env, "Coffee Co West"));
+        assert_eq!(client.view_sub_brands(&parent_id), vec![&env, east, west]);
+        assert_eq!(client.view_parent_brand(&east), Some(parent_id));
+        assert_eq!(client.get_brand_admin(&west), Some(brand_admin));
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &parent_id, &100, &None);
+        client.issue_tokens(&user, &east, &200, &None);
+        client.issue_tokens(&user, &west, &300, &None);
+
+        let supply = client.view_program_supply(&parent_id);
+        assert_eq!(supply.parent_supply, 100);
+        assert_eq!(supply.sub_brand_count, 2);
+        assert_eq!(supply.total_supply, 600);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sub-brands cannot have sub-brands")]
+    fn test_nested_sub_brand_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, parent_id) = setup(&env);
+
+        let east = client.create_sub_brand(&parent_id, &String::from_str(&env, "East"));
+        client.create_sub_brand(&east, &String::from_str(&env, "North East"));
+    }
+}
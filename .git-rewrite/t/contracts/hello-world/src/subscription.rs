@@ -0,0 +1,226 @@
+use crate::*;
+
+// Structure to store a recurring reward redemption
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub sub_id: u64,
+    pub user: Address,
+    pub brand_id: u64,
+    pub reward_id: u64,
+    pub interval_ledgers: u32,
+    pub next_due: u32,
+    pub redemptions: u32,
+    // Consecutive periods skipped because the redemption could not be made
+    pub failed_attempts: u32,
+    pub is_active: bool,
+}
+
+// Mapping for subscriptions
+#[contracttype]
+pub enum SubscriptionBook {
+    Subscription(u64),
+}
+
+// Counter for subscriptions
+const SUB_COUNT: Symbol = symbol_short!("S_COUNT");
+
+// Consecutive failed periods after which a subscription is deactivated
+pub const MAX_FAILED_ATTEMPTS: u32 = 3;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Subscribe a user to redeem a reward every `interval_ledgers`
+    /// The first redemption is due immediately
+    /// Returns the sub_id of the new subscription
+    pub fn subscribe(
+        env: Env,
+        user: Address,
+        brand_id: u64,
+        reward_id: u64,
+        interval_ledgers: u32,
+    ) -> u64 {
+        user.require_auth();
+
+        let reward = load_reward(&env, reward_id);
+        if reward.brand_id != brand_id {
+            panic!("Reward does not belong to brand");
+        }
+        if !reward.is_active {
+            panic!("Reward is not active");
+        }
+        if interval_ledgers == 0 {
+            panic!("Interval must be positive");
+        }
+
+        let mut sub_count: u64 = env.storage().instance().get(&SUB_COUNT).unwrap_or(0);
+        sub_count += 1;
+
+        let subscription = Subscription {
+            sub_id: sub_count,
+            user,
+            brand_id,
+            reward_id,
+            interval_ledgers,
+            next_due: env.ledger().sequence(),
+            redemptions: 0,
+            failed_attempts: 0,
+            is_active: true,
+        };
+        save_subscription(&env, &subscription);
+        env.storage().instance().set(&SUB_COUNT, &sub_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Subscription {} created for reward {}",
+            sub_count,
+            reward_id
+        );
+        sub_count
+    }
+
+    /// Execute a due subscription redemption; anyone may call this
+    /// When the user cannot pay or the reward is unavailable the period is
+    /// skipped, and the subscription is deactivated after MAX_FAILED_ATTEMPTS
+    /// Returns whether the redemption was made
+    pub fn process_due(env: Env, sub_id: u64) -> bool {
+        let mut subscription = load_subscription(&env, sub_id);
+        if !subscription.is_active {
+            panic!("Subscription is not active");
+        }
+        let sequence = env.ledger().sequence();
+        if sequence < subscription.next_due {
+            panic!("Subscription is not due");
+        }
+
+        let redeemable = check_redeem(&env, &subscription.user, subscription.reward_id).is_ok();
+        if redeemable {
+            let mut reward = load_reward(&env, subscription.reward_id);
+            fulfill_redemption(&env, &subscription.user, &mut reward, None);
+            deposit_treasury(
+                &env,
+                subscription.brand_id,
+                TreasurySource::Subscription,
+                reward.cost,
+            );
+            subscription.redemptions += 1;
+            subscription.failed_attempts = 0;
+            log!(&env, "✅ Subscription {} redeemed", sub_id);
+        } else {
+            subscription.failed_attempts += 1;
+            if subscription.failed_attempts >= MAX_FAILED_ATTEMPTS {
+                subscription.is_active = false;
+            }
+            log!(
+                &env,
+                "⚠️ Subscription {} skipped ({} consecutive failures)",
+                sub_id,
+                subscription.failed_attempts
+            );
+        }
+
+        subscription.next_due = sequence + subscription.interval_ledgers;
+        save_subscription(&env, &subscription);
+        env.storage().instance().extend_ttl(100000, 100000);
+        redeemable
+    }
+
+    /// Cancel a subscription (subscriber only)
+    pub fn cancel_subscription(env: Env, user: Address, sub_id: u64) {
+        user.require_auth();
+
+        let mut subscription = load_subscription(&env, sub_id);
+        if subscription.user != user {
+            panic!("Not the subscriber");
+        }
+        subscription.is_active = false;
+        save_subscription(&env, &subscription);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Subscription {} cancelled", sub_id);
+    }
+
+    /// View subscription details by sub_id
+    pub fn view_subscription(env: Env, sub_id: u64) -> Subscription {
+        load_subscription(&env, sub_id)
+    }
+}
+
+fn load_subscription(env: &Env, sub_id: u64) -> Subscription {
+    env.storage()
+        .instance()
+        .get(&SubscriptionBook::Subscription(sub_id))
+        .unwrap_or_else(|| panic!("Subscription not found"))
+}
+
+fn save_subscription(env: &Env, subscription: &Subscription) {
+    env.storage().instance().set(
+        &SubscriptionBook::Subscription(subscription.sub_id),
+        subscription,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64) {
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let brand_id = client.register_brand(&String::from_str(env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(env, "Coffee"), &100, &10);
+        (client, brand_id, reward_id)
+    }
+
+    #[test]
+    fn test_subscription_redeems_each_interval() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, reward_id) = setup(&env);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &250, &None);
+        let sub_id = client.subscribe(&user, &brand_id, &reward_id, &100);
+
+        assert!(client.process_due(&sub_id));
+        assert!(client.try_process_due(&sub_id).is_err());
+
+        env.ledger().set_sequence_number(100);
+        assert!(client.process_due(&sub_id));
+
+        let subscription = client.view_subscription(&sub_id);
+        assert_eq!(subscription.redemptions, 2);
+        assert_eq!(subscription.next_due, 200);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 50);
+        assert_eq!(client.view_reward(&reward_id).stock, 8);
+    }
+
+    #[test]
+    fn test_subscription_deactivates_after_repeated_failures() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, reward_id) = setup(&env);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &50, &None);
+        let sub_id = client.subscribe(&user, &brand_id, &reward_id, &10);
+
+        for round in 0..MAX_FAILED_ATTEMPTS {
+            env.ledger().set_sequence_number(round * 10);
+            assert!(!client.process_due(&sub_id));
+        }
+
+        let subscription = client.view_subscription(&sub_id);
+        assert_eq!(subscription.failed_attempts, MAX_FAILED_ATTEMPTS);
+        assert!(!subscription.is_active);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 50);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::*;
+
+// Reasons an operation would be rejected
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CheckFailure {
+    InvalidAmount,
+    BrandInactive,
+    SupplyCapExceeded,
+    SameBrand,
+    FromBrandInactive,
+    ToBrandInactive,
+    RoutePaused,
+    InsufficientBalance,
+    RewardNotFound,
+    RewardInactive,
+    RewardOutOfStock,
+}
+
+impl CheckFailure {
+    // Abort the current invocation with the failure's error message
+    pub(crate) fn fail(self) -> ! {
+        match self {
+            CheckFailure::InvalidAmount => panic!("Amount must be positive"),
+            CheckFailure::BrandInactive => panic!("Brand is not active"),
+            CheckFailure::SupplyCapExceeded => panic!("Supply cap exceeded"),
+            CheckFailure::SameBrand => panic!("Cannot exchange to the same brand"),
+            CheckFailure::FromBrandInactive | CheckFailure::ToBrandInactive => {
+                panic!("One or both brands are not active")
+            }
+            CheckFailure::RoutePaused => panic!("Exchange route is paused"),
+            CheckFailure::InsufficientBalance => panic!("Insufficient balance"),
+            CheckFailure::RewardNotFound => panic!("Reward not found"),
+            CheckFailure::RewardInactive => panic!("Reward is not active"),
+            CheckFailure::RewardOutOfStock => panic!("Reward is out of stock"),
+        }
+    }
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Dry-run `issue_tokens`; returns the first check that would fail, if any
+    pub fn can_issue(env: Env, brand_id: u64, amount: i64) -> Option<CheckFailure> {
+        check_issue(&env, brand_id, amount).err()
+    }
+
+    /// Dry-run `exchange_tokens`; returns the first check that would fail, if any
+    pub fn can_exchange(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i64,
+    ) -> Option<CheckFailure> {
+        check_exchange(&env, &user, from_brand, to_brand, amount).err()
+    }
+
+    /// Dry-run `redeem_reward`; returns the first check that would fail, if any
+    pub fn can_redeem(env: Env, user: Address, reward_id: u64) -> Option<CheckFailure> {
+        check_redeem(&env, &user, reward_id).err()
+    }
+}
+
+pub(crate) fn check_issue(env: &Env, brand_id: u64, amount: i64) -> Result<(), CheckFailure> {
+    if !is_brand_active(env, brand_id) {
+        return Err(CheckFailure::BrandInactive);
+    }
+    if amount <= 0 {
+        return Err(CheckFailure::InvalidAmount);
+    }
+    if let Some(cap) = param_value(env, &Param::SupplyCap(brand_id)) {
+        let supply: i64 = env
+            .storage()
+            .instance()
+            .get(&BrandSupply::Supply(brand_id))
+            .unwrap_or(0);
+        if supply + amount > cap {
+            return Err(CheckFailure::SupplyCapExceeded);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn check_exchange(
+    env: &Env,
+    user: &Address,
+    from_brand: u64,
+    to_brand: u64,
+    amount: i64,
+) -> Result<(), CheckFailure> {
+    if amount <= 0 {
+        return Err(CheckFailure::InvalidAmount);
+    }
+    if from_brand == to_brand {
+        return Err(CheckFailure::SameBrand);
+    }
+    if !is_brand_active(env, from_brand) {
+        return Err(CheckFailure::FromBrandInactive);
+    }
+    if !is_brand_active(env, to_brand) {
+        return Err(CheckFailure::ToBrandInactive);
+    }
+    if is_route_paused(env, from_brand, to_brand) {
+        return Err(CheckFailure::RoutePaused);
+    }
+    if read_balance(env, user, from_brand) < amount {
+        return Err(CheckFailure::InsufficientBalance);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_redeem(env: &Env, user: &Address, reward_id: u64) -> Result<(), CheckFailure> {
+    let reward: Reward = env
+        .storage()
+        .instance()
+        .get(&RewardBook::Reward(reward_id))
+        .ok_or(CheckFailure::RewardNotFound)?;
+    if !reward.is_active {
+        return Err(CheckFailure::RewardInactive);
+    }
+    if available_stock(env, &reward) == 0 {
+        return Err(CheckFailure::RewardOutOfStock);
+    }
+    if !is_brand_active(env, reward.brand_id) {
+        return Err(CheckFailure::BrandInactive);
+    }
+    if read_balance(env, user, reward.brand_id) < reward.cost {
+        return Err(CheckFailure::InsufficientBalance);
+    }
+    Ok(())
+}
+
+// Unregistered brands count as inactive
+pub(crate) fn is_brand_active(env: &Env, brand_id: u64) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, Brand>(&BrandBook::Brand(brand_id))
+        .map(|brand| brand.is_active)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_dry_run_reports_failing_check() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let user = Address::generate(&env);
+        let shoes = client.register_brand(&String::from_str(&env, "Shoes"));
+        let socks = client.register_brand(&String::from_str(&env, "Socks"));
+        client.set_brand_admin(&shoes, &Address::generate(&env));
+        let reward_id = client.add_reward(&shoes, &String::from_str(&env, "Laces"), &80, &1);
+        client.issue_tokens(&user, &shoes, &50, &None);
+
+        assert_eq!(client.can_issue(&shoes, &10), None);
+        assert_eq!(
+            client.can_issue(&shoes, &0),
+            Some(CheckFailure::InvalidAmount)
+        );
+        assert_eq!(
+            client.can_issue(&99, &10),
+            Some(CheckFailure::BrandInactive)
+        );
+
+        assert_eq!(client.can_exchange(&user, &shoes, &socks, &50), None);
+        assert_eq!(
+            client.can_exchange(&user, &shoes, &shoes, &50),
+            Some(CheckFailure::SameBrand)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &shoes, &99, &50),
+            Some(CheckFailure::ToBrandInactive)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &shoes, &socks, &51),
+            Some(CheckFailure::InsufficientBalance)
+        );
+
+        assert_eq!(
+            client.can_redeem(&user, &reward_id),
+            Some(CheckFailure::InsufficientBalance)
+        );
+        client.issue_tokens(&user, &shoes, &30, &None);
+        assert_eq!(client.can_redeem(&user, &reward_id), None);
+        assert_eq!(
+            client.can_redeem(&user, &42),
+            Some(CheckFailure::RewardNotFound)
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure recording voting power from a given ledger onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub votes: i64,
+}
+
+// Mapping for delegated voting power
+#[contracttype]
+pub enum VotesBook {
+    // (Holder, Brand) -> Delegate
+    Delegate(Address, u64),
+    // (Delegate, Brand) -> Checkpoints, oldest first
+    Checkpoints(Address, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Delegate the voting power of a user's brand balance to `delegate_to`
+    /// Balances only count as votes once delegated; delegate to yourself to vote directly
+    pub fn delegate(env: Env, user: Address, brand_id: u64, delegate_to: Address) {
+        user.require_auth();
+        load_brand(&env, brand_id);
+
+        let balance = read_balance(&env, &user, brand_id);
+        if let Some(previous) = Self::view_delegate(env.clone(), user.clone(), brand_id) {
+            move_votes(&env, &previous, brand_id, -balance);
+        }
+        move_votes(&env, &delegate_to, brand_id, balance);
+
+        env.storage()
+            .instance()
+            .set(&VotesBook::Delegate(user, brand_id), &delegate_to);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Voting power for brand {} delegated", brand_id);
+    }
+
+    /// View who a user's voting power is delegated to, if anyone
+    pub fn view_delegate(env: Env, user: Address, brand_id: u64) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&VotesBook::Delegate(user, brand_id))
+    }
+
+    /// Current voting power of an address for a brand
+    pub fn get_votes(env: Env, account: Address, brand_id: u64) -> i64 {
+        checkpoints(&env, &account, brand_id)
+            .last()
+            .map(|checkpoint| checkpoint.votes)
+            .unwrap_or(0)
+    }
+
+    /// Voting power of an address for a brand as of the end of `ledger`
+    pub fn get_past_votes(env: Env, account: Address, brand_id: u64, ledger: u32) -> i64 {
+        let mut votes = 0;
+        for checkpoint in checkpoints(&env, &account, brand_id).iter() {
+            if checkpoint.ledger > ledger {
+                break;
+            }
+            votes = checkpoint.votes;
+        }
+        votes
+    }
+}
+
+// Move voting power along with a balance change of a delegating holder
+pub(crate) fn track_votes(env: &Env, user: &Address, brand_id: u64, delta: i64) {
+    let delegate: Option<Address> = env
+        .storage()
+        .instance()
+        .get(&VotesBook::Delegate(user.clone(), brand_id));
+    if let Some(delegate) = delegate {
+        move_votes(env, &delegate, brand_id, delta);
+    }
+}
+
+fn checkpoints(env: &Env, account: &Address, brand_id: u64) -> Vec<Checkpoint> {
+    env.storage()
+        .instance()
+        .get(&VotesBook::Checkpoints(account.clone(), brand_id))
+        .unwrap_or(Vec::new(env))
+}
+
+fn move_votes(env: &Env, account: &Address, brand_id: u64, delta: i64) {
+    if delta == 0 {
+        return;
+    }
+
+    let ledger = env.ledger().sequence();
+    let mut history = checkpoints(env, account, brand_id);
+    let latest = history.last();
+    let votes = latest
+        .as_ref()
+        .map(|checkpoint| checkpoint.votes)
+        .unwrap_or(0)
+        + delta;
+
+    // Several changes within one ledger share a checkpoint
+    match latest {
+        Some(checkpoint) if checkpoint.ledger == ledger => {
+            history.set(history.len() - 1, Checkpoint { ledger, votes });
+        }
+        _ => history.push_back(Checkpoint { ledger, votes }),
+    }
+    env.storage()
+        .instance()
+        .set(&VotesBook::Checkpoints(account.clone(), brand_id), &history);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_delegated_votes_follow_balances() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Club"));
+        client.issue_tokens(&alice, &brand_id, &100, &None);
+        client.issue_tokens(&bob, &brand_id, &50, &None);
+
+        // Undelegated balances carry no votes
+        assert_eq!(client.get_votes(&bob, &brand_id), 0);
+
+        client.delegate(&alice, &brand_id, &carol);
+        client.delegate(&bob, &brand_id, &bob);
+        assert_eq!(client.get_votes(&carol, &brand_id), 100);
+        assert_eq!(client.get_votes(&bob, &brand_id), 50);
+
+        env.ledger().set_sequence_number(10);
+        client.transfer_tokens(&alice, &bob, &brand_id, &30, &None);
+        assert_eq!(client.get_votes(&carol, &brand_id), 70);
+        assert_eq!(client.get_votes(&bob, &brand_id), 80);
+
+        env.ledger().set_sequence_number(20);
+        client.delegate(&alice, &brand_id, &bob);
+        assert_eq!(client.get_votes(&carol, &brand_id), 0);
+        assert_eq!(client.get_votes(&bob, &brand_id), 150);
+
+        assert_eq!(client.get_past_votes(&carol, &brand_id, &5), 100);
+        assert_eq!(client.get_past_votes(&carol, &brand_id, &15), 70);
+        assert_eq!(client.get_past_votes(&bob, &brand_id, &15), 80);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+// Mapping for read access roles: Address -> Whether it is an auditor
+#[contracttype]
+pub enum AccessBook {
+    Auditor(Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Grant or revoke the Auditor role, which may read every user's history
+    /// (platform admin only)
+    pub fn set_auditor(env: Env, auditor: Address, enabled: bool) {
+        let admin = require_platform_admin(&env);
+
+        let key = AccessBook::Auditor(auditor);
+        log_admin_action(
+            &env,
+            &admin,
+            AdminAction::AuditorSet,
+            PLATFORM_BRAND_ID,
+            None,
+            Some(enabled as i128),
+        );
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Auditor role updated");
+    }
+
+    /// View whether an address holds the Auditor role
+    pub fn is_auditor(env: Env, address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&AccessBook::Auditor(address))
+            .unwrap_or(false)
+    }
+}
+
+// Whether `caller` may read an operation: the users and issuer involved, the
+// admin of either brand involved, or an auditor
+pub(crate) fn can_view_operation(env: &Env, caller: &Address, record: &OperationRecord) -> bool {
+    if *caller == record.user
+        || record.counterparty.as_ref() == Some(caller)
+        || record.issuer.as_ref() == Some(caller)
+    {
+        return true;
+    }
+    if brand_admin(env, record.brand_id).as_ref() == Some(caller) {
+        return true;
+    }
+    if let Some(to_brand) = record.to_brand {
+        if brand_admin(env, to_brand).as_ref() == Some(caller) {
+            return true;
+        }
+    }
+    LoyaltyTokenExchange::is_auditor(env.clone(), caller.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_history_is_scoped_to_involved_parties() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let bakery = client.register_brand(&String::from_str(&env, "Bakery"));
+        let cafe_admin = Address::generate(&env);
+        let bakery_admin = Address::generate(&env);
+        client.set_brand_admin(&cafe, &cafe_admin);
+        client.set_brand_admin(&bakery, &bakery_admin);
+
+        let user = Address::generate(&env);
+        let cafe_op = client.issue_tokens(&user, &cafe, &100, &None);
+        client.issue_tokens(&user, &bakery, &100, &None);
+
+        let stranger = Address::generate(&env);
+        assert_eq!(client.view_operation(&user, &cafe_op).user, user);
+        assert_eq!(client.view_operation(&cafe_admin, &cafe_op).brand_id, cafe);
+        assert!(client.try_view_operation(&stranger, &cafe_op).is_err());
+        assert!(client.try_view_operation(&bakery_admin, &cafe_op).is_err());
+
+        // Brand admins only see their brand's part of a user's history
+        assert_eq!(client.view_user_history(&user, &user, &0, &10).len(), 2);
+        assert_eq!(
+            client.view_user_history(&cafe_admin, &user, &0, &10).len(),
+            1
+        );
+        assert_eq!(client.view_user_history(&stranger, &user, &0, &10).len(), 0);
+
+        client.set_auditor(&stranger, &true);
+        assert!(client.is_auditor(&stranger));
+        assert_eq!(client.view_user_history(&stranger, &user, &0, &10).len(), 2);
+        assert_eq!(client.view_operation(&stranger, &cafe_op).op_id, cafe_op);
+    }
+}
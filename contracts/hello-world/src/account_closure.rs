@@ -0,0 +1,128 @@
+use crate::*;
+use soroban_sdk::Map;
+
+// What happens to the balances left in a closed account
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Disposition {
+    // Given to each brand's treasury wallet, or burned if it has none
+    Donate,
+    Transfer(Address),
+    Burn,
+}
+
+// Structure to store the audit record of a closed account
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClosureRecord {
+    pub user: Address,
+    pub disposition: Disposition,
+    // Brand -> Balance disposed of
+    pub balances: Map<u64, i128>,
+    pub closed_at: u32,
+}
+
+// Mapping for closed accounts: User -> Latest closure record
+// Kept in persistent storage, one entry per user
+#[contracttype]
+pub enum ClosureBook {
+    Closure(Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Close a user's account, disposing of every remaining balance as chosen
+    /// and deleting the balance entry
+    /// Points credited later open the account again
+    pub fn close_account(env: Env, user: Address, disposition: Disposition) {
+        require_user(&env, &user);
+        if let Disposition::Transfer(to) = &disposition {
+            if *to == user {
+                panic!("Cannot transfer to yourself");
+            }
+        }
+
+        let mut disposed = Map::new(&env);
+        for brand_id in Self::view_balances(env.clone(), user.clone()).keys().iter() {
+            let balance = read_balance(&env, &user, brand_id);
+            if balance > 0 {
+                dispose(&env, &user, brand_id, balance, &disposition);
+                disposed.set(brand_id, balance);
+            }
+        }
+        remove_balances(&env, &user);
+
+        let record = ClosureRecord {
+            user: user.clone(),
+            disposition,
+            balances: disposed,
+            closed_at: env.ledger().sequence(),
+        };
+        let key = ClosureBook::Closure(user);
+        env.storage().persistent().set(&key, &record);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Account closed");
+    }
+
+    /// View the record of a user's latest account closure, if any
+    pub fn view_account_closure(env: Env, user: Address) -> Option<ClosureRecord> {
+        env.storage().persistent().get(&ClosureBook::Closure(user))
+    }
+}
+
+fn dispose(env: &Env, user: &Address, brand_id: u64, amount: i128, disposition: &Disposition) {
+    let to = match disposition {
+        Disposition::Donate => LoyaltyTokenExchange::view_brand_treasury(env.clone(), brand_id)
+            .map(LedgerAccount::User)
+            .unwrap_or(LedgerAccount::Burn),
+        Disposition::Transfer(to) => LedgerAccount::User(to.clone()),
+        Disposition::Burn => LedgerAccount::Burn,
+    };
+    post(
+        env,
+        brand_id,
+        &LedgerAccount::User(user.clone()),
+        &to,
+        amount,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_closed_account_balances_follow_disposition() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let gym = client.register_brand(&String::from_str(&env, "Gym"));
+        client.set_brand_admin(&cafe, &Address::generate(&env));
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&cafe, &treasury);
+
+        let leaving = Address::generate(&env);
+        let staying = Address::generate(&env);
+        client.issue_tokens(&leaving, &cafe, &100, &None);
+        client.issue_tokens(&leaving, &gym, &40, &None);
+        client.issue_tokens(&staying, &cafe, &10, &None);
+        client.close_account(&staying, &Disposition::Transfer(leaving.clone()));
+        assert_eq!(client.view_user_balance(&leaving, &cafe), 110);
+
+        client.close_account(&leaving, &Disposition::Donate);
+        assert_eq!(client.view_user_balance(&treasury, &cafe), 110);
+        assert_eq!(client.view_brand_supply(&gym), 0);
+        assert!(client.view_balances(&leaving).is_empty());
+
+        let record = client.view_account_closure(&leaving).unwrap();
+        assert_eq!(record.balances.get(cafe), Some(110));
+        assert_eq!(record.balances.get(gym), Some(40));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::*;
+use soroban_sdk::Map;
+
+// Mapping for saved recipients: User -> (Label hash -> Recipient)
+// Kept in persistent storage, one entry per user
+#[contracttype]
+pub enum RecipientBook {
+    Recipients(Address),
+}
+
+// Most recipients one user can save
+pub const MAX_SAVED_RECIPIENTS: u32 = 50;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Save a transfer recipient under the hash of its label, replacing any
+    /// recipient saved under the same label
+    pub fn save_recipient(env: Env, user: Address, label_hash: BytesN<32>, recipient: Address) {
+        require_user(&env, &user);
+
+        let mut recipients = Self::saved_recipients(env.clone(), user.clone());
+        if !recipients.contains_key(label_hash.clone()) && recipients.len() >= MAX_SAVED_RECIPIENTS
+        {
+            panic!("Address book is full");
+        }
+        recipients.set(label_hash, recipient);
+        save_recipients(&env, &user, &recipients);
+
+        log!(&env, "✅ Recipient saved");
+    }
+
+    /// Remove a saved recipient by the hash of its label
+    pub fn remove_recipient(env: Env, user: Address, label_hash: BytesN<32>) {
+        require_user(&env, &user);
+
+        let mut recipients = Self::saved_recipients(env.clone(), user.clone());
+        if recipients.remove(label_hash).is_none() {
+            panic!("Recipient not found");
+        }
+        save_recipients(&env, &user, &recipients);
+
+        log!(&env, "✅ Recipient removed");
+    }
+
+    /// View a user's saved recipients by label hash
+    pub fn saved_recipients(env: Env, user: Address) -> Map<BytesN<32>, Address> {
+        env.storage()
+            .persistent()
+            .get(&RecipientBook::Recipients(user))
+            .unwrap_or(Map::new(&env))
+    }
+}
+
+fn save_recipients(env: &Env, user: &Address, recipients: &Map<BytesN<32>, Address>) {
+    let key = RecipientBook::Recipients(user.clone());
+    if recipients.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, recipients);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    }
+    env.storage().instance().extend_ttl(100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+
+    #[test]
+    fn test_saved_recipients_add_replace_remove() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let mom = BytesN::from_array(&env, &[1; 32]);
+        let gym = BytesN::from_array(&env, &[2; 32]);
+        let new_mom = Address::generate(&env);
+        client.save_recipient(&user, &mom, &Address::generate(&env));
+        client.save_recipient(&user, &gym, &Address::generate(&env));
+        client.save_recipient(&user, &mom, &new_mom);
+
+        let recipients = client.saved_recipients(&user);
+        assert_eq!(recipients.len(), 2);
+        assert_eq!(recipients.get(mom.clone()), Some(new_mom));
+
+        client.remove_recipient(&user, &gym);
+        assert!(client.try_remove_recipient(&user, &gym).is_err());
+        assert_eq!(client.saved_recipients(&user).len(), 1);
+        assert!(client.saved_recipients(&Address::generate(&env)).is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Kind of admin-level action recorded in the admin log
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    // A timelocked parameter (fee, rate, cap, burn, peg, share,
+    // withholding or decay) took effect
+    ParamChanged,
+    RoutePaused,
+    RouteResumed,
+    PlatformAdminSet,
+    BrandAdminSet,
+    AuditorSet,
+    RateBoundsSet,
+    ZeroBalanceCleanupSet,
+    DepositRequirementSet,
+    DepositSlashed,
+    EventVerbositySet,
+}
+
+// Structure to store one entry of the append-only admin log
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminLogEntry {
+    pub entry_id: u64,
+    pub actor: Address,
+    pub action: AdminAction,
+    // Brand the action applies to; PLATFORM_BRAND_ID for platform-wide actions
+    pub brand_id: u64,
+    pub old_value: Option<i128>,
+    pub new_value: Option<i128>,
+    pub ledger: u32,
+}
+
+// Mapping for admin log entries: entry_id -> Entry
+// Kept in persistent storage, one entry per log entry
+#[contracttype]
+pub enum AdminLogBook {
+    Entry(u64),
+}
+
+// Counter for admin log entries
+const ADMIN_LOG_COUNT: Symbol = symbol_short!("AL_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View a page of the admin log, oldest first
+    pub fn view_admin_log(env: Env, start: u32, limit: u32) -> Vec<AdminLogEntry> {
+        let count: u64 = env.storage().instance().get(&ADMIN_LOG_COUNT).unwrap_or(0);
+        let mut entries = Vec::new(&env);
+        let end = count.min(start as u64 + limit as u64);
+        for entry_id in (start as u64 + 1)..=end {
+            entries.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&AdminLogBook::Entry(entry_id))
+                    .unwrap(),
+            );
+        }
+        entries
+    }
+
+    /// View the number of entries in the admin log
+    pub fn admin_log_len(env: Env) -> u64 {
+        env.storage().instance().get(&ADMIN_LOG_COUNT).unwrap_or(0)
+    }
+}
+
+// Append an admin-level action to the admin log
+pub(crate) fn log_admin_action(
+    env: &Env,
+    actor: &Address,
+    action: AdminAction,
+    brand_id: u64,
+    old_value: Option<i128>,
+    new_value: Option<i128>,
+) {
+    let mut count: u64 = env.storage().instance().get(&ADMIN_LOG_COUNT).unwrap_or(0);
+    count += 1;
+
+    let entry = AdminLogEntry {
+        entry_id: count,
+        actor: actor.clone(),
+        action,
+        brand_id,
+        old_value,
+        new_value,
+        ledger: env.ledger().sequence(),
+    };
+    let key = AdminLogBook::Entry(count);
+    env.storage().persistent().set(&key, &entry);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    env.storage().instance().set(&ADMIN_LOG_COUNT, &count);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_admin_actions_are_logged_in_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_platform_admin(&admin);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let change_id = client.schedule_change(&Param::ExchangeFeeBps, &100);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        client.pause_route(&admin, &hotel, &airline);
+
+        assert_eq!(client.admin_log_len(), 3);
+        let page = client.view_admin_log(&1, &10);
+        assert_eq!(page.len(), 2);
+        let fee_change = page.get(0).unwrap();
+        assert_eq!(fee_change.action, AdminAction::ParamChanged);
+        assert_eq!(fee_change.actor, admin);
+        assert_eq!(
+            (fee_change.old_value, fee_change.new_value),
+            (None, Some(100))
+        );
+        assert_eq!(fee_change.ledger, TIMELOCK_LEDGERS);
+        let pause = page.get(1).unwrap();
+        assert_eq!(
+            (pause.action, pause.brand_id),
+            (AdminAction::RoutePaused, hotel)
+        );
+    }
+}
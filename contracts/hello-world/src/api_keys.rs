@@ -0,0 +1,188 @@
+use crate::*;
+use soroban_sdk::BytesN;
+
+// Structure to store a machine address allowed to issue for a brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApiKey {
+    pub brand_id: u64,
+    pub key: Address,
+    // Maximum points the key may issue per period
+    pub budget: i128,
+    pub period_ledgers: u32,
+    pub period_start: u32,
+    // Points issued in the current period
+    pub used: i128,
+}
+
+// Mapping for API keys: (Brand, Key) -> ApiKey
+#[contracttype]
+pub enum ApiKeyBook {
+    Key(u64, Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Register a machine address that may issue up to `budget` points every
+    /// `period_ledgers` ledgers on behalf of a brand (brand admin only)
+    /// Registering an existing key replaces its budget and resets its usage
+    pub fn add_api_key(env: Env, brand_id: u64, key: Address, budget: i128, period_ledgers: u32) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if budget <= 0 {
+            panic!("Budget must be positive");
+        }
+        if period_ledgers == 0 {
+            panic!("Period must be positive");
+        }
+
+        let api_key = ApiKey {
+            brand_id,
+            key: key.clone(),
+            budget,
+            period_ledgers,
+            period_start: env.ledger().sequence(),
+            used: 0,
+        };
+        env.storage()
+            .instance()
+            .set(&ApiKeyBook::Key(brand_id, key), &api_key);
+        delegate_issuance(&env, brand_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ API key registered for brand {}", brand_id);
+    }
+
+    /// Revoke a brand's API key (brand admin only)
+    pub fn revoke_api_key(env: Env, brand_id: u64, key: Address) {
+        require_brand_admin(&env, brand_id);
+
+        let key = ApiKeyBook::Key(brand_id, key);
+        if !env.storage().instance().has(&key) {
+            panic!("API key not found");
+        }
+        env.storage().instance().remove(&key);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ API key revoked for brand {}", brand_id);
+    }
+
+    /// Issue tokens to a user with a brand API key, drawing down its budget
+    /// Returns the op_id of the recorded operation
+    pub fn issue_with_key(
+        env: Env,
+        key: Address,
+        user: Address,
+        brand_id: u64,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        key.require_auth();
+        if let Err(failure) = check_reference(&env, brand_id, &memo) {
+            failure.fail();
+        }
+
+        let mut api_key = Self::view_api_key(env.clone(), brand_id, key.clone())
+            .unwrap_or_else(|| panic!("API key not found"));
+        // The budget covers the points actually issued, after any experiment
+        // multiplier
+        let amount = experiment_amount(&env, &user, brand_id, amount);
+        if amount > api_key.budget - api_key.used {
+            panic!("Issuance budget exhausted");
+        }
+        api_key.used += amount;
+        env.storage()
+            .instance()
+            .set(&ApiKeyBook::Key(brand_id, key.clone()), &api_key);
+
+        issue(&env, Some(key), user, brand_id, amount, memo)
+    }
+
+    /// View a brand's API key with its usage rolled over to the current period
+    pub fn view_api_key(env: Env, brand_id: u64, key: Address) -> Option<ApiKey> {
+        let mut api_key: ApiKey = env
+            .storage()
+            .instance()
+            .get(&ApiKeyBook::Key(brand_id, key))?;
+
+        let elapsed = env.ledger().sequence() - api_key.period_start;
+        if elapsed >= api_key.period_ledgers {
+            api_key.period_start += elapsed - elapsed % api_key.period_ledgers;
+            api_key.used = 0;
+        }
+        Some(api_key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, Address) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let brand_id = client.register_brand(&String::from_str(env, "Pharmacy"));
+        client.set_brand_admin(&brand_id, &Address::generate(env));
+
+        let key = Address::generate(env);
+        client.add_api_key(&brand_id, &key, &1000, &100);
+        (client, brand_id, key)
+    }
+
+    #[test]
+    fn test_api_key_budget_resets_each_period() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, key) = setup(&env);
+
+        let user = Address::generate(&env);
+        client.issue_with_key(&key, &user, &brand_id, &600, &None);
+        client.issue_with_key(&key, &user, &brand_id, &400, &None);
+        assert!(client
+            .try_issue_with_key(&key, &user, &brand_id, &1, &None)
+            .is_err());
+
+        env.ledger().set_sequence_number(250);
+        let api_key = client.view_api_key(&brand_id, &key).unwrap();
+        assert_eq!(api_key.period_start, 200);
+        assert_eq!(api_key.used, 0);
+
+        client.issue_with_key(&key, &user, &brand_id, &1000, &None);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 2000);
+    }
+
+    #[test]
+    fn test_api_key_budget_counts_experiment_multiplier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, key) = setup(&env);
+        client.start_experiment(&brand_id, &vec![&env, 20_000, 20_000]);
+
+        let user = Address::generate(&env);
+        client.issue_with_key(&key, &user, &brand_id, &400, &None);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 800);
+        assert_eq!(client.view_api_key(&brand_id, &key).unwrap().used, 800);
+        assert!(client
+            .try_issue_with_key(&key, &user, &brand_id, &101, &None)
+            .is_err());
+        client.issue_with_key(&key, &user, &brand_id, &100, &None);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "API key not found")]
+    fn test_revoked_api_key_cannot_issue() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, key) = setup(&env);
+
+        client.revoke_api_key(&brand_id, &key);
+        client.issue_with_key(&key, &Address::generate(&env), &brand_id, &10, &None);
+    }
+}
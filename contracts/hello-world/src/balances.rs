@@ -0,0 +1,700 @@
+use crate::*;
+use soroban_sdk::{Map, Vec};
+
+// Balances live in one persistent entry per user (brand -> balance) rather
+// than one instance entry per (user, brand), stored as i128 so brands with
+// fine-grained points do not overflow. Entries written in the legacy layout or
+// as i64 stay readable and move over on their next write or through
+// `migrate_balances`
+
+// State of a user's balance as seen from the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BalanceState {
+    Empty,
+    Active(i128),
+    // The balance entry's TTL expired and it must be restored before use
+    Archived,
+}
+
+// Structure pairing a balance with the ledger entry holding it, so light
+// clients can check the value against a ledger state proof
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceProof {
+    pub user: Address,
+    pub brand_id: u64,
+    pub balance: i128,
+    // Contract data key of the entry holding the balance
+    pub key: UserBalance,
+    // Legacy balances live inside the contract instance entry instead of
+    // their own persistent entry
+    pub in_instance: bool,
+    // Last ledger the entry was written by this contract, when known
+    pub last_modified: Option<u32>,
+}
+
+// Flag deleting a user's balance entry once all their balances are zero
+const ZERO_CLEANUP: Symbol = symbol_short!("ZERO_CLN");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Move users' legacy balance entries, and balance maps stored as i64, into
+    /// their i128 balance map
+    /// Anyone may call this since balances are unchanged
+    /// Returns the number of entries migrated
+    pub fn migrate_balances(env: Env, users: Vec<Address>) -> u32 {
+        let brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+        let mut migrated: u32 = 0;
+
+        for user in users.iter() {
+            let mut balances = load_balances(&env, &user);
+            let mut changed = has_narrow_balances(&env, &user);
+            if changed {
+                migrated += 1;
+            }
+            for brand_id in 1..=brand_count {
+                let legacy_key = UserBalance::Balance(user.clone(), brand_id);
+                let legacy: Option<i64> = env.storage().instance().get(&legacy_key);
+                if let Some(balance) = legacy {
+                    if !balances.contains_key(brand_id) && balance != 0 {
+                        balances.set(brand_id, balance as i128);
+                    }
+                    env.storage().instance().remove(&legacy_key);
+                    migrated += 1;
+                    changed = true;
+                }
+            }
+            if changed {
+                save_balances(&env, &user, &balances);
+            }
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Migrated {} balance entries", migrated);
+        migrated
+    }
+
+    /// Extend the storage TTL of customers' balance entries on behalf of a brand
+    /// (brand admin only), so inactive customers' balances are not archived
+    /// Only users holding the brand's points are bumped
+    /// Returns the number of entries extended
+    pub fn sponsor_ttl(env: Env, brand_id: u64, users: Vec<Address>) -> u32 {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let mut extended: u32 = 0;
+        for user in users.iter() {
+            if is_archived(&env, &user) || !load_balances(&env, &user).contains_key(brand_id) {
+                continue;
+            }
+            extend_balances_ttl(&env, &user);
+            extended += 1;
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} extended {} balance entries",
+            brand_id,
+            extended
+        );
+        extended
+    }
+
+    /// Extend the storage TTL of a user's balance entry; anyone may call this
+    /// Also refreshes the entry's status after it was restored from the archive
+    pub fn extend_balance_ttl(env: Env, user: Address) {
+        if !has_balance_entry(&env, &user) {
+            panic!("No balance entry");
+        }
+        extend_balances_ttl(&env, &user);
+        env.storage().instance().extend_ttl(100000, 100000);
+    }
+
+    /// View a user's balance of a brand, telling an archived entry apart from a zero balance
+    /// The entry's TTL is tracked from the contract's own extensions; one
+    /// extended or restored outside the contract reads as archived until
+    /// `extend_balance_ttl` is called
+    pub fn view_balance_state(env: Env, user: Address, brand_id: u64) -> BalanceState {
+        if is_archived(&env, &user) {
+            return BalanceState::Archived;
+        }
+        match stored_balance(&env, &user, brand_id) {
+            0 => BalanceState::Empty,
+            balance => BalanceState::Active(balance),
+        }
+    }
+
+    /// Ledger keys of the contract's persistent storage that must be restored
+    /// (RestoreFootprint) before the user's balance of a brand can be used:
+    /// the balance entry and the TTL tracking that expired with it
+    /// Empty when nothing needs restoring
+    pub fn restore_hint(env: Env, user: Address, brand_id: u64) -> Vec<UserBalance> {
+        let mut keys = Vec::new(&env);
+        let legacy = env
+            .storage()
+            .instance()
+            .has(&UserBalance::Balance(user.clone(), brand_id));
+        if legacy || !is_archived(&env, &user) {
+            return keys;
+        }
+        // Tracked entries are always in the i128 layout
+        keys.push_back(UserBalance::WideBalances(user.clone()));
+        for key in [
+            UserBalance::LiveUntil(user.clone()),
+            UserBalance::LastModified(user),
+        ] {
+            if !env.storage().instance().has(&key) {
+                keys.push_back(key);
+            }
+        }
+        keys
+    }
+
+    /// View a user's balance of a brand with the key and last-modified ledger
+    /// of the entry holding it
+    pub fn balance_with_proof(env: Env, user: Address, brand_id: u64) -> BalanceProof {
+        let legacy_key = UserBalance::Balance(user.clone(), brand_id);
+        let in_instance = !load_balances(&env, &user).contains_key(brand_id)
+            && env.storage().instance().has(&legacy_key);
+        let (key, last_modified) = if in_instance {
+            (legacy_key, None)
+        } else {
+            (
+                balances_key(&env, &user),
+                tracked(&env, &UserBalance::LastModified(user.clone())),
+            )
+        };
+
+        BalanceProof {
+            balance: stored_balance(&env, &user, brand_id),
+            user,
+            brand_id,
+            key,
+            in_instance,
+            last_modified,
+        }
+    }
+
+    /// View every brand balance of a user, including legacy entries not yet migrated
+    /// Open to any caller regardless of data-sharing consent
+    pub fn view_balances(env: Env, user: Address) -> Map<u64, i128> {
+        let mut balances = Map::new(&env);
+        for (brand_id, balance) in load_balances(&env, &user).iter() {
+            balances.set(brand_id, balance);
+        }
+        let brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+        for brand_id in 1..=brand_count {
+            if balances.contains_key(brand_id) {
+                continue;
+            }
+            let legacy: Option<i64> = env
+                .storage()
+                .instance()
+                .get(&UserBalance::Balance(user.clone(), brand_id));
+            if let Some(balance) = legacy.filter(|balance| *balance != 0) {
+                balances.set(brand_id, balance as i128);
+            }
+        }
+        balances
+    }
+
+    /// Delete a user's balance entry, instead of keeping it empty, once their
+    /// last balance reaches zero (platform admin only)
+    /// Cleaned-up users count as new for claimable issuance
+    pub fn set_zero_balance_cleanup(env: Env, enabled: bool) {
+        let admin = require_platform_admin(&env);
+
+        log_admin_action(
+            &env,
+            &admin,
+            AdminAction::ZeroBalanceCleanupSet,
+            PLATFORM_BRAND_ID,
+            Some(Self::view_zero_balance_cleanup(env.clone()) as i128),
+            Some(enabled as i128),
+        );
+        if enabled {
+            env.storage().instance().set(&ZERO_CLEANUP, &true);
+        } else {
+            env.storage().instance().remove(&ZERO_CLEANUP);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Zero-balance cleanup updated");
+    }
+
+    /// View whether empty balance entries are deleted
+    pub fn view_zero_balance_cleanup(env: Env) -> bool {
+        env.storage().instance().get(&ZERO_CLEANUP).unwrap_or(false)
+    }
+}
+
+// Whether the user has ever been credited in the persistent layout
+pub(crate) fn has_balance_entry(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&UserBalance::WideBalances(user.clone()))
+        || has_narrow_balances(env, user)
+}
+
+// Read a balance from the persistent map, falling back to the legacy entry
+pub(crate) fn stored_balance(env: &Env, user: &Address, brand_id: u64) -> i128 {
+    if let Some(balance) = load_balances(env, user).get(brand_id) {
+        return balance;
+    }
+    let legacy: i64 = env
+        .storage()
+        .instance()
+        .get(&UserBalance::Balance(user.clone(), brand_id))
+        .unwrap_or(0);
+    legacy as i128
+}
+
+// Write a balance in the persistent layout and drop any legacy entry
+pub(crate) fn write_balance(env: &Env, user: &Address, brand_id: u64, balance: i128) {
+    preserve_snapshot_balance(env, user, brand_id);
+    let mut balances = load_balances(env, user);
+    if balance == 0 {
+        balances.remove(brand_id);
+    } else {
+        balances.set(brand_id, balance);
+    }
+    if balances.is_empty() && LoyaltyTokenExchange::view_zero_balance_cleanup(env.clone()) {
+        remove_balances(env, user);
+    } else {
+        save_balances(env, user, &balances);
+    }
+    env.storage()
+        .instance()
+        .remove(&UserBalance::Balance(user.clone(), brand_id));
+}
+
+fn load_balances(env: &Env, user: &Address) -> Map<u64, i128> {
+    let wide: Option<Map<u64, i128>> = env
+        .storage()
+        .persistent()
+        .get(&UserBalance::WideBalances(user.clone()));
+    if let Some(balances) = wide {
+        return balances;
+    }
+
+    let mut balances = Map::new(env);
+    let narrow: Option<Map<u64, i64>> = env
+        .storage()
+        .persistent()
+        .get(&UserBalance::Balances(user.clone()));
+    for (brand_id, balance) in narrow.unwrap_or(Map::new(env)).iter() {
+        balances.set(brand_id, balance as i128);
+    }
+    balances
+}
+
+// Save a balance map in the i128 layout, dropping the user's i64 map
+fn save_balances(env: &Env, user: &Address, balances: &Map<u64, i128>) {
+    env.storage()
+        .persistent()
+        .set(&UserBalance::WideBalances(user.clone()), balances);
+    env.storage()
+        .persistent()
+        .remove(&UserBalance::Balances(user.clone()));
+    track(
+        env,
+        &UserBalance::LastModified(user.clone()),
+        env.ledger().sequence(),
+    );
+    extend_balances_ttl(env, user);
+}
+
+fn has_narrow_balances(env: &Env, user: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&UserBalance::Balances(user.clone()))
+}
+
+// Key of the layout a user's balances are currently stored in
+fn balances_key(env: &Env, user: &Address) -> UserBalance {
+    if has_narrow_balances(env, user) {
+        UserBalance::Balances(user.clone())
+    } else {
+        UserBalance::WideBalances(user.clone())
+    }
+}
+
+// Drop an empty balance entry and its TTL tracking; the next credit creates
+// them again
+pub(crate) fn remove_balances(env: &Env, user: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&UserBalance::WideBalances(user.clone()));
+    env.storage()
+        .persistent()
+        .remove(&UserBalance::Balances(user.clone()));
+    for key in [
+        UserBalance::LiveUntil(user.clone()),
+        UserBalance::LastModified(user.clone()),
+    ] {
+        env.storage().persistent().remove(&key);
+        env.storage().instance().remove(&key);
+    }
+}
+
+// Extend a balance entry and remember until when it is live, so views can
+// report it as archived without touching the expired entry
+// The tracking entries live and expire alongside the balance entry
+fn extend_balances_ttl(env: &Env, user: &Address) {
+    // Rewrite an i64 map first so the tracked entry is the one extended
+    if has_narrow_balances(env, user) {
+        save_balances(env, user, &load_balances(env, user));
+        return;
+    }
+    let key = UserBalance::WideBalances(user.clone());
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+
+    // Mirror the host, which clamps extensions to the maximum TTL
+    let live_until = env.ledger().sequence() + env.storage().max_ttl().min(100000);
+    let tracked_key = UserBalance::LiveUntil(user.clone());
+    let tracked_until = tracked(env, &tracked_key).unwrap_or(0);
+    track(env, &tracked_key, live_until.max(tracked_until));
+
+    let modified_key = UserBalance::LastModified(user.clone());
+    if env.storage().persistent().has(&modified_key) {
+        env.storage()
+            .persistent()
+            .extend_ttl(&modified_key, 100000, 100000);
+    }
+}
+
+fn is_archived(env: &Env, user: &Address) -> bool {
+    tracked(env, &UserBalance::LiveUntil(user.clone()))
+        .is_some_and(|live_until| live_until < env.ledger().sequence())
+}
+
+// Write a tracked ledger in persistent storage next to the balance entry,
+// dropping the copy earlier versions kept in the contract instance
+fn track(env: &Env, key: &UserBalance, ledger: u32) {
+    env.storage().persistent().set(key, &ledger);
+    env.storage().persistent().extend_ttl(key, 100000, 100000);
+    env.storage().instance().remove(key);
+}
+
+// Read a tracked ledger, falling back to the instance copy
+fn tracked(env: &Env, key: &UserBalance) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(key)
+        .or_else(|| env.storage().instance().get(key))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{storage::Persistent as _, Address as _, Ledger as _},
+        vec, Address, Env, Map, String,
+    };
+
+    #[test]
+    fn test_legacy_balances_are_read_and_migrated() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        // Balances written by a deployment using the per-entry layout
+        env.as_contract(&contract_id, || {
+            for (user, brand_id) in [(&user1, hotel), (&user1, airline), (&user2, hotel)] {
+                env.storage()
+                    .instance()
+                    .set(&UserBalance::Balance(user.clone(), brand_id), &100_i64);
+            }
+        });
+        assert_eq!(client.view_user_balance(&user1, &hotel), 100);
+        assert_eq!(client.view_balances(&user1).len(), 2);
+
+        // A write moves the touched entry to the new layout
+        client.issue_tokens(&user1, &hotel, &50, &None);
+        assert_eq!(client.view_user_balance(&user1, &hotel), 150);
+
+        assert_eq!(
+            client.migrate_balances(&vec![&env, user1.clone(), user2.clone()]),
+            2
+        );
+        assert_eq!(client.view_user_balance(&user1, &airline), 100);
+        assert_eq!(client.view_user_balance(&user2, &hotel), 100);
+        env.as_contract(&contract_id, || {
+            assert!(!env
+                .storage()
+                .instance()
+                .has(&UserBalance::Balance(user2.clone(), hotel)));
+        });
+    }
+
+    #[test]
+    fn test_i64_balance_maps_move_to_i128() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        let other = Address::generate(&env);
+
+        // Balance maps written before amounts were widened
+        env.as_contract(&contract_id, || {
+            for account in [&user, &other] {
+                let mut balances: Map<u64, i64> = Map::new(&env);
+                balances.set(hotel, 100);
+                balances.set(airline, 40);
+                env.storage()
+                    .persistent()
+                    .set(&UserBalance::Balances(account.clone()), &balances);
+            }
+        });
+        assert_eq!(client.view_user_balance(&user, &hotel), 100);
+
+        client.issue_tokens(&user, &hotel, &50, &None);
+        assert_eq!(client.view_user_balance(&user, &hotel), 150);
+        assert_eq!(client.view_user_balance(&user, &airline), 40);
+        assert_eq!(
+            client.migrate_balances(&vec![&env, user.clone(), other.clone()]),
+            1
+        );
+
+        env.as_contract(&contract_id, || {
+            for account in [&user, &other] {
+                assert!(!env
+                    .storage()
+                    .persistent()
+                    .has(&UserBalance::Balances(account.clone())));
+                let balances: Map<u64, i128> = env
+                    .storage()
+                    .persistent()
+                    .get(&UserBalance::WideBalances(account.clone()))
+                    .unwrap();
+                assert_eq!(balances.get(airline), Some(40));
+            }
+        });
+    }
+
+    #[test]
+    fn test_empty_balance_entry_cleaned_up_when_enabled() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        let has_entry = || {
+            env.as_contract(&contract_id, || {
+                env.storage()
+                    .persistent()
+                    .has(&UserBalance::WideBalances(user.clone()))
+            })
+        };
+
+        client.issue_tokens(&user, &hotel, &100, &None);
+        client.transfer_tokens(&user, &Address::generate(&env), &hotel, &100, &None);
+        assert!(has_entry());
+
+        client.set_zero_balance_cleanup(&true);
+        client.issue_tokens(&user, &hotel, &100, &None);
+        client.issue_tokens(&user, &airline, &50, &None);
+        client.transfer_tokens(&user, &Address::generate(&env), &hotel, &100, &None);
+        assert_eq!(client.view_balances(&user).len(), 1);
+        client.transfer_tokens(&user, &Address::generate(&env), &airline, &50, &None);
+        assert!(!has_entry());
+
+        client.issue_tokens(&user, &airline, &20, &None);
+        assert!(has_entry());
+        assert_eq!(client.view_user_balance(&user, &airline), 20);
+    }
+
+    #[test]
+    fn test_brand_sponsors_customer_ttls() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        let customer = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        client.issue_tokens(&customer, &hotel, &100, &None);
+
+        env.ledger().set_sequence_number(60_000);
+        let ttl = || {
+            env.as_contract(&contract_id, || {
+                env.storage()
+                    .persistent()
+                    .get_ttl(&UserBalance::WideBalances(customer.clone()))
+            })
+        };
+        assert_eq!(ttl(), 40_000);
+
+        let extended = client.sponsor_ttl(&hotel, &vec![&env, customer.clone(), stranger]);
+        assert_eq!(extended, 1);
+        assert_eq!(ttl(), 100_000);
+    }
+
+    #[test]
+    fn test_archived_balance_is_reported_with_restore_keys() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let dormant = Address::generate(&env);
+        let active = Address::generate(&env);
+        client.issue_tokens(&dormant, &hotel, &100, &None);
+        assert_eq!(
+            client.view_balance_state(&dormant, &hotel),
+            BalanceState::Active(100)
+        );
+        env.as_contract(&contract_id, || {
+            let key = UserBalance::LiveUntil(dormant.clone());
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+        assert_eq!(
+            client.view_balance_state(&active, &hotel),
+            BalanceState::Empty
+        );
+
+        // Keep the contract live while the dormant user's entry expires
+        env.ledger().set_sequence_number(60_000);
+        client.issue_tokens(&active, &hotel, &100, &None);
+        env.ledger().set_sequence_number(120_000);
+
+        assert_eq!(
+            client.view_balance_state(&dormant, &hotel),
+            BalanceState::Archived
+        );
+        assert_eq!(
+            client.restore_hint(&dormant, &hotel),
+            vec![
+                &env,
+                UserBalance::WideBalances(dormant.clone()),
+                UserBalance::LiveUntil(dormant.clone()),
+                UserBalance::LastModified(dormant.clone())
+            ]
+        );
+        assert_eq!(client.restore_hint(&active, &hotel).len(), 0);
+    }
+
+    #[test]
+    fn test_tracked_live_until_matches_ledger_ttl() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let user = Address::generate(&env);
+        let narrow = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            let mut balances: Map<u64, i64> = Map::new(&env);
+            balances.set(hotel, 100);
+            env.storage()
+                .persistent()
+                .set(&UserBalance::Balances(narrow.clone()), &balances);
+        });
+        let assert_tracked = |account: &Address| {
+            env.as_contract(&contract_id, || {
+                let storage = env.storage().persistent();
+                let tracked: u32 = storage
+                    .get(&UserBalance::LiveUntil(account.clone()))
+                    .unwrap();
+                let sequence = env.ledger().sequence();
+                for key in [
+                    UserBalance::WideBalances(account.clone()),
+                    UserBalance::LiveUntil(account.clone()),
+                    UserBalance::LastModified(account.clone()),
+                ] {
+                    assert_eq!(sequence + storage.get_ttl(&key), tracked);
+                }
+            })
+        };
+
+        env.ledger().set_sequence_number(10);
+        client.issue_tokens(&user, &hotel, &100, &None);
+        assert_tracked(&user);
+
+        env.ledger().set_sequence_number(30_000);
+        client.extend_balance_ttl(&user);
+        client.extend_balance_ttl(&narrow);
+        assert_tracked(&user);
+        assert_tracked(&narrow);
+
+        // Extensions past the maximum TTL are clamped
+        env.ledger().set_sequence_number(100_000);
+        env.ledger().set_max_entry_ttl(50_000);
+        client.extend_balance_ttl(&user);
+        assert_tracked(&user);
+        assert_eq!(
+            client.view_balance_state(&narrow, &hotel),
+            BalanceState::Active(100)
+        );
+    }
+
+    #[test]
+    fn test_balance_proof_names_entry_and_last_write() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let user = Address::generate(&env);
+        env.ledger().set_sequence_number(40);
+        client.issue_tokens(&user, &hotel, &100, &None);
+        env.ledger().set_sequence_number(90);
+
+        let proof = client.balance_with_proof(&user, &hotel);
+        assert_eq!(proof.balance, 100);
+        assert_eq!(proof.key, UserBalance::WideBalances(user.clone()));
+        assert!(!proof.in_instance);
+        assert_eq!(proof.last_modified, Some(40));
+
+        // Balances written by a deployment using the per-entry layout
+        let legacy = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .instance()
+                .set(&UserBalance::Balance(legacy.clone(), hotel), &70_i64);
+        });
+        let proof = client.balance_with_proof(&legacy, &hotel);
+        assert_eq!(proof.balance, 70);
+        assert_eq!(proof.key, UserBalance::Balance(legacy.clone(), hotel));
+        assert!(proof.in_instance);
+    }
+
+    #[test]
+    fn test_balances_cannot_overflow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &i128::MAX, &None);
+        assert_eq!(client.view_user_balance(&user, &brand_id), i128::MAX);
+        assert!(client
+            .try_issue_tokens(&user, &brand_id, &1, &None)
+            .is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// One conversion of a batch exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExchangeLeg {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub amount: i128,
+    pub min_out: i128,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Execute several exchanges in order, all or nothing
+    /// Each leg sees the balances left by the legs before it and fails the
+    /// whole batch if its output is below its `min_out`
+    /// Returns the op_ids of the recorded exchanges
+    pub fn multi_exchange(env: Env, user: Address, legs: Vec<ExchangeLeg>) -> Vec<u64> {
+        require_user(&env, &user);
+        if legs.is_empty() {
+            panic!("No exchange legs");
+        }
+
+        let mut op_ids = Vec::new(&env);
+        for leg in legs.iter() {
+            op_ids.push_back(exchange(
+                &env,
+                &user,
+                leg.from_brand,
+                leg.to_brand,
+                leg.amount,
+                leg.min_out,
+                None,
+            ));
+        }
+        op_ids
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    fn leg(from_brand: u64, to_brand: u64, amount: i128, min_out: i128) -> ExchangeLeg {
+        ExchangeLeg {
+            from_brand,
+            to_brand,
+            amount,
+            min_out,
+        }
+    }
+
+    #[test]
+    fn test_multi_exchange_is_all_or_nothing() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        // The second leg's minimum cannot be met, so the first is rolled back
+        let failing = vec![
+            &env,
+            leg(hotel, airline, 400, 400),
+            leg(hotel, cafe, 300, 301),
+        ];
+        assert!(client.try_multi_exchange(&user, &failing).is_err());
+        assert_eq!(client.view_user_balance(&user, &hotel), 1000);
+
+        // Later legs can spend the output of earlier ones
+        let legs = vec![
+            &env,
+            leg(hotel, airline, 400, 400),
+            leg(airline, cafe, 150, 150),
+        ];
+        assert_eq!(client.multi_exchange(&user, &legs).len(), 2);
+        assert_eq!(client.view_user_balance(&user, &hotel), 600);
+        assert_eq!(client.view_user_balance(&user, &airline), 250);
+        assert_eq!(client.view_user_balance(&user, &cafe), 150);
+    }
+}
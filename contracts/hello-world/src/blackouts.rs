@@ -0,0 +1,167 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure describing a recurring window closing a brand to exchanges:
+// every `period` ledgers starting at `start`, for `duration` ledgers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlackoutWindow {
+    pub start: u32,
+    pub period: u32,
+    pub duration: u32,
+}
+
+// Mapping for blackout windows: Brand -> Windows
+#[contracttype]
+pub enum BlackoutBook {
+    Windows(u64),
+}
+
+// Most blackout windows one brand can define
+pub const MAX_BLACKOUT_WINDOWS: u32 = 10;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Add a recurring window during which exchanges into and out of the brand
+    /// are rejected, e.g. during accounting close (brand admin only)
+    pub fn add_blackout_window(env: Env, brand_id: u64, window: BlackoutWindow) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if window.duration == 0 || window.duration >= window.period {
+            panic!("Duration must be positive and shorter than the period");
+        }
+
+        let mut windows = Self::view_blackout_windows(env.clone(), brand_id);
+        if windows.len() >= MAX_BLACKOUT_WINDOWS {
+            panic!("Too many blackout windows");
+        }
+        windows.push_back(window);
+        save_windows(&env, brand_id, &windows);
+
+        log!(&env, "✅ Blackout window added for brand {}", brand_id);
+    }
+
+    /// Remove a brand's blackout window by its position (brand admin only)
+    pub fn remove_blackout_window(env: Env, brand_id: u64, index: u32) {
+        require_brand_admin(&env, brand_id);
+
+        let mut windows = Self::view_blackout_windows(env.clone(), brand_id);
+        if index >= windows.len() {
+            panic!("Blackout window not found");
+        }
+        windows.remove(index);
+        save_windows(&env, brand_id, &windows);
+
+        log!(&env, "✅ Blackout window removed for brand {}", brand_id);
+    }
+
+    /// View a brand's blackout windows
+    pub fn view_blackout_windows(env: Env, brand_id: u64) -> Vec<BlackoutWindow> {
+        env.storage()
+            .instance()
+            .get(&BlackoutBook::Windows(brand_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View whether a brand is closed to exchanges at the current ledger
+    pub fn is_in_blackout(env: Env, brand_id: u64) -> bool {
+        blackout_end(&env, brand_id, env.ledger().sequence()).is_some()
+    }
+
+    /// View the first ledger, from the current one on, at which the brand is
+    /// open to exchanges
+    pub fn next_open_ledger(env: Env, brand_id: u64) -> u32 {
+        let mut ledger = env.ledger().sequence();
+        // Each step leaves one window; overlapping windows can chain
+        for _ in 0..=MAX_BLACKOUT_WINDOWS {
+            match blackout_end(&env, brand_id, ledger) {
+                Some(end) => ledger = end,
+                None => break,
+            }
+        }
+        ledger
+    }
+}
+
+// First ledger after the blackout covering `ledger`, if one does
+fn blackout_end(env: &Env, brand_id: u64, ledger: u32) -> Option<u32> {
+    let windows = LoyaltyTokenExchange::view_blackout_windows(env.clone(), brand_id);
+    let mut end: Option<u32> = None;
+    for window in windows.iter() {
+        if ledger < window.start {
+            continue;
+        }
+        let into_period = (ledger - window.start) % window.period;
+        if into_period < window.duration {
+            let window_end = ledger - into_period + window.duration;
+            end = Some(end.map_or(window_end, |end| end.max(window_end)));
+        }
+    }
+    end
+}
+
+fn save_windows(env: &Env, brand_id: u64, windows: &Vec<BlackoutWindow>) {
+    let key = BlackoutBook::Windows(brand_id);
+    if windows.is_empty() {
+        env.storage().instance().remove(&key);
+    } else {
+        env.storage().instance().set(&key, windows);
+    }
+    env.storage().instance().extend_ttl(100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_exchanges_rejected_during_blackout() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&airline, &Address::generate(&env));
+        client.add_blackout_window(
+            &airline,
+            &BlackoutWindow {
+                start: 100,
+                period: 1000,
+                duration: 50,
+            },
+        );
+        client.add_blackout_window(
+            &airline,
+            &BlackoutWindow {
+                start: 140,
+                period: 1000,
+                duration: 30,
+            },
+        );
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        env.ledger().set_sequence_number(1120);
+        assert!(client.is_in_blackout(&airline));
+        assert_eq!(client.next_open_ledger(&airline), 1170);
+        assert_eq!(
+            client.can_exchange(&user, &hotel, &airline, &100),
+            Some(CheckFailure::Blackout)
+        );
+
+        env.ledger().set_sequence_number(1170);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+
+        client.remove_blackout_window(&airline, &0);
+        env.ledger().set_sequence_number(2120);
+        assert!(!client.is_in_blackout(&airline));
+        assert_eq!(client.next_open_ledger(&airline), 2120);
+    }
+}
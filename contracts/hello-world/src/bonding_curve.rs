@@ -0,0 +1,400 @@
+use crate::*;
+use soroban_sdk::token;
+
+// Shapes of price curves for point sales
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CurveKind {
+    // price = base_price + slope * supply / CURVE_SCALE
+    Linear,
+    // price = base_price + slope * (supply / step_size)
+    Step,
+}
+
+// Structure to store a brand's bonding-curve sale configuration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondingCurve {
+    pub brand_id: u64,
+    // SAC token used to pay for points
+    pub token: Address,
+    pub kind: CurveKind,
+    pub base_price: i128,
+    pub slope: i128,
+    pub step_size: i128,
+    // Tokens held by the contract to pay out sells
+    pub reserve: i128,
+    pub total_bought: i128,
+    pub total_sold: i128,
+}
+
+// Mapping for bonding curves: Brand -> BondingCurve
+#[contracttype]
+pub enum CurveBook {
+    Curve(u64),
+    // (Brand, User) -> Points bought from the curve and not yet sold back
+    Held(u64, Address),
+}
+
+// Scale applied to the slope of linear curves
+pub const CURVE_SCALE: i128 = 1_000_000;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Configure bonding-curve sales of a brand's points (brand admin only)
+    /// Reconfiguring keeps the accumulated reserve and totals
+    pub fn set_bonding_curve(
+        env: Env,
+        brand_id: u64,
+        token: Address,
+        kind: CurveKind,
+        base_price: i128,
+        slope: i128,
+        step_size: i128,
+    ) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if base_price < 0 || slope < 0 {
+            panic!("Curve prices cannot be negative");
+        }
+        if kind == CurveKind::Step && step_size <= 0 {
+            panic!("Step size must be positive");
+        }
+
+        let previous = Self::view_bonding_curve(env.clone(), brand_id);
+        if let Some(previous) = &previous {
+            if previous.token != token && previous.reserve > 0 {
+                panic!("Cannot change token while reserve is held");
+            }
+        }
+
+        let curve = BondingCurve {
+            brand_id,
+            token,
+            kind,
+            base_price,
+            slope,
+            step_size,
+            reserve: previous.as_ref().map(|curve| curve.reserve).unwrap_or(0),
+            total_bought: previous
+                .as_ref()
+                .map(|curve| curve.total_bought)
+                .unwrap_or(0),
+            total_sold: previous.as_ref().map(|curve| curve.total_sold).unwrap_or(0),
+        };
+        save_curve(&env, &curve);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Bonding curve configured for brand {}", brand_id);
+    }
+
+    /// Quote the token cost of buying `amount` points at the current curve supply
+    pub fn quote_buy(env: Env, brand_id: u64, amount: i128) -> i128 {
+        let curve = load_curve(&env, brand_id);
+        curve_cost(&curve, curve_supply(&curve), amount)
+    }
+
+    /// Quote the tokens paid out for selling `amount` points at the current curve supply
+    /// Only points bought from the curve count towards its supply
+    pub fn quote_sell(env: Env, brand_id: u64, amount: i128) -> i128 {
+        let curve = load_curve(&env, brand_id);
+        let supply = curve_supply(&curve);
+        if amount > supply {
+            panic!("Amount exceeds supply");
+        }
+        curve_cost(&curve, supply - amount, amount)
+    }
+
+    /// Buy points along the brand's bonding curve, paying at most `max_cost` tokens
+    /// Returns the token cost paid
+    pub fn buy_points(
+        env: Env,
+        buyer: Address,
+        brand_id: u64,
+        amount: i128,
+        max_cost: i128,
+    ) -> i128 {
+        require_user(&env, &buyer);
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        let mut curve = load_curve(&env, brand_id);
+        let cost = Self::quote_buy(env.clone(), brand_id, amount);
+        if cost > max_cost {
+            panic!("Cost exceeds maximum");
+        }
+
+        token::TokenClient::new(&env, &curve.token).transfer(
+            &buyer,
+            env.current_contract_address(),
+            &cost,
+        );
+        curve.reserve += cost;
+        curve.total_bought += amount;
+        save_curve(&env, &curve);
+        set_curve_held(
+            &env,
+            brand_id,
+            &buyer,
+            Self::view_curve_held(env.clone(), brand_id, buyer.clone()) + amount,
+        );
+
+        issue(&env, None, buyer, brand_id, amount, None);
+
+        log!(
+            &env,
+            "✅ Bought {} tokens of brand {} for {}",
+            amount,
+            brand_id,
+            cost
+        );
+        cost
+    }
+
+    /// Sell points back along the brand's bonding curve for at least `min_proceeds` tokens
+    /// Only points the seller bought from the curve can be sold back
+    /// Returns the tokens paid out
+    pub fn sell_points(
+        env: Env,
+        seller: Address,
+        brand_id: u64,
+        amount: i128,
+        min_proceeds: i128,
+    ) -> i128 {
+        require_user(&env, &seller);
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let held = Self::view_curve_held(env.clone(), brand_id, seller.clone());
+        if amount > held {
+            panic!("Amount exceeds points bought from the curve");
+        }
+
+        let mut curve = load_curve(&env, brand_id);
+        let proceeds = Self::quote_sell(env.clone(), brand_id, amount);
+        if proceeds < min_proceeds {
+            panic!("Proceeds below minimum");
+        }
+        if proceeds > curve.reserve {
+            panic!("Insufficient curve reserve");
+        }
+
+        debit(&env, &seller, brand_id, amount);
+        save_operation(
+            &env,
+            &OperationRecord::new(&env, OperationKind::Sell, &seller, brand_id, amount),
+        );
+
+        curve.reserve -= proceeds;
+        curve.total_sold += amount;
+        save_curve(&env, &curve);
+        set_curve_held(&env, brand_id, &seller, held - amount);
+        token::TokenClient::new(&env, &curve.token).transfer(
+            &env.current_contract_address(),
+            &seller,
+            &proceeds,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Sold {} tokens of brand {} for {}",
+            amount,
+            brand_id,
+            proceeds
+        );
+        proceeds
+    }
+
+    /// View the bonding curve of a brand, if configured
+    pub fn view_bonding_curve(env: Env, brand_id: u64) -> Option<BondingCurve> {
+        env.storage().instance().get(&CurveBook::Curve(brand_id))
+    }
+
+    /// View the points a user bought from a brand's curve and can still sell back
+    pub fn view_curve_held(env: Env, brand_id: u64, user: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&CurveBook::Held(brand_id, user))
+            .unwrap_or(0)
+    }
+}
+
+fn load_curve(env: &Env, brand_id: u64) -> BondingCurve {
+    env.storage()
+        .instance()
+        .get(&CurveBook::Curve(brand_id))
+        .unwrap_or_else(|| panic!("Bonding curve not configured"))
+}
+
+fn save_curve(env: &Env, curve: &BondingCurve) {
+    env.storage()
+        .instance()
+        .set(&CurveBook::Curve(curve.brand_id), curve);
+}
+
+fn set_curve_held(env: &Env, brand_id: u64, user: &Address, held: i128) {
+    let key = CurveBook::Held(brand_id, user.clone());
+    if held == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &held);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    }
+}
+
+// Points bought from the curve and not yet sold back, which the curve prices off
+fn curve_supply(curve: &BondingCurve) -> i128 {
+    curve.total_bought - curve.total_sold
+}
+
+// Token cost of moving the supply from `supply` to `supply + amount`
+fn curve_cost(curve: &BondingCurve, supply: i128, amount: i128) -> i128 {
+    if amount <= 0 {
+        panic!("Amount must be positive");
+    }
+    cumulative_cost(curve, supply + amount) - cumulative_cost(curve, supply)
+}
+
+// Token cost of buying the first `supply` points along the curve
+fn cumulative_cost(curve: &BondingCurve, supply: i128) -> i128 {
+    let base = checked(curve.base_price.checked_mul(supply));
+    let premium = match curve.kind {
+        CurveKind::Linear => {
+            let squared = checked(supply.checked_mul(supply));
+            checked(curve.slope.checked_mul(squared)) / (2 * CURVE_SCALE)
+        }
+        CurveKind::Step => {
+            // Sum of floor(i / step) for every unit i below the supply
+            let step = curve.step_size;
+            let steps = supply / step;
+            let remainder = supply % step;
+            let units = step * steps * (steps - 1).max(0) / 2 + remainder * steps;
+            checked(curve.slope.checked_mul(units))
+        }
+    };
+    checked(base.checked_add(premium))
+}
+
+fn checked(value: Option<i128>) -> i128 {
+    value.unwrap_or_else(|| panic!("Curve overflow"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::Address as _,
+        token::{StellarAssetClient, TokenClient},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, Address) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let brand_id = client.register_brand(&String::from_str(env, "Arcade"));
+        client.set_brand_admin(&brand_id, &Address::generate(env));
+
+        let sac = env.register_stellar_asset_contract_v2(Address::generate(env));
+        (client, brand_id, sac.address())
+    }
+
+    #[test]
+    fn test_linear_curve_buy_and_sell() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, token) = setup(&env);
+
+        // Price starts at 100 and rises by 1 per point in circulation
+        client.set_bonding_curve(
+            &brand_id,
+            &token,
+            &CurveKind::Linear,
+            &100,
+            &CURVE_SCALE,
+            &0,
+        );
+
+        let buyer = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&buyer, &5000);
+
+        assert_eq!(client.quote_buy(&brand_id, &10), 1050);
+        assert_eq!(client.buy_points(&buyer, &brand_id, &10, &1050), 1050);
+        assert_eq!(client.view_user_balance(&buyer, &brand_id), 10);
+        assert_eq!(client.quote_buy(&brand_id, &10), 1150);
+
+        assert_eq!(client.sell_points(&buyer, &brand_id, &4, &0), 432);
+        assert_eq!(
+            TokenClient::new(&env, &token).balance(&buyer),
+            5000 - 1050 + 432
+        );
+
+        let curve = client.view_bonding_curve(&brand_id).unwrap();
+        assert_eq!(curve.reserve, 1050 - 432);
+        assert_eq!(curve.total_bought, 10);
+        assert_eq!(curve.total_sold, 4);
+    }
+
+    #[test]
+    fn test_only_curve_points_can_be_sold() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, token) = setup(&env);
+        client.set_bonding_curve(
+            &brand_id,
+            &token,
+            &CurveKind::Linear,
+            &100,
+            &CURVE_SCALE,
+            &0,
+        );
+
+        let buyer = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&buyer, &5000);
+        client.buy_points(&buyer, &brand_id, &10, &1050);
+
+        // Points issued outside the curve neither move its price nor sell into it
+        let attacker = Address::generate(&env);
+        client.issue_tokens(&attacker, &brand_id, &1000, &None);
+        assert_eq!(client.quote_buy(&brand_id, &10), 1150);
+        assert!(client
+            .try_sell_points(&attacker, &brand_id, &1, &0)
+            .is_err());
+
+        // The buyer can sell back only what it bought
+        client.issue_tokens(&buyer, &brand_id, &5, &None);
+        assert!(client.try_sell_points(&buyer, &brand_id, &11, &0).is_err());
+        client.sell_points(&buyer, &brand_id, &10, &0);
+        assert_eq!(client.view_curve_held(&brand_id, &buyer), 0);
+        assert_eq!(client.view_bonding_curve(&brand_id).unwrap().reserve, 0);
+    }
+
+    #[test]
+    fn test_step_curve_quote() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, token) = setup(&env);
+
+        // Price rises by 50 every 5 points
+        client.set_bonding_curve(&brand_id, &token, &CurveKind::Step, &100, &50, &5);
+
+        // 5 points at 100, 5 at 150 and 2 at 200
+        assert_eq!(client.quote_buy(&brand_id, &12), 1650);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cost exceeds maximum")]
+    fn test_buy_respects_max_cost() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, token) = setup(&env);
+
+        client.set_bonding_curve(&brand_id, &token, &CurveKind::Step, &100, &50, &5);
+        let buyer = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&buyer, &5000);
+        client.buy_points(&buyer, &brand_id, &12, &1600);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::*;
+
+// Mapping for symbolic brand aliases, so integrations need not hardcode the
+// numeric brand_id of each deployment
+#[contracttype]
+pub enum AliasBook {
+    // Alias -> Brand
+    Alias(Symbol),
+    // Brand -> Alias
+    BrandAlias(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Point a symbolic alias such as `symbol_short!("NIKE")` at a brand,
+    /// replacing the brand's previous alias (brand admin only)
+    pub fn set_brand_alias(env: Env, brand_id: u64, alias: Symbol) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = AliasBook::Alias(alias.clone());
+        if let Some(owner) = env.storage().instance().get::<_, u64>(&key) {
+            if owner != brand_id {
+                panic!("Alias already taken");
+            }
+        }
+        if let Some(previous) = Self::view_brand_alias(env.clone(), brand_id) {
+            env.storage().instance().remove(&AliasBook::Alias(previous));
+        }
+        env.storage().instance().set(&key, &brand_id);
+        env.storage()
+            .instance()
+            .set(&AliasBook::BrandAlias(brand_id), &alias);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Alias set for brand {}", brand_id);
+    }
+
+    /// Release a brand's alias so another brand can claim it (brand admin only)
+    pub fn remove_brand_alias(env: Env, brand_id: u64) {
+        require_brand_admin(&env, brand_id);
+
+        let alias = Self::view_brand_alias(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Alias not found"));
+        env.storage().instance().remove(&AliasBook::Alias(alias));
+        env.storage()
+            .instance()
+            .remove(&AliasBook::BrandAlias(brand_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Alias removed for brand {}", brand_id);
+    }
+
+    /// Resolve an alias to the brand_id it currently points at
+    pub fn resolve_brand_alias(env: Env, alias: Symbol) -> u64 {
+        env.storage()
+            .instance()
+            .get(&AliasBook::Alias(alias))
+            .unwrap_or_else(|| panic!("Alias not found"))
+    }
+
+    /// View a brand's alias, if it has one
+    pub fn view_brand_alias(env: Env, brand_id: u64) -> Option<Symbol> {
+        env.storage()
+            .instance()
+            .get(&AliasBook::BrandAlias(brand_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_aliases_resolve_and_rotate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let shoes = client.register_brand(&String::from_str(&env, "Shoes"));
+        let socks = client.register_brand(&String::from_str(&env, "Socks"));
+        client.set_brand_admin(&shoes, &Address::generate(&env));
+        client.set_brand_admin(&socks, &Address::generate(&env));
+
+        client.set_brand_alias(&shoes, &symbol_short!("SHOE"));
+        assert_eq!(client.resolve_brand_alias(&symbol_short!("SHOE")), shoes);
+        assert!(client
+            .try_set_brand_alias(&socks, &symbol_short!("SHOE"))
+            .is_err());
+
+        client.set_brand_alias(&shoes, &symbol_short!("KICKS"));
+        assert!(client
+            .try_resolve_brand_alias(&symbol_short!("SHOE"))
+            .is_err());
+        client.set_brand_alias(&socks, &symbol_short!("SHOE"));
+        assert_eq!(client.resolve_brand_alias(&symbol_short!("SHOE")), socks);
+
+        client.remove_brand_alias(&shoes);
+        assert_eq!(client.view_brand_alias(&shoes), None);
+        assert!(client
+            .try_resolve_brand_alias(&symbol_short!("KICKS"))
+            .is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// One piece of brand-scoped configuration in an export
+// New variants are only ever appended so older snapshots keep decoding
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BrandStateEntry {
+    Brand(Brand),
+    Admin(Address),
+    ParentBrand(u64),
+    Treasury(Address),
+    ExchangeBurnBps(u32),
+    SupplyCap(i128),
+    ClaimWindow(u32),
+    DecayPolicy(DecayPolicy),
+    ReferenceValue(i128),
+    BondingCurve(BondingCurve),
+    ExportConfig(ExportConfig),
+    DropPool(DropPool),
+    ReceiverWhitelist(bool),
+    CoalitionMember(CoalitionMember),
+    Reward(Reward),
+    Route(RouteConfig),
+}
+
+// Configuration of a route out of the exported brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteConfig {
+    pub to_brand: u64,
+    // Rate set for the route, if any
+    pub rate: Option<u32>,
+    pub fee_payer: FeePayer,
+    pub revenue_share_bps: u32,
+}
+
+// A chunk of an export; pass `next_cursor` back to continue
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrandStatePage {
+    pub entries: Vec<BrandStateEntry>,
+    // None once the export is complete
+    pub next_cursor: Option<u32>,
+}
+
+// Cursor positions before rewards start: one per single-valued setting
+const SETTING_SLOTS: u32 = 14;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Export a chunk of a brand's configuration, starting at `cursor` (0 for
+    /// the first chunk), so brands can snapshot their setup before upgrades
+    /// Unset settings are skipped; rewards follow the settings, then routes
+    pub fn export_brand_state(env: Env, brand_id: u64, cursor: u32, limit: u32) -> BrandStatePage {
+        load_brand(&env, brand_id);
+        if limit == 0 {
+            panic!("Limit must be positive");
+        }
+
+        let reward_slots = reward_count(&env) as u32;
+        let end = SETTING_SLOTS + reward_slots + Self::get_brand_count(env.clone()) as u32;
+        let mut entries = Vec::new(&env);
+        let mut slot = cursor;
+        while slot < end && entries.len() < limit {
+            let entry = if slot < SETTING_SLOTS {
+                setting_entry(&env, brand_id, slot)
+            } else if slot < SETTING_SLOTS + reward_slots {
+                reward_entry(&env, brand_id, (slot - SETTING_SLOTS) as u64 + 1)
+            } else {
+                route_entry(
+                    &env,
+                    brand_id,
+                    (slot - SETTING_SLOTS - reward_slots) as u64 + 1,
+                )
+            };
+            if let Some(entry) = entry {
+                entries.push_back(entry);
+            }
+            slot += 1;
+        }
+
+        BrandStatePage {
+            entries,
+            next_cursor: if slot < end { Some(slot) } else { None },
+        }
+    }
+}
+
+fn setting_entry(env: &Env, brand_id: u64, slot: u32) -> Option<BrandStateEntry> {
+    let storage = env.storage().instance();
+    match slot {
+        0 => Some(BrandStateEntry::Brand(load_brand(env, brand_id))),
+        1 => storage
+            .get(&BrandAdmin::Admin(brand_id))
+            .map(BrandStateEntry::Admin),
+        2 => LoyaltyTokenExchange::view_parent_brand(env.clone(), brand_id)
+            .map(BrandStateEntry::ParentBrand),
+        3 => LoyaltyTokenExchange::view_brand_treasury(env.clone(), brand_id)
+            .map(BrandStateEntry::Treasury),
+        4 => Some(exchange_burn_bps(env, brand_id))
+            .filter(|bps| *bps > 0)
+            .map(BrandStateEntry::ExchangeBurnBps),
+        5 => param_value(env, &Param::SupplyCap(brand_id)).map(BrandStateEntry::SupplyCap),
+        6 => LoyaltyTokenExchange::view_claim_window(env.clone(), brand_id)
+            .map(BrandStateEntry::ClaimWindow),
+        7 => LoyaltyTokenExchange::view_decay_policy(env.clone(), brand_id)
+            .map(BrandStateEntry::DecayPolicy),
+        8 => LoyaltyTokenExchange::view_reference_value(env.clone(), brand_id)
+            .map(BrandStateEntry::ReferenceValue),
+        9 => LoyaltyTokenExchange::view_bonding_curve(env.clone(), brand_id)
+            .map(BrandStateEntry::BondingCurve),
+        10 => LoyaltyTokenExchange::view_export_config(env.clone(), brand_id)
+            .map(BrandStateEntry::ExportConfig),
+        11 => storage
+            .get(&DropBook::Pool(brand_id))
+            .map(BrandStateEntry::DropPool),
+        12 => Some(LoyaltyTokenExchange::view_receiver_whitelist_mode(
+            env.clone(),
+            brand_id,
+        ))
+        .filter(|enabled| *enabled)
+        .map(BrandStateEntry::ReceiverWhitelist),
+        _ => LoyaltyTokenExchange::view_coalition_membership(env.clone(), brand_id)
+            .map(BrandStateEntry::CoalitionMember),
+    }
+}
+
+fn reward_entry(env: &Env, brand_id: u64, reward_id: u64) -> Option<BrandStateEntry> {
+    let reward = load_reward(env, reward_id);
+    if reward.brand_id != brand_id {
+        return None;
+    }
+    Some(BrandStateEntry::Reward(reward))
+}
+
+// Routes are exported only when something about them was configured
+fn route_entry(env: &Env, brand_id: u64, to_brand: u64) -> Option<BrandStateEntry> {
+    if to_brand == brand_id {
+        return None;
+    }
+    let route = RouteConfig {
+        to_brand,
+        rate: param_value(env, &Param::RouteRate(brand_id, to_brand)).map(|rate| rate as u32),
+        fee_payer: route_fee_payer(env, brand_id, to_brand),
+        revenue_share_bps: LoyaltyTokenExchange::view_revenue_share(
+            env.clone(),
+            brand_id,
+            to_brand,
+        ),
+    };
+    if route.rate.is_none() && route.fee_payer == FeePayer::Input && route.revenue_share_bps == 0 {
+        return None;
+    }
+    Some(BrandStateEntry::Route(route))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_export_pages_through_brand_state() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let admin = Address::generate(&env);
+        client.set_brand_admin(&hotel, &admin);
+        client.set_brand_admin(&airline, &Address::generate(&env));
+        let burn_change = client.set_exchange_burn(&hotel, &500);
+        client.add_reward(&airline, &String::from_str(&env, "Lounge"), &100, &1);
+        let suite = client.add_reward(&hotel, &String::from_str(&env, "Suite"), &900, &2);
+        let share_change = client.set_revenue_share(&hotel, &airline, &1000);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&burn_change);
+        client.execute_change(&share_change);
+
+        let first = client.export_brand_state(&hotel, &0, &2);
+        assert_eq!(first.entries.len(), 2);
+        assert_eq!(first.entries.get(1).unwrap(), BrandStateEntry::Admin(admin));
+
+        let rest = client.export_brand_state(&hotel, &first.next_cursor.unwrap(), &10);
+        assert_eq!(rest.next_cursor, None);
+        assert_eq!(rest.entries.len(), 3);
+        assert_eq!(
+            rest.entries.get(0).unwrap(),
+            BrandStateEntry::ExchangeBurnBps(500)
+        );
+        assert_eq!(
+            rest.entries.get(1).unwrap(),
+            BrandStateEntry::Reward(client.view_reward(&suite))
+        );
+        match rest.entries.get(2).unwrap() {
+            BrandStateEntry::Route(route) => {
+                assert_eq!((route.to_brand, route.revenue_share_bps), (airline, 1000))
+            }
+            other => panic!("unexpected entry {:?}", other),
+        }
+    }
+}
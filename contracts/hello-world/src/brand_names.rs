@@ -0,0 +1,113 @@
+use crate::*;
+
+// Mapping for localized display names: (Brand, Locale) -> Name
+#[contracttype]
+pub enum BrandNameBook {
+    Name(u64, String),
+}
+
+// Longest accepted locale code, e.g. "zh-Hant-TW"
+pub const MAX_LOCALE_LEN: u32 = 16;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the display name of a brand for a locale code such as "fr" or
+    /// "pt-BR" (brand admin only); an empty name removes the translation
+    pub fn set_brand_name(env: Env, brand_id: u64, locale: String, name: String) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if locale.is_empty() || locale.len() > MAX_LOCALE_LEN {
+            panic!("Invalid locale");
+        }
+
+        let key = BrandNameBook::Name(brand_id, locale);
+        if name.is_empty() {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &name);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Localized name set for brand {}", brand_id);
+    }
+
+    /// View a brand's display name for a locale
+    /// Falls back from the full locale ("pt-BR") to its language ("pt") and
+    /// then to the name the brand registered with
+    pub fn view_brand_name(env: Env, brand_id: u64, locale: String) -> String {
+        let brand = load_brand(&env, brand_id);
+        if locale.is_empty() || locale.len() > MAX_LOCALE_LEN {
+            return brand.brand_name;
+        }
+
+        if let Some(name) = localized_name(&env, brand_id, locale.clone()) {
+            return name;
+        }
+        if let Some(language) = language_of(&env, &locale) {
+            if let Some(name) = localized_name(&env, brand_id, language) {
+                return name;
+            }
+        }
+        brand.brand_name
+    }
+}
+
+fn localized_name(env: &Env, brand_id: u64, locale: String) -> Option<String> {
+    env.storage()
+        .instance()
+        .get(&BrandNameBook::Name(brand_id, locale))
+}
+
+// Language subtag of a locale with a region or script, e.g. "pt" for "pt-BR"
+fn language_of(env: &Env, locale: &String) -> Option<String> {
+    let mut buffer = [0u8; MAX_LOCALE_LEN as usize];
+    let bytes = &mut buffer[..locale.len() as usize];
+    locale.copy_into_slice(bytes);
+    let split = bytes.iter().position(|b| *b == b'-' || *b == b'_')?;
+    Some(String::from_bytes(env, &bytes[..split]))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_localized_names_fall_back_to_language_then_default() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Sky Miles"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let s = |text: &str| String::from_str(&env, text);
+
+        client.set_brand_name(&brand_id, &s("fr"), &s("Miles du Ciel"));
+        client.set_brand_name(&brand_id, &s("pt-BR"), &s("Milhas do Céu"));
+
+        assert_eq!(
+            client.view_brand_name(&brand_id, &s("pt-BR")),
+            s("Milhas do Céu")
+        );
+        assert_eq!(
+            client.view_brand_name(&brand_id, &s("fr-CA")),
+            s("Miles du Ciel")
+        );
+        assert_eq!(
+            client.view_brand_name(&brand_id, &s("pt-PT")),
+            s("Sky Miles")
+        );
+        assert_eq!(client.view_brand_name(&brand_id, &s("de")), s("Sky Miles"));
+
+        client.set_brand_name(&brand_id, &s("fr"), &s(""));
+        assert_eq!(
+            client.view_brand_name(&brand_id, &s("fr-CA")),
+            s("Sky Miles")
+        );
+        assert!(client
+            .try_set_brand_name(&brand_id, &s(""), &s("Nameless"))
+            .is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::*;
+
+// Mapping for admin quotas: Admin -> Brands the address directly administers
+#[contracttype]
+pub enum BrandQuotaBook {
+    AdminBrands(Address),
+}
+
+// Most brands the platform registers; unset when unlimited
+const MAX_BRANDS: Symbol = symbol_short!("MAX_BRND");
+
+// Most brands one address may directly administer; unset when unlimited
+const ADMIN_QUOTA: Symbol = symbol_short!("ADM_QUOTA");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Cap the total number of brands; `None` lifts the cap (platform admin only)
+    /// Brands already registered are kept when the cap is lowered below them
+    pub fn set_max_brands(env: Env, max_brands: Option<u64>) {
+        require_platform_admin(&env);
+
+        match max_brands {
+            Some(max_brands) => env.storage().instance().set(&MAX_BRANDS, &max_brands),
+            None => env.storage().instance().remove(&MAX_BRANDS),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Brand cap updated");
+    }
+
+    /// View the cap on total brands, if any
+    pub fn view_max_brands(env: Env) -> Option<u64> {
+        env.storage().instance().get(&MAX_BRANDS)
+    }
+
+    /// Cap how many brands one address may directly administer; `None` lifts
+    /// the quota (platform admin only)
+    pub fn set_admin_brand_quota(env: Env, quota: Option<u32>) {
+        require_platform_admin(&env);
+
+        match quota {
+            Some(quota) => env.storage().instance().set(&ADMIN_QUOTA, &quota),
+            None => env.storage().instance().remove(&ADMIN_QUOTA),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Admin brand quota updated");
+    }
+
+    /// View the per-admin brand quota, if any
+    pub fn view_admin_brand_quota(env: Env) -> Option<u32> {
+        env.storage().instance().get(&ADMIN_QUOTA)
+    }
+
+    /// View how many brands an address directly administers
+    pub fn view_admin_brand_count(env: Env, admin: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&BrandQuotaBook::AdminBrands(admin))
+            .unwrap_or(0)
+    }
+
+    /// Dry-run registering a brand, optionally administered by `admin`;
+    /// returns the first check that would fail, if any
+    pub fn can_register_brand(env: Env, admin: Option<Address>) -> Option<CheckFailure> {
+        check_brand_capacity(&env)
+            .err()
+            .or_else(|| admin.and_then(|admin| check_admin_quota(&env, &admin).err()))
+    }
+}
+
+pub(crate) fn check_brand_capacity(env: &Env) -> Result<(), CheckFailure> {
+    if let Some(max_brands) = LoyaltyTokenExchange::view_max_brands(env.clone()) {
+        if LoyaltyTokenExchange::get_brand_count(env.clone()) >= max_brands {
+            return Err(CheckFailure::BrandCapReached);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn check_admin_quota(env: &Env, admin: &Address) -> Result<(), CheckFailure> {
+    if let Some(quota) = LoyaltyTokenExchange::view_admin_brand_quota(env.clone()) {
+        if LoyaltyTokenExchange::view_admin_brand_count(env.clone(), admin.clone()) >= quota {
+            return Err(CheckFailure::AdminQuotaReached);
+        }
+    }
+    Ok(())
+}
+
+// Make `new_admin` the brand's own admin, moving the brand from the previous
+// admin's count to the new admin's within the quota
+pub(crate) fn assign_brand_admin(env: &Env, brand_id: u64, new_admin: &Address) {
+    let key = BrandAdmin::Admin(brand_id);
+    let previous: Option<Address> = env.storage().instance().get(&key);
+    if previous.as_ref() == Some(new_admin) {
+        return;
+    }
+    if let Err(failure) = check_admin_quota(env, new_admin) {
+        failure.fail();
+    }
+
+    if let Some(previous) = previous {
+        let count = LoyaltyTokenExchange::view_admin_brand_count(env.clone(), previous.clone());
+        set_admin_count(env, &previous, count.saturating_sub(1));
+    }
+    let count = LoyaltyTokenExchange::view_admin_brand_count(env.clone(), new_admin.clone());
+    set_admin_count(env, new_admin, count + 1);
+    env.storage().instance().set(&key, new_admin);
+}
+
+fn set_admin_count(env: &Env, admin: &Address, count: u32) {
+    let key = BrandQuotaBook::AdminBrands(admin.clone());
+    if count == 0 {
+        env.storage().instance().remove(&key);
+    } else {
+        env.storage().instance().set(&key, &count);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_brand_cap_and_admin_quota_enforced() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+        client.set_max_brands(&Some(3));
+        client.set_admin_brand_quota(&Some(1));
+
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let gym = client.register_brand(&String::from_str(&env, "Gym"));
+        let owner = Address::generate(&env);
+        client.set_brand_admin(&cafe, &owner);
+        assert_eq!(client.view_admin_brand_count(&owner), 1);
+        assert_eq!(
+            client.can_register_brand(&Some(owner.clone())),
+            Some(CheckFailure::AdminQuotaReached)
+        );
+        assert!(client.try_set_brand_admin(&gym, &owner).is_err());
+
+        // Handing the brand over frees the quota
+        let successor = Address::generate(&env);
+        client.set_brand_admin(&cafe, &successor);
+        assert_eq!(client.view_admin_brand_count(&owner), 0);
+        client.set_brand_admin(&gym, &owner);
+
+        client.register_brand(&String::from_str(&env, "Spa"));
+        assert_eq!(
+            client.can_register_brand(&None),
+            Some(CheckFailure::BrandCapReached)
+        );
+        assert!(client
+            .try_register_brand(&String::from_str(&env, "Bakery"))
+            .is_err());
+        client.set_max_brands(&None);
+        assert_eq!(client.can_register_brand(&None), None);
+    }
+}
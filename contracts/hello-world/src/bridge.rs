@@ -0,0 +1,213 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure marking a brand as backed by a classic Stellar asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportConfig {
+    // Bridge operator that pays out exports as claimable balances
+    pub operator: Address,
+    // Classic asset code the points are backed by
+    pub asset_code: String,
+}
+
+// Structure to store points redeemed for payout as a classic claimable balance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportRequest {
+    pub export_id: u64,
+    pub brand_id: u64,
+    pub user: Address,
+    pub amount: i128,
+    // Stellar account the claimable balance is created for
+    pub destination: Address,
+    // Redemption the points were spent in
+    pub op_id: u64,
+    pub requested_at: u32,
+    // Claimable balance id reported by the operator; None while pending
+    pub balance_id: Option<BytesN<32>>,
+}
+
+// Mapping for the redeem-to-Stellar bridge
+#[contracttype]
+pub enum BridgeBook {
+    Config(u64),
+    Export(u64),
+    // Brand -> export_ids awaiting acknowledgement, oldest first
+    PendingExports(u64),
+}
+
+// Counter for export requests
+const EXPORT_COUNT: Symbol = symbol_short!("EX_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Back a brand's points with a classic asset paid out by `operator`
+    /// (brand admin only)
+    pub fn set_export_config(env: Env, brand_id: u64, operator: Address, asset_code: String) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if asset_code.is_empty() || asset_code.len() > 12 {
+            panic!("Invalid asset code");
+        }
+
+        env.storage().instance().set(
+            &BridgeBook::Config(brand_id),
+            &ExportConfig {
+                operator,
+                asset_code,
+            },
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Export configured for brand {}", brand_id);
+    }
+
+    /// Redeem points of a backed brand for a claimable balance of its asset
+    /// on `destination`, to be created by the bridge operator
+    /// Returns the export_id of the new request
+    pub fn redeem_to_stellar(
+        env: Env,
+        user: Address,
+        brand_id: u64,
+        amount: i128,
+        destination: Address,
+    ) -> u64 {
+        require_user(&env, &user);
+        if Self::view_export_config(env.clone(), brand_id).is_none() {
+            panic!("Brand is not export-backed");
+        }
+        let op_id = redeem_points(&env, user.clone(), brand_id, amount, None);
+
+        let mut export_count: u64 = env.storage().instance().get(&EXPORT_COUNT).unwrap_or(0);
+        export_count += 1;
+
+        let request = ExportRequest {
+            export_id: export_count,
+            brand_id,
+            user: user.clone(),
+            amount,
+            destination: destination.clone(),
+            op_id,
+            requested_at: env.ledger().sequence(),
+            balance_id: None,
+        };
+        env.storage()
+            .instance()
+            .set(&BridgeBook::Export(export_count), &request);
+        let mut pending = Self::view_pending_exports(env.clone(), brand_id);
+        pending.push_back(export_count);
+        env.storage()
+            .instance()
+            .set(&BridgeBook::PendingExports(brand_id), &pending);
+        env.storage().instance().set(&EXPORT_COUNT, &export_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        ExportRequested {
+            brand_id,
+            user,
+            export_id: export_count,
+            amount,
+            destination,
+        }
+        .publish(&env);
+        log!(&env, "✅ Export {} requested", export_count);
+        export_count
+    }
+
+    /// Record the claimable balance created for an export (bridge operator only)
+    pub fn acknowledge_export(env: Env, export_id: u64, balance_id: BytesN<32>) {
+        let mut request = Self::view_export(env.clone(), export_id);
+        let config = Self::view_export_config(env.clone(), request.brand_id)
+            .unwrap_or_else(|| panic!("Brand is not export-backed"));
+        config.operator.require_auth();
+        if request.balance_id.is_some() {
+            panic!("Export already acknowledged");
+        }
+
+        request.balance_id = Some(balance_id.clone());
+        env.storage()
+            .instance()
+            .set(&BridgeBook::Export(export_id), &request);
+        let mut pending = Self::view_pending_exports(env.clone(), request.brand_id);
+        if let Some(index) = pending.first_index_of(export_id) {
+            pending.remove(index);
+        }
+        env.storage()
+            .instance()
+            .set(&BridgeBook::PendingExports(request.brand_id), &pending);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        ExportAcknowledged {
+            brand_id: request.brand_id,
+            export_id,
+            balance_id,
+        }
+        .publish(&env);
+        log!(&env, "✅ Export {} acknowledged", export_id);
+    }
+
+    /// View the export configuration of a brand, if it is backed
+    pub fn view_export_config(env: Env, brand_id: u64) -> Option<ExportConfig> {
+        env.storage().instance().get(&BridgeBook::Config(brand_id))
+    }
+
+    /// View an export request by export_id
+    pub fn view_export(env: Env, export_id: u64) -> ExportRequest {
+        env.storage()
+            .instance()
+            .get(&BridgeBook::Export(export_id))
+            .unwrap_or_else(|| panic!("Export not found"))
+    }
+
+    /// View the exports of a brand still waiting for the operator
+    pub fn view_pending_exports(env: Env, brand_id: u64) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&BridgeBook::PendingExports(brand_id))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+    #[test]
+    fn test_export_is_recorded_and_acknowledged() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let user = Address::generate(&env);
+        let destination = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        assert!(client
+            .try_redeem_to_stellar(&user, &brand_id, &100, &destination)
+            .is_err());
+
+        let operator = Address::generate(&env);
+        client.set_export_config(&brand_id, &operator, &String::from_str(&env, "MILES"));
+        let export_id = client.redeem_to_stellar(&user, &brand_id, &200, &destination);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 300);
+        assert_eq!(
+            client.view_pending_exports(&brand_id),
+            vec![&env, export_id]
+        );
+
+        let balance_id = BytesN::from_array(&env, &[9; 32]);
+        client.acknowledge_export(&export_id, &balance_id);
+        let request = client.view_export(&export_id);
+        assert_eq!(request.balance_id, Some(balance_id.clone()));
+        assert_eq!(request.destination, destination);
+        assert_eq!(client.view_pending_exports(&brand_id).len(), 0);
+        assert!(client
+            .try_acknowledge_export(&export_id, &balance_id)
+            .is_err());
+    }
+}
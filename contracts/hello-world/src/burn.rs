@@ -0,0 +1,100 @@
+use crate::*;
+
+// Structure reporting a brand's supply figures
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupplyStats {
+    pub brand_id: u64,
+    pub circulating: i128,
+    pub burned: i128,
+    pub burn_bps: u32,
+}
+
+// Mapping for burn-on-exchange totals
+#[contracttype]
+pub enum BurnBook {
+    // Brand -> Total points burned by exchanges
+    Burned(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Schedule the share of every outbound exchange that is burned; it takes
+    /// effect through `execute_change` after the timelock (brand admin only)
+    /// Returns the change_id of the queued change
+    pub fn set_exchange_burn(env: Env, brand_id: u64, burn_bps: u32) -> u64 {
+        Self::schedule_change(env, Param::ExchangeBurnBps(brand_id), burn_bps as i128)
+    }
+
+    /// View circulating supply, total burned and burn policy of a brand
+    pub fn view_supply_stats(env: Env, brand_id: u64) -> SupplyStats {
+        SupplyStats {
+            brand_id,
+            circulating: Self::view_brand_supply(env.clone(), brand_id),
+            burned: env
+                .storage()
+                .instance()
+                .get(&BurnBook::Burned(brand_id))
+                .unwrap_or(0),
+            burn_bps: exchange_burn_bps(&env, brand_id),
+        }
+    }
+}
+
+pub(crate) fn exchange_burn_bps(env: &Env, brand_id: u64) -> u32 {
+    param_value(env, &Param::ExchangeBurnBps(brand_id)).unwrap_or(0) as u32
+}
+
+pub(crate) fn record_burn(env: &Env, brand_id: u64, amount: i128) {
+    if amount == 0 {
+        return;
+    }
+    let key = BurnBook::Burned(brand_id);
+    let burned: i128 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage().instance().set(&key, &(burned + amount));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_exchange_burns_configured_share() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let grocer = client.register_brand(&String::from_str(&env, "Grocer"));
+        let fuel = client.register_brand(&String::from_str(&env, "Fuel"));
+        client.set_brand_admin(&grocer, &Address::generate(&env));
+        let change_id = client.set_exchange_burn(&grocer, &1000);
+        assert_eq!(client.quote_exchange(&grocer, &fuel, &500).burned, 0);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+
+        let quote = client.quote_exchange(&grocer, &fuel, &500);
+        assert_eq!(quote.burned, 50);
+        assert_eq!(quote.amount_out, 450);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &grocer, &500, &None);
+        client.exchange_tokens(&user, &grocer, &fuel, &500);
+        assert_eq!(client.view_user_balance(&user, &fuel), 450);
+
+        let stats = client.view_supply_stats(&grocer);
+        assert_eq!(stats.circulating, 0);
+        assert_eq!(stats.burned, 50);
+        assert_eq!(stats.burn_bps, 1000);
+
+        // Inbound exchanges into the grocer are not burned
+        client.exchange_tokens(&user, &fuel, &grocer, &450);
+        assert_eq!(client.view_user_balance(&user, &grocer), 450);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::*;
+
+// Structure to store a brand's pledge to match donations to charities
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchPledge {
+    pub brand_id: u64,
+    // Points escrowed from the brand treasury still available for matching
+    pub remaining: i128,
+    pub matched: i128,
+}
+
+// Mapping for charities and match pledges
+#[contracttype]
+pub enum CharityBook {
+    // Charity -> Whether it can receive donations
+    Charity(Address),
+    // Brand -> Match pledge
+    Pledge(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Allow or stop donations to a charity address (platform admin only)
+    pub fn set_charity(env: Env, charity: Address, approved: bool) {
+        require_platform_admin(&env);
+
+        let key = CharityBook::Charity(charity);
+        if approved {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Charity updated");
+    }
+
+    /// View whether a charity can receive donations
+    pub fn is_charity(env: Env, charity: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&CharityBook::Charity(charity))
+            .unwrap_or(false)
+    }
+
+    /// Pledge to match donations of the brand's points one for one, escrowing
+    /// `amount` more points from the brand treasury as match capacity
+    /// (brand admin only)
+    pub fn pledge_donation_match(env: Env, brand_id: u64, amount: i128) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand treasury not set"));
+        escrow(&env, &treasury, brand_id, amount);
+
+        let mut pledge = Self::view_match_pledge(env.clone(), brand_id);
+        pledge.remaining += amount;
+        save_pledge(&env, &pledge);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Brand {} pledged {} in matches", brand_id, amount);
+    }
+
+    /// End a brand's match pledge, returning the unused capacity to the
+    /// brand treasury (brand admin only)
+    pub fn end_donation_match(env: Env, brand_id: u64) {
+        require_brand_admin(&env, brand_id);
+
+        let mut pledge = Self::view_match_pledge(env.clone(), brand_id);
+        if pledge.remaining > 0 {
+            let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+                .unwrap_or_else(|| panic!("Brand treasury not set"));
+            release(&env, &treasury, brand_id, pledge.remaining);
+        }
+        pledge.remaining = 0;
+        save_pledge(&env, &pledge);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Match pledge ended for brand {}", brand_id);
+    }
+
+    /// View a brand's match pledge
+    pub fn view_match_pledge(env: Env, brand_id: u64) -> MatchPledge {
+        env.storage()
+            .instance()
+            .get(&CharityBook::Pledge(brand_id))
+            .unwrap_or(MatchPledge {
+                brand_id,
+                remaining: 0,
+                matched: 0,
+            })
+    }
+
+    /// Donate points to a charity; the brand adds its match while pledged
+    /// capacity lasts
+    /// Returns the points matched
+    pub fn donate(env: Env, user: Address, charity: Address, brand_id: u64, amount: i128) -> i128 {
+        require_user(&env, &user);
+        if !Self::is_charity(env.clone(), charity.clone()) {
+            panic!("Not an approved charity");
+        }
+        if !is_brand_active(&env, brand_id) {
+            panic!("Brand is not active");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        post(
+            &env,
+            brand_id,
+            &LedgerAccount::User(user.clone()),
+            &LedgerAccount::User(charity.clone()),
+            amount,
+        );
+        let mut record =
+            OperationRecord::new(&env, OperationKind::Transfer, &user, brand_id, amount);
+        record.counterparty = Some(charity.clone());
+        save_operation(&env, &record);
+
+        let mut pledge = Self::view_match_pledge(env.clone(), brand_id);
+        let matched = amount.min(pledge.remaining);
+        if matched > 0 {
+            release(&env, &charity, brand_id, matched);
+            pledge.remaining -= matched;
+            pledge.matched += matched;
+            save_pledge(&env, &pledge);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Donated {}, matched {}", amount, matched);
+        matched
+    }
+}
+
+fn save_pledge(env: &Env, pledge: &MatchPledge) {
+    env.storage()
+        .instance()
+        .set(&CharityBook::Pledge(pledge.brand_id), pledge);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_donations_matched_up_to_pledge() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Grocer"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&brand_id, &treasury);
+        client.issue_tokens(&treasury, &brand_id, &1000, &None);
+        client.pledge_donation_match(&brand_id, &150);
+
+        let charity = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        assert!(client.try_donate(&user, &charity, &brand_id, &100).is_err());
+
+        client.set_charity(&charity, &true);
+        assert_eq!(client.donate(&user, &charity, &brand_id, &100), 100);
+        assert_eq!(client.donate(&user, &charity, &brand_id, &100), 50);
+        assert_eq!(client.view_user_balance(&charity, &brand_id), 350);
+        assert_eq!(client.view_match_pledge(&brand_id).remaining, 0);
+
+        client.pledge_donation_match(&brand_id, &100);
+        client.end_donation_match(&brand_id);
+        assert_eq!(client.view_user_balance(&treasury, &brand_id), 850);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure configuring the volume circuit breaker of a route
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitBreaker {
+    pub window_ledgers: u32,
+    // Number of past windows averaged into the baseline
+    pub lookback_windows: u32,
+    // Multiple of the baseline, in basis points, that trips the breaker
+    pub max_multiple_bps: u32,
+}
+
+// Structure storing a route's exchange volume for one window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WindowVolume {
+    pub window: u32,
+    pub volume: i128,
+}
+
+// Mapping for circuit breakers
+#[contracttype]
+pub enum BreakerBook {
+    // (From, To) -> Breaker configuration
+    Breaker(u64, u64),
+    // (From, To) -> Volumes of the current and lookback windows, oldest first
+    Volumes(u64, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Configure the circuit breaker of a route (platform admin only)
+    /// The route is paused once volume within a window exceeds `max_multiple_bps`
+    /// of the average over the previous `lookback_windows` windows
+    pub fn set_circuit_breaker(
+        env: Env,
+        from_brand: u64,
+        to_brand: u64,
+        window_ledgers: u32,
+        lookback_windows: u32,
+        max_multiple_bps: u32,
+    ) {
+        require_platform_admin(&env);
+        load_brand(&env, from_brand);
+        load_brand(&env, to_brand);
+        if window_ledgers == 0 || lookback_windows == 0 {
+            panic!("Window and lookback must be positive");
+        }
+        if max_multiple_bps <= RATE_DENOMINATOR as u32 {
+            panic!("Multiple must exceed 1x");
+        }
+
+        let breaker = CircuitBreaker {
+            window_ledgers,
+            lookback_windows,
+            max_multiple_bps,
+        };
+        env.storage()
+            .instance()
+            .set(&BreakerBook::Breaker(from_brand, to_brand), &breaker);
+        env.storage()
+            .instance()
+            .remove(&BreakerBook::Volumes(from_brand, to_brand));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Circuit breaker set for route {} -> {}",
+            from_brand,
+            to_brand
+        );
+    }
+
+    /// Remove the circuit breaker of a route (platform admin only)
+    pub fn remove_circuit_breaker(env: Env, from_brand: u64, to_brand: u64) {
+        require_platform_admin(&env);
+        env.storage()
+            .instance()
+            .remove(&BreakerBook::Breaker(from_brand, to_brand));
+        env.storage()
+            .instance()
+            .remove(&BreakerBook::Volumes(from_brand, to_brand));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Circuit breaker removed for route {} -> {}",
+            from_brand,
+            to_brand
+        );
+    }
+
+    /// View the circuit breaker of a route, if configured
+    pub fn view_circuit_breaker(
+        env: Env,
+        from_brand: u64,
+        to_brand: u64,
+    ) -> Option<CircuitBreaker> {
+        env.storage()
+            .instance()
+            .get(&BreakerBook::Breaker(from_brand, to_brand))
+    }
+
+    /// View the tracked window volumes of a route, oldest first
+    pub fn view_route_volumes(env: Env, from_brand: u64, to_brand: u64) -> Vec<WindowVolume> {
+        env.storage()
+            .instance()
+            .get(&BreakerBook::Volumes(from_brand, to_brand))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+// Add an exchange to the route's current window and pause the route when the
+// window's volume is abnormal; the triggering exchange itself still settles
+pub(crate) fn track_route_volume(env: &Env, from_brand: u64, to_brand: u64, amount: i128) {
+    let breaker: CircuitBreaker = match env
+        .storage()
+        .instance()
+        .get(&BreakerBook::Breaker(from_brand, to_brand))
+    {
+        Some(breaker) => breaker,
+        None => return,
+    };
+
+    let window = env.ledger().sequence() / breaker.window_ledgers;
+    let stored = LoyaltyTokenExchange::view_route_volumes(env.clone(), from_brand, to_brand);
+
+    // Keep the current window and the lookback windows before it
+    let mut volumes = Vec::new(env);
+    let mut baseline_total: i128 = 0;
+    let mut current: i128 = amount;
+    for entry in stored.iter() {
+        if entry.window == window {
+            current += entry.volume;
+        } else if entry.window + breaker.lookback_windows >= window {
+            baseline_total += entry.volume;
+            volumes.push_back(entry);
+        }
+    }
+    volumes.push_back(WindowVolume {
+        window,
+        volume: current,
+    });
+    env.storage()
+        .instance()
+        .set(&BreakerBook::Volumes(from_brand, to_brand), &volumes);
+
+    // Windows without exchanges count as zero volume in the average
+    let baseline = baseline_total / breaker.lookback_windows as i128;
+    if baseline > 0
+        && current > apply_bps(baseline, breaker.max_multiple_bps)
+        && !is_route_paused(env, from_brand, to_brand)
+    {
+        pause(env, from_brand, to_brand, env.current_contract_address());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_abnormal_volume_trips_breaker() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_platform_admin(&admin);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &100_000, &None);
+
+        // Windows of 100 ledgers, tripping above 3x the average of the last 2
+        client.set_circuit_breaker(&hotel, &airline, &100, &2, &30000);
+
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        env.ledger().set_sequence_number(100);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+
+        // Average is 100: 300 is still fine, going past it trips the breaker
+        env.ledger().set_sequence_number(200);
+        client.exchange_tokens(&user, &hotel, &airline, &300);
+        assert_eq!(client.view_paused_routes().len(), 0);
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+
+        let paused = client.view_paused_routes();
+        assert_eq!(paused.len(), 1);
+        assert_eq!(paused.get(0).unwrap().paused_by, contract_id);
+        assert!(client
+            .try_exchange_tokens(&user, &hotel, &airline, &10)
+            .is_err());
+        assert_eq!(client.view_user_balance(&user, &airline), 1500);
+
+        client.unpause_route(&admin, &hotel, &airline);
+        client.exchange_tokens(&user, &hotel, &airline, &10);
+    }
+}
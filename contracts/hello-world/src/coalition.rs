@@ -0,0 +1,434 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a coalition of brands sharing a currency
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Coalition {
+    pub coalition_id: u64,
+    pub name: String,
+    // Code of the shared coalition unit
+    pub currency: Symbol,
+    pub council: Vec<Address>,
+    pub members: Vec<u64>,
+}
+
+// Structure to store a brand's coalition membership
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoalitionMember {
+    pub coalition_id: u64,
+    // Coalition units per RATE_DENOMINATOR brand points
+    pub peg: u32,
+    // Set once a council member accepts the brand
+    pub approved: bool,
+}
+
+// Mapping for coalitions
+#[contracttype]
+pub enum CoalitionBook {
+    Coalition(u64),
+    // Brand -> Membership
+    Member(u64),
+}
+
+// Counter for coalitions
+const COALITION_COUNT: Symbol = symbol_short!("C_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Create a coalition with a shared currency and its council (platform admin only)
+    /// Returns the coalition_id of the new coalition
+    pub fn create_coalition(
+        env: Env,
+        name: String,
+        currency: Symbol,
+        council: Vec<Address>,
+    ) -> u64 {
+        require_platform_admin(&env);
+        if council.is_empty() {
+            panic!("Council needs at least one member");
+        }
+
+        let mut coalition_count: u64 = env.storage().instance().get(&COALITION_COUNT).unwrap_or(0);
+        coalition_count += 1;
+
+        let coalition = Coalition {
+            coalition_id: coalition_count,
+            name,
+            currency,
+            council,
+            members: Vec::new(&env),
+        };
+        save_coalition(&env, &coalition);
+        env.storage()
+            .instance()
+            .set(&COALITION_COUNT, &coalition_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Coalition registered with ID: {}", coalition_count);
+        coalition_count
+    }
+
+    /// Ask to join a coalition at the given peg (brand admin only)
+    /// The brand converts through the coalition once a council member approves it
+    pub fn request_join_coalition(env: Env, coalition_id: u64, brand_id: u64, peg: u32) {
+        load_coalition(&env, coalition_id);
+        let brand = load_brand(&env, brand_id);
+        if !brand.is_active {
+            panic!("Brand is not active");
+        }
+        require_brand_admin(&env, brand_id);
+        if peg == 0 {
+            panic!("Peg must be positive");
+        }
+        if Self::view_coalition_membership(env.clone(), brand_id).is_some() {
+            panic!("Brand already belongs to a coalition");
+        }
+
+        let member = CoalitionMember {
+            coalition_id,
+            peg,
+            approved: false,
+        };
+        env.storage()
+            .instance()
+            .set(&CoalitionBook::Member(brand_id), &member);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} requested to join coalition {}",
+            brand_id,
+            coalition_id
+        );
+    }
+
+    /// Accept a brand's pending request to join (council member only)
+    pub fn approve_coalition_member(env: Env, council_member: Address, brand_id: u64) {
+        let mut member = load_member(&env, brand_id);
+        let mut coalition = load_coalition(&env, member.coalition_id);
+        require_council(&coalition, &council_member);
+        if member.approved {
+            panic!("Brand is already a member");
+        }
+
+        member.approved = true;
+        env.storage()
+            .instance()
+            .set(&CoalitionBook::Member(brand_id), &member);
+        coalition.members.push_back(brand_id);
+        save_coalition(&env, &coalition);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} joined coalition {}",
+            brand_id,
+            coalition.coalition_id
+        );
+    }
+
+    /// Schedule a change of a member brand's peg; it takes effect through
+    /// `execute_change` after the timelock (brand admin only)
+    /// Returns the change_id of the queued change
+    pub fn set_coalition_peg(env: Env, brand_id: u64, peg: u32) -> u64 {
+        load_member(&env, brand_id);
+        Self::schedule_change(env, Param::CoalitionPeg(brand_id), peg as i128)
+    }
+
+    /// Leave the brand's coalition or withdraw its pending request (brand admin only)
+    pub fn leave_coalition(env: Env, brand_id: u64) {
+        let member = load_member(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        remove_member(&env, &member, brand_id);
+
+        log!(&env, "✅ Brand {} left its coalition", brand_id);
+    }
+
+    /// Remove a brand from the coalition (council member only)
+    pub fn expel_coalition_member(env: Env, council_member: Address, brand_id: u64) {
+        let member = load_member(&env, brand_id);
+        let coalition = load_coalition(&env, member.coalition_id);
+        require_council(&coalition, &council_member);
+        remove_member(&env, &member, brand_id);
+
+        log!(&env, "✅ Brand {} expelled from its coalition", brand_id);
+    }
+
+    /// Replace the council of a coalition (council member only)
+    pub fn set_coalition_council(
+        env: Env,
+        council_member: Address,
+        coalition_id: u64,
+        council: Vec<Address>,
+    ) {
+        let mut coalition = load_coalition(&env, coalition_id);
+        require_council(&coalition, &council_member);
+        if council.is_empty() {
+            panic!("Council needs at least one member");
+        }
+
+        coalition.council = council;
+        save_coalition(&env, &coalition);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Council updated for coalition {}", coalition_id);
+    }
+
+    /// Schedule making conversions between members always settle through their
+    /// pegs, ignoring rates set for individual routes, so the coalition needs
+    /// one peg per member instead of one rate per pair; it takes effect through
+    /// `execute_change` after the timelock (council member only)
+    /// Returns the change_id of the queued change
+    pub fn set_peg_settlement(
+        env: Env,
+        council_member: Address,
+        coalition_id: u64,
+        enabled: bool,
+    ) -> u64 {
+        let coalition = load_coalition(&env, coalition_id);
+        require_council(&coalition, &council_member);
+
+        queue_change(&env, Param::PegSettlement(coalition_id), enabled as i128)
+    }
+
+    /// Withdraw a queued peg settlement change (council member only)
+    pub fn cancel_peg_settlement(env: Env, council_member: Address, change_id: u64) {
+        let Param::PegSettlement(coalition_id) = load_change(&env, change_id).param else {
+            panic!("Not a peg settlement change");
+        };
+        let coalition = load_coalition(&env, coalition_id);
+        require_council(&coalition, &council_member);
+
+        drop_change(&env, change_id);
+    }
+
+    /// View whether a coalition's member conversions settle only through pegs
+    pub fn view_peg_settlement(env: Env, coalition_id: u64) -> bool {
+        param_value(&env, &Param::PegSettlement(coalition_id)) == Some(1)
+    }
+
+    /// View coalition details by coalition_id
+    pub fn view_coalition(env: Env, coalition_id: u64) -> Coalition {
+        load_coalition(&env, coalition_id)
+    }
+
+    /// View a brand's coalition membership or pending request, if any
+    pub fn view_coalition_membership(env: Env, brand_id: u64) -> Option<CoalitionMember> {
+        env.storage()
+            .instance()
+            .get(&CoalitionBook::Member(brand_id))
+    }
+}
+
+// Rate between two approved members of the same coalition, via the coalition unit
+pub(crate) fn coalition_rate(env: &Env, from_brand: u64, to_brand: u64) -> Option<u32> {
+    let from = coalition_member(env, from_brand)?;
+    let to = coalition_member(env, to_brand)?;
+    if !from.approved || !to.approved || from.coalition_id != to.coalition_id {
+        return None;
+    }
+
+    let rate = from.peg as u64 * RATE_DENOMINATOR as u64 / to.peg as u64;
+    Some(u32::try_from(rate).unwrap_or_else(|_| panic!("Rate out of range")))
+}
+
+// Coalition rate between two members, when their coalition settles only
+// through pegs
+pub(crate) fn pegged_rate(env: &Env, from_brand: u64, to_brand: u64) -> Option<u32> {
+    let member = coalition_member(env, from_brand)?;
+    if !LoyaltyTokenExchange::view_peg_settlement(env.clone(), member.coalition_id) {
+        return None;
+    }
+    coalition_rate(env, from_brand, to_brand)
+}
+
+// Apply a peg change that passed the timelock
+pub(crate) fn apply_coalition_peg(env: &Env, brand_id: u64, peg: u32) {
+    let mut member = load_member(env, brand_id);
+    member.peg = peg;
+    env.storage()
+        .instance()
+        .set(&CoalitionBook::Member(brand_id), &member);
+}
+
+// Coalition membership a brand converts under, inherited by sub-brands
+fn coalition_member(env: &Env, brand_id: u64) -> Option<CoalitionMember> {
+    inherited(env, brand_id, |brand_id| {
+        LoyaltyTokenExchange::view_coalition_membership(env.clone(), brand_id)
+    })
+}
+
+fn load_coalition(env: &Env, coalition_id: u64) -> Coalition {
+    env.storage()
+        .instance()
+        .get(&CoalitionBook::Coalition(coalition_id))
+        .unwrap_or_else(|| panic!("Coalition not found"))
+}
+
+fn save_coalition(env: &Env, coalition: &Coalition) {
+    env.storage()
+        .instance()
+        .set(&CoalitionBook::Coalition(coalition.coalition_id), coalition);
+}
+
+fn load_member(env: &Env, brand_id: u64) -> CoalitionMember {
+    env.storage()
+        .instance()
+        .get(&CoalitionBook::Member(brand_id))
+        .unwrap_or_else(|| panic!("Brand is not in a coalition"))
+}
+
+fn require_council(coalition: &Coalition, council_member: &Address) {
+    if !coalition.council.contains(council_member) {
+        panic!("Not a council member");
+    }
+    council_member.require_auth();
+}
+
+fn remove_member(env: &Env, member: &CoalitionMember, brand_id: u64) {
+    if member.approved {
+        let mut coalition = load_coalition(env, member.coalition_id);
+        if let Some(index) = coalition.members.first_index_of(brand_id) {
+            coalition.members.remove(index);
+        }
+        save_coalition(env, &coalition);
+    }
+    env.storage()
+        .instance()
+        .remove(&CoalitionBook::Member(brand_id));
+    env.storage().instance().extend_ttl(100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        symbol_short,
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    #[test]
+    fn test_members_convert_through_coalition_pegs() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let council = Address::generate(&env);
+        let coalition_id = client.create_coalition(
+            &String::from_str(&env, "Travel Alliance"),
+            &symbol_short!("MILE"),
+            &vec![&env, council.clone()],
+        );
+
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        for brand_id in [airline, hotel, cafe] {
+            client.set_brand_admin(&brand_id, &Address::generate(&env));
+        }
+
+        // 100 airline points or 50 hotel points make one MILE
+        client.request_join_coalition(&coalition_id, &airline, &100);
+        client.request_join_coalition(&coalition_id, &hotel, &200);
+
+        // Pending members still convert at parity
+        assert_eq!(
+            client.quote_exchange(&airline, &hotel, &1000).amount_out,
+            1000
+        );
+
+        client.approve_coalition_member(&council, &airline);
+        client.approve_coalition_member(&council, &hotel);
+        assert_eq!(client.view_coalition(&coalition_id).members.len(), 2);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &airline, &1000, &None);
+        client.exchange_tokens(&user, &airline, &hotel, &1000);
+        assert_eq!(client.view_user_balance(&user, &hotel), 500);
+
+        // Routes leaving the coalition are unaffected
+        assert_eq!(client.quote_exchange(&hotel, &cafe, &500).amount_out, 500);
+
+        client.leave_coalition(&hotel);
+        assert_eq!(client.view_coalition_membership(&hotel), None);
+        assert_eq!(
+            client.quote_exchange(&airline, &hotel, &1000).amount_out,
+            1000
+        );
+    }
+
+    #[test]
+    fn test_peg_settlement_overrides_route_rates() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let council = Address::generate(&env);
+        let coalition_id = client.create_coalition(
+            &String::from_str(&env, "Travel Alliance"),
+            &symbol_short!("MILE"),
+            &vec![&env, council.clone()],
+        );
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        for brand_id in [airline, hotel] {
+            client.set_brand_admin(&brand_id, &Address::generate(&env));
+        }
+        client.request_join_coalition(&coalition_id, &airline, &100);
+        client.request_join_coalition(&coalition_id, &hotel, &200);
+        client.approve_coalition_member(&council, &airline);
+        client.approve_coalition_member(&council, &hotel);
+
+        let change_id = client.schedule_change(&Param::RouteRate(airline, hotel), &20_000);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        assert_eq!(
+            client.quote_exchange(&airline, &hotel, &1000).amount_out,
+            2000
+        );
+
+        assert!(client
+            .try_set_peg_settlement(&Address::generate(&env), &coalition_id, &true)
+            .is_err());
+        let settlement_change = client.set_peg_settlement(&council, &coalition_id, &true);
+        assert!(client.try_cancel_change(&settlement_change).is_err());
+        assert!(!client.view_peg_settlement(&coalition_id));
+        env.ledger().set_sequence_number(2 * TIMELOCK_LEDGERS);
+        client.execute_change(&settlement_change);
+        assert_eq!(
+            client.quote_exchange(&airline, &hotel, &1000).amount_out,
+            500
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a council member")]
+    fn test_only_council_approves_members() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let coalition_id = client.create_coalition(
+            &String::from_str(&env, "Retail"),
+            &symbol_short!("RPT"),
+            &vec![&env, Address::generate(&env)],
+        );
+        let brand_id = client.register_brand(&String::from_str(&env, "Shop"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        client.request_join_coalition(&coalition_id, &brand_id, &100);
+
+        client.approve_coalition_member(&Address::generate(&env), &brand_id);
+    }
+}
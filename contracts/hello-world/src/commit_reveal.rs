@@ -0,0 +1,166 @@
+use crate::*;
+use soroban_sdk::xdr::ToXdr;
+
+// Parameters of an exchange hidden behind a commitment
+// The commitment is the SHA-256 of this struct's XDR encoding
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExchangeCommitment {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub amount: i128,
+    pub min_out: i128,
+    // Random value keeping the parameters from being guessed
+    pub salt: BytesN<32>,
+}
+
+// Mapping for exchange commitments: (User, Hash) -> Ledger committed at
+// Kept in temporary storage, live only until the reveal window closes
+#[contracttype]
+pub enum CommitBook {
+    Commit(Address, BytesN<32>),
+}
+
+// Ledgers after committing during which a commitment can be revealed
+pub const REVEAL_WINDOW_LEDGERS: u32 = 720;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Commit to an exchange without disclosing it, to be revealed and
+    /// executed in a later ledger so others cannot trade ahead of it
+    pub fn commit_exchange(env: Env, user: Address, commitment: BytesN<32>) {
+        require_user(&env, &user);
+
+        let key = CommitBook::Commit(user, commitment);
+        if env.storage().temporary().has(&key) {
+            panic!("Commitment already exists");
+        }
+        env.storage()
+            .temporary()
+            .set(&key, &env.ledger().sequence());
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, REVEAL_WINDOW_LEDGERS, REVEAL_WINDOW_LEDGERS);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Exchange committed");
+    }
+
+    /// Reveal a committed exchange and execute it, from the ledger after the
+    /// commitment until REVEAL_WINDOW_LEDGERS later
+    /// Returns the op_id of the recorded exchange
+    pub fn reveal_exchange(env: Env, user: Address, params: ExchangeCommitment) -> u64 {
+        require_user(&env, &user);
+
+        let key = CommitBook::Commit(user.clone(), commitment_hash(&env, &params));
+        let committed_at: u32 = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .unwrap_or_else(|| panic!("Commitment not found"));
+        let sequence = env.ledger().sequence();
+        if sequence <= committed_at {
+            panic!("Reveal must wait for a later ledger");
+        }
+        if sequence > committed_at + REVEAL_WINDOW_LEDGERS {
+            panic!("Commitment expired");
+        }
+        env.storage().temporary().remove(&key);
+
+        exchange(
+            &env,
+            &user,
+            params.from_brand,
+            params.to_brand,
+            params.amount,
+            params.min_out,
+            None,
+        )
+    }
+
+    /// Compute the commitment of exchange parameters
+    pub fn exchange_commitment(env: Env, params: ExchangeCommitment) -> BytesN<32> {
+        commitment_hash(&env, &params)
+    }
+}
+
+fn commitment_hash(env: &Env, params: &ExchangeCommitment) -> BytesN<32> {
+    env.crypto().sha256(&params.clone().to_xdr(env)).into()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, BytesN, Env, String,
+    };
+
+    #[test]
+    fn test_committed_exchange_executes_after_reveal() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+
+        let params = ExchangeCommitment {
+            from_brand: hotel,
+            to_brand: airline,
+            amount: 200,
+            min_out: 200,
+            salt: BytesN::from_array(&env, &[3; 32]),
+        };
+        env.ledger().set_sequence_number(10);
+        client.commit_exchange(&user, &client.exchange_commitment(&params));
+        assert!(client.try_reveal_exchange(&user, &params).is_err());
+
+        env.ledger().set_sequence_number(11);
+        let mut altered = params.clone();
+        altered.amount = 300;
+        assert!(client.try_reveal_exchange(&user, &altered).is_err());
+
+        client.reveal_exchange(&user, &params);
+        assert_eq!(client.view_user_balance(&user, &airline), 200);
+        assert!(client.try_reveal_exchange(&user, &params).is_err());
+    }
+
+    #[test]
+    fn test_commitment_expires_with_reveal_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+
+        let params = ExchangeCommitment {
+            from_brand: hotel,
+            to_brand: airline,
+            amount: 200,
+            min_out: 200,
+            salt: BytesN::from_array(&env, &[4; 32]),
+        };
+        let commitment = client.exchange_commitment(&params);
+        env.ledger().set_sequence_number(10);
+        client.commit_exchange(&user, &commitment);
+
+        env.ledger()
+            .set_sequence_number(10 + REVEAL_WINDOW_LEDGERS + 1);
+        assert!(client.try_reveal_exchange(&user, &params).is_err());
+        env.as_contract(&contract_id, || {
+            let key = CommitBook::Commit(user.clone(), commitment.clone());
+            assert!(!env.storage().temporary().has(&key));
+        });
+
+        // The expired commitment no longer blocks committing again
+        client.commit_exchange(&user, &commitment);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::*;
+
+// Mapping for data-sharing consent: (User, Brand) -> Whether partners may
+// query the user's data for the brand
+// Kept in persistent storage, one entry per (user, brand)
+#[contracttype]
+pub enum ConsentBook {
+    Sharing(Address, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Allow or stop partners querying the user's data for a brand through
+    /// the attestation API; consent can be revoked at any time (user only)
+    /// Consent only gates the attestation API: balances are public contract
+    /// state, readable by anyone through `view_user_balance`, `view_balances`
+    /// or the ledger itself, so it cannot keep them private
+    pub fn set_data_sharing(env: Env, user: Address, brand_id: u64, allowed: bool) {
+        require_user(&env, &user);
+        load_brand(&env, brand_id);
+
+        let key = ConsentBook::Sharing(user, brand_id);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(&key, 100000, 100000);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Data sharing for brand {} updated", brand_id);
+    }
+
+    /// View whether a user lets partners query their data for a brand
+    pub fn view_data_sharing(env: Env, user: Address, brand_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&ConsentBook::Sharing(user, brand_id))
+            .unwrap_or(false)
+    }
+}
+
+// Reject partner queries of a user's data the user has not consented to
+pub(crate) fn require_consent(env: &Env, user: &Address, brand_id: u64) {
+    if !LoyaltyTokenExchange::view_data_sharing(env.clone(), user.clone(), brand_id) {
+        panic!("User has not consented to data sharing");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_attestation_requires_consent_until_revoked() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let partner = Address::generate(&env);
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.issue_tokens(&user, &hotel, &500, &None);
+        client.issue_tokens(&user, &airline, &500, &None);
+
+        assert!(client.try_attest_balance(&user, &hotel, &100).is_err());
+        client.set_data_sharing(&user, &hotel, &true);
+        assert!(client.attest_balance(&user, &hotel, &100));
+        assert!(client.attest_balance_emit(&partner, &user, &hotel, &100));
+        assert!(client
+            .try_attest_balance_emit(&partner, &user, &airline, &100)
+            .is_err());
+
+        client.set_data_sharing(&user, &hotel, &false);
+        assert!(!client.view_data_sharing(&user, &hotel));
+        assert!(client.try_attest_balance(&user, &hotel, &100).is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Operation to estimate, naming the users whose balances it moves
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OperationSpec {
+    // Recipient
+    Issue(Address),
+    // Sender, recipient
+    Transfer(Address, Address),
+    Exchange(Address),
+    Redeem(Address),
+}
+
+// Storage footprint an operation is expected to have
+// The contract instance counts as one entry; each user's balances are one
+// persistent entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CostEstimate {
+    pub entries_read: u32,
+    pub entries_written: u32,
+    // Balance entries the operation creates, paying for new storage
+    pub entries_created: u32,
+    // Entries whose TTL the operation extends
+    pub rent_bumps: u32,
+    // Archived balance entries to restore before submitting
+    pub entries_to_restore: u32,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Approximate the storage entries an operation touches and the rent it
+    /// bumps, to budget fees for batch jobs before submitting them
+    pub fn estimate_operation_cost(env: Env, op: OperationSpec) -> CostEstimate {
+        let mut users = Vec::new(&env);
+        match op {
+            OperationSpec::Issue(user)
+            | OperationSpec::Exchange(user)
+            | OperationSpec::Redeem(user) => users.push_back(user),
+            OperationSpec::Transfer(from, to) => {
+                users.push_back(from.clone());
+                if to != from {
+                    users.push_back(to);
+                }
+            }
+        }
+
+        let mut estimate = CostEstimate {
+            entries_read: 1,
+            entries_written: 1,
+            entries_created: 0,
+            rent_bumps: 1,
+            entries_to_restore: 0,
+        };
+        for user in users.iter() {
+            estimate.entries_read += 1;
+            estimate.entries_written += 1;
+            estimate.rent_bumps += 1;
+            if !has_balance_entry(&env, &user) {
+                estimate.entries_created += 1;
+            } else if Self::view_balance_state(env.clone(), user.clone(), 0)
+                == BalanceState::Archived
+            {
+                estimate.entries_to_restore += 1;
+            }
+        }
+        estimate
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_estimate_counts_new_and_existing_balance_entries() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let holder = Address::generate(&env);
+        let newcomer = Address::generate(&env);
+        client.issue_tokens(&holder, &brand_id, &100, &None);
+
+        let issue = client.estimate_operation_cost(&OperationSpec::Issue(holder.clone()));
+        assert_eq!(issue.entries_read, 2);
+        assert_eq!(issue.entries_created, 0);
+
+        let transfer = client.estimate_operation_cost(&OperationSpec::Transfer(holder, newcomer));
+        assert_eq!(transfer.entries_written, 3);
+        assert_eq!(transfer.entries_created, 1);
+        assert_eq!(transfer.rent_bumps, 3);
+        assert_eq!(transfer.entries_to_restore, 0);
+    }
+}
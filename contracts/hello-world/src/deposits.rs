@@ -0,0 +1,293 @@
+use crate::*;
+use soroban_sdk::token;
+
+// Structure to store the deposit new brands must post to register
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositRequirement {
+    // SAC token the deposit is paid in
+    pub token: Address,
+    pub amount: i128,
+}
+
+// Structure to store the deposit a brand posted at registration
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrandDeposit {
+    pub brand_id: u64,
+    pub depositor: Address,
+    pub token: Address,
+    // Tokens still held, after slashing and refunds
+    pub amount: i128,
+    pub slashed: i128,
+}
+
+// Rule violations a brand's deposit can be slashed for
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlashReason {
+    ConfirmedFraud,
+    UnhonoredRedemptions,
+}
+
+// Mapping for brand deposits: Brand -> Deposit
+#[contracttype]
+pub enum DepositBook {
+    Deposit(u64),
+}
+
+// Deposit required to register a brand
+const DEPOSIT_REQUIREMENT: Symbol = symbol_short!("DEP_REQ");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Require new brands to post a deposit of `amount` tokens to register
+    /// (platform admin only); an amount of 0 lifts the requirement
+    pub fn set_deposit_requirement(env: Env, token: Address, amount: i128) {
+        let admin = require_platform_admin(&env);
+        if amount < 0 {
+            panic!("Deposit cannot be negative");
+        }
+
+        if amount == 0 {
+            env.storage().instance().remove(&DEPOSIT_REQUIREMENT);
+        } else {
+            env.storage()
+                .instance()
+                .set(&DEPOSIT_REQUIREMENT, &DepositRequirement { token, amount });
+        }
+        log_admin_action(
+            &env,
+            &admin,
+            AdminAction::DepositRequirementSet,
+            PLATFORM_BRAND_ID,
+            None,
+            None,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Brand deposit requirement updated");
+    }
+
+    /// View the deposit new brands must post, if one is required
+    pub fn view_deposit_requirement(env: Env) -> Option<DepositRequirement> {
+        env.storage().instance().get(&DEPOSIT_REQUIREMENT)
+    }
+
+    /// Register a new brand, paying the required deposit from `depositor`
+    /// Use `register_brand_v2` while an invitation is also required
+    /// Returns the brand_id of the new brand
+    pub fn register_brand_with_deposit(env: Env, depositor: Address, brand_name: String) -> u64 {
+        if Self::view_deposit_requirement(env.clone()).is_none() {
+            panic!("No deposit required");
+        }
+        Self::register_brand_v2(env, depositor, brand_name, None)
+    }
+
+    /// Slash part of a brand's deposit for a rule violation, paying it to
+    /// `recipient` (platform admin only)
+    pub fn slash_brand_deposit(
+        env: Env,
+        brand_id: u64,
+        amount: i128,
+        reason: SlashReason,
+        recipient: Address,
+    ) {
+        let admin = require_platform_admin(&env);
+        let mut deposit = load_deposit(&env, brand_id);
+        if amount <= 0 || amount > deposit.amount {
+            panic!("Invalid slash amount");
+        }
+
+        token::TokenClient::new(&env, &deposit.token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+        deposit.amount -= amount;
+        deposit.slashed += amount;
+        save_deposit(&env, &deposit);
+        log_admin_action(
+            &env,
+            &admin,
+            AdminAction::DepositSlashed,
+            brand_id,
+            None,
+            None,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        BrandDepositSlashed {
+            brand_id,
+            amount,
+            reason,
+            remaining: deposit.amount,
+        }
+        .publish(&env);
+
+        log!(&env, "✅ Deposit of brand {} slashed", brand_id);
+    }
+
+    /// Exit gracefully: deactivate the brand and refund what is left of its
+    /// deposit to the depositor (brand admin only)
+    /// Every point of the brand, including points held in escrow, must be
+    /// redeemed, paid out or burned first
+    /// Returns the tokens refunded
+    pub fn withdraw_brand_deposit(env: Env, brand_id: u64) -> i128 {
+        let mut brand = load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        let mut deposit = load_deposit(&env, brand_id);
+        if Self::view_brand_liabilities(env.clone(), brand_id) != 0 {
+            panic!("Outstanding points must be settled");
+        }
+
+        let refund = deposit.amount;
+        if refund > 0 {
+            token::TokenClient::new(&env, &deposit.token).transfer(
+                &env.current_contract_address(),
+                &deposit.depositor,
+                &refund,
+            );
+        }
+        deposit.amount = 0;
+        save_deposit(&env, &deposit);
+        brand.is_active = false;
+        env.storage()
+            .instance()
+            .set(&BrandBook::Brand(brand_id), &brand);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} exited and its deposit refunded",
+            brand_id
+        );
+        refund
+    }
+
+    /// View the deposit a brand posted, if any
+    pub fn view_brand_deposit(env: Env, brand_id: u64) -> Option<BrandDeposit> {
+        env.storage()
+            .instance()
+            .get(&DepositBook::Deposit(brand_id))
+    }
+}
+
+// Collect the required deposit from `depositor` for a brand just created
+pub(crate) fn post_deposit(env: &Env, brand_id: u64, depositor: &Address) {
+    let requirement = LoyaltyTokenExchange::view_deposit_requirement(env.clone())
+        .unwrap_or_else(|| panic!("No deposit required"));
+
+    token::TokenClient::new(env, &requirement.token).transfer(
+        depositor,
+        env.current_contract_address(),
+        &requirement.amount,
+    );
+    let deposit = BrandDeposit {
+        brand_id,
+        depositor: depositor.clone(),
+        token: requirement.token,
+        amount: requirement.amount,
+        slashed: 0,
+    };
+    save_deposit(env, &deposit);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Deposit posted for brand {}", brand_id);
+}
+
+fn load_deposit(env: &Env, brand_id: u64) -> BrandDeposit {
+    env.storage()
+        .instance()
+        .get(&DepositBook::Deposit(brand_id))
+        .unwrap_or_else(|| panic!("Deposit not found"))
+}
+
+fn save_deposit(env: &Env, deposit: &BrandDeposit) {
+    env.storage()
+        .instance()
+        .set(&DepositBook::Deposit(deposit.brand_id), deposit);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        token::{StellarAssetClient, TokenClient},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_deposit_is_slashed_then_refunded_on_exit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let depositor = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+        client.set_deposit_requirement(&token, &1000);
+        assert!(client
+            .try_register_brand(&String::from_str(&env, "Kiosk"))
+            .is_err());
+
+        let brand_id =
+            client.register_brand_with_deposit(&depositor, &String::from_str(&env, "Kiosk"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+
+        let victim = Address::generate(&env);
+        client.slash_brand_deposit(&brand_id, &300, &SlashReason::UnhonoredRedemptions, &victim);
+        assert_eq!(TokenClient::new(&env, &token).balance(&victim), 300);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &50, &None);
+        assert!(client.try_withdraw_brand_deposit(&brand_id).is_err());
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Snack"), &50, &1);
+        client.redeem_reward(&user, &reward_id, &None);
+
+        assert_eq!(client.withdraw_brand_deposit(&brand_id), 700);
+        assert_eq!(TokenClient::new(&env, &token).balance(&depositor), 700);
+        assert!(!client.view_brand(&brand_id).is_active);
+        assert_eq!(client.view_brand_deposit(&brand_id).unwrap().slashed, 300);
+    }
+
+    #[test]
+    fn test_withdrawal_waits_for_escrowed_points() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let depositor = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+        client.set_deposit_requirement(&token, &1000);
+        let brand_id =
+            client.register_brand_with_deposit(&depositor, &String::from_str(&env, "Kiosk"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+
+        // A scheduled gift holds every outstanding point in escrow
+        let sender = Address::generate(&env);
+        let friend = Address::generate(&env);
+        client.issue_tokens(&sender, &brand_id, &50, &None);
+        let gift_id = client.send_gift(&sender, &friend, &brand_id, &50, &Some(100), &None);
+        assert_eq!(client.view_user_balance(&sender, &brand_id), 0);
+        assert_eq!(client.view_brand_liabilities(&brand_id), 50);
+        assert!(client.try_withdraw_brand_deposit(&brand_id).is_err());
+
+        env.ledger().set_sequence_number(100);
+        client.claim_gift(&friend, &gift_id);
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Snack"), &50, &1);
+        client.redeem_reward(&friend, &reward_id, &None);
+        assert_eq!(client.view_brand_liabilities(&brand_id), 0);
+        assert_eq!(client.withdraw_brand_deposit(&brand_id), 1000);
+    }
+}
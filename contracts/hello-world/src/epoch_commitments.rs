@@ -0,0 +1,218 @@
+use crate::*;
+use soroban_sdk::{xdr::ToXdr, Bytes, Vec};
+
+// Structure to store the Merkle commitment of one epoch's operations
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochCommitment {
+    pub epoch: u32,
+    // Operations committed, first_op..=last_op; empty when last_op < first_op
+    pub first_op: u64,
+    pub last_op: u64,
+    // Root over SHA-256 leaves of each record's XDR, in op_id order; an odd
+    // node is paired with itself
+    pub root: BytesN<32>,
+    pub committed_at: u32,
+}
+
+// Mapping for epoch commitments: Epoch -> Commitment
+#[contracttype]
+pub enum CommitmentBook {
+    Commitment(u32),
+}
+
+// Last operation covered by a commitment
+const COMMITTED_OP: Symbol = symbol_short!("CMT_OP");
+
+// Last epoch committed
+const COMMITTED_EPOCH: Symbol = symbol_short!("CMT_EPOCH");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Commit a Merkle root of every operation recorded before the current
+    /// epoch and not yet committed, under the epoch that just closed;
+    /// anyone may call this
+    /// Returns the closed epoch
+    pub fn commit_epoch(env: Env) -> u32 {
+        let current = Self::current_epoch(env.clone());
+        if current == 0 {
+            panic!("No epoch has closed yet");
+        }
+        let epoch = current - 1;
+        if let Some(last) = env.storage().instance().get::<_, u32>(&COMMITTED_EPOCH) {
+            if last >= epoch {
+                panic!("Epoch already committed");
+            }
+        }
+
+        let first_op: u64 = env.storage().instance().get(&COMMITTED_OP).unwrap_or(0) + 1;
+        let boundary = current * EPOCH_LEDGERS;
+        let mut last_op = first_op - 1;
+        while last_op < operation_count(&env) && load_operation(&env, last_op + 1).ledger < boundary
+        {
+            last_op += 1;
+        }
+
+        let commitment = EpochCommitment {
+            epoch,
+            first_op,
+            last_op,
+            root: merkle_root(&env, operation_leaves(&env, first_op, last_op)),
+            committed_at: env.ledger().sequence(),
+        };
+        env.storage()
+            .instance()
+            .set(&CommitmentBook::Commitment(epoch), &commitment);
+        env.storage().instance().set(&COMMITTED_OP, &last_op);
+        env.storage().instance().set(&COMMITTED_EPOCH, &epoch);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Epoch {} committed", epoch);
+        epoch
+    }
+
+    /// View the commitment of an epoch, if it was committed
+    pub fn view_epoch_commitment(env: Env, epoch: u32) -> Option<EpochCommitment> {
+        env.storage()
+            .instance()
+            .get(&CommitmentBook::Commitment(epoch))
+    }
+
+    /// Build the Merkle proof of a committed operation: sibling hashes from
+    /// the leaf up to the root
+    pub fn operation_proof(env: Env, epoch: u32, op_id: u64) -> Vec<BytesN<32>> {
+        let commitment = load_commitment(&env, epoch, op_id);
+        let mut index = (op_id - commitment.first_op) as u32;
+        let mut level = operation_leaves(&env, commitment.first_op, commitment.last_op);
+
+        let mut proof = Vec::new(&env);
+        while level.len() > 1 {
+            let sibling = if index.is_multiple_of(2) {
+                level.get(index + 1).unwrap_or(level.get_unchecked(index))
+            } else {
+                level.get_unchecked(index - 1)
+            };
+            proof.push_back(sibling);
+            level = next_level(&env, &level);
+            index /= 2;
+        }
+        proof
+    }
+
+    /// Check a Merkle proof that an operation is part of an epoch commitment
+    pub fn verify_operation_inclusion(
+        env: Env,
+        epoch: u32,
+        op_id: u64,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let commitment = load_commitment(&env, epoch, op_id);
+        let mut index = op_id - commitment.first_op;
+        let mut node = operation_leaf(&env, op_id);
+        for sibling in proof.iter() {
+            node = if index.is_multiple_of(2) {
+                hash_pair(&env, &node, &sibling)
+            } else {
+                hash_pair(&env, &sibling, &node)
+            };
+            index /= 2;
+        }
+        node == commitment.root
+    }
+}
+
+fn load_commitment(env: &Env, epoch: u32, op_id: u64) -> EpochCommitment {
+    let commitment = LoyaltyTokenExchange::view_epoch_commitment(env.clone(), epoch)
+        .unwrap_or_else(|| panic!("Epoch not committed"));
+    if op_id < commitment.first_op || op_id > commitment.last_op {
+        panic!("Operation not in epoch");
+    }
+    commitment
+}
+
+fn operation_leaf(env: &Env, op_id: u64) -> BytesN<32> {
+    env.crypto()
+        .sha256(&load_operation(env, op_id).to_xdr(env))
+        .into()
+}
+
+fn operation_leaves(env: &Env, first_op: u64, last_op: u64) -> Vec<BytesN<32>> {
+    let mut leaves = Vec::new(env);
+    for op_id in first_op..=last_op {
+        leaves.push_back(operation_leaf(env, op_id));
+    }
+    leaves
+}
+
+fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &left.to_array());
+    data.extend_from_array(&right.to_array());
+    env.crypto().sha256(&data).into()
+}
+
+fn next_level(env: &Env, level: &Vec<BytesN<32>>) -> Vec<BytesN<32>> {
+    let mut next = Vec::new(env);
+    let mut index = 0;
+    while index < level.len() {
+        let left = level.get_unchecked(index);
+        let right = level.get(index + 1).unwrap_or(left.clone());
+        next.push_back(hash_pair(env, &left, &right));
+        index += 2;
+    }
+    next
+}
+
+// Root of an empty epoch is all zeros
+fn merkle_root(env: &Env, mut level: Vec<BytesN<32>>) -> BytesN<32> {
+    if level.is_empty() {
+        return BytesN::from_array(env, &[0; 32]);
+    }
+    while level.len() > 1 {
+        level = next_level(env, &level);
+    }
+    level.get_unchecked(0)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, BytesN, Env, String,
+    };
+
+    #[test]
+    fn test_epoch_operations_provable_against_root() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        for _ in 0..3 {
+            client.issue_tokens(&user, &brand_id, &10, &None);
+        }
+        assert!(client.try_commit_epoch().is_err());
+
+        env.ledger().set_sequence_number(EPOCH_LEDGERS);
+        let late_op = client.issue_tokens(&user, &brand_id, &10, &None);
+        assert_eq!(client.commit_epoch(), 0);
+        assert!(client.try_commit_epoch().is_err());
+
+        let commitment = client.view_epoch_commitment(&0).unwrap();
+        assert_eq!((commitment.first_op, commitment.last_op), (1, 3));
+        for op_id in 1..=3 {
+            let proof = client.operation_proof(&0, &op_id);
+            assert!(client.verify_operation_inclusion(&0, &op_id, &proof));
+        }
+        let mut forged = client.operation_proof(&0, &3);
+        forged.set(0, BytesN::from_array(&env, &[1; 32]));
+        assert!(!client.verify_operation_inclusion(&0, &3, &forged));
+        assert!(client.try_operation_proof(&0, &late_op).is_err());
+
+        env.ledger().set_sequence_number(EPOCH_LEDGERS * 2);
+        assert_eq!(client.commit_epoch(), 1);
+        assert_eq!(client.view_epoch_commitment(&1).unwrap().first_op, late_op);
+    }
+}
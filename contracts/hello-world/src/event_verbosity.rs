@@ -0,0 +1,93 @@
+use crate::*;
+
+// How much data operation events carry
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventVerbosity {
+    // Brand, user and op_id only; the rest is read back from the history
+    Minimal,
+    // Full payload of each operation
+    Detailed,
+}
+
+// Platform-wide event verbosity
+const EVENT_VERBOSITY: Symbol = symbol_short!("EVT_VERB");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set how much data operation events carry (platform admin only)
+    /// High-volume deployments can emit minimal events to save on fees
+    pub fn set_event_verbosity(env: Env, verbosity: EventVerbosity) {
+        let admin = require_platform_admin(&env);
+
+        env.storage().instance().set(&EVENT_VERBOSITY, &verbosity);
+        log_admin_action(
+            &env,
+            &admin,
+            AdminAction::EventVerbositySet,
+            PLATFORM_BRAND_ID,
+            None,
+            None,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Event verbosity updated");
+    }
+
+    /// View how much data operation events carry
+    pub fn view_event_verbosity(env: Env) -> EventVerbosity {
+        env.storage()
+            .instance()
+            .get(&EVENT_VERBOSITY)
+            .unwrap_or(EventVerbosity::Detailed)
+    }
+}
+
+// Announce a recorded operation, calling `detailed` to publish its full event
+// unless the platform emits minimal events
+pub(crate) fn emit_operation(
+    env: &Env,
+    brand_id: u64,
+    user: &Address,
+    op_id: u64,
+    detailed: impl FnOnce(),
+) {
+    match LoyaltyTokenExchange::view_event_verbosity(env.clone()) {
+        EventVerbosity::Minimal => OperationRecorded {
+            brand_id,
+            user: user.clone(),
+            op_id,
+        }
+        .publish(env),
+        EventVerbosity::Detailed => detailed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_operations_record_under_minimal_events() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        assert!(client
+            .try_set_event_verbosity(&EventVerbosity::Minimal)
+            .is_err());
+        client.set_platform_admin(&Address::generate(&env));
+        assert_eq!(client.view_event_verbosity(), EventVerbosity::Detailed);
+
+        client.set_event_verbosity(&EventVerbosity::Minimal);
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        let op_id = client.issue_tokens(&user, &brand_id, &100, &None);
+        client.transfer_tokens(&user, &Address::generate(&env), &brand_id, &40, &None);
+
+        assert_eq!(client.view_event_verbosity(), EventVerbosity::Minimal);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 60);
+        assert_eq!(client.view_operation(&user, &op_id).amount, 100);
+    }
+}
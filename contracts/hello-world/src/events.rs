@@ -0,0 +1,312 @@
+use crate::{FulfillmentStatus, Param, SlashReason, TreasurySource, VelocityRule};
+use soroban_sdk::{contractevent, Address, BytesN};
+
+// Every event carries the brand it concerns as its first topic after the
+// event name, so RPC filters can subscribe to a single brand's events
+
+// Brand topic of events that concern the whole platform rather than one brand
+pub const PLATFORM_BRAND_ID: u64 = 0;
+
+// Emitted when a brand issues tokens to a user
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokensIssued {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when tokens move between two users
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokensTransferred {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when a user redeems a reward
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardRedeemed {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub reward_id: u64,
+    pub cost: i128,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when a partner contract records a balance attestation
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceAttested {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub requester: Address,
+    pub min_amount: i128,
+    pub attested: bool,
+}
+
+// Emitted when a parameter change is queued behind the timelock
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeScheduled {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub change_id: u64,
+    pub param: Param,
+    pub value: i128,
+    pub eta: u32,
+}
+
+// Emitted when a queued parameter change takes effect
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeExecuted {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub change_id: u64,
+    pub param: Param,
+    pub value: i128,
+}
+
+// Emitted when a queued parameter change is withdrawn
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChangeCancelled {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub change_id: u64,
+    pub param: Param,
+}
+
+// Emitted for each brand balance guardians move to a user's new address
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalancesRecovered {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub new_address: Address,
+    pub amount: i128,
+}
+
+// Emitted when an exchange route is paused or resumed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoutePauseChanged {
+    #[topic]
+    pub from_brand: u64,
+    #[topic]
+    pub to_brand: u64,
+    pub paused: bool,
+    pub caller: Address,
+}
+
+// Emitted when a user spends points directly, without a catalog reward
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsRedeemed {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    pub op_id: u64,
+    pub memo: Option<BytesN<32>>,
+}
+
+// Emitted when an exchange wins a surprise bonus from a brand's pool
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardDropped {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub bonus: i128,
+    pub pool_balance: i128,
+}
+
+// Emitted when a brand admin points the brand treasury at a new address
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasurySet {
+    #[topic]
+    pub brand_id: u64,
+    pub treasury: Address,
+}
+
+// Emitted when points are added to a brand's treasury reserve
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryDeposited {
+    #[topic]
+    pub brand_id: u64,
+    pub source: TreasurySource,
+    pub amount: i128,
+    pub reserve: i128,
+}
+
+// Emitted when a brand admin pays out of the treasury reserve
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryWithdrawn {
+    #[topic]
+    pub brand_id: u64,
+    pub treasury: Address,
+    pub amount: i128,
+    pub reserve: i128,
+}
+
+// Emitted when an escrowed promotion bonus is paid out or reclaimed
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromotionResolved {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub promo_id: u64,
+    pub released: bool,
+    pub bonus: i128,
+}
+
+// Emitted when a user redeems points for a classic claimable balance
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportRequested {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub export_id: u64,
+    pub amount: i128,
+    pub destination: Address,
+}
+
+// Emitted when the bridge operator reports the claimable balance of an export
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExportAcknowledged {
+    #[topic]
+    pub brand_id: u64,
+    pub export_id: u64,
+    pub balance_id: BytesN<32>,
+}
+
+// Emitted when points are sent as a gift, possibly for later delivery
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GiftSent {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub sender: Address,
+    #[topic]
+    pub recipient: Address,
+    pub gift_id: u64,
+    pub amount: i128,
+    pub deliver_at: u32,
+    pub message_hash: Option<BytesN<32>>,
+}
+
+// Emitted when a user enrolling with a brand receives its welcome bonus
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WelcomeBonusGranted {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub bonus: i128,
+    pub budget_left: i128,
+}
+
+// Emitted when a redeemed reward moves to a new fulfillment status
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FulfillmentUpdated {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub op_id: u64,
+    pub status: FulfillmentStatus,
+}
+
+// Emitted when a brand's reference price changes
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferencePriceUpdated {
+    #[topic]
+    pub brand_id: u64,
+    pub points_per_unit: i128,
+    pub previous: Option<i128>,
+    pub timestamp: u64,
+}
+
+// Emitted when governance slashes a brand's registration deposit
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrandDepositSlashed {
+    #[topic]
+    pub brand_id: u64,
+    pub amount: i128,
+    pub reason: SlashReason,
+    pub remaining: i128,
+}
+
+// Emitted instead of an operation's full event when events are minimal
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationRecorded {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub op_id: u64,
+}
+
+// Emitted when a velocity rule freezes a user's activity with a brand
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PairFrozen {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub rule: VelocityRule,
+}
+
+// Emitted when points converted into a brand are withheld for tax
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxWithheld {
+    #[topic]
+    pub brand_id: u64,
+    #[topic]
+    pub user: Address,
+    pub amount: i128,
+    pub tax_address: Address,
+    pub year: u32,
+}
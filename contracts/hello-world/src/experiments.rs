@@ -0,0 +1,243 @@
+use crate::*;
+use soroban_sdk::{xdr::ToXdr, Vec};
+
+// Structure to store a campaign experiment splitting a brand's users into
+// buckets that earn under different multipliers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Experiment {
+    pub experiment_id: u64,
+    pub brand_id: u64,
+    // Issuance multiplier of each bucket, in basis points
+    pub multipliers_bps: Vec<u32>,
+    pub started_at: u32,
+    pub ended_at: Option<u32>,
+}
+
+// Structure counting what the users of one bucket did during an experiment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BucketOutcome {
+    pub issuances: u32,
+    // Points issued, multiplier included
+    pub issued: i128,
+    pub redemptions: u32,
+    pub redeemed: i128,
+}
+
+// Mapping for experiments
+#[contracttype]
+pub enum ExperimentBook {
+    Experiment(u64),
+    // Brand -> Experiment currently running
+    Running(u64),
+    // (Experiment, Bucket) -> Outcome
+    Outcome(u64, u32),
+}
+
+// Counter for experiment IDs
+const EXPERIMENT_COUNT: Symbol = symbol_short!("EXP_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Start an experiment that splits the brand's users into one bucket per
+    /// multiplier; issuances are scaled by the user's bucket multiplier until
+    /// the experiment ends (brand admin only)
+    /// Returns the experiment_id
+    pub fn start_experiment(env: Env, brand_id: u64, multipliers_bps: Vec<u32>) -> u64 {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if multipliers_bps.len() < 2 {
+            panic!("Experiment needs at least two buckets");
+        }
+        if multipliers_bps.iter().any(|multiplier| multiplier == 0) {
+            panic!("Multiplier must be positive");
+        }
+        if running_experiment(&env, brand_id).is_some() {
+            panic!("Experiment already running");
+        }
+
+        let experiment_id: u64 = env.storage().instance().get(&EXPERIMENT_COUNT).unwrap_or(0) + 1;
+        let experiment = Experiment {
+            experiment_id,
+            brand_id,
+            multipliers_bps,
+            started_at: env.ledger().sequence(),
+            ended_at: None,
+        };
+        save_experiment(&env, &experiment);
+        env.storage()
+            .instance()
+            .set(&ExperimentBook::Running(brand_id), &experiment_id);
+        env.storage()
+            .instance()
+            .set(&EXPERIMENT_COUNT, &experiment_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Experiment {} started for brand {}",
+            experiment_id,
+            brand_id
+        );
+        experiment_id
+    }
+
+    /// Stop a running experiment; its outcomes stay readable (brand admin only)
+    pub fn end_experiment(env: Env, experiment_id: u64) {
+        let mut experiment = Self::view_experiment(env.clone(), experiment_id);
+        require_brand_admin(&env, experiment.brand_id);
+        if experiment.ended_at.is_some() {
+            panic!("Experiment already ended");
+        }
+
+        experiment.ended_at = Some(env.ledger().sequence());
+        save_experiment(&env, &experiment);
+        env.storage()
+            .instance()
+            .remove(&ExperimentBook::Running(experiment.brand_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Experiment {} ended", experiment_id);
+    }
+
+    /// View an experiment
+    pub fn view_experiment(env: Env, experiment_id: u64) -> Experiment {
+        env.storage()
+            .instance()
+            .get(&ExperimentBook::Experiment(experiment_id))
+            .unwrap_or_else(|| panic!("Experiment not found"))
+    }
+
+    /// View the bucket a user falls into for an experiment
+    /// Buckets are a hash of the experiment and the address, so a user keeps
+    /// their bucket for the whole experiment
+    pub fn experiment_bucket(env: Env, experiment_id: u64, user: Address) -> u32 {
+        let experiment = Self::view_experiment(env.clone(), experiment_id);
+        bucket_of(&env, &experiment, &user)
+    }
+
+    /// View what the users of one bucket did during an experiment
+    pub fn view_bucket_outcome(env: Env, experiment_id: u64, bucket: u32) -> BucketOutcome {
+        env.storage()
+            .instance()
+            .get(&ExperimentBook::Outcome(experiment_id, bucket))
+            .unwrap_or(BucketOutcome {
+                issuances: 0,
+                issued: 0,
+                redemptions: 0,
+                redeemed: 0,
+            })
+    }
+}
+
+// Scale an issuance by the user's bucket multiplier when the brand runs an
+// experiment, counting it towards the bucket's outcome
+pub(crate) fn experiment_amount(env: &Env, user: &Address, brand_id: u64, amount: i128) -> i128 {
+    let Some(experiment) = running_experiment(env, brand_id) else {
+        return amount;
+    };
+    let bucket = bucket_of(env, &experiment, user);
+    let scaled = apply_bps(amount, experiment.multipliers_bps.get_unchecked(bucket));
+
+    let mut outcome =
+        LoyaltyTokenExchange::view_bucket_outcome(env.clone(), experiment.experiment_id, bucket);
+    outcome.issuances += 1;
+    outcome.issued += scaled;
+    save_outcome(env, experiment.experiment_id, bucket, &outcome);
+    scaled
+}
+
+// Count a redemption towards the user's bucket when the brand runs an experiment
+pub(crate) fn track_experiment_redemption(env: &Env, user: &Address, brand_id: u64, cost: i128) {
+    let Some(experiment) = running_experiment(env, brand_id) else {
+        return;
+    };
+    let bucket = bucket_of(env, &experiment, user);
+
+    let mut outcome =
+        LoyaltyTokenExchange::view_bucket_outcome(env.clone(), experiment.experiment_id, bucket);
+    outcome.redemptions += 1;
+    outcome.redeemed += cost;
+    save_outcome(env, experiment.experiment_id, bucket, &outcome);
+}
+
+fn bucket_of(env: &Env, experiment: &Experiment, user: &Address) -> u32 {
+    let digest = env
+        .crypto()
+        .sha256(&(experiment.experiment_id, user.clone()).to_xdr(env))
+        .to_array();
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+        % experiment.multipliers_bps.len()
+}
+
+fn running_experiment(env: &Env, brand_id: u64) -> Option<Experiment> {
+    let experiment_id: u64 = env
+        .storage()
+        .instance()
+        .get(&ExperimentBook::Running(brand_id))?;
+    Some(LoyaltyTokenExchange::view_experiment(
+        env.clone(),
+        experiment_id,
+    ))
+}
+
+fn save_experiment(env: &Env, experiment: &Experiment) {
+    env.storage().instance().set(
+        &ExperimentBook::Experiment(experiment.experiment_id),
+        experiment,
+    );
+}
+
+fn save_outcome(env: &Env, experiment_id: u64, bucket: u32, outcome: &BucketOutcome) {
+    env.storage()
+        .instance()
+        .set(&ExperimentBook::Outcome(experiment_id, bucket), outcome);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    #[test]
+    fn test_buckets_earn_under_their_multiplier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let experiment_id = client.start_experiment(&brand_id, &vec![&env, 10_000, 15_000]);
+        assert!(client
+            .try_start_experiment(&brand_id, &vec![&env, 10_000, 20_000])
+            .is_err());
+
+        // Find one user in each bucket
+        let mut users = [None, None];
+        while users.iter().any(|user| user.is_none()) {
+            let user = Address::generate(&env);
+            let bucket = client.experiment_bucket(&experiment_id, &user);
+            assert_eq!(client.experiment_bucket(&experiment_id, &user), bucket);
+            users[bucket as usize].get_or_insert(user);
+        }
+        let [control, boosted] = users.map(Option::unwrap);
+
+        client.issue_tokens(&control, &brand_id, &100, &None);
+        client.issue_tokens(&boosted, &brand_id, &100, &None);
+        client.issue_tokens(&boosted, &brand_id, &100, &None);
+        assert_eq!(client.view_user_balance(&control, &brand_id), 100);
+        assert_eq!(client.view_user_balance(&boosted, &brand_id), 300);
+
+        let outcome = client.view_bucket_outcome(&experiment_id, &1);
+        assert_eq!(outcome.issuances, 2);
+        assert_eq!(outcome.issued, 300);
+        assert_eq!(client.view_bucket_outcome(&experiment_id, &0).issued, 100);
+
+        client.end_experiment(&experiment_id);
+        client.issue_tokens(&boosted, &brand_id, &100, &None);
+        assert_eq!(client.view_user_balance(&boosted, &brand_id), 400);
+    }
+}
@@ -0,0 +1,279 @@
+use crate::*;
+use soroban_sdk::{BytesN, Vec};
+
+// Structure to store a franchisee issuing under its franchisor brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Franchise {
+    pub franchise_id: u64,
+    // Franchisor brand whose points the franchise issues
+    pub parent_id: u64,
+    pub name: String,
+    pub owner: Address,
+    // Points the franchise may issue across all of its locations
+    pub budget: i128,
+    pub issued: i128,
+}
+
+// Structure to store one issuing location of a franchise
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FranchiseLocation {
+    pub franchise_id: u64,
+    pub issuer: Address,
+    pub budget: i128,
+    pub issued: i128,
+}
+
+// Mapping for franchises
+#[contracttype]
+pub enum FranchiseBook {
+    Franchise(u64),
+    // Franchisor brand -> Franchises under it
+    Franchises(u64),
+    // (Franchise, Issuer) -> Location
+    Location(u64, Address),
+    // Franchise -> Location issuers
+    Locations(u64),
+}
+
+// Counter for franchise IDs
+const FRANCHISE_COUNT: Symbol = symbol_short!("FR_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Add a franchisee that issues the parent brand's points up to `budget`
+    /// (parent brand admin only)
+    /// Returns the franchise_id
+    pub fn add_franchise(
+        env: Env,
+        parent_id: u64,
+        name: String,
+        owner: Address,
+        budget: i128,
+    ) -> u64 {
+        load_brand(&env, parent_id);
+        require_brand_admin(&env, parent_id);
+        if budget < 0 {
+            panic!("Budget cannot be negative");
+        }
+
+        let franchise_id: u64 = env.storage().instance().get(&FRANCHISE_COUNT).unwrap_or(0) + 1;
+        let franchise = Franchise {
+            franchise_id,
+            parent_id,
+            name,
+            owner,
+            budget,
+            issued: 0,
+        };
+        save_franchise(&env, &franchise);
+        env.storage()
+            .instance()
+            .set(&FRANCHISE_COUNT, &franchise_id);
+
+        let mut franchises = Self::view_franchises(env.clone(), parent_id);
+        franchises.push_back(franchise_id);
+        env.storage()
+            .instance()
+            .set(&FranchiseBook::Franchises(parent_id), &franchises);
+        delegate_issuance(&env, parent_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Franchise {} added under brand {}",
+            franchise_id,
+            parent_id
+        );
+        franchise_id
+    }
+
+    /// Change how many points a franchise may issue in total (parent brand
+    /// admin only); it cannot drop below what was already issued
+    pub fn set_franchise_budget(env: Env, franchise_id: u64, budget: i128) {
+        let mut franchise = Self::view_franchise(env.clone(), franchise_id);
+        require_brand_admin(&env, franchise.parent_id);
+        if budget < franchise.issued {
+            panic!("Budget below points already issued");
+        }
+
+        franchise.budget = budget;
+        save_franchise(&env, &franchise);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Budget updated for franchise {}", franchise_id);
+    }
+
+    /// Authorize a location issuer with its own budget, carved out of the
+    /// franchise's (franchise owner only)
+    /// Setting an existing location's budget keeps what it already issued
+    pub fn set_franchise_location(env: Env, franchise_id: u64, issuer: Address, budget: i128) {
+        let franchise = Self::view_franchise(env.clone(), franchise_id);
+        franchise.owner.require_auth();
+
+        let key = FranchiseBook::Location(franchise_id, issuer.clone());
+        let location =
+            match Self::view_franchise_location(env.clone(), franchise_id, issuer.clone()) {
+                Some(mut location) => {
+                    location.budget = budget;
+                    location
+                }
+                None => {
+                    let mut locations = franchise_locations(&env, franchise_id);
+                    locations.push_back(issuer.clone());
+                    env.storage()
+                        .instance()
+                        .set(&FranchiseBook::Locations(franchise_id), &locations);
+                    FranchiseLocation {
+                        franchise_id,
+                        issuer,
+                        budget,
+                        issued: 0,
+                    }
+                }
+            };
+        if location.budget < location.issued {
+            panic!("Budget below points already issued");
+        }
+        env.storage().instance().set(&key, &location);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Location updated for franchise {}", franchise_id);
+    }
+
+    /// Issue the franchisor's points to a user from a franchise location,
+    /// drawing down both the location's and the franchise's budget
+    /// Returns the op_id of the recorded issuance
+    pub fn issue_as_franchise(
+        env: Env,
+        issuer: Address,
+        franchise_id: u64,
+        user: Address,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        issuer.require_auth();
+
+        let mut franchise = Self::view_franchise(env.clone(), franchise_id);
+        let mut location = Self::view_franchise_location(env.clone(), franchise_id, issuer.clone())
+            .unwrap_or_else(|| panic!("Not a location of this franchise"));
+        if let Err(failure) = check_reference(&env, franchise.parent_id, &memo) {
+            failure.fail();
+        }
+        if amount > location.budget - location.issued {
+            panic!("Location budget exhausted");
+        }
+        if amount > franchise.budget - franchise.issued {
+            panic!("Franchise budget exhausted");
+        }
+
+        location.issued += amount;
+        franchise.issued += amount;
+        env.storage().instance().set(
+            &FranchiseBook::Location(franchise_id, issuer.clone()),
+            &location,
+        );
+        save_franchise(&env, &franchise);
+
+        let amount = experiment_amount(&env, &user, franchise.parent_id, amount);
+        issue(&env, Some(issuer), user, franchise.parent_id, amount, memo)
+    }
+
+    /// View a franchise
+    pub fn view_franchise(env: Env, franchise_id: u64) -> Franchise {
+        env.storage()
+            .instance()
+            .get(&FranchiseBook::Franchise(franchise_id))
+            .unwrap_or_else(|| panic!("Franchise not found"))
+    }
+
+    /// View the franchises of a franchisor brand
+    pub fn view_franchises(env: Env, parent_id: u64) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&FranchiseBook::Franchises(parent_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View a location of a franchise, if the issuer belongs to it
+    pub fn view_franchise_location(
+        env: Env,
+        franchise_id: u64,
+        issuer: Address,
+    ) -> Option<FranchiseLocation> {
+        env.storage()
+            .instance()
+            .get(&FranchiseBook::Location(franchise_id, issuer))
+    }
+
+    /// View the issuers of a franchise's locations
+    pub fn view_franchise_locations(env: Env, franchise_id: u64) -> Vec<Address> {
+        franchise_locations(&env, franchise_id)
+    }
+}
+
+fn save_franchise(env: &Env, franchise: &Franchise) {
+    env.storage()
+        .instance()
+        .set(&FranchiseBook::Franchise(franchise.franchise_id), franchise);
+}
+
+fn franchise_locations(env: &Env, franchise_id: u64) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&FranchiseBook::Locations(franchise_id))
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_franchise_issuance_rolls_up_to_parent() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let parent_id = client.register_brand(&String::from_str(&env, "Burger Co"));
+        client.set_brand_admin(&parent_id, &Address::generate(&env));
+        let downtown = client.add_franchise(
+            &parent_id,
+            &String::from_str(&env, "Downtown"),
+            &Address::generate(&env),
+            &500,
+        );
+        let airport = client.add_franchise(
+            &parent_id,
+            &String::from_str(&env, "Airport"),
+            &Address::generate(&env),
+            &500,
+        );
+        let till = Address::generate(&env);
+        let kiosk = Address::generate(&env);
+        client.set_franchise_location(&downtown, &till, &400);
+        client.set_franchise_location(&downtown, &kiosk, &400);
+        client.set_franchise_location(&airport, &kiosk, &100);
+
+        let user = Address::generate(&env);
+        client.issue_as_franchise(&till, &downtown, &user, &300, &None);
+        client.issue_as_franchise(&kiosk, &airport, &user, &100, &None);
+        assert!(client
+            .try_issue_as_franchise(&till, &downtown, &user, &101, &None)
+            .is_err());
+        assert!(client
+            .try_issue_as_franchise(&kiosk, &downtown, &user, &201, &None)
+            .is_err());
+        assert!(client
+            .try_issue_as_franchise(&till, &airport, &user, &1, &None)
+            .is_err());
+
+        assert_eq!(client.view_franchise(&downtown).issued, 300);
+        assert_eq!(client.view_franchise_locations(&downtown).len(), 2);
+        assert_eq!(client.view_user_balance(&user, &parent_id), 400);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::*;
+
+// Delivery state of a redeemed reward
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FulfillmentStatus {
+    Pending,
+    Shipped,
+    Delivered,
+    Cancelled,
+}
+
+// Structure to store the fulfillment of one redemption, keyed by its op_id
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fulfillment {
+    pub op_id: u64,
+    pub user: Address,
+    pub brand_id: u64,
+    pub reward_id: u64,
+    pub cost: i128,
+    pub status: FulfillmentStatus,
+    pub updated_at: u32,
+}
+
+// Mapping for fulfillments and the addresses allowed to update them
+#[contracttype]
+pub enum FulfillmentBook {
+    // Redemption op_id -> Fulfillment, kept in persistent storage
+    Fulfillment(u64),
+    // (Brand, Address) -> Whether the address fulfills the brand's rewards
+    Fulfiller(u64, Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Grant or revoke the fulfillment role for a brand's rewards (brand admin only)
+    pub fn set_fulfiller(env: Env, brand_id: u64, fulfiller: Address, enabled: bool) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = FulfillmentBook::Fulfiller(brand_id, fulfiller);
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Fulfillment role updated for brand {}", brand_id);
+    }
+
+    /// View whether an address holds the fulfillment role for a brand
+    pub fn is_fulfiller(env: Env, brand_id: u64, address: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&FulfillmentBook::Fulfiller(brand_id, address))
+            .unwrap_or(false)
+    }
+
+    /// Move a redemption to Shipped, Delivered or Cancelled (brand fulfiller only)
+    /// Cancelling refunds the cost to the user and returns the unit to stock
+    pub fn update_fulfillment(env: Env, fulfiller: Address, op_id: u64, status: FulfillmentStatus) {
+        fulfiller.require_auth();
+
+        let mut fulfillment = Self::view_fulfillment(env.clone(), op_id);
+        if !Self::is_fulfiller(env.clone(), fulfillment.brand_id, fulfiller) {
+            panic!("Not a fulfiller for this brand");
+        }
+        let allowed = matches!(
+            (fulfillment.status, status),
+            (FulfillmentStatus::Pending, FulfillmentStatus::Shipped)
+                | (FulfillmentStatus::Shipped, FulfillmentStatus::Delivered)
+                | (
+                    FulfillmentStatus::Pending | FulfillmentStatus::Shipped,
+                    FulfillmentStatus::Cancelled
+                )
+        );
+        if !allowed {
+            panic!("Invalid fulfillment transition");
+        }
+
+        if status == FulfillmentStatus::Cancelled {
+            credit(
+                &env,
+                &fulfillment.user,
+                fulfillment.brand_id,
+                fulfillment.cost,
+            );
+            track_redeemed(&env, fulfillment.brand_id, -fulfillment.cost);
+            let mut reward = load_reward(&env, fulfillment.reward_id);
+            reward.stock += 1;
+            save_reward(&env, &reward);
+        }
+        set_status(&env, &mut fulfillment, status);
+
+        log!(&env, "✅ Fulfillment of operation {} updated", op_id);
+    }
+
+    /// Confirm receipt of a shipped reward (redeeming user only)
+    pub fn confirm_delivery(env: Env, user: Address, op_id: u64) {
+        require_user(&env, &user);
+
+        let mut fulfillment = Self::view_fulfillment(env.clone(), op_id);
+        if fulfillment.user != user {
+            panic!("Not the redeeming user");
+        }
+        if fulfillment.status != FulfillmentStatus::Shipped {
+            panic!("Reward not shipped");
+        }
+        set_status(&env, &mut fulfillment, FulfillmentStatus::Delivered);
+
+        log!(&env, "✅ Delivery of operation {} confirmed", op_id);
+    }
+
+    /// View the fulfillment of a redemption by its op_id
+    pub fn view_fulfillment(env: Env, op_id: u64) -> Fulfillment {
+        env.storage()
+            .persistent()
+            .get(&FulfillmentBook::Fulfillment(op_id))
+            .unwrap_or_else(|| panic!("Fulfillment not found"))
+    }
+}
+
+// Track a new redemption as Pending
+pub(crate) fn open_fulfillment(env: &Env, op_id: u64, user: &Address, reward: &Reward, cost: i128) {
+    let fulfillment = Fulfillment {
+        op_id,
+        user: user.clone(),
+        brand_id: reward.brand_id,
+        reward_id: reward.reward_id,
+        cost,
+        status: FulfillmentStatus::Pending,
+        updated_at: env.ledger().sequence(),
+    };
+    save_fulfillment(env, &fulfillment);
+}
+
+fn set_status(env: &Env, fulfillment: &mut Fulfillment, status: FulfillmentStatus) {
+    fulfillment.status = status;
+    fulfillment.updated_at = env.ledger().sequence();
+    save_fulfillment(env, fulfillment);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    FulfillmentUpdated {
+        brand_id: fulfillment.brand_id,
+        user: fulfillment.user.clone(),
+        op_id: fulfillment.op_id,
+        status,
+    }
+    .publish(env);
+}
+
+fn save_fulfillment(env: &Env, fulfillment: &Fulfillment) {
+    let key = FulfillmentBook::Fulfillment(fulfillment.op_id);
+    env.storage().persistent().set(&key, fulfillment);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_fulfillment_ships_delivers_and_refunds_on_cancel() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Outfitter"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Tent"), &100, &2);
+        let fulfiller = Address::generate(&env);
+        client.set_fulfiller(&brand_id, &fulfiller, &true);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &200, &None);
+        let shipped = client.redeem_reward(&user, &reward_id, &None);
+        let cancelled = client.redeem_reward(&user, &reward_id, &None);
+        assert_eq!(
+            client.view_fulfillment(&shipped).status,
+            FulfillmentStatus::Pending
+        );
+        env.as_contract(&contract_id, || {
+            let key = FulfillmentBook::Fulfillment(shipped);
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+
+        assert!(client.try_confirm_delivery(&user, &shipped).is_err());
+        assert!(client
+            .try_update_fulfillment(&user, &shipped, &FulfillmentStatus::Shipped)
+            .is_err());
+        client.update_fulfillment(&fulfiller, &shipped, &FulfillmentStatus::Shipped);
+        client.confirm_delivery(&user, &shipped);
+        assert_eq!(
+            client.view_fulfillment(&shipped).status,
+            FulfillmentStatus::Delivered
+        );
+        assert!(client
+            .try_update_fulfillment(&fulfiller, &shipped, &FulfillmentStatus::Cancelled)
+            .is_err());
+
+        client.update_fulfillment(&fulfiller, &cancelled, &FulfillmentStatus::Cancelled);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 100);
+        assert_eq!(client.view_reward(&reward_id).stock, 1);
+    }
+}
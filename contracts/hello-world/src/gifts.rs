@@ -0,0 +1,209 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store points sent as a gift, held until the recipient claims them
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Gift {
+    pub gift_id: u64,
+    pub brand_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    // Ledger from which the recipient may claim
+    pub deliver_at: u32,
+    // Hash of the message attached off-chain, e.g. a birthday card
+    pub message_hash: Option<BytesN<32>>,
+}
+
+// Mapping for gifts
+#[contracttype]
+pub enum GiftBook {
+    // Gift ID -> Gift, kept in persistent storage
+    Gift(u64),
+    // Recipient -> gift_ids not claimed yet, kept in persistent storage
+    Recipient(Address),
+}
+
+// Counter for gifts
+const GIFT_COUNT: Symbol = symbol_short!("GF_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Send points as a gift the recipient can claim from `deliver_at`
+    /// (immediately when None), with an optional message hash
+    /// Returns the gift_id
+    pub fn send_gift(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        brand_id: u64,
+        amount: i128,
+        deliver_at: Option<u32>,
+        message_hash: Option<BytesN<32>>,
+    ) -> u64 {
+        require_user(&env, &sender);
+
+        if !is_brand_active(&env, brand_id) {
+            panic!("Brand is not active");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if sender == recipient {
+            panic!("Cannot gift to yourself");
+        }
+        if !Self::can_receive(env.clone(), brand_id, recipient.clone()) {
+            panic!("Receiver is not whitelisted");
+        }
+
+        escrow(&env, &sender, brand_id, amount);
+
+        let mut gift_count: u64 = env.storage().instance().get(&GIFT_COUNT).unwrap_or(0);
+        gift_count += 1;
+
+        let gift = Gift {
+            gift_id: gift_count,
+            brand_id,
+            sender: sender.clone(),
+            recipient: recipient.clone(),
+            amount,
+            deliver_at: deliver_at.unwrap_or(env.ledger().sequence()),
+            message_hash: message_hash.clone(),
+        };
+        let key = GiftBook::Gift(gift_count);
+        env.storage().persistent().set(&key, &gift);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+        let mut gifts = Self::view_pending_gifts(env.clone(), recipient.clone());
+        gifts.push_back(gift_count);
+        save_pending_gifts(&env, &recipient, &gifts);
+        env.storage().instance().set(&GIFT_COUNT, &gift_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        GiftSent {
+            brand_id,
+            sender,
+            recipient,
+            gift_id: gift_count,
+            amount,
+            deliver_at: gift.deliver_at,
+            message_hash,
+        }
+        .publish(&env);
+        log!(&env, "✅ Gift {} sent", gift_count);
+        gift_count
+    }
+
+    /// Claim a gift once its delivery ledger is reached
+    /// Returns the op_id of the recorded transfer
+    pub fn claim_gift(env: Env, recipient: Address, gift_id: u64) -> u64 {
+        require_user(&env, &recipient);
+
+        let gift = Self::view_gift(env.clone(), gift_id);
+        if gift.recipient != recipient {
+            panic!("Not the gift recipient");
+        }
+        if env.ledger().sequence() < gift.deliver_at {
+            panic!("Gift not yet delivered");
+        }
+
+        release(&env, &recipient, gift.brand_id, gift.amount);
+        env.storage().persistent().remove(&GiftBook::Gift(gift_id));
+        let mut gifts = Self::view_pending_gifts(env.clone(), recipient.clone());
+        if let Some(index) = gifts.first_index_of(gift_id) {
+            gifts.remove(index);
+        }
+        save_pending_gifts(&env, &recipient, &gifts);
+
+        let mut record = OperationRecord::new(
+            &env,
+            OperationKind::Transfer,
+            &gift.sender,
+            gift.brand_id,
+            gift.amount,
+        );
+        record.counterparty = Some(recipient);
+        record.memo = gift.message_hash;
+        save_operation(&env, &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Gift {} claimed", gift_id);
+        record.op_id
+    }
+
+    /// View a gift that has not been claimed yet
+    pub fn view_gift(env: Env, gift_id: u64) -> Gift {
+        env.storage()
+            .persistent()
+            .get(&GiftBook::Gift(gift_id))
+            .unwrap_or_else(|| panic!("Gift not found"))
+    }
+
+    /// View the unclaimed gifts of a recipient, including ones not delivered yet
+    pub fn view_pending_gifts(env: Env, recipient: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&GiftBook::Recipient(recipient))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+fn save_pending_gifts(env: &Env, recipient: &Address, gifts: &Vec<u64>) {
+    let key = GiftBook::Recipient(recipient.clone());
+    if gifts.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, gifts);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, BytesN, Env, String,
+    };
+
+    #[test]
+    fn test_scheduled_gift_claimed_after_delivery() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        client.issue_tokens(&sender, &brand_id, &500, &None);
+
+        let message = BytesN::from_array(&env, &[7; 32]);
+        let gift_id = client.send_gift(
+            &sender,
+            &recipient,
+            &brand_id,
+            &200,
+            &Some(100),
+            &Some(message.clone()),
+        );
+        assert_eq!(client.view_user_balance(&sender, &brand_id), 300);
+        assert_eq!(client.view_pending_gifts(&recipient), vec![&env, gift_id]);
+        assert_eq!(client.view_gift(&gift_id).message_hash, Some(message));
+        env.as_contract(&contract_id, || {
+            let key = GiftBook::Recipient(recipient.clone());
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+
+        assert!(client.try_claim_gift(&recipient, &gift_id).is_err());
+        env.ledger().set_sequence_number(100);
+        assert!(client.try_claim_gift(&sender, &gift_id).is_err());
+        client.claim_gift(&recipient, &gift_id);
+        assert_eq!(client.view_user_balance(&recipient, &brand_id), 200);
+        assert_eq!(client.view_pending_gifts(&recipient).len(), 0);
+        assert!(client.try_claim_gift(&recipient, &gift_id).is_err());
+    }
+}
@@ -0,0 +1,379 @@
+use crate::*;
+use soroban_sdk::{IntoVal, Val, Vec};
+
+// Kinds of balance-changing operations kept in the history
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperationKind {
+    Issue,
+    Transfer,
+    Redeem,
+    Exchange,
+    Sell,
+    // Points burned as payment to a partner contract
+    Burn,
+}
+
+// Structure to store one executed operation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperationRecord {
+    pub op_id: u64,
+    pub kind: OperationKind,
+    pub user: Address,
+    pub brand_id: u64,
+    pub amount: i128,
+    // Receiving user of a transfer
+    pub counterparty: Option<Address>,
+    // Destination brand of an exchange
+    pub to_brand: Option<u64>,
+    // Issuer that performed an issuance on the brand's behalf
+    pub issuer: Option<Address>,
+    pub memo: Option<BytesN<32>>,
+    pub ledger: u32,
+}
+
+// Mapping for operation history
+// Records and indexes live in persistent storage, one entry per key, so the
+// history grows without growing the contract instance
+#[contracttype]
+pub enum HistoryBook {
+    Operation(u64),
+    // (User, page) -> op_ids involving the user, oldest first
+    UserOps(Address, u32),
+    // User -> number of op_ids indexed under the user
+    UserOpCount(Address),
+    // (Brand, Memo, index) -> op_id of the brand's index-th operation carrying
+    // the memo, oldest first
+    Reference(u64, BytesN<32>, u32),
+    // (Brand, Memo) -> Number of the brand's operations carrying the memo
+    ReferenceCount(u64, BytesN<32>),
+}
+
+// Counter for operations
+const OP_COUNT: Symbol = symbol_short!("OP_COUNT");
+
+// Number of op_ids kept in one page of a user's index
+const USER_OPS_PAGE_SIZE: u32 = 32;
+
+impl OperationRecord {
+    // Start a record for the current ledger with a freshly allocated op_id
+    pub(crate) fn new(
+        env: &Env,
+        kind: OperationKind,
+        user: &Address,
+        brand_id: u64,
+        amount: i128,
+    ) -> Self {
+        let mut op_count: u64 = env.storage().instance().get(&OP_COUNT).unwrap_or(0);
+        op_count += 1;
+        env.storage().instance().set(&OP_COUNT, &op_count);
+
+        OperationRecord {
+            op_id: op_count,
+            kind,
+            user: user.clone(),
+            brand_id,
+            amount,
+            counterparty: None,
+            to_brand: None,
+            issuer: None,
+            memo: None,
+            ledger: env.ledger().sequence(),
+        }
+    }
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View an operation by op_id
+    /// Only parties to the operation, its brand admins and auditors may read it
+    pub fn view_operation(env: Env, caller: Address, op_id: u64) -> OperationRecord {
+        caller.require_auth();
+        let record = load_operation(&env, op_id);
+        if !can_view_operation(&env, &caller, &record) {
+            panic!("Not authorized to view operation");
+        }
+        record
+    }
+
+    /// Find the latest operation of a brand submitted with `reference` as its
+    /// memo that the caller may read (same access as `view_operation`)
+    /// Every operation carrying a memo stays indexed, so reusing a memo cannot
+    /// hide the operations that carried it before
+    pub fn find_operation(
+        env: Env,
+        caller: Address,
+        brand_id: u64,
+        reference: BytesN<32>,
+    ) -> OperationRecord {
+        caller.require_auth();
+
+        let mut index = reference_count(&env, brand_id, &reference);
+        while index > 0 {
+            index -= 1;
+            let record = load_operation(&env, reference_op(&env, brand_id, &reference, index));
+            if can_view_operation(&env, &caller, &record) {
+                return record;
+            }
+        }
+        panic!("Operation not found");
+    }
+
+    /// View a page of the operations of a brand submitted with `reference` as
+    /// their memo, oldest first
+    /// Operations the caller may not read are left out of the page
+    pub fn find_operations(
+        env: Env,
+        caller: Address,
+        brand_id: u64,
+        reference: BytesN<32>,
+        start: u32,
+        limit: u32,
+    ) -> Vec<OperationRecord> {
+        caller.require_auth();
+
+        let mut records = Vec::new(&env);
+        let end = reference_count(&env, brand_id, &reference).min(start.saturating_add(limit));
+        for index in start..end {
+            let record = load_operation(&env, reference_op(&env, brand_id, &reference, index));
+            if can_view_operation(&env, &caller, &record) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+
+    /// View a page of the operations involving a user, oldest first
+    /// Operations the caller may not read are left out of the page
+    pub fn view_user_history(
+        env: Env,
+        caller: Address,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<OperationRecord> {
+        caller.require_auth();
+
+        let mut records = Vec::new(&env);
+        let end = user_op_count(&env, &user).min(start.saturating_add(limit));
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            if index == start || index % USER_OPS_PAGE_SIZE == 0 {
+                page = user_op_page(&env, &user, index / USER_OPS_PAGE_SIZE);
+            }
+            let op_id = page.get(index % USER_OPS_PAGE_SIZE).unwrap();
+            let record = load_operation(&env, op_id);
+            if can_view_operation(&env, &caller, &record) {
+                records.push_back(record);
+            }
+        }
+        records
+    }
+}
+
+// Number of operations recorded so far, which is also the latest op_id
+pub(crate) fn operation_count(env: &Env) -> u64 {
+    env.storage().instance().get(&OP_COUNT).unwrap_or(0)
+}
+
+pub(crate) fn load_operation(env: &Env, op_id: u64) -> OperationRecord {
+    env.storage()
+        .persistent()
+        .get(&HistoryBook::Operation(op_id))
+        .unwrap_or_else(|| panic!("Operation not found"))
+}
+
+// Store a record and index it under every user it involves
+pub(crate) fn save_operation(env: &Env, record: &OperationRecord) {
+    write_entry(env, &HistoryBook::Operation(record.op_id), record);
+
+    index_operation(env, &record.user, record.op_id);
+    if let Some(counterparty) = &record.counterparty {
+        index_operation(env, counterparty, record.op_id);
+    }
+    if let Some(memo) = &record.memo {
+        let count = reference_count(env, record.brand_id, memo);
+        write_entry(
+            env,
+            &HistoryBook::Reference(record.brand_id, memo.clone(), count),
+            &record.op_id,
+        );
+        write_entry(
+            env,
+            &HistoryBook::ReferenceCount(record.brand_id, memo.clone()),
+            &(count + 1),
+        );
+    }
+}
+
+fn reference_count(env: &Env, brand_id: u64, memo: &BytesN<32>) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&HistoryBook::ReferenceCount(brand_id, memo.clone()))
+        .unwrap_or(0)
+}
+
+fn reference_op(env: &Env, brand_id: u64, memo: &BytesN<32>, index: u32) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&HistoryBook::Reference(brand_id, memo.clone(), index))
+        .unwrap_or_else(|| panic!("Operation not found"))
+}
+
+fn user_op_count(env: &Env, user: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&HistoryBook::UserOpCount(user.clone()))
+        .unwrap_or(0)
+}
+
+fn user_op_page(env: &Env, user: &Address, page: u32) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&HistoryBook::UserOps(user.clone(), page))
+        .unwrap_or(Vec::new(env))
+}
+
+// Append an op_id to the last page of the user's index
+fn index_operation(env: &Env, user: &Address, op_id: u64) {
+    let count = user_op_count(env, user);
+    let page = count / USER_OPS_PAGE_SIZE;
+    let mut op_ids = user_op_page(env, user, page);
+    op_ids.push_back(op_id);
+    write_entry(env, &HistoryBook::UserOps(user.clone(), page), &op_ids);
+    write_entry(env, &HistoryBook::UserOpCount(user.clone()), &(count + 1));
+}
+
+fn write_entry<V: IntoVal<Env, Val>>(env: &Env, key: &HistoryBook, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage().persistent().extend_ttl(key, 100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+    #[test]
+    fn test_memos_are_stored_in_history() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Bakery"));
+
+        let order = BytesN::from_array(&env, &[7; 32]);
+        let issue_op = client.issue_tokens(&alice, &brand_id, &300, &Some(order.clone()));
+        let transfer_op = client.transfer_tokens(&alice, &bob, &brand_id, &100, &None);
+
+        assert_eq!(client.view_user_balance(&alice, &brand_id), 200);
+        assert_eq!(client.view_user_balance(&bob, &brand_id), 100);
+
+        let issued = client.view_operation(&alice, &issue_op);
+        assert_eq!(issued.kind, OperationKind::Issue);
+        assert_eq!(issued.memo, Some(order));
+
+        let transfer = client.view_operation(&bob, &transfer_op);
+        assert_eq!(transfer.counterparty, Some(bob.clone()));
+        assert_eq!(transfer.memo, None);
+
+        assert_eq!(client.view_user_history(&alice, &alice, &0, &10).len(), 2);
+        let bob_history = client.view_user_history(&bob, &bob, &0, &10);
+        assert_eq!(bob_history.len(), 1);
+        assert_eq!(bob_history.get(0).unwrap().op_id, transfer_op);
+    }
+
+    #[test]
+    fn test_find_operation_by_reference() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        client.set_platform_admin(&Address::generate(&env));
+
+        let user = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Bakery"));
+        let brand_admin = Address::generate(&env);
+        client.set_brand_admin(&brand_id, &brand_admin);
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Cake"), &50, &5);
+
+        let order = BytesN::from_array(&env, &[1; 32]);
+        let receipt = BytesN::from_array(&env, &[2; 32]);
+        client.issue_tokens(&user, &brand_id, &100, &Some(order.clone()));
+        let redeem_op = client.redeem_reward(&user, &reward_id, &Some(receipt.clone()));
+
+        let found = client.find_operation(&user, &brand_id, &receipt);
+        assert_eq!(found.op_id, redeem_op);
+        assert_eq!(found.kind, OperationKind::Redeem);
+        assert_eq!(
+            client.find_operation(&user, &brand_id, &order).kind,
+            OperationKind::Issue
+        );
+
+        let unknown = BytesN::from_array(&env, &[3; 32]);
+        assert!(client
+            .try_find_operation(&user, &brand_id, &unknown)
+            .is_err());
+
+        // Reusing the memo, on this brand or another, does not shadow the
+        // user's operation
+        let other = client.register_brand(&String::from_str(&env, "Other"));
+        let attacker = Address::generate(&env);
+        client.issue_tokens(&attacker, &other, &1, &Some(receipt.clone()));
+        let shadow_op = client.issue_tokens(&attacker, &brand_id, &1, &Some(receipt.clone()));
+        assert_eq!(
+            client.find_operation(&user, &brand_id, &receipt).op_id,
+            redeem_op
+        );
+        assert_eq!(
+            client.find_operation(&attacker, &brand_id, &receipt).op_id,
+            shadow_op
+        );
+        assert_eq!(
+            client
+                .find_operations(&brand_admin, &brand_id, &receipt, &0, &10)
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_user_history_spans_persistent_pages() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Bakery"));
+        let mut op_ids = soroban_sdk::Vec::new(&env);
+        for _ in 0..40 {
+            op_ids.push_back(client.issue_tokens(&user, &brand_id, &1, &None));
+        }
+
+        let history = client.view_user_history(&user, &user, &30, &5);
+        assert_eq!(history.len(), 5);
+        for (offset, record) in history.iter().enumerate() {
+            assert_eq!(record.op_id, op_ids.get(30 + offset as u32).unwrap());
+        }
+        assert_eq!(client.view_user_history(&user, &user, &0, &100).len(), 40);
+
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            assert!(storage.has(&HistoryBook::UserOps(user.clone(), 1)));
+            assert!(storage.has(&HistoryBook::Operation(op_ids.get(0).unwrap())));
+            assert!(!env
+                .storage()
+                .instance()
+                .has(&HistoryBook::Operation(op_ids.get(0).unwrap())));
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use crate::*;
+use soroban_sdk::token;
+
+// Structure to store who may self-enroll with a brand on the strength of an
+// external token holding
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderRule {
+    // SAC token whose holders qualify
+    pub token: Address,
+    // Holdings must be strictly above this
+    pub min_balance: i128,
+    // Starter bonus, paid from the welcome budget while it lasts
+    pub bonus: i128,
+}
+
+// Mapping for holder rules: Brand -> Rule
+#[contracttype]
+pub enum HolderRuleBook {
+    Rule(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Let holders of more than `min_balance` of a token self-enroll with the
+    /// brand for a starter bonus; `None` ends the campaign (brand admin only)
+    pub fn set_holder_rule(env: Env, brand_id: u64, rule: Option<HolderRule>) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = HolderRuleBook::Rule(brand_id);
+        match rule {
+            Some(rule) => {
+                if rule.min_balance < 0 || rule.bonus < 0 {
+                    panic!("Amount cannot be negative");
+                }
+                env.storage().instance().set(&key, &rule);
+            }
+            None => env.storage().instance().remove(&key),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Holder rule updated for brand {}", brand_id);
+    }
+
+    /// View a brand's holder rule, if a campaign is running
+    pub fn view_holder_rule(env: Env, brand_id: u64) -> Option<HolderRule> {
+        env.storage()
+            .instance()
+            .get(&HolderRuleBook::Rule(brand_id))
+    }
+
+    /// Enroll with a brand as a qualifying token holder, crediting the
+    /// starter bonus while the welcome budget lasts
+    /// Returns the bonus credited, 0 when none was
+    pub fn enroll_as_holder(env: Env, user: Address, brand_id: u64) -> i128 {
+        require_user(&env, &user);
+        let rule = Self::view_holder_rule(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("No holder campaign"));
+        if token::TokenClient::new(&env, &rule.token).balance(&user) <= rule.min_balance {
+            panic!("Holding below minimum");
+        }
+        enroll_user(&env, user, brand_id, rule.bonus)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+
+    #[test]
+    fn test_token_holders_self_enroll_for_bonus() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&brand_id, &treasury);
+        client.issue_tokens(&treasury, &brand_id, &500, &None);
+        client.fund_welcome_budget(&brand_id, &500);
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_holder_rule(
+            &brand_id,
+            &Some(HolderRule {
+                token: token.clone(),
+                min_balance: 1_000,
+                bonus: 200,
+            }),
+        );
+
+        let whale = Address::generate(&env);
+        let minnow = Address::generate(&env);
+        StellarAssetClient::new(&env, &token).mint(&whale, &5_000);
+        StellarAssetClient::new(&env, &token).mint(&minnow, &1_000);
+
+        assert!(client.try_enroll_as_holder(&minnow, &brand_id).is_err());
+        assert_eq!(client.enroll_as_holder(&whale, &brand_id), 200);
+        assert!(client.try_enroll_as_holder(&whale, &brand_id).is_err());
+        assert_eq!(client.view_user_balance(&whale, &brand_id), 200);
+        assert_eq!(client.view_welcome_bonus(&brand_id).budget, 300);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::*;
+use soroban_sdk::{vec, IntoVal};
+
+// Structure describing an exchange a user signs off-chain for a matcher to settle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExchangeIntent {
+    pub user: Address,
+    pub from_brand: u64,
+    pub to_brand: u64,
+    // Source points the user gives up
+    pub amount: i128,
+    // Fewest destination points the user accepts
+    pub min_out: i128,
+    // Must equal the user's next intent nonce
+    pub nonce: u64,
+    // Last ledger at which the intent can be settled
+    pub deadline: u32,
+}
+
+// Mapping for intent nonces: User -> Next nonce, kept in persistent storage
+#[contracttype]
+pub enum IntentBook {
+    Nonce(Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Settle two opposite exchange intents against each other (matcher only)
+    /// Each user must have authorized their intent; both legs settle atomically
+    /// as a swap between the two users
+    /// Returns the op_ids of the two recorded exchanges
+    pub fn settle_intents(
+        env: Env,
+        matcher: Address,
+        first: ExchangeIntent,
+        second: ExchangeIntent,
+    ) -> (u64, u64) {
+        matcher.require_auth();
+
+        if first.user == second.user {
+            panic!("Intents must come from different users");
+        }
+        if first.from_brand != second.to_brand || first.to_brand != second.from_brand {
+            panic!("Intents do not match");
+        }
+        if second.amount < first.min_out || first.amount < second.min_out {
+            panic!("Output below minimum");
+        }
+        if let Err(failure) = check_exchange(
+            &env,
+            &first.user,
+            first.from_brand,
+            first.to_brand,
+            first.amount,
+        ) {
+            failure.fail();
+        }
+        if let Err(failure) = check_exchange(
+            &env,
+            &second.user,
+            second.from_brand,
+            second.to_brand,
+            second.amount,
+        ) {
+            failure.fail();
+        }
+        accept_intent(&env, &first);
+        accept_intent(&env, &second);
+
+        let first_op = swap(&env, &first, &second.user);
+        let second_op = swap(&env, &second, &first.user);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Matched {} tokens of brand {} against {} tokens of brand {}",
+            first.amount,
+            first.from_brand,
+            second.amount,
+            second.from_brand
+        );
+        (first_op, second_op)
+    }
+
+    /// View the nonce a user's next intent must carry
+    pub fn view_intent_nonce(env: Env, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&IntentBook::Nonce(user))
+            .unwrap_or(0)
+    }
+}
+
+// Verify the user's authorization of the intent, its deadline and nonce,
+// and consume the nonce
+fn accept_intent(env: &Env, intent: &ExchangeIntent) {
+    intent
+        .user
+        .require_auth_for_args(vec![env, intent.clone().into_val(env)]);
+
+    if env.ledger().sequence() > intent.deadline {
+        panic!("Deadline has passed");
+    }
+    let nonce = LoyaltyTokenExchange::view_intent_nonce(env.clone(), intent.user.clone());
+    if intent.nonce != nonce {
+        panic!("Invalid intent nonce");
+    }
+    let key = IntentBook::Nonce(intent.user.clone());
+    env.storage().persistent().set(&key, &(nonce + 1));
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+}
+
+// Move the intent's source points to the counterparty and record the leg
+fn swap(env: &Env, intent: &ExchangeIntent, counterparty: &Address) -> u64 {
+    post(
+        env,
+        intent.from_brand,
+        &LedgerAccount::User(intent.user.clone()),
+        &LedgerAccount::User(counterparty.clone()),
+        intent.amount,
+    );
+    track_exchanged(
+        env,
+        intent.from_brand,
+        intent.to_brand,
+        intent.amount,
+        intent.amount,
+    );
+
+    let mut record = OperationRecord::new(
+        env,
+        OperationKind::Exchange,
+        &intent.user,
+        intent.from_brand,
+        intent.amount,
+    );
+    record.to_brand = Some(intent.to_brand);
+    record.counterparty = Some(counterparty.clone());
+    save_operation(env, &record);
+    record.op_id
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_matcher_settles_opposite_intents() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.issue_tokens(&alice, &hotel, &500, &None);
+        client.issue_tokens(&bob, &airline, &300, &None);
+
+        let first = ExchangeIntent {
+            user: alice.clone(),
+            from_brand: hotel,
+            to_brand: airline,
+            amount: 500,
+            min_out: 250,
+            nonce: 0,
+            deadline: 100,
+        };
+        let mut second = ExchangeIntent {
+            user: bob.clone(),
+            from_brand: airline,
+            to_brand: hotel,
+            amount: 300,
+            min_out: 600,
+            nonce: 0,
+            deadline: 100,
+        };
+        let matcher = Address::generate(&env);
+        assert!(client
+            .try_settle_intents(&matcher, &first, &second)
+            .is_err());
+
+        second.min_out = 450;
+        client.settle_intents(&matcher, &first, &second);
+        assert_eq!(client.view_user_balance(&alice, &airline), 300);
+        assert_eq!(client.view_user_balance(&bob, &hotel), 500);
+        assert_eq!(client.view_intent_nonce(&alice), 1);
+        env.as_contract(&contract_id, || {
+            let key = IntentBook::Nonce(alice.clone());
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+
+        // A settled intent cannot be replayed
+        client.issue_tokens(&alice, &hotel, &500, &None);
+        client.issue_tokens(&bob, &airline, &300, &None);
+        assert!(client
+            .try_settle_intents(&matcher, &first, &second)
+            .is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure advertising one version of a family of public entry points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterfaceVersion {
+    pub name: Symbol,
+    pub version: u32,
+    // Deprecated versions still work but forward to the newest version
+    pub deprecated: bool,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// List the public API versions this contract serves, so integrators can
+    /// detect which entry points are available before calling them
+    pub fn supported_interfaces(env: Env) -> Vec<InterfaceVersion> {
+        let mut interfaces = Vec::new(&env);
+        // exchange_tokens
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("exchange"),
+            version: 1,
+            deprecated: true,
+        });
+        // exchange_tokens_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("exchange"),
+            version: 2,
+            deprecated: true,
+        });
+        // exchange_tokens_v3
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("exchange"),
+            version: 3,
+            deprecated: false,
+        });
+        // transfer_tokens
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("transfer"),
+            version: 1,
+            deprecated: true,
+        });
+        // transfer_tokens_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("transfer"),
+            version: 2,
+            deprecated: false,
+        });
+        // redeem_reward
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("redeem"),
+            version: 1,
+            deprecated: true,
+        });
+        // redeem_reward_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("redeem"),
+            version: 2,
+            deprecated: false,
+        });
+        // exchange_via
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("exch_via"),
+            version: 1,
+            deprecated: true,
+        });
+        // exchange_via_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("exch_via"),
+            version: 2,
+            deprecated: false,
+        });
+        // register_brand_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("register"),
+            version: 2,
+            deprecated: false,
+        });
+        // create_sub_brand
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("sub_brand"),
+            version: 1,
+            deprecated: true,
+        });
+        // create_sub_brand_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("sub_brand"),
+            version: 2,
+            deprecated: false,
+        });
+        // register_program_brand
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("prg_brand"),
+            version: 1,
+            deprecated: true,
+        });
+        // register_program_brand_v2
+        interfaces.push_back(InterfaceVersion {
+            name: symbol_short!("prg_brand"),
+            version: 2,
+            deprecated: false,
+        });
+        interfaces
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, BytesN, Env, String,
+    };
+
+    #[test]
+    fn test_exchange_v2_enforces_min_out_and_deadline() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        let out = client.quote_exchange(&hotel, &airline, &100).amount_out;
+        assert!(client
+            .try_exchange_tokens_v2(&user, &hotel, &airline, &100, &(out + 1), &100, &None)
+            .is_err());
+
+        env.ledger().set_sequence_number(101);
+        assert!(client
+            .try_exchange_tokens_v2(&user, &hotel, &airline, &100, &out, &100, &None)
+            .is_err());
+
+        let memo = BytesN::from_array(&env, &[7; 32]);
+        let op_id = client.exchange_tokens_v2(
+            &user,
+            &hotel,
+            &airline,
+            &100,
+            &out,
+            &200,
+            &Some(memo.clone()),
+        );
+        assert_eq!(client.view_operation(&user, &op_id).memo, Some(memo));
+        assert_eq!(client.view_user_balance(&user, &hotel), 900);
+        assert_eq!(client.view_user_balance(&user, &airline), out);
+
+        // The legacy entry point keeps working through the shim
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        assert_eq!(client.view_user_balance(&user, &hotel), 800);
+    }
+
+    #[test]
+    fn test_supported_interfaces_lists_exchange_versions() {
+        let env = Env::default();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let interfaces = client.supported_interfaces();
+        assert_eq!(interfaces.len(), 14);
+        assert!(interfaces.get(0).unwrap().deprecated);
+        assert!(interfaces.get(1).unwrap().deprecated);
+        assert_eq!(interfaces.get(2).unwrap().version, 3);
+        assert!(!interfaces.get(2).unwrap().deprecated);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::*;
+use soroban_sdk::Bytes;
+
+// Structure to store a single-use brand invitation code
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invitation {
+    // SHA-256 of the code handed to the invitee
+    pub code_hash: BytesN<32>,
+    // Last ledger the code can be redeemed on
+    pub expires_at: u32,
+    // Brand onboarded with the code, once redeemed
+    pub used_by: Option<u64>,
+}
+
+// Mapping for invitations
+#[contracttype]
+pub enum InvitationBook {
+    // Code hash -> Invitation
+    Invitation(BytesN<32>),
+    // Brand -> Hash of the code it registered with
+    BrandCode(u64),
+}
+
+// Flag closing open brand registration
+const INVITE_ONLY: Symbol = symbol_short!("INV_ONLY");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Require an invitation code to register a brand (platform admin only)
+    pub fn set_invite_only(env: Env, invite_only: bool) {
+        require_platform_admin(&env);
+
+        if invite_only {
+            env.storage().instance().set(&INVITE_ONLY, &true);
+        } else {
+            env.storage().instance().remove(&INVITE_ONLY);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Invite-only registration updated");
+    }
+
+    /// View whether registering a brand requires an invitation code
+    pub fn is_invite_only(env: Env) -> bool {
+        env.storage().instance().get(&INVITE_ONLY).unwrap_or(false)
+    }
+
+    /// Mint a single-use invitation from the hash of its code, redeemable up
+    /// to ledger `expires_at` (platform admin only)
+    pub fn mint_invitation(env: Env, code_hash: BytesN<32>, expires_at: u32) {
+        require_platform_admin(&env);
+        if expires_at < env.ledger().sequence() {
+            panic!("Invitation already expired");
+        }
+
+        let key = InvitationBook::Invitation(code_hash.clone());
+        if env.storage().instance().has(&key) {
+            panic!("Invitation already exists");
+        }
+        let invitation = Invitation {
+            code_hash,
+            expires_at,
+            used_by: None,
+        };
+        env.storage().instance().set(&key, &invitation);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Invitation minted");
+    }
+
+    /// Register a new brand by presenting an unused, unexpired invitation code
+    /// Returns the brand_id of the new brand
+    /// Use `register_brand_v2` while a deposit is also required
+    pub fn register_brand_with_invitation(env: Env, brand_name: String, code: Bytes) -> u64 {
+        if Self::view_deposit_requirement(env.clone()).is_some() {
+            panic!("Brand deposit required");
+        }
+        let brand_id = create_brand(&env, brand_name);
+        redeem_invitation(&env, brand_id, &code);
+        brand_id
+    }
+
+    /// View an invitation by the hash of its code
+    pub fn view_invitation(env: Env, code_hash: BytesN<32>) -> Option<Invitation> {
+        env.storage()
+            .instance()
+            .get(&InvitationBook::Invitation(code_hash))
+    }
+
+    /// View the hash of the invitation code a brand registered with, if any
+    pub fn view_brand_invitation(env: Env, brand_id: u64) -> Option<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&InvitationBook::BrandCode(brand_id))
+    }
+}
+
+// Reject registration paths that skip the invitation while it is required
+pub(crate) fn require_open_registration(env: &Env) {
+    if LoyaltyTokenExchange::is_invite_only(env.clone()) {
+        panic!("Invitation required");
+    }
+}
+
+// Apply the registration gates to a brand just created: redeem `code` or
+// require open registration, and collect the deposit from `payer` while one
+// is required
+pub(crate) fn admit_brand(env: &Env, brand_id: u64, payer: &Address, code: Option<Bytes>) {
+    match code {
+        Some(code) => redeem_invitation(env, brand_id, &code),
+        None => require_open_registration(env),
+    }
+    if LoyaltyTokenExchange::view_deposit_requirement(env.clone()).is_some() {
+        post_deposit(env, brand_id, payer);
+    }
+}
+
+// Mark an unused, unexpired invitation as used by a new brand
+fn redeem_invitation(env: &Env, brand_id: u64, code: &Bytes) {
+    let code_hash: BytesN<32> = env.crypto().sha256(code).into();
+    let mut invitation = LoyaltyTokenExchange::view_invitation(env.clone(), code_hash.clone())
+        .unwrap_or_else(|| panic!("Invalid invitation"));
+    if invitation.used_by.is_some() {
+        panic!("Invitation already used");
+    }
+    if env.ledger().sequence() > invitation.expires_at {
+        panic!("Invitation expired");
+    }
+
+    invitation.used_by = Some(brand_id);
+    env.storage()
+        .instance()
+        .set(&InvitationBook::Invitation(code_hash.clone()), &invitation);
+    env.storage()
+        .instance()
+        .set(&InvitationBook::BrandCode(brand_id), &code_hash);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Brand {} registered by invitation", brand_id);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        token::{StellarAssetClient, TokenClient},
+        Address, Bytes, BytesN, Env, String,
+    };
+
+    #[test]
+    fn test_invitation_codes_are_single_use_and_expire() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+        client.set_invite_only(&true);
+        assert!(client
+            .try_register_brand(&String::from_str(&env, "Cafe"))
+            .is_err());
+
+        let code = Bytes::from_slice(&env, b"welcome-cafe");
+        let late = Bytes::from_slice(&env, b"welcome-gym");
+        let code_hash: BytesN<32> = env.crypto().sha256(&code).into();
+        client.mint_invitation(&code_hash, &100);
+        client.mint_invitation(&env.crypto().sha256(&late).into(), &100);
+
+        let name = String::from_str(&env, "Cafe");
+        let cafe = client.register_brand_with_invitation(&name, &code);
+        assert_eq!(client.view_brand_invitation(&cafe), Some(code_hash.clone()));
+        assert_eq!(
+            client.view_invitation(&code_hash).unwrap().used_by,
+            Some(cafe)
+        );
+        assert!(client
+            .try_register_brand_with_invitation(&name, &code)
+            .is_err());
+
+        env.ledger().set_sequence_number(101);
+        assert!(client
+            .try_register_brand_with_invitation(&String::from_str(&env, "Gym"), &late)
+            .is_err());
+    }
+
+    #[test]
+    fn test_every_registration_path_needs_invitation_and_deposit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let payer = Address::generate(&env);
+        let brand_admin = Address::generate(&env);
+        let program_admin = Address::generate(&env);
+        for funded in [&payer, &brand_admin, &program_admin] {
+            StellarAssetClient::new(&env, &token).mint(funded, &1000);
+        }
+        client.set_deposit_requirement(&token, &1000);
+        client.set_invite_only(&true);
+
+        let mut codes = [b"code-1", b"code-2", b"code-3"]
+            .map(|code| Bytes::from_slice(&env, code))
+            .into_iter();
+        let mut next_code = || {
+            let code = codes.next().unwrap();
+            client.mint_invitation(&env.crypto().sha256(&code).into(), &100);
+            code
+        };
+
+        // Neither gate alone is enough
+        let name = String::from_str(&env, "Cafe");
+        let code = next_code();
+        assert!(client
+            .try_register_brand_with_invitation(&name, &code)
+            .is_err());
+        assert!(client
+            .try_register_brand_with_deposit(&payer, &name)
+            .is_err());
+        let cafe = client.register_brand_v2(&payer, &name, &Some(code));
+        assert_eq!(client.view_brand_deposit(&cafe).unwrap().depositor, payer);
+        client.set_brand_admin(&cafe, &brand_admin);
+
+        // Sub-brands pass the same gates, paid by the parent's admin
+        let name = String::from_str(&env, "Cafe East");
+        assert!(client.try_create_sub_brand(&cafe, &name).is_err());
+        let east = client.create_sub_brand_v2(&cafe, &name, &Some(next_code()));
+        assert_eq!(
+            client.view_brand_deposit(&east).unwrap().depositor,
+            brand_admin
+        );
+
+        // So do program brands, paid by the program admin
+        let program_id = client.create_program(&String::from_str(&env, "Mall"), &program_admin);
+        let name = String::from_str(&env, "Kiosk");
+        assert!(client
+            .try_register_program_brand(&program_id, &name)
+            .is_err());
+        let kiosk = client.register_program_brand_v2(&program_id, &name, &Some(next_code()));
+        assert_eq!(
+            client.view_brand_deposit(&kiosk).unwrap().depositor,
+            program_admin
+        );
+
+        let balances = TokenClient::new(&env, &token);
+        assert_eq!(balances.balance(&payer), 0);
+        assert_eq!(balances.balance(&brand_admin), 0);
+        assert_eq!(balances.balance(&program_admin), 0);
+    }
+}
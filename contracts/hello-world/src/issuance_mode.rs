@@ -0,0 +1,91 @@
+use crate::*;
+
+// Who has to authorize an issuance of a brand's points
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IssuanceMode {
+    // Either side may issue on its own
+    Unilateral,
+    // Both the brand side and the receiving user must authorize
+    CoSigned,
+}
+
+// Mapping for issuance modes: Brand -> Mode, Unilateral when unset
+#[contracttype]
+pub enum IssuanceModeBook {
+    Mode(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Choose whether issuances of a brand need the user's co-signature
+    /// (brand admin only)
+    pub fn set_issuance_mode(env: Env, brand_id: u64, mode: IssuanceMode) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .set(&IssuanceModeBook::Mode(brand_id), &mode);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Issuance mode set for brand {}", brand_id);
+    }
+
+    /// View the issuance mode of a brand
+    pub fn view_issuance_mode(env: Env, brand_id: u64) -> IssuanceMode {
+        env.storage()
+            .instance()
+            .get(&IssuanceModeBook::Mode(brand_id))
+            .unwrap_or(IssuanceMode::Unilateral)
+    }
+}
+
+// Require the co-signature missing from an issuance in CoSigned mode: the
+// user's when an issuer acts, the brand admin's when the user does
+pub(crate) fn require_cosigner(env: &Env, issuer: Option<&Address>, user: &Address, brand_id: u64) {
+    if LoyaltyTokenExchange::view_issuance_mode(env.clone(), brand_id) != IssuanceMode::CoSigned {
+        return;
+    }
+    match issuer {
+        Some(issuer) if issuer != user => require_user(env, user),
+        Some(_) => {}
+        None => {
+            require_brand_admin(env, brand_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_cosigned_issuance_needs_both_sides() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let admin = Address::generate(&env);
+        client.set_brand_admin(&brand_id, &admin);
+        let issuer = Address::generate(&env);
+        client.add_issuer(&brand_id, &issuer);
+        let user = Address::generate(&env);
+
+        client.issue_as(&issuer, &user, &brand_id, &10, &None);
+        assert!(!env.auths().iter().any(|(address, _)| *address == user));
+
+        client.set_issuance_mode(&brand_id, &IssuanceMode::CoSigned);
+        client.issue_as(&issuer, &user, &brand_id, &10, &None);
+        assert!(env.auths().iter().any(|(address, _)| *address == user));
+        assert!(env.auths().iter().any(|(address, _)| *address == issuer));
+
+        client.issue_tokens(&user, &brand_id, &10, &None);
+        assert!(env.auths().iter().any(|(address, _)| *address == admin));
+        assert_eq!(client.view_user_balance(&user, &brand_id), 30);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store an address allowed to issue a brand's points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IssuerRecord {
+    pub brand_id: u64,
+    pub issuer: Address,
+    pub added_at: u32,
+    // Set once the issuer is rotated out; revoked issuers cannot be re-added
+    pub revoked_at: Option<u32>,
+}
+
+// Mapping for brand issuers and the issuances attributed to them
+// Issuance attributions live in persistent storage, one entry per issuance
+#[contracttype]
+pub enum IssuerBook {
+    Issuer(u64, Address),
+    // (Issuer, Brand, index) -> op_id of the issuer's index-th issuance
+    Issuance(Address, u64, u32),
+    // (Issuer, Brand) -> Number of issuances attributed to the issuer
+    IssuanceCount(Address, u64),
+    // Brand -> Set once the brand delegates issuing to issuers, API keys or
+    // franchises; direct issuance then needs the brand admin
+    Delegated(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Authorize an issuer key for a brand (brand admin only)
+    pub fn add_issuer(env: Env, brand_id: u64, issuer: Address) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = IssuerBook::Issuer(brand_id, issuer.clone());
+        if let Some(existing) = env.storage().instance().get::<_, IssuerRecord>(&key) {
+            if existing.revoked_at.is_some() {
+                panic!("Issuer was revoked");
+            }
+            panic!("Issuer already added");
+        }
+
+        let record = IssuerRecord {
+            brand_id,
+            issuer,
+            added_at: env.ledger().sequence(),
+            revoked_at: None,
+        };
+        env.storage().instance().set(&key, &record);
+        delegate_issuance(&env, brand_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Issuer added for brand {}", brand_id);
+    }
+
+    /// Revoke an issuer key (brand admin only); its past issuances stay attributed to it
+    pub fn revoke_issuer(env: Env, brand_id: u64, issuer: Address) {
+        require_brand_admin(&env, brand_id);
+
+        let mut record = Self::view_issuer(env.clone(), brand_id, issuer.clone())
+            .unwrap_or_else(|| panic!("Issuer not found"));
+        if record.revoked_at.is_some() {
+            panic!("Issuer already revoked");
+        }
+        record.revoked_at = Some(env.ledger().sequence());
+        env.storage()
+            .instance()
+            .set(&IssuerBook::Issuer(brand_id, issuer), &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Issuer revoked for brand {}", brand_id);
+    }
+
+    /// Issue points to a user as one of the brand's issuers
+    /// Returns the op_id of the recorded issuance
+    pub fn issue_as(
+        env: Env,
+        issuer: Address,
+        user: Address,
+        brand_id: u64,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        issuer.require_auth();
+
+        let record = Self::view_issuer(env.clone(), brand_id, issuer.clone())
+            .unwrap_or_else(|| panic!("Not an issuer of this brand"));
+        if record.revoked_at.is_some() {
+            panic!("Issuer was revoked");
+        }
+        if let Err(failure) = check_reference(&env, brand_id, &memo) {
+            failure.fail();
+        }
+        let amount = experiment_amount(&env, &user, brand_id, amount);
+        issue(&env, Some(issuer), user, brand_id, amount, memo)
+    }
+
+    /// View an issuer of a brand, including revoked ones
+    pub fn view_issuer(env: Env, brand_id: u64, issuer: Address) -> Option<IssuerRecord> {
+        env.storage()
+            .instance()
+            .get(&IssuerBook::Issuer(brand_id, issuer))
+    }
+
+    /// View a page of the issuances an issuer performed for a brand, oldest first
+    /// Readable by the issuer, the brand admin and auditors
+    pub fn list_issuances_by(
+        env: Env,
+        caller: Address,
+        issuer: Address,
+        brand_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<OperationRecord> {
+        caller.require_auth();
+        if caller != issuer
+            && brand_admin(&env, brand_id).as_ref() != Some(&caller)
+            && !Self::is_auditor(env.clone(), caller.clone())
+        {
+            panic!("Not authorized to view issuances");
+        }
+
+        let mut records = Vec::new(&env);
+        let end = issuance_count(&env, &issuer, brand_id).min(start.saturating_add(limit));
+        for index in start..end {
+            let op_id: u64 = env
+                .storage()
+                .persistent()
+                .get(&IssuerBook::Issuance(issuer.clone(), brand_id, index))
+                .unwrap();
+            records.push_back(load_operation(&env, op_id));
+        }
+        records
+    }
+
+    /// Whether a brand delegates issuing to issuers, API keys or franchises,
+    /// so that `issue_tokens` needs the brand admin's authorization
+    pub fn is_issuance_delegated(env: Env, brand_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .has(&IssuerBook::Delegated(brand_id))
+    }
+}
+
+// Attribute an issuance to the issuer that performed it
+pub(crate) fn index_issuance(env: &Env, issuer: &Address, brand_id: u64, op_id: u64) {
+    let count = issuance_count(env, issuer, brand_id);
+    let key = IssuerBook::Issuance(issuer.clone(), brand_id, count);
+    env.storage().persistent().set(&key, &op_id);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+
+    let key = IssuerBook::IssuanceCount(issuer.clone(), brand_id);
+    env.storage().persistent().set(&key, &(count + 1));
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+}
+
+fn issuance_count(env: &Env, issuer: &Address, brand_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&IssuerBook::IssuanceCount(issuer.clone(), brand_id))
+        .unwrap_or(0)
+}
+
+// Mark a brand as issuing through delegated keys from now on
+pub(crate) fn delegate_issuance(env: &Env, brand_id: u64) {
+    env.storage()
+        .instance()
+        .set(&IssuerBook::Delegated(brand_id), &true);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_rotated_issuer_keeps_attribution() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let admin = Address::generate(&env);
+        client.set_brand_admin(&brand_id, &admin);
+        let old_key = Address::generate(&env);
+        let new_key = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        assert!(client
+            .try_issue_as(&old_key, &user, &brand_id, &10, &None)
+            .is_err());
+        client.add_issuer(&brand_id, &old_key);
+        client.issue_as(&old_key, &user, &brand_id, &100, &None);
+        client.issue_as(&old_key, &user, &brand_id, &50, &None);
+
+        // Rotate: the new key takes over and the old one is blocked
+        client.add_issuer(&brand_id, &new_key);
+        client.revoke_issuer(&brand_id, &old_key);
+        assert!(client
+            .try_issue_as(&old_key, &user, &brand_id, &10, &None)
+            .is_err());
+        assert!(client.try_add_issuer(&brand_id, &old_key).is_err());
+
+        // Nor can it issue to itself directly: that now takes the brand admin
+        assert!(client.is_issuance_delegated(&brand_id));
+        client.issue_tokens(&old_key, &brand_id, &10, &None);
+        assert!(env.auths().iter().any(|(address, _)| *address == admin));
+        let op_id = client.issue_as(&new_key, &user, &brand_id, &25, &None);
+        assert_eq!(
+            client.view_operation(&new_key, &op_id).issuer,
+            Some(new_key.clone())
+        );
+
+        let old_issuances = client.list_issuances_by(&old_key, &old_key, &brand_id, &0, &10);
+        assert_eq!(old_issuances.len(), 2);
+        assert_eq!(old_issuances.get(1).unwrap().amount, 50);
+        assert_eq!(
+            client
+                .list_issuances_by(&old_key, &old_key, &brand_id, &1, &10)
+                .len(),
+            1
+        );
+        assert_eq!(
+            client
+                .list_issuances_by(&new_key, &new_key, &brand_id, &0, &10)
+                .len(),
+            1
+        );
+        assert_eq!(client.view_user_balance(&user, &brand_id), 175);
+        assert_eq!(client.view_user_balance(&old_key, &brand_id), 10);
+    }
+}
@@ -0,0 +1,319 @@
+use crate::*;
+
+// Balance changes are posted as double entries: every posting debits one
+// account and credits another by the same amount. Points enter circulation
+// from a brand's Mint account and leave it into its Burn account. Points a
+// feature holds on a user's behalf (bids, collateral, funded pools) sit in
+// the brand's Escrow account: out of circulation but still owed. The brand's
+// supply is always minted minus burned minus escrowed and the sum of user
+// balances must equal it
+
+// Account a posting moves a brand's points between
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LedgerAccount {
+    User(Address),
+    // Source of newly issued points
+    Mint,
+    // Sink of points taken out of circulation
+    Burn,
+    // Points held by the contract until they are paid out or spent
+    Escrow,
+}
+
+// Structure to store the running totals of a brand's postings
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LedgerTotals {
+    pub entries: u64,
+    // Total debited from the Mint account
+    pub minted: i128,
+    // Total credited to the Burn account
+    pub burned: i128,
+}
+
+// Mapping for ledger totals
+#[contracttype]
+pub enum LedgerBook {
+    // Brand -> Totals
+    Totals(u64),
+    // Brand -> Balance of the Escrow account
+    Escrowed(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View the running totals of a brand's ledger postings
+    pub fn view_ledger_totals(env: Env, brand_id: u64) -> LedgerTotals {
+        load_totals(&env, brand_id)
+    }
+
+    /// View the balance of a ledger account for a brand
+    /// The Mint account is negative by what it has issued
+    pub fn view_ledger_balance(env: Env, brand_id: u64, account: LedgerAccount) -> i128 {
+        match account {
+            LedgerAccount::User(user) => stored_balance(&env, &user, brand_id),
+            LedgerAccount::Mint => -load_totals(&env, brand_id).minted,
+            LedgerAccount::Burn => load_totals(&env, brand_id).burned,
+            LedgerAccount::Escrow => escrowed(&env, brand_id),
+        }
+    }
+
+    /// View what a brand owes its users: the points they hold plus the
+    /// points held in escrow for them
+    pub fn view_brand_liabilities(env: Env, brand_id: u64) -> i128 {
+        brand_supply(&env, brand_id)
+            .saturating_add(Points::from(escrowed(&env, brand_id)))
+            .into()
+    }
+
+    /// Whether a brand's stored supply equals minted minus burned
+    pub fn reconcile_ledger(env: Env, brand_id: u64) -> bool {
+        ledger_supply(&env, brand_id) == brand_supply(&env, brand_id)
+    }
+}
+
+// Debit `from` and credit `to` by `amount` of a brand's points
+pub(crate) fn post(
+    env: &Env,
+    brand_id: u64,
+    from: &LedgerAccount,
+    to: &LedgerAccount,
+    amount: i128,
+) {
+    if from == to {
+        panic!("Cannot post to the same account");
+    }
+
+    if *from == LedgerAccount::Burn {
+        panic!("Burn account cannot be debited");
+    }
+    if *to == LedgerAccount::Mint {
+        panic!("Mint account cannot be credited");
+    }
+
+    // Every spend of a user's points counts against the brand's velocity
+    // rules, except spends from the brand's own treasury
+    if let LedgerAccount::User(user) = from {
+        if LoyaltyTokenExchange::view_brand_treasury(env.clone(), brand_id).as_ref() != Some(user) {
+            let counterparty = match to {
+                LedgerAccount::User(counterparty) => Some(counterparty),
+                _ => None,
+            };
+            check_velocity(env, user, brand_id, counterparty);
+        }
+    }
+
+    // User sides first: settling decay on them may post burns of its own
+    if let LedgerAccount::User(user) = from {
+        debit_user(env, user, brand_id, amount);
+    }
+    if let LedgerAccount::User(user) = to {
+        credit_user(env, user, brand_id, amount);
+    }
+
+    // Circulating supply is what user accounts hold
+    let mut totals = load_totals(env, brand_id);
+    match (from, to) {
+        (LedgerAccount::User(_), LedgerAccount::User(_)) => {}
+        (LedgerAccount::User(_), _) => adjust_supply(env, brand_id, Points::from(-amount)),
+        (_, LedgerAccount::User(_)) => adjust_supply(env, brand_id, Points::from(amount)),
+        _ => {}
+    }
+    if *from == LedgerAccount::Mint {
+        totals.minted += amount;
+    }
+    if *to == LedgerAccount::Burn {
+        totals.burned += amount;
+    }
+    if *from == LedgerAccount::Escrow {
+        let held = escrowed(env, brand_id);
+        if held < amount {
+            panic!("Insufficient escrow");
+        }
+        set_escrowed(env, brand_id, held - amount);
+    }
+    if *to == LedgerAccount::Escrow {
+        set_escrowed(env, brand_id, escrowed(env, brand_id) + amount);
+    }
+    totals.entries += 1;
+    save_totals(env, brand_id, &totals);
+
+    #[cfg(feature = "debug-assertions")]
+    crate::invariants::check_brand(env, brand_id);
+}
+
+// Take points out of circulation whose balance was already written, e.g.
+// decayed points
+pub(crate) fn post_burn(env: &Env, brand_id: u64, amount: i128) {
+    let mut totals = load_totals(env, brand_id);
+    adjust_supply(env, brand_id, Points::from(-amount));
+    totals.burned += amount;
+    totals.entries += 1;
+    save_totals(env, brand_id, &totals);
+}
+
+// Supply implied by the ledger
+pub(crate) fn ledger_supply(env: &Env, brand_id: u64) -> Points {
+    let totals = load_totals(env, brand_id);
+    Points::from(totals.minted)
+        .saturating_sub(Points::from(totals.burned))
+        .saturating_sub(Points::from(escrowed(env, brand_id)))
+}
+
+pub(crate) fn escrowed(env: &Env, brand_id: u64) -> i128 {
+    env.storage()
+        .instance()
+        .get(&LedgerBook::Escrowed(brand_id))
+        .unwrap_or(0)
+}
+
+fn set_escrowed(env: &Env, brand_id: u64, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&LedgerBook::Escrowed(brand_id), &amount);
+}
+
+fn credit_user(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    #[cfg(feature = "debug-assertions")]
+    crate::invariants::note_holder(env, user, brand_id);
+
+    settle_decay(env, user, brand_id);
+    let balance = Points::from(read_balance(env, user, brand_id))
+        .checked_add(Points::from(amount))
+        .unwrap_or_else(|| panic!("Amount out of range"));
+    write_balance(env, user, brand_id, balance.into());
+    track_votes(env, user, brand_id, amount);
+}
+
+fn debit_user(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    #[cfg(feature = "debug-assertions")]
+    crate::invariants::note_holder(env, user, brand_id);
+
+    settle_decay(env, user, brand_id);
+    let balance = Points::from(read_balance(env, user, brand_id));
+    let remaining = balance
+        .checked_sub(Points::from(amount))
+        .filter(|remaining| *remaining >= Points::ZERO)
+        .unwrap_or_else(|| panic!("Insufficient balance"));
+    write_balance(env, user, brand_id, remaining.into());
+    track_votes(env, user, brand_id, -amount);
+}
+
+// Brands with supply from before the ledger open it as already minted
+fn load_totals(env: &Env, brand_id: u64) -> LedgerTotals {
+    env.storage()
+        .instance()
+        .get(&LedgerBook::Totals(brand_id))
+        .unwrap_or_else(|| LedgerTotals {
+            entries: 0,
+            minted: brand_supply(env, brand_id).into(),
+            burned: 0,
+        })
+}
+
+fn save_totals(env: &Env, brand_id: u64, totals: &LedgerTotals) {
+    env.storage()
+        .instance()
+        .set(&LedgerBook::Totals(brand_id), totals);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    #[test]
+    fn test_postings_reconcile_with_supply() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cinema"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Popcorn"), &30, &1);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        client.issue_tokens(&alice, &brand_id, &100, &None);
+        client.transfer_tokens(&alice, &bob, &brand_id, &40, &None);
+        client.redeem_reward(&bob, &reward_id, &None);
+
+        let totals = client.view_ledger_totals(&brand_id);
+        assert_eq!(totals.entries, 3);
+        assert_eq!(totals.minted, 100);
+        assert_eq!(totals.burned, 30);
+        assert_eq!(
+            client.view_ledger_balance(&brand_id, &LedgerAccount::Mint),
+            -100
+        );
+        assert_eq!(
+            client.view_ledger_balance(&brand_id, &LedgerAccount::User(bob)),
+            10
+        );
+        assert!(client.reconcile_ledger(&brand_id));
+    }
+
+    #[test]
+    fn test_swaps_and_recoveries_move_points_between_users() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.issue_tokens(&alice, &hotel, &500, &None);
+        client.issue_tokens(&bob, &airline, &500, &None);
+        let before = (
+            client.view_ledger_totals(&hotel),
+            client.view_ledger_totals(&airline),
+        );
+
+        let intent = |user: &Address, from_brand, to_brand| ExchangeIntent {
+            user: user.clone(),
+            from_brand,
+            to_brand,
+            amount: 200,
+            min_out: 200,
+            nonce: 0,
+            deadline: 100,
+        };
+        client.settle_intents(
+            &Address::generate(&env),
+            &intent(&alice, hotel, airline),
+            &intent(&bob, airline, hotel),
+        );
+
+        let guardian = Address::generate(&env);
+        let new_address = Address::generate(&env);
+        client.set_guardians(&alice, &vec![&env, guardian.clone()], &1);
+        client.initiate_recovery(&guardian, &alice, &new_address);
+        env.ledger().set_sequence_number(RECOVERY_DELAY_LEDGERS);
+        client.execute_recovery(&alice);
+        assert_eq!(client.view_user_balance(&new_address, &hotel), 300);
+        assert_eq!(client.view_user_balance(&new_address, &airline), 200);
+
+        let after = (
+            client.view_ledger_totals(&hotel),
+            client.view_ledger_totals(&airline),
+        );
+        for (before, after) in [(before.0, after.0), (before.1, after.1)] {
+            assert_eq!((after.minted, after.burned), (before.minted, before.burned));
+        }
+        assert_eq!(
+            client.view_ledger_balance(&hotel, &LedgerAccount::Mint),
+            -500
+        );
+        assert_eq!(client.view_ledger_balance(&hotel, &LedgerAccount::Burn), 0);
+        assert!(client.reconcile_ledger(&hotel));
+        assert!(client.reconcile_ledger(&airline));
+    }
+}
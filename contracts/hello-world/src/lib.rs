@@ -1,11 +1,199 @@
 #![no_std]
+// Versioned entry points take more arguments than clippy allows, and the
+// generated argument helpers do not carry a function-level allow
+#![allow(clippy::too_many_arguments)]
+mod access;
+mod account_closure;
+mod address_book;
+mod admin_log;
+mod analytics;
+mod api_keys;
+mod attestation;
+mod auction;
+mod balances;
+mod basket;
+mod batch_exchange;
+mod blackouts;
+mod bonding_curve;
+mod brand_aliases;
+mod brand_export;
+mod brand_names;
+mod brand_quota;
+mod bridge;
+mod burn;
+mod charity;
+mod circuit_breaker;
+mod claimable;
+mod coalition;
+mod commit_reveal;
+mod consent;
+mod cost_estimate;
+mod decay;
+mod deposits;
+mod disputes;
+mod epoch_commitments;
+mod event_verbosity;
+mod events;
+mod experiments;
+mod fee_discounts;
+mod franchises;
+mod fulfillment;
+mod gifts;
+mod history;
+mod holder_enrollment;
+mod intents;
+mod interfaces;
+#[cfg(feature = "debug-assertions")]
+mod invariants;
+mod invitations;
+mod issuance_mode;
+mod issuers;
+mod ledger;
+mod listing_stake;
+mod loans;
+mod maintenance;
+mod memo_policy;
+mod merge;
+mod multi_hop;
+mod notifier;
+mod params;
+mod partner_airdrop;
+mod partner_burn;
+mod pending_redemption;
+mod platform_config;
+mod points;
+mod programs;
+mod promotions;
+mod quick_pay;
+mod rate_bounds;
+mod rate_history;
+mod reason_codes;
+mod receiver_whitelist;
+mod reciprocal_rates;
+mod recovery;
+mod reference_value;
+mod regret;
+#[cfg(test)]
+mod replay;
+mod reputation;
+mod reservation;
+mod revenue_share;
+mod reward_drops;
+mod rewards;
+mod route_activity;
+mod routes;
+mod sequencing;
+mod settlement;
+mod snapshots;
+mod standing_order;
+mod sub_brand;
+mod subscription;
+mod tax_withholding;
+mod treasury;
+mod validation;
+mod velocity;
+mod voting;
+mod welcome_bonus;
+mod wind_down;
+
+pub use access::*;
+pub use account_closure::*;
+pub use address_book::*;
+pub use admin_log::*;
+pub use analytics::*;
+pub use api_keys::*;
+pub use attestation::*;
+pub use auction::*;
+pub use balances::*;
+pub use basket::*;
+pub use batch_exchange::*;
+pub use blackouts::*;
+pub use bonding_curve::*;
+pub use brand_aliases::*;
+pub use brand_export::*;
+pub use brand_names::*;
+pub use brand_quota::*;
+pub use bridge::*;
+pub use burn::*;
+pub use charity::*;
+pub use circuit_breaker::*;
+pub use claimable::*;
+pub use coalition::*;
+pub use commit_reveal::*;
+pub use consent::*;
+pub use cost_estimate::*;
+pub use decay::*;
+pub use deposits::*;
+pub use disputes::*;
+pub use epoch_commitments::*;
+pub use event_verbosity::*;
+pub use events::*;
+pub use experiments::*;
+pub use fee_discounts::*;
+pub use franchises::*;
+pub use fulfillment::*;
+pub use gifts::*;
+pub use history::*;
+pub use holder_enrollment::*;
+pub use intents::*;
+pub use interfaces::*;
+pub use invitations::*;
+pub use issuance_mode::*;
+pub use issuers::*;
+pub use ledger::*;
+pub use listing_stake::*;
+pub use loans::*;
+pub use maintenance::*;
+pub use memo_policy::*;
+pub use merge::*;
+pub use multi_hop::*;
+pub use notifier::*;
+pub use params::*;
+pub use partner_airdrop::*;
+pub use partner_burn::*;
+pub use pending_redemption::*;
+pub use platform_config::*;
+pub use points::*;
+pub use programs::*;
+pub use promotions::*;
+pub use quick_pay::*;
+pub use rate_bounds::*;
+pub use rate_history::*;
+pub use reason_codes::*;
+pub use receiver_whitelist::*;
+pub use reciprocal_rates::*;
+pub use recovery::*;
+pub use reference_value::*;
+pub use regret::*;
+pub use reputation::*;
+pub use reservation::*;
+pub use revenue_share::*;
+pub use reward_drops::*;
+pub use rewards::*;
+pub use route_activity::*;
+pub use routes::*;
+pub use sequencing::*;
+pub use settlement::*;
+pub use snapshots::*;
+pub use standing_order::*;
+pub use sub_brand::*;
+pub use subscription::*;
+pub use tax_withholding::*;
+pub use treasury::*;
+pub use validation::*;
+pub use velocity::*;
+pub use voting::*;
+pub use welcome_bonus::*;
+pub use wind_down::*;
+
 use soroban_sdk::{
-    contract, contractimpl, contracttype, log, symbol_short, Address, Env, String, Symbol,
+    contract, contractimpl, contracttype, log, symbol_short, Address, Bytes, BytesN, Env, String,
+    Symbol,
 };
 
 // Structure to store brand information
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Brand {
     pub brand_id: u64,
     pub brand_name: String,
@@ -21,10 +209,43 @@ pub enum BrandBook {
 // Counter for brands
 const BRAND_COUNT: Symbol = symbol_short!("B_COUNT");
 
-// Mapping for user balances: (User, Brand) -> Balance
+// Platform administrator address
+const PLATFORM_ADMIN: Symbol = symbol_short!("P_ADMIN");
+
+// Denominator for rates expressed in basis points (10_000 = 1:1)
+pub const RATE_DENOMINATOR: i128 = 10_000;
+
+// Mapping for user balances
 #[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum UserBalance {
+    // (User, Brand) -> Balance; legacy layout in instance storage
     Balance(Address, u64),
+    // User -> (Brand -> Balance) as i64; persistent layout before i128 amounts
+    Balances(Address),
+    // User -> Last ledger the user's balance entry is known to be live until;
+    // persistent, kept in instance storage by earlier versions
+    LiveUntil(Address),
+    // User -> (Brand -> Balance) as i128; current layout in persistent storage
+    WideBalances(Address),
+    // User -> Last ledger the user's balance entry was written; persistent,
+    // kept in instance storage by earlier versions
+    LastModified(Address),
+}
+
+// Mapping for circulating supply: Brand -> Total balances held by users
+#[contracttype]
+pub enum BrandSupply {
+    // Supply as i64, before i128 amounts
+    Supply(u64),
+    // Supply as i128; current layout
+    WideSupply(u64),
+}
+
+// Mapping for brand administrators: Brand -> Admin
+#[contracttype]
+pub enum BrandAdmin {
+    Admin(u64),
 }
 
 #[contract]
@@ -35,104 +256,198 @@ impl LoyaltyTokenExchange {
     /// Register a new brand in the exchange platform
     /// Returns the brand_id of the newly registered brand
     pub fn register_brand(env: Env, brand_name: String) -> u64 {
-        // Get current brand count or start from 0
-        let mut brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
-        brand_count += 1;
-
-        // Create new brand instance
-        let new_brand = Brand {
-            brand_id: brand_count,
-            brand_name: brand_name.clone(),
-            is_active: true,
-        };
-
-        // Store the brand
-        env.storage()
-            .instance()
-            .set(&BrandBook::Brand(brand_count), &new_brand);
-        env.storage().instance().set(&BRAND_COUNT, &brand_count);
-        env.storage().instance().extend_ttl(100000, 100000);
+        require_open_registration(&env);
+        if Self::view_deposit_requirement(env.clone()).is_some() {
+            panic!("Brand deposit required");
+        }
+        create_brand(&env, brand_name)
+    }
 
-        log!(&env, "✅ Brand registered with ID: {}", brand_count);
-        brand_count
+    /// Register a new brand through every registration gate in force: `code`
+    /// redeems an invitation while registration is invite-only, and `payer`
+    /// posts the deposit while one is required
+    /// Returns the brand_id of the new brand
+    pub fn register_brand_v2(
+        env: Env,
+        payer: Address,
+        brand_name: String,
+        code: Option<Bytes>,
+    ) -> u64 {
+        payer.require_auth();
+        let brand_id = create_brand(&env, brand_name);
+        admit_brand(&env, brand_id, &payer, code);
+        brand_id
     }
 
     /// Issue loyalty tokens to a user from a specific brand
-    pub fn issue_tokens(env: Env, user: Address, brand_id: u64, amount: i64) {
-        user.require_auth();
+    /// Once the brand delegates issuing to issuers, API keys or franchises,
+    /// this also needs the brand admin's authorization
+    /// `memo` carries an optional external reference such as a POS order id
+    /// Returns the op_id of the recorded operation
+    pub fn issue_tokens(
+        env: Env,
+        user: Address,
+        brand_id: u64,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        require_user(&env, &user);
+        if Self::is_issuance_delegated(env.clone(), brand_id) {
+            require_brand_admin(&env, brand_id);
+        } else {
+            require_cosigner(&env, None, &user, brand_id);
+        }
+        if let Err(failure) = check_reference(&env, brand_id, &memo) {
+            failure.fail();
+        }
+        let amount = experiment_amount(&env, &user, brand_id, amount);
+        issue(&env, None, user, brand_id, amount, memo)
+    }
+
+    /// Transfer tokens of a brand from one user to another
+    /// Deprecated: forwards to `transfer_tokens_v2` with no sequence number
+    pub fn transfer_tokens(
+        env: Env,
+        from: Address,
+        to: Address,
+        brand_id: u64,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        Self::transfer_tokens_v2(env, from, to, brand_id, amount, memo, None)
+    }
 
-        // Verify brand exists and is active
-        let brand = Self::view_brand(env.clone(), brand_id);
+    /// Transfer tokens of a brand from one user to another
+    /// `sequence`, if given, must equal the sender's next sequence number
+    /// Returns the op_id of the recorded operation
+    pub fn transfer_tokens_v2(
+        env: Env,
+        from: Address,
+        to: Address,
+        brand_id: u64,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+        sequence: Option<u64>,
+    ) -> u64 {
+        from.require_auth();
+        consume_sequence(&env, &from, sequence);
+
+        let brand = load_brand(&env, brand_id);
         if !brand.is_active {
             panic!("Brand is not active");
         }
         if amount <= 0 {
             panic!("Amount must be positive");
         }
+        if from == to {
+            panic!("Cannot transfer to yourself");
+        }
+        if !Self::can_receive(env.clone(), brand_id, to.clone()) {
+            panic!("Receiver is not whitelisted");
+        }
+        post(
+            &env,
+            brand_id,
+            &LedgerAccount::User(from.clone()),
+            &LedgerAccount::User(to.clone()),
+            amount,
+        );
 
-        // Update user balance
-        let balance_key = UserBalance::Balance(user.clone(), brand_id);
-        let current_balance: i64 = env.storage().instance().get(&balance_key).unwrap_or(0);
-        let new_balance = current_balance + amount;
-        env.storage().instance().set(&balance_key, &new_balance);
+        let mut record =
+            OperationRecord::new(&env, OperationKind::Transfer, &from, brand_id, amount);
+        record.counterparty = Some(to.clone());
+        record.memo = memo.clone();
+        save_operation(&env, &record);
         env.storage().instance().extend_ttl(100000, 100000);
 
+        emit_operation(&env, brand_id, &from, record.op_id, || {
+            TokensTransferred {
+                brand_id,
+                from: from.clone(),
+                to,
+                amount,
+                op_id: record.op_id,
+                memo,
+            }
+            .publish(&env)
+        });
+
         log!(
             &env,
-            "✅ Issued {} tokens from brand {} to user",
+            "✅ Transferred {} tokens of brand {}",
             amount,
             brand_id
         );
+        record.op_id
     }
 
-    /// Exchange tokens between two brands (1:1 ratio)
-    pub fn exchange_tokens(env: Env, user: Address, from_brand: u64, to_brand: u64, amount: i64) {
-        user.require_auth();
+    /// Exchange tokens between two brands as priced by `quote_exchange`
+    /// Deprecated: forwards to `exchange_tokens_v3` with no minimum output,
+    /// no deadline, no memo and no sequence number
+    pub fn exchange_tokens(env: Env, user: Address, from_brand: u64, to_brand: u64, amount: i128) {
+        Self::exchange_tokens_v3(
+            env,
+            user,
+            from_brand,
+            to_brand,
+            amount,
+            0,
+            u32::MAX,
+            None,
+            None,
+        );
+    }
 
-        if amount <= 0 {
-            panic!("Amount must be positive");
-        }
-        if from_brand == to_brand {
-            panic!("Cannot exchange to the same brand");
-        }
+    /// Exchange tokens between two brands as priced by `quote_exchange`
+    /// Deprecated: forwards to `exchange_tokens_v3` with no sequence number
+    pub fn exchange_tokens_v2(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+        min_out: i128,
+        deadline: u32,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        Self::exchange_tokens_v3(
+            env, user, from_brand, to_brand, amount, min_out, deadline, memo, None,
+        )
+    }
 
-        // Check both brands
-        let from_brand_data = Self::view_brand(env.clone(), from_brand);
-        let to_brand_data = Self::view_brand(env.clone(), to_brand);
-        if !from_brand_data.is_active || !to_brand_data.is_active {
-            panic!("One or both brands are not active");
-        }
+    /// Exchange tokens between two brands as priced by `quote_exchange`
+    /// Fails if the output is below `min_out` or the ledger is past `deadline`
+    /// `sequence`, if given, must equal the user's next sequence number
+    /// Returns the op_id of the recorded operation
+    pub fn exchange_tokens_v3(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+        min_out: i128,
+        deadline: u32,
+        memo: Option<BytesN<32>>,
+        sequence: Option<u64>,
+    ) -> u64 {
+        user.require_auth();
+        consume_sequence(&env, &user, sequence);
 
-        // Deduct from source balance
-        let from_balance_key = UserBalance::Balance(user.clone(), from_brand);
-        let from_balance: i64 = env.storage().instance().get(&from_balance_key).unwrap_or(0);
-        if from_balance < amount {
-            panic!("Insufficient balance");
+        if env.ledger().sequence() > deadline {
+            panic!("Deadline has passed");
         }
-
-        let new_from_balance = from_balance - amount;
-        env.storage().instance().set(&from_balance_key, &new_from_balance);
-
-        // Add to destination
-        let to_balance_key = UserBalance::Balance(user.clone(), to_brand);
-        let to_balance: i64 = env.storage().instance().get(&to_balance_key).unwrap_or(0);
-        let new_to_balance = to_balance + amount;
-        env.storage().instance().set(&to_balance_key, &new_to_balance);
-        env.storage().instance().extend_ttl(100000, 100000);
-
-        log!(
-            &env,
-            "✅ Exchanged {} tokens from brand {} → brand {}",
-            amount,
-            from_brand,
-            to_brand
-        );
+        exchange(&env, &user, from_brand, to_brand, amount, min_out, memo)
     }
 
     /// View user's token balance
-    pub fn view_user_balance(env: Env, user: Address, brand_id: u64) -> i64 {
-        let balance_key = UserBalance::Balance(user, brand_id);
-        env.storage().instance().get(&balance_key).unwrap_or(0)
+    /// Open to any caller regardless of data-sharing consent
+    pub fn view_user_balance(env: Env, user: Address, brand_id: u64) -> i128 {
+        read_balance(&env, &user, brand_id)
+    }
+
+    /// View the circulating supply of a brand
+    pub fn view_brand_supply(env: Env, brand_id: u64) -> i128 {
+        brand_supply(&env, brand_id).into()
     }
 
     /// View brand details by brand_id
@@ -149,6 +464,342 @@ impl LoyaltyTokenExchange {
     pub fn get_brand_count(env: Env) -> u64 {
         env.storage().instance().get(&BRAND_COUNT).unwrap_or(0)
     }
+
+    /// Set the platform admin
+    /// The first call bootstraps the admin; later calls must be authorized by the current admin
+    pub fn set_platform_admin(env: Env, new_admin: Address) {
+        let actor = match env.storage().instance().get::<_, Address>(&PLATFORM_ADMIN) {
+            Some(admin) => admin,
+            None => new_admin.clone(),
+        };
+        actor.require_auth();
+
+        env.storage().instance().set(&PLATFORM_ADMIN, &new_admin);
+        log_admin_action(
+            &env,
+            &actor,
+            AdminAction::PlatformAdminSet,
+            PLATFORM_BRAND_ID,
+            None,
+            None,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Platform admin updated");
+    }
+
+    /// View the platform admin, if one has been set
+    pub fn get_platform_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&PLATFORM_ADMIN)
+    }
+
+    /// Set the admin of a brand
+    /// Must be authorized by the current brand admin, or when the brand has no
+    /// admin yet by its program's admin, or the platform admin outside programs
+    pub fn set_brand_admin(env: Env, brand_id: u64, new_admin: Address) {
+        load_brand(&env, brand_id);
+        let actor = match brand_admin(&env, brand_id) {
+            Some(admin) => {
+                admin.require_auth();
+                admin
+            }
+            None => match Self::view_brand_program(env.clone(), brand_id) {
+                Some(program_id) => require_program_admin(&env, program_id).admin,
+                None => require_platform_admin(&env),
+            },
+        };
+
+        assign_brand_admin(&env, brand_id, &new_admin);
+        log_admin_action(
+            &env,
+            &actor,
+            AdminAction::BrandAdminSet,
+            brand_id,
+            None,
+            None,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Admin updated for brand {}", brand_id);
+    }
+
+    /// View the admin of a brand, including one inherited from a parent brand
+    pub fn get_brand_admin(env: Env, brand_id: u64) -> Option<Address> {
+        brand_admin(&env, brand_id)
+    }
+}
+
+// Validate and credit an issuance, record it with its issuer and announce it
+fn issue(
+    env: &Env,
+    issuer: Option<Address>,
+    user: Address,
+    brand_id: u64,
+    amount: i128,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    // Verify brand is active and amount is valid
+    if let Err(failure) = check_issue(env, brand_id, amount) {
+        failure.fail();
+    }
+    if let Some(issuer) = &issuer {
+        require_cosigner(env, Some(issuer), &user, brand_id);
+    }
+    check_velocity(env, &user, brand_id, None);
+
+    // Update user balance, or hold the points until a new user claims them
+    let claim_window = parking_window(env, &user, brand_id);
+    if claim_window.is_none() {
+        credit(env, &user, brand_id, amount);
+    }
+    record_brand_activity(env, brand_id);
+    track_issued(env, brand_id, amount);
+
+    let mut record = OperationRecord::new(env, OperationKind::Issue, &user, brand_id, amount);
+    record.memo = memo.clone();
+    record.issuer = issuer.clone();
+    save_operation(env, &record);
+    if let Some(issuer) = issuer {
+        index_issuance(env, &issuer, brand_id, record.op_id);
+    }
+    if let Some(window) = claim_window {
+        park_claimable(env, &user, brand_id, amount, record.op_id, window);
+    }
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    emit_operation(env, brand_id, &user, record.op_id, || {
+        TokensIssued {
+            brand_id,
+            user: user.clone(),
+            amount,
+            op_id: record.op_id,
+            memo,
+        }
+        .publish(env)
+    });
+
+    log!(
+        env,
+        "✅ Issued {} tokens from brand {} to user",
+        amount,
+        brand_id
+    );
+    record.op_id
+}
+
+// Validate and settle an exchange at the current quote and record it
+fn exchange(
+    env: &Env,
+    user: &Address,
+    from_brand: u64,
+    to_brand: u64,
+    amount: i128,
+    min_out: i128,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    // Check amount, both brands and the source balance
+    if let Err(failure) = check_exchange(env, user, from_brand, to_brand, amount) {
+        failure.fail();
+    }
+    alert_large_exchange(env, user, from_brand, amount);
+
+    let quote = quote(env, Some(user), from_brand, to_brand, amount);
+    if quote.amount_out < min_out {
+        panic!("Output below minimum");
+    }
+
+    record_exchange_volume(env, user, amount);
+    settle_exchange(env, user, &quote);
+    roll_reward_drop(env, user, to_brand);
+
+    let mut record = OperationRecord::new(env, OperationKind::Exchange, user, from_brand, amount);
+    record.to_brand = Some(to_brand);
+    record.memo = memo;
+    save_operation(env, &record);
+    record_exchange_receipt(env, record.op_id, user, &quote);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(
+        env,
+        "✅ Exchanged {} tokens from brand {} → {} tokens of brand {}",
+        amount,
+        from_brand,
+        quote.amount_out,
+        to_brand
+    );
+    record.op_id
+}
+
+// Move a user's points along one route at the quoted terms
+fn settle_exchange(env: &Env, user: &Address, quote: &ExchangeQuote) {
+    let (from_brand, to_brand, amount) = (quote.from_brand, quote.to_brand, quote.amount_in);
+
+    // Deduct from source balance
+    debit(env, user, from_brand, amount);
+    record_burn(env, from_brand, quote.burned);
+    settle_fee(env, quote);
+    record_rate_snapshot(env, from_brand, to_brand, quote.rate);
+    track_route_volume(env, from_brand, to_brand, amount);
+    record_route_use(env, from_brand, to_brand);
+    track_exchanged(env, from_brand, to_brand, amount, quote.amount_out);
+
+    // Add to destination
+    credit(env, user, to_brand, quote.amount_out);
+    withhold_tax(env, user, quote);
+}
+
+// Store a new active brand and return its id
+fn create_brand(env: &Env, brand_name: String) -> u64 {
+    if let Err(failure) = check_brand_capacity(env) {
+        failure.fail();
+    }
+
+    // Get current brand count or start from 0
+    let mut brand_count: u64 = env.storage().instance().get(&BRAND_COUNT).unwrap_or(0);
+    brand_count += 1;
+
+    // Create new brand instance
+    let new_brand = Brand {
+        brand_id: brand_count,
+        brand_name,
+        is_active: true,
+    };
+
+    // Store the brand
+    env.storage()
+        .instance()
+        .set(&BrandBook::Brand(brand_count), &new_brand);
+    env.storage().instance().set(&BRAND_COUNT, &brand_count);
+    init_reputation(env, brand_count);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Brand registered with ID: {}", brand_count);
+    brand_count
+}
+
+// Require the platform admin's authorization and return its address
+fn require_platform_admin(env: &Env) -> Address {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&PLATFORM_ADMIN)
+        .unwrap_or_else(|| panic!("Platform admin not set"));
+    admin.require_auth();
+    admin
+}
+
+// Load a brand, panicking if it was never registered
+fn load_brand(env: &Env, brand_id: u64) -> Brand {
+    env.storage()
+        .instance()
+        .get(&BrandBook::Brand(brand_id))
+        .unwrap_or_else(|| panic!("Brand not found"))
+}
+
+// Resolve a brand's admin; sub-brands without their own admin use the parent's
+fn brand_admin(env: &Env, brand_id: u64) -> Option<Address> {
+    inherited(env, brand_id, |brand_id| {
+        env.storage().instance().get(&BrandAdmin::Admin(brand_id))
+    })
+}
+
+// Require the brand admin's authorization and return its address
+fn require_brand_admin(env: &Env, brand_id: u64) -> Address {
+    let admin = brand_admin(env, brand_id).unwrap_or_else(|| panic!("Brand admin not set"));
+    admin.require_auth();
+    admin
+}
+
+// Spendable balance, net of any decay pending on an inactive balance
+fn read_balance(env: &Env, user: &Address, brand_id: u64) -> i128 {
+    stored_balance(env, user, brand_id) - pending_decay(env, user, brand_id)
+}
+
+// Add to a user's balance and to the brand's circulating supply, posted from
+// the brand's Mint account
+fn credit(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    post(
+        env,
+        brand_id,
+        &LedgerAccount::Mint,
+        &LedgerAccount::User(user.clone()),
+        amount,
+    );
+}
+
+// Remove from a user's balance and from the brand's circulating supply,
+// posted to the brand's Burn account
+fn debit(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    post(
+        env,
+        brand_id,
+        &LedgerAccount::User(user.clone()),
+        &LedgerAccount::Burn,
+        amount,
+    );
+}
+
+// Hold points of a user's balance in the brand's Escrow account until a
+// feature pays them out or spends them
+fn escrow(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    post(
+        env,
+        brand_id,
+        &LedgerAccount::User(user.clone()),
+        &LedgerAccount::Escrow,
+        amount,
+    );
+}
+
+// Pay escrowed points out to a user
+fn release(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    post(
+        env,
+        brand_id,
+        &LedgerAccount::Escrow,
+        &LedgerAccount::User(user.clone()),
+        amount,
+    );
+}
+
+// Take escrowed points out of circulation for good, e.g. spent on a reward
+fn burn_escrow(env: &Env, brand_id: u64, amount: i128) {
+    post(
+        env,
+        brand_id,
+        &LedgerAccount::Escrow,
+        &LedgerAccount::Burn,
+        amount,
+    );
+}
+
+fn adjust_supply(env: &Env, brand_id: u64, delta: Points) {
+    let supply = brand_supply(env, brand_id)
+        .checked_add(delta)
+        .unwrap_or_else(|| panic!("Amount out of range"));
+    env.storage()
+        .instance()
+        .set(&BrandSupply::WideSupply(brand_id), &i128::from(supply));
+    env.storage()
+        .instance()
+        .remove(&BrandSupply::Supply(brand_id));
+}
+
+// Circulating supply of a brand, falling back to a supply stored as i64
+fn brand_supply(env: &Env, brand_id: u64) -> Points {
+    let wide: Option<i128> = env
+        .storage()
+        .instance()
+        .get(&BrandSupply::WideSupply(brand_id));
+    if let Some(supply) = wide {
+        return Points::from(supply);
+    }
+    let narrow: i64 = env
+        .storage()
+        .instance()
+        .get(&BrandSupply::Supply(brand_id))
+        .unwrap_or(0);
+    Points::from(narrow as i128)
 }
 
 #[cfg(test)]
@@ -159,7 +810,7 @@ mod test {
     #[test]
     fn test_register_brand() {
         let env = Env::default();
-        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let contract_id = env.register(LoyaltyTokenExchange, ());
         let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
 
         let brand_name = String::from_str(&env, "Starbucks");
@@ -176,14 +827,14 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let contract_id = env.register(LoyaltyTokenExchange, ());
         let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
 
         let user = Address::generate(&env);
         let brand_name = String::from_str(&env, "Nike");
         let brand_id = client.register_brand(&brand_name);
 
-        client.issue_tokens(&user, &brand_id, &1000);
+        client.issue_tokens(&user, &brand_id, &1000, &None);
         let balance = client.view_user_balance(&user, &brand_id);
         assert_eq!(balance, 1000);
     }
@@ -193,7 +844,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let contract_id = env.register(LoyaltyTokenExchange, ());
         let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
 
         let user = Address::generate(&env);
@@ -204,7 +855,7 @@ mod test {
         let brand_id_1 = client.register_brand(&brand1);
         let brand_id_2 = client.register_brand(&brand2);
 
-        client.issue_tokens(&user, &brand_id_1, &1000);
+        client.issue_tokens(&user, &brand_id_1, &1000, &None);
         client.exchange_tokens(&user, &brand_id_1, &brand_id_2, &500);
 
         let balance1 = client.view_user_balance(&user, &brand_id_1);
@@ -214,13 +865,51 @@ mod test {
         assert_eq!(balance2, 500);
     }
 
+    #[test]
+    fn test_brand_supply_follows_exchanges() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let user = Address::generate(&env);
+        let brand_id_1 = client.register_brand(&String::from_str(&env, "Adidas"));
+        let brand_id_2 = client.register_brand(&String::from_str(&env, "Puma"));
+
+        client.issue_tokens(&user, &brand_id_1, &1000, &None);
+        client.exchange_tokens(&user, &brand_id_1, &brand_id_2, &400);
+
+        assert_eq!(client.view_brand_supply(&brand_id_1), 600);
+        assert_eq!(client.view_brand_supply(&brand_id_2), 400);
+    }
+
+    #[test]
+    fn test_set_brand_admin() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let platform_admin = Address::generate(&env);
+        let brand_admin = Address::generate(&env);
+        client.set_platform_admin(&platform_admin);
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Lego"));
+        assert_eq!(client.get_brand_admin(&brand_id), None);
+
+        client.set_brand_admin(&brand_id, &brand_admin);
+        assert_eq!(client.get_brand_admin(&brand_id), Some(brand_admin));
+    }
+
     #[test]
     #[should_panic(expected = "Insufficient balance")]
     fn test_exchange_insufficient_balance() {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(None, LoyaltyTokenExchange);
+        let contract_id = env.register(LoyaltyTokenExchange, ());
         let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
 
         let user = Address::generate(&env);
@@ -231,7 +920,7 @@ mod test {
         let brand_id_1 = client.register_brand(&brand1);
         let brand_id_2 = client.register_brand(&brand2);
 
-        client.issue_tokens(&user, &brand_id_1, &100);
+        client.issue_tokens(&user, &brand_id_1, &100, &None);
         client.exchange_tokens(&user, &brand_id_1, &brand_id_2, &500);
     }
 }
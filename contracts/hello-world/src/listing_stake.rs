@@ -0,0 +1,191 @@
+use crate::*;
+use soroban_sdk::{token, Vec};
+
+// Structure reporting a brand's place in the listing ranking
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingEntry {
+    pub brand_id: u64,
+    pub stake: i128,
+}
+
+// Mapping for listing stakes
+#[contracttype]
+pub enum ListingBook {
+    // Brand -> Tokens staked
+    Stake(u64),
+}
+
+// SAC token brands stake to rank their listing
+const LISTING_TOKEN: Symbol = symbol_short!("LST_TOKEN");
+
+// Brands with a stake, highest stake first; ties keep the earlier staker first
+const LISTING_RANK: Symbol = symbol_short!("LST_RANK");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the token brands stake to rank their listing (platform admin only)
+    /// Cannot change while any stake is held
+    pub fn set_listing_token(env: Env, token: Address) {
+        require_platform_admin(&env);
+        if !ranking(&env).is_empty() {
+            panic!("Cannot change token while stakes are held");
+        }
+
+        env.storage().instance().set(&LISTING_TOKEN, &token);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Listing token set");
+    }
+
+    /// Stake tokens from the brand admin to raise the brand's listing rank
+    /// (brand admin only)
+    pub fn stake_listing(env: Env, brand_id: u64, amount: i128) {
+        load_brand(&env, brand_id);
+        let admin = require_brand_admin(&env, brand_id);
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+
+        token::TokenClient::new(&env, &listing_token(&env)).transfer(
+            &admin,
+            env.current_contract_address(),
+            &amount,
+        );
+        let stake = Self::view_listing_stake(env.clone(), brand_id) + amount;
+        set_stake(&env, brand_id, stake);
+
+        log!(&env, "✅ Brand {} staked {} for listing", brand_id, amount);
+    }
+
+    /// Withdraw staked tokens to the brand admin (brand admin only)
+    pub fn unstake_listing(env: Env, brand_id: u64, amount: i128) {
+        let admin = require_brand_admin(&env, brand_id);
+        let stake = Self::view_listing_stake(env.clone(), brand_id);
+        if amount <= 0 || amount > stake {
+            panic!("Invalid unstake amount");
+        }
+
+        token::TokenClient::new(&env, &listing_token(&env)).transfer(
+            &env.current_contract_address(),
+            &admin,
+            &amount,
+        );
+        set_stake(&env, brand_id, stake - amount);
+
+        log!(&env, "✅ Brand {} unstaked {}", brand_id, amount);
+    }
+
+    /// View the tokens a brand has staked for its listing
+    pub fn view_listing_stake(env: Env, brand_id: u64) -> i128 {
+        env.storage()
+            .instance()
+            .get(&ListingBook::Stake(brand_id))
+            .unwrap_or(0)
+    }
+
+    /// View staked brands by listing rank, highest stake first
+    /// `start` is the 0-based rank to start from
+    pub fn ranked_brands(env: Env, start: u32, limit: u32) -> Vec<ListingEntry> {
+        let rank = ranking(&env);
+        let mut entries = Vec::new(&env);
+        let end = start.saturating_add(limit).min(rank.len());
+        for index in start..end {
+            let brand_id = rank.get_unchecked(index);
+            entries.push_back(ListingEntry {
+                brand_id,
+                stake: Self::view_listing_stake(env.clone(), brand_id),
+            });
+        }
+        entries
+    }
+}
+
+// Store a brand's new stake and move it to its place in the ranking
+fn set_stake(env: &Env, brand_id: u64, stake: i128) {
+    let mut rank = ranking(env);
+    if let Some(index) = rank.first_index_of(brand_id) {
+        rank.remove(index);
+    }
+
+    let key = ListingBook::Stake(brand_id);
+    if stake == 0 {
+        env.storage().instance().remove(&key);
+    } else {
+        env.storage().instance().set(&key, &stake);
+        let mut index = 0;
+        while index < rank.len()
+            && LoyaltyTokenExchange::view_listing_stake(env.clone(), rank.get_unchecked(index))
+                >= stake
+        {
+            index += 1;
+        }
+        rank.insert(index, brand_id);
+    }
+    env.storage().instance().set(&LISTING_RANK, &rank);
+    env.storage().instance().extend_ttl(100000, 100000);
+}
+
+fn ranking(env: &Env) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&LISTING_RANK)
+        .unwrap_or(Vec::new(env))
+}
+
+fn listing_token(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&LISTING_TOKEN)
+        .unwrap_or_else(|| panic!("Listing token not set"))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env, String};
+
+    #[test]
+    fn test_brands_ranked_by_stake() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let token = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        client.set_listing_token(&token);
+
+        let mut brands = [0u64; 3];
+        for (index, name) in ["Cafe", "Gym", "Spa"].iter().enumerate() {
+            let brand_id = client.register_brand(&String::from_str(&env, name));
+            let admin = Address::generate(&env);
+            client.set_brand_admin(&brand_id, &admin);
+            StellarAssetClient::new(&env, &token).mint(&admin, &1000);
+            brands[index] = brand_id;
+        }
+        let [cafe, gym, spa] = brands;
+
+        client.stake_listing(&cafe, &300);
+        client.stake_listing(&gym, &500);
+        client.stake_listing(&spa, &300);
+        let ranked = client.ranked_brands(&0, &10);
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked.get_unchecked(0).brand_id, gym);
+        assert_eq!(ranked.get_unchecked(1).brand_id, cafe);
+        assert_eq!(ranked.get_unchecked(2).brand_id, spa);
+
+        client.stake_listing(&spa, &400);
+        client.unstake_listing(&gym, &500);
+        let ranked = client.ranked_brands(&0, &10);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked.get_unchecked(0).brand_id, spa);
+        assert_eq!(ranked.get_unchecked(0).stake, 700);
+        assert_eq!(client.ranked_brands(&1, &5).get_unchecked(0).brand_id, cafe);
+        assert!(client
+            .try_set_listing_token(&Address::generate(&env))
+            .is_err());
+    }
+}
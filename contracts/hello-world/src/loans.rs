@@ -0,0 +1,221 @@
+use crate::*;
+
+// Structure to store the terms a lender and borrower agree on
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanTerms {
+    // Brand whose points are lent
+    pub loan_brand: u64,
+    pub principal: i128,
+    // Brand whose points the borrower locks as collateral
+    pub collateral_brand: u64,
+    pub collateral: i128,
+    // Loan-brand points owed to the lender, principal included
+    pub repayment: i128,
+    // Last ledger the loan can be repaid on before the lender may seize
+    pub deadline: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoanStatus {
+    Open,
+    // Repayment made; the collateral went back to the borrower
+    Repaid,
+    // Deadline passed unpaid; the lender took the collateral
+    Seized,
+}
+
+// Structure to store a loan of points secured by another brand's points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Loan {
+    pub loan_id: u64,
+    pub lender: Address,
+    pub borrower: Address,
+    pub terms: LoanTerms,
+    pub status: LoanStatus,
+}
+
+// Mapping for loans: loan_id -> Loan
+#[contracttype]
+pub enum LoanBook {
+    Loan(u64),
+}
+
+// Counter for loans
+const LOAN_COUNT: Symbol = symbol_short!("LN_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Lend `principal` loan-brand points to the borrower, locking their
+    /// collateral until the loan is repaid or seized; both parties must sign
+    /// Returns the loan_id
+    pub fn open_loan(env: Env, lender: Address, borrower: Address, terms: LoanTerms) -> u64 {
+        require_user(&env, &lender);
+        require_user(&env, &borrower);
+        if lender == borrower {
+            panic!("Cannot lend to yourself");
+        }
+        if terms.loan_brand == terms.collateral_brand {
+            panic!("Collateral must be in another brand");
+        }
+        if !is_brand_active(&env, terms.loan_brand)
+            || !is_brand_active(&env, terms.collateral_brand)
+        {
+            panic!("One or both brands are not active");
+        }
+        if terms.principal <= 0 || terms.collateral <= 0 {
+            panic!("Amount must be positive");
+        }
+        if terms.repayment < terms.principal {
+            panic!("Repayment below principal");
+        }
+        if terms.deadline <= env.ledger().sequence() {
+            panic!("Deadline must be in the future");
+        }
+
+        post(
+            &env,
+            terms.loan_brand,
+            &LedgerAccount::User(lender.clone()),
+            &LedgerAccount::User(borrower.clone()),
+            terms.principal,
+        );
+        escrow(&env, &borrower, terms.collateral_brand, terms.collateral);
+
+        let loan_id: u64 = env.storage().instance().get(&LOAN_COUNT).unwrap_or(0) + 1;
+        let loan = Loan {
+            loan_id,
+            lender,
+            borrower,
+            terms,
+            status: LoanStatus::Open,
+        };
+        save_loan(&env, &loan);
+        env.storage().instance().set(&LOAN_COUNT, &loan_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Loan {} opened", loan_id);
+        loan_id
+    }
+
+    /// Pay the lender back and release the collateral; possible until the
+    /// lender seizes it, even after the deadline
+    pub fn repay_loan(env: Env, loan_id: u64) {
+        let mut loan = load_open_loan(&env, loan_id);
+        require_user(&env, &loan.borrower);
+
+        post(
+            &env,
+            loan.terms.loan_brand,
+            &LedgerAccount::User(loan.borrower.clone()),
+            &LedgerAccount::User(loan.lender.clone()),
+            loan.terms.repayment,
+        );
+        release(
+            &env,
+            &loan.borrower,
+            loan.terms.collateral_brand,
+            loan.terms.collateral,
+        );
+        loan.status = LoanStatus::Repaid;
+        save_loan(&env, &loan);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Loan {} repaid", loan_id);
+    }
+
+    /// Take the collateral of a loan left unpaid past its deadline
+    /// (lender only)
+    pub fn seize_collateral(env: Env, loan_id: u64) {
+        let mut loan = load_open_loan(&env, loan_id);
+        require_user(&env, &loan.lender);
+        if env.ledger().sequence() <= loan.terms.deadline {
+            panic!("Loan is not past its deadline");
+        }
+
+        release(
+            &env,
+            &loan.lender,
+            loan.terms.collateral_brand,
+            loan.terms.collateral,
+        );
+        loan.status = LoanStatus::Seized;
+        save_loan(&env, &loan);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Collateral of loan {} seized", loan_id);
+    }
+
+    /// View loan details by loan_id
+    pub fn view_loan(env: Env, loan_id: u64) -> Loan {
+        env.storage()
+            .instance()
+            .get(&LoanBook::Loan(loan_id))
+            .unwrap_or_else(|| panic!("Loan not found"))
+    }
+}
+
+fn load_open_loan(env: &Env, loan_id: u64) -> Loan {
+    let loan = LoyaltyTokenExchange::view_loan(env.clone(), loan_id);
+    if loan.status != LoanStatus::Open {
+        panic!("Loan is not open");
+    }
+    loan
+}
+
+fn save_loan(env: &Env, loan: &Loan) {
+    env.storage()
+        .instance()
+        .set(&LoanBook::Loan(loan.loan_id), loan);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_loan_repaid_or_collateral_seized() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let lender = Address::generate(&env);
+        let borrower = Address::generate(&env);
+        client.issue_tokens(&lender, &airline, &1000, &None);
+        client.issue_tokens(&borrower, &hotel, &1000, &None);
+
+        let terms = LoanTerms {
+            loan_brand: airline,
+            principal: 400,
+            collateral_brand: hotel,
+            collateral: 600,
+            repayment: 440,
+            deadline: 100,
+        };
+        let repaid = client.open_loan(&lender, &borrower, &terms);
+        assert_eq!(client.view_user_balance(&borrower, &airline), 400);
+        assert_eq!(client.view_user_balance(&borrower, &hotel), 400);
+        assert!(client.try_seize_collateral(&repaid).is_err());
+
+        client.issue_tokens(&borrower, &airline, &40, &None);
+        client.repay_loan(&repaid);
+        assert_eq!(client.view_user_balance(&lender, &airline), 1040);
+        assert_eq!(client.view_user_balance(&borrower, &hotel), 1000);
+
+        let defaulted = client.open_loan(&lender, &borrower, &terms);
+        env.ledger().set_sequence_number(101);
+        client.seize_collateral(&defaulted);
+        assert_eq!(client.view_loan(&defaulted).status, LoanStatus::Seized);
+        assert_eq!(client.view_user_balance(&lender, &hotel), 600);
+        assert!(client.try_repay_loan(&defaulted).is_err());
+    }
+}
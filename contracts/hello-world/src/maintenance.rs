@@ -0,0 +1,258 @@
+use crate::*;
+
+// Maintenance job anyone may run for a bounty
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MaintenanceJob {
+    // Claim ID of an expired claimable balance
+    ExpireClaimable(u64),
+    // Pending ID of an expired pending redemption
+    ReleaseRedemption(u64),
+    // Promo ID of an expired promotion
+    ReclaimPromotion(u64),
+    // Order ID of a due standing order
+    StandingOrder(u64),
+    // Sub ID of a due subscription
+    Subscription(u64),
+    // Reward ID whose reservation queue can be served
+    FulfillReservation(u64),
+}
+
+// Structure to store a brand's maintenance bounty and the budget funding it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaintenanceBounty {
+    pub bounty: i128,
+    // Points set aside from the brand treasury, left to pay out
+    pub budget: i128,
+    pub paid: u32,
+}
+
+// Mapping for maintenance bounties
+#[contracttype]
+pub enum MaintenanceBook {
+    // Brand -> Maintenance bounty
+    Bounty(u64),
+    // Job -> Ledger a bounty was last paid for running it
+    LastPaid(MaintenanceJob),
+}
+
+// Ledgers between bounties paid for the same job
+pub const BOUNTY_COOLDOWN_LEDGERS: u32 = 720;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the bounty paid for running a brand's maintenance jobs (brand admin only)
+    /// A bounty of 0 turns it off and returns the remaining budget to the
+    /// brand treasury
+    pub fn set_maintenance_bounty(env: Env, brand_id: u64, bounty: i128) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if bounty < 0 {
+            panic!("Bounty cannot be negative");
+        }
+
+        let mut maintenance = load_bounty(&env, brand_id);
+        if bounty == 0 && maintenance.budget > 0 {
+            let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+                .unwrap_or_else(|| panic!("Brand treasury not set"));
+            release(&env, &treasury, brand_id, maintenance.budget);
+            maintenance.budget = 0;
+        }
+        maintenance.bounty = bounty;
+        env.storage()
+            .instance()
+            .set(&MaintenanceBook::Bounty(brand_id), &maintenance);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Maintenance bounty for brand {} set to {}",
+            brand_id,
+            bounty
+        );
+    }
+
+    /// Move points from the brand treasury into the maintenance bounty budget
+    /// (brand admin only)
+    pub fn fund_maintenance_budget(env: Env, brand_id: u64, amount: i128) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand treasury not set"));
+        escrow(&env, &treasury, brand_id, amount);
+
+        let mut maintenance = load_bounty(&env, brand_id);
+        maintenance.budget += amount;
+        env.storage()
+            .instance()
+            .set(&MaintenanceBook::Bounty(brand_id), &maintenance);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Maintenance budget of brand {} funded", brand_id);
+    }
+
+    /// Run a maintenance job and pay the caller the brand's bounty while the
+    /// budget lasts
+    /// Only runs that did the job's work are paid: a skipped standing order or
+    /// subscription is not. Each job is paid at most once per
+    /// BOUNTY_COOLDOWN_LEDGERS, whoever runs it
+    /// Returns the bounty paid, 0 when none was
+    pub fn run_maintenance(env: Env, caller: Address, job: MaintenanceJob) -> i128 {
+        caller.require_auth();
+
+        let (brand_id, done) = match job {
+            MaintenanceJob::ExpireClaimable(claim_id) => {
+                let brand_id = load_claimable(&env, claim_id).brand_id;
+                Self::expire_claimable(env.clone(), claim_id);
+                (brand_id, true)
+            }
+            MaintenanceJob::ReleaseRedemption(pending_id) => {
+                let brand_id = Self::view_pending_redemption(env.clone(), pending_id)
+                    .unwrap_or_else(|| panic!("Pending redemption not found"))
+                    .brand_id;
+                Self::release_expired_redemption(env.clone(), pending_id);
+                (brand_id, true)
+            }
+            MaintenanceJob::ReclaimPromotion(promo_id) => {
+                let brand_id = Self::view_promotion(env.clone(), promo_id).brand_id;
+                Self::reclaim_promotion(env.clone(), promo_id);
+                (brand_id, true)
+            }
+            MaintenanceJob::StandingOrder(order_id) => {
+                let brand_id = Self::view_standing_order(env.clone(), order_id).from_brand;
+                (brand_id, Self::tick(env.clone(), order_id))
+            }
+            MaintenanceJob::Subscription(sub_id) => {
+                let brand_id = Self::view_subscription(env.clone(), sub_id).brand_id;
+                (brand_id, Self::process_due(env.clone(), sub_id))
+            }
+            MaintenanceJob::FulfillReservation(reward_id) => {
+                let brand_id = Self::view_reward(env.clone(), reward_id).brand_id;
+                Self::fulfill_next(env.clone(), reward_id);
+                (brand_id, true)
+            }
+        };
+
+        if !done {
+            return 0;
+        }
+        pay_bounty(&env, &caller, brand_id, &job)
+    }
+
+    /// View a brand's maintenance bounty and its remaining budget
+    pub fn view_maintenance_bounty(env: Env, brand_id: u64) -> MaintenanceBounty {
+        load_bounty(&env, brand_id)
+    }
+
+    /// View the ledger a maintenance bounty was last paid for running a job
+    pub fn view_last_bounty(env: Env, job: MaintenanceJob) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&MaintenanceBook::LastPaid(job))
+    }
+}
+
+// Credit the brand's bounty to the caller, unless the budget ran out or the
+// job was paid for within the cooldown
+fn pay_bounty(env: &Env, caller: &Address, brand_id: u64, job: &MaintenanceJob) -> i128 {
+    let mut maintenance = load_bounty(env, brand_id);
+    if maintenance.bounty == 0 || maintenance.budget < maintenance.bounty {
+        return 0;
+    }
+    let sequence = env.ledger().sequence();
+    let last_paid = LoyaltyTokenExchange::view_last_bounty(env.clone(), job.clone());
+    if let Some(last_paid) = last_paid {
+        if sequence < last_paid + BOUNTY_COOLDOWN_LEDGERS {
+            return 0;
+        }
+    }
+
+    release(env, caller, brand_id, maintenance.bounty);
+    maintenance.budget -= maintenance.bounty;
+    maintenance.paid += 1;
+    env.storage()
+        .instance()
+        .set(&MaintenanceBook::Bounty(brand_id), &maintenance);
+    env.storage()
+        .instance()
+        .set(&MaintenanceBook::LastPaid(job.clone()), &sequence);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Maintenance bounty of {} paid", maintenance.bounty);
+    maintenance.bounty
+}
+
+fn load_bounty(env: &Env, brand_id: u64) -> MaintenanceBounty {
+    env.storage()
+        .instance()
+        .get(&MaintenanceBook::Bounty(brand_id))
+        .unwrap_or(MaintenanceBounty {
+            bounty: 0,
+            budget: 0,
+            paid: 0,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_maintenance_bounty_paid_once_per_job_and_cooldown() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&hotel, &treasury);
+        client.issue_tokens(&treasury, &hotel, &15, &None);
+        client.set_maintenance_bounty(&hotel, &5);
+        client.fund_maintenance_budget(&hotel, &15);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        let order_id = client.create_standing_order(&user, &hotel, &airline, &100, &10);
+        let keeper = Address::generate(&env);
+        let job = MaintenanceJob::StandingOrder(order_id);
+
+        assert_eq!(client.run_maintenance(&keeper, &job), 5);
+        // Not due yet: the job fails and nothing is paid
+        assert!(client.try_run_maintenance(&keeper, &job).is_err());
+
+        // Within the cooldown the job is unpaid, whoever runs it
+        env.ledger().set_sequence_number(10);
+        let other_keeper = Address::generate(&env);
+        assert_eq!(client.run_maintenance(&other_keeper, &job), 0);
+        assert_eq!(client.view_standing_order(&order_id).executions, 2);
+
+        env.ledger().set_sequence_number(BOUNTY_COOLDOWN_LEDGERS);
+        assert_eq!(client.run_maintenance(&other_keeper, &job), 5);
+        assert_eq!(client.view_user_balance(&other_keeper, &hotel), 5);
+        assert_eq!(client.view_last_bounty(&job), Some(BOUNTY_COOLDOWN_LEDGERS));
+
+        // A skipped order did no work and is not paid
+        let broke = Address::generate(&env);
+        let empty_order = client.create_standing_order(&broke, &hotel, &airline, &100, &10);
+        let empty_job = MaintenanceJob::StandingOrder(empty_order);
+        assert_eq!(client.run_maintenance(&keeper, &empty_job), 0);
+        assert_eq!(client.view_standing_order(&empty_order).skipped, 1);
+        assert_eq!(client.view_last_bounty(&empty_job), None);
+
+        client.set_maintenance_bounty(&hotel, &0);
+        assert_eq!(client.view_user_balance(&treasury, &hotel), 5);
+        assert_eq!(client.view_maintenance_bounty(&hotel).paid, 2);
+    }
+}
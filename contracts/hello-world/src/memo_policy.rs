@@ -0,0 +1,89 @@
+use crate::*;
+
+// Mapping for memo policies: Brand -> Whether issuances must carry a reference
+#[contracttype]
+pub enum MemoPolicyBook {
+    RequireReference(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Require every issuance of a brand to carry a non-empty reference memo,
+    /// so each one traces back to a point-of-sale record (brand admin only)
+    pub fn set_require_reference(env: Env, brand_id: u64, required: bool) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = MemoPolicyBook::RequireReference(brand_id);
+        if required {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Reference policy updated for brand {}", brand_id);
+    }
+
+    /// View whether issuances of a brand must carry a reference memo
+    pub fn requires_reference(env: Env, brand_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&MemoPolicyBook::RequireReference(brand_id))
+            .unwrap_or(false)
+    }
+}
+
+// Reject an issuance without a reference when the brand requires one; an
+// all-zero memo counts as empty
+pub(crate) fn check_reference(
+    env: &Env,
+    brand_id: u64,
+    memo: &Option<BytesN<32>>,
+) -> Result<(), CheckFailure> {
+    if !LoyaltyTokenExchange::requires_reference(env.clone(), brand_id) {
+        return Ok(());
+    }
+    match memo {
+        Some(memo) if memo.to_array() != [0; 32] => Ok(()),
+        _ => Err(CheckFailure::MissingReference),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+    #[test]
+    fn test_issuance_needs_reference_when_required() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let issuer = Address::generate(&env);
+        client.add_issuer(&brand_id, &issuer);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &10, &None);
+
+        client.set_require_reference(&brand_id, &true);
+        let receipt = Some(BytesN::from_array(&env, &[7; 32]));
+        let empty = Some(BytesN::from_array(&env, &[0; 32]));
+        assert!(client
+            .try_issue_tokens(&user, &brand_id, &10, &None)
+            .is_err());
+        assert!(client
+            .try_issue_tokens(&user, &brand_id, &10, &empty)
+            .is_err());
+        assert!(client
+            .try_issue_as(&issuer, &user, &brand_id, &10, &None)
+            .is_err());
+        client.issue_tokens(&user, &brand_id, &10, &receipt);
+        client.issue_as(&issuer, &user, &brand_id, &10, &receipt);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 30);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure recording a brand that was folded into another brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrandMerge {
+    pub source_brand: u64,
+    pub target_brand: u64,
+    // Target points credited per RATE_DENOMINATOR source points
+    pub rate: u32,
+    pub merged_at: u32,
+}
+
+// Mapping for merged brands: source brand -> merge record
+#[contracttype]
+pub enum MergeBook {
+    Merge(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Merge `source_brand` into `target_brand` (platform admin only)
+    /// The source brand is deactivated and its balances become convertible
+    /// into the target brand at the fixed `rate` (basis points)
+    pub fn merge_brands(env: Env, source_brand: u64, target_brand: u64, rate: u32) {
+        require_platform_admin(&env);
+
+        if source_brand == target_brand {
+            panic!("Cannot merge a brand into itself");
+        }
+        if rate == 0 {
+            panic!("Rate must be positive");
+        }
+
+        let mut source = load_brand(&env, source_brand);
+        let target = load_brand(&env, target_brand);
+        if !source.is_active || !target.is_active {
+            panic!("One or both brands are not active");
+        }
+
+        // Stop new issuance and exchanges against the source brand
+        source.is_active = false;
+        env.storage()
+            .instance()
+            .set(&BrandBook::Brand(source_brand), &source);
+
+        let merge = BrandMerge {
+            source_brand,
+            target_brand,
+            rate,
+            merged_at: env.ledger().sequence(),
+        };
+        env.storage()
+            .instance()
+            .set(&MergeBook::Merge(source_brand), &merge);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} merged into brand {} at rate {}",
+            source_brand,
+            target_brand,
+            rate
+        );
+    }
+
+    /// Convert a user's remaining balance of a merged brand into the target brand
+    /// Anyone may call this since the conversion rate is fixed by the merge
+    /// Returns the amount credited in the target brand
+    pub fn convert_merged_balance(env: Env, user: Address, source_brand: u64) -> i128 {
+        let merge = Self::load_merge(&env, source_brand);
+        let credited = Self::convert_balance(&env, &merge, user);
+        env.storage().instance().extend_ttl(100000, 100000);
+        credited
+    }
+
+    /// Batch variant of `convert_merged_balance` for migration jobs
+    /// Returns the total amount credited in the target brand
+    pub fn convert_merged_balances(env: Env, source_brand: u64, users: Vec<Address>) -> i128 {
+        let merge = Self::load_merge(&env, source_brand);
+        let mut total: i128 = 0;
+        for user in users.iter() {
+            total += Self::convert_balance(&env, &merge, user);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+        total
+    }
+
+    /// View the merge record of a brand, if it was merged
+    pub fn view_merge(env: Env, source_brand: u64) -> Option<BrandMerge> {
+        env.storage()
+            .instance()
+            .get(&MergeBook::Merge(source_brand))
+    }
+}
+
+impl LoyaltyTokenExchange {
+    fn load_merge(env: &Env, source_brand: u64) -> BrandMerge {
+        env.storage()
+            .instance()
+            .get(&MergeBook::Merge(source_brand))
+            .unwrap_or_else(|| panic!("Brand has not been merged"))
+    }
+
+    // Move the user's whole source balance into the target brand
+    fn convert_balance(env: &Env, merge: &BrandMerge, user: Address) -> i128 {
+        let from_balance = read_balance(env, &user, merge.source_brand);
+        if from_balance <= 0 {
+            return 0;
+        }
+
+        let credited = apply_bps(from_balance, merge.rate);
+        debit(env, &user, merge.source_brand, from_balance);
+        credit(env, &user, merge.target_brand, credited);
+
+        log!(
+            env,
+            "✅ Converted {} tokens of brand {} into {} tokens of brand {}",
+            from_balance,
+            merge.source_brand,
+            credited,
+            merge.target_brand
+        );
+        credited
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+
+        let admin = Address::generate(env);
+        client.set_platform_admin(&admin);
+
+        let source = client.register_brand(&String::from_str(env, "Old Air"));
+        let target = client.register_brand(&String::from_str(env, "New Air"));
+        (client, source, target)
+    }
+
+    #[test]
+    fn test_merge_and_convert_balances() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, source, target) = setup(&env);
+
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+        client.issue_tokens(&user1, &source, &1000, &None);
+        client.issue_tokens(&user2, &source, &300, &None);
+
+        // 2 source points are worth 1 target point
+        client.merge_brands(&source, &target, &5000);
+        assert!(!client.view_brand(&source).is_active);
+        assert_eq!(client.view_merge(&source).unwrap().target_brand, target);
+
+        assert_eq!(client.convert_merged_balance(&user1, &source), 500);
+        assert_eq!(client.view_user_balance(&user1, &source), 0);
+        assert_eq!(client.view_user_balance(&user1, &target), 500);
+
+        // Already converted users are skipped by the batch job
+        let total =
+            client.convert_merged_balances(&source, &vec![&env, user1.clone(), user2.clone()]);
+        assert_eq!(total, 150);
+        assert_eq!(client.view_user_balance(&user1, &target), 500);
+        assert_eq!(client.view_user_balance(&user2, &target), 150);
+    }
+
+    #[test]
+    #[should_panic(expected = "Brand is not active")]
+    fn test_merged_brand_cannot_issue() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, source, target) = setup(&env);
+
+        client.merge_brands(&source, &target, &10000);
+        client.issue_tokens(&Address::generate(&env), &source, &100, &None);
+    }
+}
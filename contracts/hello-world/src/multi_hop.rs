@@ -0,0 +1,241 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure attributing one hop of a multi-hop exchange to the brands it touched
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HopSettlement {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub amount_in: i128,
+    pub burned: i128,
+    // Exchange fee, in destination points for FeePayer::Output and source points otherwise
+    pub fee: i128,
+    pub fee_payer: FeePayer,
+    pub partner_share: i128,
+    pub withheld: i128,
+    pub amount_out: i128,
+    // Change in each brand's circulating supply caused by the hop
+    pub from_supply_change: Points,
+    pub to_supply_change: Points,
+}
+
+// Mapping for multi-hop receipts: Op -> Settlement of each hop, in order
+// Receipts live in persistent storage, one entry per operation
+#[contracttype]
+pub enum HopBook {
+    Hops(u64),
+}
+
+// Most hops one exchange may route through
+pub const MAX_HOPS: u32 = 4;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Exchange tokens along `path`, converting the whole output of each hop
+    /// into the next brand; fails if the final output is below `min_out`
+    /// Deprecated: forwards to `exchange_via_v2` with no sequence number
+    pub fn exchange_via(
+        env: Env,
+        user: Address,
+        path: Vec<u64>,
+        amount: i128,
+        min_out: i128,
+    ) -> u64 {
+        Self::exchange_via_v2(env, user, path, amount, min_out, None)
+    }
+
+    /// Exchange tokens along `path`, converting the whole output of each hop
+    /// into the next brand; fails if the final output is below `min_out`
+    /// `sequence`, if given, must equal the user's next sequence number
+    /// Returns the op_id of the recorded operation; its hop breakdown is
+    /// available through `view_hop_receipt`
+    pub fn exchange_via_v2(
+        env: Env,
+        user: Address,
+        path: Vec<u64>,
+        amount: i128,
+        min_out: i128,
+        sequence: Option<u64>,
+    ) -> u64 {
+        user.require_auth();
+        consume_sequence(&env, &user, sequence);
+        if path.len() < 2 || path.len() > MAX_HOPS + 1 {
+            panic!("Invalid exchange path");
+        }
+        let from_brand = path.get_unchecked(0);
+        let to_brand = path.get_unchecked(path.len() - 1);
+        alert_large_exchange(&env, &user, from_brand, amount);
+        record_exchange_volume(&env, &user, amount);
+
+        let mut hops = Vec::new(&env);
+        let mut rates = Vec::new(&env);
+        let mut amount_in = amount;
+        for index in 0..path.len() - 1 {
+            let hop_from = path.get_unchecked(index);
+            let hop_to = path.get_unchecked(index + 1);
+            if let Err(failure) = check_exchange(&env, &user, hop_from, hop_to, amount_in) {
+                failure.fail();
+            }
+
+            let from_supply = brand_supply(&env, hop_from);
+            let to_supply = brand_supply(&env, hop_to);
+            let quote = quote(&env, Some(&user), hop_from, hop_to, amount_in);
+            settle_exchange(&env, &user, &quote);
+
+            hops.push_back(HopSettlement {
+                from_brand: hop_from,
+                to_brand: hop_to,
+                amount_in,
+                burned: quote.burned,
+                fee: quote.fee,
+                fee_payer: quote.fee_payer,
+                partner_share: quote.partner_share,
+                withheld: quote.withheld,
+                amount_out: quote.amount_out,
+                from_supply_change: brand_supply(&env, hop_from).saturating_sub(from_supply),
+                to_supply_change: brand_supply(&env, hop_to).saturating_sub(to_supply),
+            });
+            rates.push_back(quote.rate);
+            amount_in = quote.amount_out;
+        }
+        if amount_in < min_out {
+            panic!("Output below minimum");
+        }
+        roll_reward_drop(&env, &user, to_brand);
+
+        let mut record =
+            OperationRecord::new(&env, OperationKind::Exchange, &user, from_brand, amount);
+        record.to_brand = Some(to_brand);
+        save_operation(&env, &record);
+        let key = HopBook::Hops(record.op_id);
+        env.storage().persistent().set(&key, &hops);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+        record_route_receipt(&env, record.op_id, &user, &hops, &rates);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Exchanged {} tokens from brand {} → {} tokens of brand {} in {} hops",
+            amount,
+            from_brand,
+            amount_in,
+            to_brand,
+            hops.len()
+        );
+        record.op_id
+    }
+
+    /// View the per-hop settlement of a multi-hop exchange
+    pub fn view_hop_receipt(env: Env, op_id: u64) -> Vec<HopSettlement> {
+        env.storage()
+            .persistent()
+            .get(&HopBook::Hops(op_id))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    #[test]
+    fn test_multi_hop_exchange_attributes_each_hop() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let change_id = client.schedule_change(&Param::RouteRate(hotel, airline), &20_000);
+        client.set_brand_admin(&cafe, &Address::generate(&env));
+        let withholding_change = client.set_tax_withholding(
+            &cafe,
+            &Some(TaxWithholding {
+                rate_bps: 1_000,
+                tax_address: Address::generate(&env),
+            }),
+        );
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        client.execute_change(&withholding_change);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+
+        let path = vec![&env, hotel, airline, cafe];
+        assert!(client.try_exchange_via(&user, &path, &100, &181).is_err());
+        let op_id = client.exchange_via(&user, &path, &100, &180);
+
+        let hops = client.view_hop_receipt(&op_id);
+        assert_eq!(hops.len(), 2);
+        env.as_contract(&contract_id, || {
+            assert!(env.storage().persistent().has(&HopBook::Hops(op_id)));
+        });
+        let first = hops.get_unchecked(0);
+        assert_eq!((first.amount_in, first.amount_out), (100, 200));
+        assert_eq!(
+            (first.from_supply_change, first.to_supply_change),
+            (Points::from(-100), Points::from(200))
+        );
+        let second = hops.get_unchecked(1);
+        assert_eq!((second.withheld, second.amount_out), (20, 180));
+        assert_eq!(
+            (second.from_supply_change, second.to_supply_change),
+            (Points::from(-200), Points::from(200))
+        );
+
+        // The intermediate brand ends with no net liability
+        assert_eq!(client.view_brand_supply(&airline), 0);
+        assert_eq!(client.view_user_balance(&user, &cafe), 180);
+        assert_eq!(client.view_operation(&user, &op_id).to_brand, Some(cafe));
+        assert!(client
+            .try_exchange_via(&user, &vec![&env, hotel], &100, &0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_multi_hop_exchange_uses_sequence_and_can_be_reverted() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        client.set_brand_admin(&cafe, &Address::generate(&env));
+        client.set_regret_window(&hotel, &100);
+        client.set_regret_window(&cafe, &100);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+
+        let path = vec![&env, hotel, airline, cafe];
+        let next = client.view_user_sequence(&user);
+        assert!(client
+            .try_exchange_via_v2(&user, &path, &100, &0, &Some(next + 1))
+            .is_err());
+        let op_id = client.exchange_via_v2(&user, &path, &100, &0, &Some(next));
+        assert_eq!(client.view_user_sequence(&user), next + 1);
+
+        let receipt = client.view_exchange_receipt(&op_id).unwrap();
+        assert_eq!((receipt.from_brand, receipt.to_brand), (hotel, cafe));
+        assert_eq!(receipt.amount_out, client.view_user_balance(&user, &cafe));
+
+        client.revert_exchange(&user, &op_id);
+        assert_eq!(client.view_user_balance(&user, &cafe), 0);
+        assert_eq!(
+            client.view_user_balance(&user, &hotel),
+            400 + receipt.refund
+        );
+        assert_eq!(client.view_brand_supply(&airline), 0);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::*;
+use soroban_sdk::contractclient;
+
+// High-priority activity forwarded to the notifier contract
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlertKind {
+    // Exchange at or above the large exchange threshold
+    LargeExchange,
+    // Velocity rule froze a user's activity with a brand
+    PairFrozen,
+    // Exchange route paused
+    RoutePaused,
+}
+
+// Structure sent to the notifier contract for each alert
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub brand_id: u64,
+    pub user: Option<Address>,
+    pub amount: i128,
+    pub ledger: u32,
+}
+
+// Interface the notifier contract implements
+#[contractclient(name = "NotifierClient")]
+pub trait Notifier {
+    fn notify(env: Env, alert: Alert);
+}
+
+// Contract receiving alerts; unset when alerts are not forwarded
+const NOTIFIER: Symbol = symbol_short!("NOTIFIER");
+
+// Smallest exchange, in source points, forwarded as a large exchange
+const LARGE_EXCHANGE: Symbol = symbol_short!("LARGE_EXC");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Forward high-priority events to a notifier contract as well as emitting
+    /// them; `None` stops forwarding (platform admin only)
+    pub fn set_notifier(env: Env, notifier: Option<Address>) {
+        require_platform_admin(&env);
+
+        match notifier {
+            Some(notifier) => env.storage().instance().set(&NOTIFIER, &notifier),
+            None => env.storage().instance().remove(&NOTIFIER),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Notifier updated");
+    }
+
+    /// View the notifier contract, if alerts are forwarded
+    pub fn view_notifier(env: Env) -> Option<Address> {
+        env.storage().instance().get(&NOTIFIER)
+    }
+
+    /// Set the smallest exchange forwarded as a large exchange; `None` stops
+    /// forwarding exchanges (platform admin only)
+    pub fn set_large_exchange_threshold(env: Env, threshold: Option<i128>) {
+        require_platform_admin(&env);
+
+        match threshold {
+            Some(threshold) => {
+                if threshold <= 0 {
+                    panic!("Threshold must be positive");
+                }
+                env.storage().instance().set(&LARGE_EXCHANGE, &threshold);
+            }
+            None => env.storage().instance().remove(&LARGE_EXCHANGE),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Large exchange threshold updated");
+    }
+
+    /// View the smallest exchange forwarded as a large exchange, if any
+    pub fn view_large_exchange_threshold(env: Env) -> Option<i128> {
+        env.storage().instance().get(&LARGE_EXCHANGE)
+    }
+}
+
+// Forward an exchange to the notifier if it reaches the large exchange threshold
+pub(crate) fn alert_large_exchange(env: &Env, user: &Address, from_brand: u64, amount: i128) {
+    let Some(threshold) = LoyaltyTokenExchange::view_large_exchange_threshold(env.clone()) else {
+        return;
+    };
+    if amount >= threshold {
+        forward_alert(
+            env,
+            AlertKind::LargeExchange,
+            from_brand,
+            Some(user),
+            amount,
+        );
+    }
+}
+
+// Send an alert to the notifier contract, if one is set
+// A failing notifier is ignored so alerting can never block the operation
+pub(crate) fn forward_alert(
+    env: &Env,
+    kind: AlertKind,
+    brand_id: u64,
+    user: Option<&Address>,
+    amount: i128,
+) {
+    let Some(notifier) = LoyaltyTokenExchange::view_notifier(env.clone()) else {
+        return;
+    };
+    let alert = Alert {
+        kind,
+        brand_id,
+        user: user.cloned(),
+        amount,
+        ledger: env.ledger().sequence(),
+    };
+    let _ = NotifierClient::new(env, &notifier).try_notify(&alert);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+    // Notifier keeping every alert it receives
+    #[contract]
+    struct RecordingNotifier;
+
+    #[contractimpl]
+    impl RecordingNotifier {
+        pub fn notify(env: Env, alert: Alert) {
+            let mut alerts = Self::recorded_alerts(env.clone());
+            alerts.push_back(alert);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("ALERTS"), &alerts);
+        }
+
+        pub fn recorded_alerts(env: Env) -> Vec<Alert> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("ALERTS"))
+                .unwrap_or(Vec::new(&env))
+        }
+    }
+
+    #[test]
+    fn test_large_exchanges_and_pauses_forwarded_to_notifier() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_platform_admin(&admin);
+
+        let notifier_id = env.register(RecordingNotifier, ());
+        let notifier = RecordingNotifierClient::new(&env, &notifier_id);
+        client.set_notifier(&Some(notifier_id));
+        client.set_large_exchange_threshold(&Some(500));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        assert!(notifier.recorded_alerts().is_empty());
+        client.exchange_tokens(&user, &hotel, &airline, &600);
+        client.pause_route(&admin, &hotel, &airline);
+
+        let alerts = notifier.recorded_alerts();
+        assert_eq!(alerts.len(), 2);
+        let large = alerts.get_unchecked(0);
+        assert_eq!(large.kind, AlertKind::LargeExchange);
+        assert_eq!((large.brand_id, large.amount), (hotel, 600));
+        assert_eq!(large.user, Some(user));
+        assert_eq!(alerts.get_unchecked(1).kind, AlertKind::RoutePaused);
+    }
+}
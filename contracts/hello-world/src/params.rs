@@ -0,0 +1,477 @@
+use crate::*;
+
+// Parameters that can only change through the timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Param {
+    // Platform fee on every exchange, in basis points
+    ExchangeFeeBps,
+    // (From, To) -> Rate overriding the coalition or parity rate
+    RouteRate(u64, u64),
+    // Brand -> Maximum circulating supply reachable through issuance
+    SupplyCap(u64),
+    // Brand -> Share of outbound exchanges burned, in basis points
+    ExchangeBurnBps(u64),
+    // Brand -> Peg the brand converts at within its coalition
+    CoalitionPeg(u64),
+    // Coalition -> 1 when member conversions settle only through pegs
+    PegSettlement(u64),
+    // Program -> Exchange fee on the program's routes, in basis points
+    ProgramFeeBps(u64),
+    // (From, To) -> Share of the platform fee paid to the source brand, in basis points
+    RevenueShare(u64, u64),
+    // Fee discount tiers; the value is the number of tiers queued with the change
+    FeeDiscountSchedule,
+    // Spread taken off derived reverse rates, in basis points
+    ReciprocalSpread,
+    // Brand -> Withholding on exchanges into the brand, in basis points; the
+    // tax address is queued with the change
+    TaxWithholding(u64),
+    // Brand -> Share of inactive balances decayed, in basis points; the
+    // inactivity period is queued with the change
+    DecayPolicy(u64),
+}
+
+impl Param {
+    // Brand a parameter belongs to; the source brand for route rates
+    pub fn brand_id(&self) -> u64 {
+        match self {
+            Param::RouteRate(from_brand, _) | Param::RevenueShare(from_brand, _) => *from_brand,
+            Param::SupplyCap(brand_id)
+            | Param::ExchangeBurnBps(brand_id)
+            | Param::CoalitionPeg(brand_id)
+            | Param::TaxWithholding(brand_id)
+            | Param::DecayPolicy(brand_id) => *brand_id,
+            Param::ExchangeFeeBps
+            | Param::PegSettlement(_)
+            | Param::ProgramFeeBps(_)
+            | Param::FeeDiscountSchedule
+            | Param::ReciprocalSpread => PLATFORM_BRAND_ID,
+        }
+    }
+}
+
+// Structure to store a queued parameter change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledChange {
+    pub change_id: u64,
+    pub param: Param,
+    pub value: i128,
+    // First ledger at which the change can be executed
+    pub eta: u32,
+}
+
+// Mapping for parameters and their queued changes
+#[contracttype]
+pub enum ParamBook {
+    Value(Param),
+    Change(u64),
+}
+
+// Counter for scheduled changes
+const CHANGE_COUNT: Symbol = symbol_short!("CH_COUNT");
+
+// Delay between scheduling and executing a change (about one day)
+pub const TIMELOCK_LEDGERS: u32 = 17_280;
+
+// Value clearing an optional parameter (program fee, reciprocal spread)
+pub const PARAM_UNSET: i128 = -1;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Queue a parameter change that can be executed after TIMELOCK_LEDGERS
+    /// Platform fees, route rates and revenue shares are managed by the platform
+    /// admin, supply caps, burns and pegs by the brand admin and program fees by
+    /// the program admin; peg settlement, discount tiers, tax withholding and
+    /// decay policies are scheduled through their own setters
+    /// Returns the change_id of the queued change
+    pub fn schedule_change(env: Env, param: Param, value: i128) -> u64 {
+        require_param_admin(&env, &param);
+        queue_change(&env, param, value)
+    }
+
+    /// Apply a queued change once its timelock has passed; anyone may call this
+    /// The value is validated again, as bounds may have changed since scheduling
+    pub fn execute_change(env: Env, change_id: u64) {
+        let change = load_change(&env, change_id);
+        if env.ledger().sequence() < change.eta {
+            panic!("Timelock has not expired");
+        }
+        validate_change(&env, &change.param, change.value);
+
+        if let Some(admin) = param_admin(&env, &change.param) {
+            log_admin_action(
+                &env,
+                &admin,
+                AdminAction::ParamChanged,
+                change.param.brand_id(),
+                param_value(&env, &change.param),
+                Some(change.value),
+            );
+        }
+        let key = ParamBook::Value(change.param.clone());
+        if change.value == PARAM_UNSET {
+            env.storage().instance().remove(&key);
+        } else {
+            env.storage().instance().set(&key, &change.value);
+        }
+        env.storage()
+            .instance()
+            .remove(&ParamBook::Change(change_id));
+        match change.param {
+            Param::RouteRate(from_brand, to_brand) => {
+                record_rate_snapshot(&env, from_brand, to_brand, change.value as u32);
+                derive_reciprocal_rate(&env, from_brand, to_brand, change.value as u32);
+            }
+            Param::CoalitionPeg(brand_id) => {
+                apply_coalition_peg(&env, brand_id, change.value as u32)
+            }
+            Param::FeeDiscountSchedule => apply_discount_schedule(&env, change_id),
+            Param::TaxWithholding(brand_id) => apply_tax_withholding(&env, brand_id, change_id),
+            Param::DecayPolicy(brand_id) => apply_decay_policy(&env, brand_id, change_id),
+            _ => {}
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        ChangeExecuted {
+            brand_id: change.param.brand_id(),
+            change_id,
+            param: change.param,
+            value: change.value,
+        }
+        .publish(&env);
+    }
+
+    /// Withdraw a queued change (same admin as for scheduling)
+    pub fn cancel_change(env: Env, change_id: u64) {
+        let change = load_change(&env, change_id);
+        require_param_admin(&env, &change.param);
+        drop_change(&env, change_id);
+    }
+
+    /// View a queued change, if it is still pending
+    pub fn view_scheduled_change(env: Env, change_id: u64) -> Option<ScheduledChange> {
+        env.storage().instance().get(&ParamBook::Change(change_id))
+    }
+
+    /// View the current value of a parameter, if it has been set
+    pub fn view_param(env: Env, param: Param) -> Option<i128> {
+        param_value(&env, &param)
+    }
+}
+
+pub(crate) fn param_value(env: &Env, param: &Param) -> Option<i128> {
+    env.storage()
+        .instance()
+        .get(&ParamBook::Value(param.clone()))
+}
+
+// Queue a change whose admin has already authorized it
+// Returns the change_id of the queued change
+pub(crate) fn queue_change(env: &Env, param: Param, value: i128) -> u64 {
+    validate_change(env, &param, value);
+
+    let mut change_count: u64 = env.storage().instance().get(&CHANGE_COUNT).unwrap_or(0);
+    change_count += 1;
+
+    let change = ScheduledChange {
+        change_id: change_count,
+        param: param.clone(),
+        value,
+        eta: env.ledger().sequence() + TIMELOCK_LEDGERS,
+    };
+    env.storage()
+        .instance()
+        .set(&ParamBook::Change(change_count), &change);
+    env.storage().instance().set(&CHANGE_COUNT, &change_count);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    ChangeScheduled {
+        brand_id: param.brand_id(),
+        change_id: change_count,
+        param,
+        value,
+        eta: change.eta,
+    }
+    .publish(env);
+    change_count
+}
+
+// Withdraw a queued change whose admin has already authorized it
+pub(crate) fn drop_change(env: &Env, change_id: u64) {
+    let change = load_change(env, change_id);
+    env.storage()
+        .instance()
+        .remove(&ParamBook::Change(change_id));
+    match change.param {
+        Param::FeeDiscountSchedule => drop_discount_schedule(env, change_id),
+        Param::TaxWithholding(_) => drop_tax_withholding(env, change_id),
+        Param::DecayPolicy(_) => drop_decay_policy(env, change_id),
+        _ => {}
+    }
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    ChangeCancelled {
+        brand_id: change.param.brand_id(),
+        change_id,
+        param: change.param,
+    }
+    .publish(env);
+}
+
+pub(crate) fn load_change(env: &Env, change_id: u64) -> ScheduledChange {
+    env.storage()
+        .instance()
+        .get(&ParamBook::Change(change_id))
+        .unwrap_or_else(|| panic!("Scheduled change not found"))
+}
+
+fn require_param_admin(env: &Env, param: &Param) {
+    match param {
+        Param::SupplyCap(brand_id) | Param::ExchangeBurnBps(brand_id) => {
+            load_brand(env, *brand_id);
+            require_brand_admin(env, *brand_id);
+        }
+        Param::CoalitionPeg(brand_id) => {
+            require_brand_admin(env, *brand_id);
+        }
+        Param::ProgramFeeBps(program_id) => {
+            require_program_admin(env, *program_id);
+        }
+        Param::RouteRate(from_brand, to_brand) | Param::RevenueShare(from_brand, to_brand) => {
+            match route_program(env, *from_brand, *to_brand) {
+                Some(program_id) => {
+                    require_program_admin(env, program_id);
+                }
+                None => {
+                    require_platform_admin(env);
+                }
+            }
+        }
+        Param::ExchangeFeeBps | Param::ReciprocalSpread => {
+            require_platform_admin(env);
+        }
+        Param::PegSettlement(_)
+        | Param::FeeDiscountSchedule
+        | Param::TaxWithholding(_)
+        | Param::DecayPolicy(_) => {
+            panic!("Change must be scheduled through its setter");
+        }
+    }
+}
+
+// Admin governing a parameter, credited with its changes in the admin log;
+// `None` for changes governed by a coalition council
+fn param_admin(env: &Env, param: &Param) -> Option<Address> {
+    let admin = match param {
+        Param::SupplyCap(brand_id)
+        | Param::ExchangeBurnBps(brand_id)
+        | Param::CoalitionPeg(brand_id)
+        | Param::TaxWithholding(brand_id)
+        | Param::DecayPolicy(brand_id) => brand_admin(env, *brand_id),
+        Param::ProgramFeeBps(program_id) => {
+            Some(LoyaltyTokenExchange::view_program(env.clone(), *program_id).admin)
+        }
+        Param::RouteRate(from_brand, to_brand) | Param::RevenueShare(from_brand, to_brand) => {
+            match route_program(env, *from_brand, *to_brand) {
+                Some(program_id) => {
+                    Some(LoyaltyTokenExchange::view_program(env.clone(), program_id).admin)
+                }
+                None => LoyaltyTokenExchange::get_platform_admin(env.clone()),
+            }
+        }
+        Param::ExchangeFeeBps | Param::FeeDiscountSchedule | Param::ReciprocalSpread => {
+            LoyaltyTokenExchange::get_platform_admin(env.clone())
+        }
+        Param::PegSettlement(_) => return None,
+    };
+    Some(admin.unwrap_or_else(|| panic!("Admin not set")))
+}
+
+// Check a change against its range and the current state of the contract
+fn validate_change(env: &Env, param: &Param, value: i128) {
+    validate_param(param, value);
+    match param {
+        Param::RouteRate(..) => check_rate_bounds(env, value as u32),
+        Param::CoalitionPeg(brand_id)
+            if LoyaltyTokenExchange::view_coalition_membership(env.clone(), *brand_id)
+                .is_none() =>
+        {
+            panic!("Brand is not in a coalition");
+        }
+        Param::RevenueShare(from_brand, to_brand) => {
+            load_brand(env, *from_brand);
+            load_brand(env, *to_brand);
+        }
+        Param::ProgramFeeBps(program_id) => {
+            LoyaltyTokenExchange::view_program(env.clone(), *program_id);
+        }
+        Param::TaxWithholding(brand_id) | Param::DecayPolicy(brand_id) => {
+            load_brand(env, *brand_id);
+        }
+        _ => {}
+    }
+}
+
+fn validate_param(param: &Param, value: i128) {
+    match param {
+        Param::ExchangeFeeBps => {
+            if !(0..=RATE_DENOMINATOR).contains(&value) {
+                panic!("Fee must be between 0 and 10000 bps");
+            }
+        }
+        Param::RouteRate(from_brand, to_brand) => {
+            if from_brand == to_brand {
+                panic!("Cannot exchange to the same brand");
+            }
+            if value <= 0 || value > u32::MAX as i128 {
+                panic!("Rate out of range");
+            }
+        }
+        Param::SupplyCap(_) => {
+            if value < 0 {
+                panic!("Cap cannot be negative");
+            }
+        }
+        Param::ExchangeBurnBps(_) => {
+            if !(0..=RATE_DENOMINATOR).contains(&value) {
+                panic!("Burn cannot exceed 100%");
+            }
+        }
+        Param::RevenueShare(..) => {
+            if !(0..=RATE_DENOMINATOR).contains(&value) {
+                panic!("Share cannot exceed 100%");
+            }
+        }
+        Param::CoalitionPeg(_) => {
+            if value <= 0 || value > u32::MAX as i128 {
+                panic!("Peg must be positive");
+            }
+        }
+        Param::PegSettlement(_) => {
+            if value != 0 && value != 1 {
+                panic!("Peg settlement must be 0 or 1");
+            }
+        }
+        Param::ProgramFeeBps(_) => {
+            if value != PARAM_UNSET && !(0..RATE_DENOMINATOR).contains(&value) {
+                panic!("Fee must be below 10000 bps");
+            }
+        }
+        Param::ReciprocalSpread => {
+            if value != PARAM_UNSET && !(0..RATE_DENOMINATOR).contains(&value) {
+                panic!("Spread must be below 10000 bps");
+            }
+        }
+        Param::FeeDiscountSchedule => {
+            if value < 0 {
+                panic!("Invalid discount tier");
+            }
+        }
+        Param::TaxWithholding(_) => {
+            if value != PARAM_UNSET && !(0..RATE_DENOMINATOR).contains(&value) {
+                panic!("Withholding must be below 10000 bps");
+            }
+        }
+        Param::DecayPolicy(_) => {
+            if value <= 0 || value > RATE_DENOMINATOR {
+                panic!("Invalid decay rate");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let hotel = client.register_brand(&String::from_str(env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(env, "Airline"));
+        (client, hotel, airline)
+    }
+
+    #[test]
+    fn test_fee_and_rate_changes_wait_for_timelock() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, airline) = setup(&env);
+
+        let fee_change = client.schedule_change(&Param::ExchangeFeeBps, &100);
+        let rate_change = client.schedule_change(&Param::RouteRate(hotel, airline), &20000);
+        assert!(client.try_execute_change(&fee_change).is_err());
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&fee_change);
+        client.execute_change(&rate_change);
+        assert_eq!(client.view_param(&Param::ExchangeFeeBps), Some(100));
+        assert_eq!(client.view_scheduled_change(&fee_change), None);
+
+        // 1% fee on 1000 hotel points, the rest converts at 2:1
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!(quote.fee, 10);
+        assert_eq!(quote.amount_out, 1980);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_user_balance(&user, &airline), 1980);
+        assert_eq!(client.view_collected_fees(&hotel), 10);
+    }
+
+    #[test]
+    fn test_supply_cap_limits_issuance() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, _) = setup(&env);
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+
+        let change_id = client.schedule_change(&Param::SupplyCap(hotel), &500);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+        assert_eq!(
+            client.can_issue(&hotel, &1),
+            Some(CheckFailure::SupplyCapExceeded)
+        );
+    }
+
+    #[test]
+    fn test_execute_rechecks_rate_bounds() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, airline) = setup(&env);
+
+        let change_id = client.schedule_change(&Param::RouteRate(hotel, airline), &50_000);
+        client.set_rate_bounds(&1_000, &40_000);
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        assert!(client.try_execute_change(&change_id).is_err());
+        assert_eq!(client.view_param(&Param::RouteRate(hotel, airline)), None);
+    }
+
+    #[test]
+    fn test_cancelled_change_cannot_execute() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, _) = setup(&env);
+
+        let change_id = client.schedule_change(&Param::ExchangeFeeBps, &50);
+        client.cancel_change(&change_id);
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        assert!(client.try_execute_change(&change_id).is_err());
+        assert_eq!(client.view_param(&Param::ExchangeFeeBps), None);
+    }
+}
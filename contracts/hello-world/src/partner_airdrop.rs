@@ -0,0 +1,261 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a brand's airdrop to the holders of a partner brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartnerAirdrop {
+    pub airdrop_id: u64,
+    // Brand whose points are dropped, funded from its treasury
+    pub brand_id: u64,
+    // Brand whose holders are eligible
+    pub holder_brand: u64,
+    // Snapshot of the holder brand eligibility is checked against
+    pub snapshot_id: u64,
+    pub min_balance: i128,
+    pub amount_per_holder: i128,
+    // Points left to drop
+    pub budget: i128,
+    pub recipients: u32,
+    pub is_open: bool,
+}
+
+// Mapping for partner airdrops
+#[contracttype]
+pub enum AirdropBook {
+    Airdrop(u64),
+    // (Airdrop, Holder) -> Whether the holder received the drop
+    Received(u64, Address),
+}
+
+// Counter for partner airdrops
+const AIRDROP_COUNT: Symbol = symbol_short!("AD_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Drop `amount_per_holder` points of `brand_id` to every holder of
+    /// `holder_brand` with at least `min_balance` points right now, funded by
+    /// `budget` points from the brand treasury (brand admin only)
+    /// Holders claim their drop, or the brand pushes it to them in batches
+    /// Returns the airdrop_id
+    pub fn create_partner_airdrop(
+        env: Env,
+        brand_id: u64,
+        holder_brand: u64,
+        min_balance: i128,
+        amount_per_holder: i128,
+        budget: i128,
+    ) -> u64 {
+        load_brand(&env, brand_id);
+        load_brand(&env, holder_brand);
+        require_brand_admin(&env, brand_id);
+        if brand_id == holder_brand {
+            panic!("Cannot airdrop to the brand's own holders");
+        }
+        if min_balance <= 0 || amount_per_holder <= 0 || budget < amount_per_holder {
+            panic!("Amount must be positive");
+        }
+        let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand treasury not set"));
+        escrow(&env, &treasury, brand_id, budget);
+
+        let mut airdrop_count: u64 = env.storage().instance().get(&AIRDROP_COUNT).unwrap_or(0);
+        airdrop_count += 1;
+
+        let airdrop = PartnerAirdrop {
+            airdrop_id: airdrop_count,
+            brand_id,
+            holder_brand,
+            snapshot_id: snapshot(&env, holder_brand),
+            min_balance,
+            amount_per_holder,
+            budget,
+            recipients: 0,
+            is_open: true,
+        };
+        save_airdrop(&env, &airdrop);
+        env.storage().instance().set(&AIRDROP_COUNT, &airdrop_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Airdrop {} to holders of brand {} created",
+            airdrop_count,
+            holder_brand
+        );
+        airdrop_count
+    }
+
+    /// Claim a partner airdrop the user is eligible for
+    /// Returns the amount credited
+    pub fn claim_partner_airdrop(env: Env, user: Address, airdrop_id: u64) -> i128 {
+        require_user(&env, &user);
+
+        let mut airdrop = Self::view_partner_airdrop(env.clone(), airdrop_id);
+        if !is_eligible(&env, &airdrop, &user) {
+            panic!("Not eligible for airdrop");
+        }
+        if airdrop.budget < airdrop.amount_per_holder {
+            panic!("Airdrop budget exhausted");
+        }
+        deliver(&env, &mut airdrop, &user);
+        save_airdrop(&env, &airdrop);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Airdrop {} claimed", airdrop_id);
+        airdrop.amount_per_holder
+    }
+
+    /// Deliver a partner airdrop to a batch of holders (brand admin only)
+    /// Ineligible holders and those already served are skipped
+    /// Returns the number of holders credited
+    pub fn push_partner_airdrop(env: Env, airdrop_id: u64, holders: Vec<Address>) -> u32 {
+        let mut airdrop = Self::view_partner_airdrop(env.clone(), airdrop_id);
+        require_brand_admin(&env, airdrop.brand_id);
+
+        let mut delivered: u32 = 0;
+        for holder in holders.iter() {
+            if airdrop.budget < airdrop.amount_per_holder {
+                break;
+            }
+            if !is_eligible(&env, &airdrop, &holder) {
+                continue;
+            }
+            deliver(&env, &mut airdrop, &holder);
+            delivered += 1;
+        }
+        save_airdrop(&env, &airdrop);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Airdrop {} pushed to {} holders",
+            airdrop_id,
+            delivered
+        );
+        delivered
+    }
+
+    /// Close a partner airdrop and return its remaining budget to the brand
+    /// treasury (brand admin only)
+    pub fn close_partner_airdrop(env: Env, airdrop_id: u64) {
+        let mut airdrop = Self::view_partner_airdrop(env.clone(), airdrop_id);
+        require_brand_admin(&env, airdrop.brand_id);
+        if !airdrop.is_open {
+            panic!("Airdrop is closed");
+        }
+
+        if airdrop.budget > 0 {
+            let treasury = Self::view_brand_treasury(env.clone(), airdrop.brand_id)
+                .unwrap_or_else(|| panic!("Brand treasury not set"));
+            release(&env, &treasury, airdrop.brand_id, airdrop.budget);
+        }
+        airdrop.budget = 0;
+        airdrop.is_open = false;
+        save_airdrop(&env, &airdrop);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Airdrop {} closed", airdrop_id);
+    }
+
+    /// View a partner airdrop by airdrop_id
+    pub fn view_partner_airdrop(env: Env, airdrop_id: u64) -> PartnerAirdrop {
+        env.storage()
+            .instance()
+            .get(&AirdropBook::Airdrop(airdrop_id))
+            .unwrap_or_else(|| panic!("Airdrop not found"))
+    }
+
+    /// View whether a holder can still receive a partner airdrop
+    pub fn is_airdrop_eligible(env: Env, airdrop_id: u64, holder: Address) -> bool {
+        let airdrop = Self::view_partner_airdrop(env.clone(), airdrop_id);
+        is_eligible(&env, &airdrop, &holder)
+    }
+}
+
+// Open, not yet served, and holding enough at the snapshot
+fn is_eligible(env: &Env, airdrop: &PartnerAirdrop, holder: &Address) -> bool {
+    if !airdrop.is_open {
+        return false;
+    }
+    if env
+        .storage()
+        .instance()
+        .has(&AirdropBook::Received(airdrop.airdrop_id, holder.clone()))
+    {
+        return false;
+    }
+    LoyaltyTokenExchange::balance_at_snapshot(env.clone(), holder.clone(), airdrop.snapshot_id)
+        >= airdrop.min_balance
+}
+
+fn deliver(env: &Env, airdrop: &mut PartnerAirdrop, holder: &Address) {
+    release(env, holder, airdrop.brand_id, airdrop.amount_per_holder);
+    airdrop.budget -= airdrop.amount_per_holder;
+    airdrop.recipients += 1;
+    env.storage().instance().set(
+        &AirdropBook::Received(airdrop.airdrop_id, holder.clone()),
+        &true,
+    );
+}
+
+fn save_airdrop(env: &Env, airdrop: &PartnerAirdrop) {
+    env.storage()
+        .instance()
+        .set(&AirdropBook::Airdrop(airdrop.airdrop_id), airdrop);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+    #[test]
+    fn test_partner_airdrop_reaches_holders_at_snapshot() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        client.set_brand_admin(&airline, &Address::generate(&env));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&hotel, &treasury);
+        client.issue_tokens(&treasury, &hotel, &1000, &None);
+
+        let frequent = Address::generate(&env);
+        let steady = Address::generate(&env);
+        let occasional = Address::generate(&env);
+        let latecomer = Address::generate(&env);
+        client.issue_tokens(&frequent, &airline, &500, &None);
+        client.issue_tokens(&steady, &airline, &150, &None);
+        client.issue_tokens(&occasional, &airline, &50, &None);
+
+        let airdrop_id = client.create_partner_airdrop(&hotel, &airline, &100, &200, &500);
+        assert_eq!(client.view_user_balance(&treasury, &hotel), 500);
+
+        // Balances after the snapshot do not count
+        client.issue_tokens(&latecomer, &airline, &500, &None);
+        client.transfer_tokens(&frequent, &latecomer, &airline, &500, &None);
+        assert!(client
+            .try_claim_partner_airdrop(&latecomer, &airdrop_id)
+            .is_err());
+
+        client.claim_partner_airdrop(&frequent, &airdrop_id);
+        assert_eq!(client.view_user_balance(&frequent, &hotel), 200);
+        assert_eq!(
+            client.push_partner_airdrop(
+                &airdrop_id,
+                &vec![&env, frequent, steady.clone(), occasional, latecomer]
+            ),
+            1
+        );
+        assert_eq!(client.view_user_balance(&steady, &hotel), 200);
+
+        client.close_partner_airdrop(&airdrop_id);
+        assert_eq!(client.view_user_balance(&treasury, &hotel), 600);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::*;
+
+// Structure returned to a partner contract as proof that points were burned
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BurnReceipt {
+    pub op_id: u64,
+    pub partner: Address,
+    pub user: Address,
+    pub brand_id: u64,
+    pub amount: i128,
+    pub ledger: u32,
+}
+
+// Mapping for burn partners: (Brand, Partner contract) -> Whether it may burn
+#[contracttype]
+pub enum BurnPartnerBook {
+    Partner(u64, Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Allow or stop a partner contract burning the brand's points as payment
+    /// (brand admin only)
+    pub fn set_burn_partner(env: Env, brand_id: u64, partner: Address, allowed: bool) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = BurnPartnerBook::Partner(brand_id, partner);
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Burn partner updated for brand {}", brand_id);
+    }
+
+    /// View whether a partner contract may burn a brand's points
+    pub fn is_burn_partner(env: Env, brand_id: u64, partner: Address) -> bool {
+        env.storage()
+            .instance()
+            .get(&BurnPartnerBook::Partner(brand_id, partner))
+            .unwrap_or(false)
+    }
+
+    /// Burn a user's points as payment to a partner contract, which gets the
+    /// receipt back in the same invocation to complete its side atomically
+    pub fn burn_for(
+        env: Env,
+        partner_contract: Address,
+        user: Address,
+        brand_id: u64,
+        amount: i128,
+    ) -> BurnReceipt {
+        partner_contract.require_auth();
+        require_user(&env, &user);
+        if !Self::is_burn_partner(env.clone(), brand_id, partner_contract.clone()) {
+            panic!("Not a burn partner of this brand");
+        }
+        if !is_brand_active(&env, brand_id) {
+            panic!("Brand is not active");
+        }
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if read_balance(&env, &user, brand_id) < amount {
+            panic!("Insufficient balance");
+        }
+
+        debit(&env, &user, brand_id, amount);
+        record_burn(&env, brand_id, amount);
+        let mut record = OperationRecord::new(&env, OperationKind::Burn, &user, brand_id, amount);
+        record.counterparty = Some(partner_contract.clone());
+        save_operation(&env, &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ {} points burned for partner", amount);
+        BurnReceipt {
+            op_id: record.op_id,
+            partner: partner_contract,
+            user,
+            brand_id,
+            amount,
+            ledger: record.ledger,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_partner_burns_points_for_receipt() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cinema"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let ticketing = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        assert!(client
+            .try_burn_for(&ticketing, &user, &brand_id, &100)
+            .is_err());
+
+        client.set_burn_partner(&brand_id, &ticketing, &true);
+        let receipt = client.burn_for(&ticketing, &user, &brand_id, &200);
+        assert_eq!(receipt.amount, 200);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 300);
+        assert_eq!(client.view_brand_supply(&brand_id), 300);
+
+        let record = client.view_operation(&user, &receipt.op_id);
+        assert_eq!(record.kind, OperationKind::Burn);
+        assert_eq!(record.counterparty, Some(ticketing.clone()));
+        assert!(client
+            .try_burn_for(&ticketing, &user, &brand_id, &301)
+            .is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::*;
+
+// Structure summarizing the platform's settings and size in a single view
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformConfig {
+    pub version: String,
+    pub admin: Option<Address>,
+    pub exchange_fee_bps: u32,
+    // Spread taken off derived reverse rates, if rates are derived
+    pub reciprocal_spread_bps: Option<u32>,
+    pub event_verbosity: EventVerbosity,
+    // Ledgers every write keeps contract and balance storage alive for
+    pub ttl_ledgers: u32,
+    pub zero_balance_cleanup: bool,
+    pub invite_only: bool,
+    pub paused_routes: u32,
+    pub brand_count: u64,
+    pub operation_count: u64,
+    pub reward_count: u64,
+}
+
+// Ledgers storage is extended to on every write
+pub const TTL_LEDGERS: u32 = 100000;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View the platform's admin, fees, storage policy, pause state, version
+    /// and counts in one call, for explorers and health dashboards
+    pub fn platform_config(env: Env) -> PlatformConfig {
+        PlatformConfig {
+            version: String::from_str(&env, env!("CARGO_PKG_VERSION")),
+            admin: Self::get_platform_admin(env.clone()),
+            exchange_fee_bps: param_value(&env, &Param::ExchangeFeeBps).unwrap_or(0) as u32,
+            reciprocal_spread_bps: Self::view_reciprocal_spread(env.clone()),
+            event_verbosity: Self::view_event_verbosity(env.clone()),
+            ttl_ledgers: TTL_LEDGERS,
+            zero_balance_cleanup: Self::view_zero_balance_cleanup(env.clone()),
+            invite_only: Self::is_invite_only(env.clone()),
+            paused_routes: Self::view_paused_routes(env.clone()).len(),
+            brand_count: Self::get_brand_count(env.clone()),
+            operation_count: operation_count(&env),
+            reward_count: reward_count(&env),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_platform_config_reflects_settings_and_counts() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let config = client.platform_config();
+        assert_eq!(config.admin, None);
+        assert_eq!(config.brand_count, 0);
+        assert_eq!(config.ttl_ledgers, TTL_LEDGERS);
+
+        let admin = Address::generate(&env);
+        client.set_platform_admin(&admin);
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.issue_tokens(&Address::generate(&env), &hotel, &100, &None);
+        client.pause_route(&admin, &hotel, &airline);
+        client.set_event_verbosity(&EventVerbosity::Minimal);
+
+        let config = client.platform_config();
+        assert_eq!(config.admin, Some(admin));
+        assert_eq!(config.version, String::from_str(&env, "0.0.0"));
+        assert_eq!(config.exchange_fee_bps, 0);
+        assert_eq!(config.event_verbosity, EventVerbosity::Minimal);
+        assert_eq!(config.paused_routes, 1);
+        assert_eq!(config.brand_count, 2);
+        assert_eq!(config.operation_count, 1);
+        assert_eq!(config.reward_count, 0);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+// Amount of brand points. The amount is private so supply and balance math
+// goes through the checked and saturating helpers, and raw amounts convert
+// in and out explicitly through `From`
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Points(i128);
+
+impl Points {
+    pub const ZERO: Points = Points(0);
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    pub fn checked_add(self, other: Points) -> Option<Points> {
+        self.0.checked_add(other.0).map(Points)
+    }
+
+    pub fn checked_sub(self, other: Points) -> Option<Points> {
+        self.0.checked_sub(other.0).map(Points)
+    }
+
+    pub fn saturating_add(self, other: Points) -> Points {
+        Points(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Points) -> Points {
+        Points(self.0.saturating_sub(other.0))
+    }
+}
+
+impl From<i128> for Points {
+    fn from(amount: i128) -> Self {
+        Points(amount)
+    }
+}
+
+impl From<Points> for i128 {
+    fn from(points: Points) -> Self {
+        points.0
+    }
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View a user's spendable balance of a brand as Points
+    pub fn view_user_points(env: Env, user: Address, brand_id: u64) -> Points {
+        Points::from(read_balance(&env, &user, brand_id))
+    }
+
+    /// View the circulating supply of a brand as Points
+    pub fn view_supply_points(env: Env, brand_id: u64) -> Points {
+        brand_supply(&env, brand_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_points_arithmetic_is_checked() {
+        let max = Points::from(i128::MAX);
+        assert_eq!(max.checked_add(Points::from(1)), None);
+        assert_eq!(Points::from(i128::MIN).checked_sub(Points::from(1)), None);
+        assert_eq!(max.saturating_add(Points::from(1)), max);
+        assert_eq!(
+            Points::from(5).checked_sub(Points::from(7)),
+            Some(Points::from(-2))
+        );
+        assert!(!Points::ZERO.is_positive());
+        assert_eq!(i128::from(Points::from(42)), 42);
+    }
+
+    #[test]
+    fn test_balances_cannot_overflow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &i128::MAX, &None);
+        assert_eq!(
+            client.view_user_points(&user, &brand_id),
+            Points::from(i128::MAX)
+        );
+        assert_eq!(
+            client.view_supply_points(&brand_id),
+            Points::from(i128::MAX)
+        );
+        assert!(client
+            .try_issue_tokens(&Address::generate(&env), &brand_id, &1, &None)
+            .is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// A program hosts a group of brands under its own admin, who registers them,
+// appoints their admins and governs the fee and rates of routes between them.
+// Program state is keyed by program_id: its brands, fee, route parameters'
+// admin and the fees collected on its routes. Brands of different programs
+// cannot exchange with each other. Brand ids are unique across programs, so
+// state keyed by brand stays inside the brand's program
+
+// Structure to store a group of brands hosted by the contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Program {
+    pub program_id: u64,
+    pub name: String,
+    pub admin: Address,
+}
+
+// Mapping for programs
+#[contracttype]
+pub enum ProgramBook {
+    // Program -> Program
+    Program(u64),
+    // Brand -> Program it belongs to
+    BrandProgram(u64),
+    // Program -> Brands registered in it
+    Brands(u64),
+    // (Program, Brand) -> Fees collected on the program's routes in the brand's points
+    Fees(u64, u64),
+}
+
+// Counter for program IDs
+const PROGRAM_COUNT: Symbol = symbol_short!("PRG_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Host a new program whose admin registers its brands and governs the
+    /// fee and rates on routes between them (platform admin only)
+    /// Fees on the program's routes are collected under the program
+    /// Brands of different programs cannot exchange with each other
+    pub fn create_program(env: Env, name: String, admin: Address) -> u64 {
+        require_platform_admin(&env);
+
+        let program_id: u64 = env.storage().instance().get(&PROGRAM_COUNT).unwrap_or(0) + 1;
+        let program = Program {
+            program_id,
+            name,
+            admin,
+        };
+        env.storage()
+            .instance()
+            .set(&ProgramBook::Program(program_id), &program);
+        env.storage().instance().set(&PROGRAM_COUNT, &program_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Program registered with ID: {}", program_id);
+        program_id
+    }
+
+    /// Hand a program over to a new admin (program admin only)
+    pub fn set_program_admin(env: Env, program_id: u64, new_admin: Address) {
+        let mut program = require_program_admin(&env, program_id);
+
+        program.admin = new_admin;
+        env.storage()
+            .instance()
+            .set(&ProgramBook::Program(program_id), &program);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Admin updated for program {}", program_id);
+    }
+
+    /// Schedule the exchange fee on routes between the program's brands; `None`
+    /// falls back to the platform fee; it takes effect through
+    /// `execute_change` after the timelock (program admin only)
+    /// Returns the change_id of the queued change
+    pub fn set_program_fee(env: Env, program_id: u64, fee_bps: Option<u32>) -> u64 {
+        let value = fee_bps.map_or(PARAM_UNSET, |fee_bps| fee_bps as i128);
+        Self::schedule_change(env, Param::ProgramFeeBps(program_id), value)
+    }
+
+    /// View the exchange fee on routes between the program's brands, if set
+    pub fn view_program_fee(env: Env, program_id: u64) -> Option<u32> {
+        param_value(&env, &Param::ProgramFeeBps(program_id)).map(|fee_bps| fee_bps as u32)
+    }
+
+    /// Register a brand inside a program, administered by the program admin
+    /// until it appoints a brand admin (program admin only)
+    pub fn register_program_brand(env: Env, program_id: u64, brand_name: String) -> u64 {
+        Self::register_program_brand_v2(env, program_id, brand_name, None)
+    }
+
+    /// Register a brand inside a program through the registration gates:
+    /// `code` redeems an invitation while registration is invite-only, and
+    /// the program admin posts the deposit while one is required
+    pub fn register_program_brand_v2(
+        env: Env,
+        program_id: u64,
+        brand_name: String,
+        code: Option<Bytes>,
+    ) -> u64 {
+        let program = require_program_admin(&env, program_id);
+
+        let brand_id = create_brand(&env, brand_name);
+        admit_brand(&env, brand_id, &program.admin, code);
+        assign_brand_admin(&env, brand_id, &program.admin);
+        join_program(&env, program_id, brand_id);
+        brand_id
+    }
+
+    /// Issue points of a brand inside `program_id`
+    /// Fails if the brand belongs to another program or to none
+    /// Returns the op_id of the recorded operation
+    pub fn issue_in_program(
+        env: Env,
+        program_id: u64,
+        user: Address,
+        brand_id: u64,
+        amount: i128,
+        memo: Option<BytesN<32>>,
+    ) -> u64 {
+        require_program_brand(&env, program_id, brand_id);
+        Self::issue_tokens(env, user, brand_id, amount, memo)
+    }
+
+    /// Exchange points between two brands inside `program_id`
+    /// Fails if either brand belongs to another program or to none
+    /// Returns the op_id of the recorded operation
+    pub fn exchange_in_program(
+        env: Env,
+        program_id: u64,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+        min_out: i128,
+    ) -> u64 {
+        require_program_brand(&env, program_id, from_brand);
+        require_program_brand(&env, program_id, to_brand);
+        Self::exchange_tokens_v3(
+            env,
+            user,
+            from_brand,
+            to_brand,
+            amount,
+            min_out,
+            u32::MAX,
+            None,
+            None,
+        )
+    }
+
+    /// View a user's balance of a brand inside `program_id`
+    pub fn view_program_balance(env: Env, program_id: u64, user: Address, brand_id: u64) -> i128 {
+        require_program_brand(&env, program_id, brand_id);
+        Self::view_user_balance(env, user, brand_id)
+    }
+
+    /// View a program
+    pub fn view_program(env: Env, program_id: u64) -> Program {
+        env.storage()
+            .instance()
+            .get(&ProgramBook::Program(program_id))
+            .unwrap_or_else(|| panic!("Program not found"))
+    }
+
+    /// View the brands registered in a program
+    pub fn view_program_brands(env: Env, program_id: u64) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&ProgramBook::Brands(program_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View the program a brand belongs to; `None` for brands registered
+    /// directly with the platform
+    pub fn view_brand_program(env: Env, brand_id: u64) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&ProgramBook::BrandProgram(brand_id))
+    }
+
+    /// View the exchange fees collected on a program's routes in a brand's points
+    pub fn view_program_fees(env: Env, program_id: u64, brand_id: u64) -> i128 {
+        env.storage()
+            .instance()
+            .get(&ProgramBook::Fees(program_id, brand_id))
+            .unwrap_or(0)
+    }
+}
+
+// Require the program admin's authorization and return the program
+pub(crate) fn require_program_admin(env: &Env, program_id: u64) -> Program {
+    let program = LoyaltyTokenExchange::view_program(env.clone(), program_id);
+    program.admin.require_auth();
+    program
+}
+
+// Add a brand to a program's namespace
+pub(crate) fn join_program(env: &Env, program_id: u64, brand_id: u64) {
+    env.storage()
+        .instance()
+        .set(&ProgramBook::BrandProgram(brand_id), &program_id);
+    let mut brands = LoyaltyTokenExchange::view_program_brands(env.clone(), program_id);
+    brands.push_back(brand_id);
+    env.storage()
+        .instance()
+        .set(&ProgramBook::Brands(program_id), &brands);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Brand {} added to program {}", brand_id, program_id);
+}
+
+// Reject a brand that does not belong to `program_id`
+fn require_program_brand(env: &Env, program_id: u64, brand_id: u64) {
+    if LoyaltyTokenExchange::view_brand_program(env.clone(), brand_id) != Some(program_id) {
+        panic!("Brand is not in this program");
+    }
+}
+
+// Whether two brands belong to the same program, or both to none
+pub(crate) fn same_program(env: &Env, brand_a: u64, brand_b: u64) -> bool {
+    LoyaltyTokenExchange::view_brand_program(env.clone(), brand_a)
+        == LoyaltyTokenExchange::view_brand_program(env.clone(), brand_b)
+}
+
+// Program both brands of a route belong to, if they share one
+pub(crate) fn route_program(env: &Env, from_brand: u64, to_brand: u64) -> Option<u64> {
+    let program_id = LoyaltyTokenExchange::view_brand_program(env.clone(), from_brand)?;
+    if LoyaltyTokenExchange::view_brand_program(env.clone(), to_brand) != Some(program_id) {
+        return None;
+    }
+    Some(program_id)
+}
+
+// Add exchange fees, in the given brand's points, to its program's collected
+// total; returns false for brands outside any program
+pub(crate) fn record_program_fee(env: &Env, brand_id: u64, fee: i128) -> bool {
+    let Some(program_id) = LoyaltyTokenExchange::view_brand_program(env.clone(), brand_id) else {
+        return false;
+    };
+    let collected = LoyaltyTokenExchange::view_program_fees(env.clone(), program_id, brand_id);
+    env.storage()
+        .instance()
+        .set(&ProgramBook::Fees(program_id, brand_id), &(collected + fee));
+    true
+}
+
+// Exchange fee set by the program a brand belongs to, if any
+pub(crate) fn program_fee_bps(env: &Env, brand_id: u64) -> Option<u32> {
+    let program_id = LoyaltyTokenExchange::view_brand_program(env.clone(), brand_id)?;
+    LoyaltyTokenExchange::view_program_fee(env.clone(), program_id)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_programs_keep_exchanges_inside() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let airlines = client.create_program(
+            &String::from_str(&env, "Airlines"),
+            &Address::generate(&env),
+        );
+        let retail =
+            client.create_program(&String::from_str(&env, "Retail"), &Address::generate(&env));
+        let jet = client.register_program_brand(&airlines, &String::from_str(&env, "Jet"));
+        let wing = client.register_program_brand(&airlines, &String::from_str(&env, "Wing"));
+        let shop = client.register_program_brand(&retail, &String::from_str(&env, "Shop"));
+        assert_eq!(client.view_program_brands(&airlines).len(), 2);
+        assert_eq!(client.view_brand_program(&shop), Some(retail));
+
+        // Sub-brands live in their parent's program
+        let jet_east = client.create_sub_brand(&jet, &String::from_str(&env, "Jet East"));
+        assert_eq!(client.view_brand_program(&jet_east), Some(airlines));
+        assert_eq!(client.view_program_brands(&airlines).len(), 3);
+
+        let change_id = client.set_program_fee(&airlines, &Some(100));
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        assert_eq!(client.view_program_fee(&airlines), Some(100));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &jet, &1000, &None);
+        client.exchange_tokens(&user, &jet, &wing, &500);
+        assert_eq!(client.view_user_balance(&user, &wing), 495);
+
+        assert_eq!(
+            client.can_exchange(&user, &jet, &shop, &100),
+            Some(CheckFailure::CrossProgram)
+        );
+        assert!(client
+            .try_exchange_tokens(&user, &jet, &shop, &100)
+            .is_err());
+
+        // Program-scoped entry points reject brands of other programs
+        assert!(client
+            .try_issue_in_program(&airlines, &user, &shop, &100, &None)
+            .is_err());
+        client.issue_in_program(&retail, &user, &shop, &100, &None);
+        assert_eq!(client.view_program_balance(&retail, &user, &shop), 100);
+        assert!(client
+            .try_view_program_balance(&airlines, &user, &shop)
+            .is_err());
+        assert!(client
+            .try_exchange_in_program(&retail, &user, &jet, &wing, &100, &0)
+            .is_err());
+        client.exchange_in_program(&airlines, &user, &jet, &jet_east, &100, &0);
+        assert_eq!(client.view_program_balance(&airlines, &user, &jet), 400);
+    }
+
+    #[test]
+    fn test_programs_govern_their_routes_and_fees() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        let platform_admin = Address::generate(&env);
+        client.set_platform_admin(&platform_admin);
+
+        let program_admin = Address::generate(&env);
+        let airlines = client.create_program(&String::from_str(&env, "Airlines"), &program_admin);
+        let jet = client.register_program_brand(&airlines, &String::from_str(&env, "Jet"));
+        let wing = client.register_program_brand(&airlines, &String::from_str(&env, "Wing"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let bakery = client.register_brand(&String::from_str(&env, "Bakery"));
+
+        // Routes between program brands answer to the program admin, others
+        // to the platform admin
+        let fee_change = client.set_program_fee(&airlines, &Some(100));
+        let rate_change = client.schedule_change(&Param::RouteRate(jet, wing), &20_000);
+        assert_eq!(env.auths()[0].0, program_admin);
+        client.schedule_change(&Param::RouteRate(cafe, bakery), &20_000);
+        assert_eq!(env.auths()[0].0, platform_admin);
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&fee_change);
+        client.execute_change(&rate_change);
+
+        // Fees on the program's routes are collected under the program
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &jet, &1000, &None);
+        client.exchange_tokens(&user, &jet, &wing, &500);
+        assert_eq!(client.view_user_balance(&user, &wing), 990);
+        assert_eq!(client.view_program_fees(&airlines, &jet), 5);
+        assert_eq!(client.view_collected_fees(&jet), 0);
+    }
+}
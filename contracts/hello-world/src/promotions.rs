@@ -0,0 +1,280 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store bonus points escrowed until a user meets a spend target
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Promotion {
+    pub promo_id: u64,
+    pub brand_id: u64,
+    pub user: Address,
+    pub bonus: i128,
+    // Points the user has to redeem with the brand to earn the bonus
+    pub spend_target: i128,
+    pub spent: i128,
+    pub expires_at: u32,
+    pub status: PromotionStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PromotionStatus {
+    Active,
+    // Target met; the bonus was paid to the user
+    Released,
+    // Window passed; the bonus went back to the brand treasury
+    Reclaimed,
+}
+
+// Mapping for promotions
+#[contracttype]
+pub enum PromotionBook {
+    // Promo ID -> Promotion, kept in persistent storage
+    Promotion(u64),
+    // User -> promo_ids still active for the user, kept in persistent storage
+    UserPromos(Address),
+}
+
+// Counter for promotions
+const PROMO_COUNT: Symbol = symbol_short!("PROMO_CNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Escrow `bonus` points from the brand treasury for `user`, released once
+    /// they redeem at least `spend_target` points with the brand within
+    /// `window_ledgers` and reclaimed otherwise (brand admin only)
+    /// Returns the promo_id of the new promotion
+    pub fn create_promotion(
+        env: Env,
+        brand_id: u64,
+        user: Address,
+        spend_target: i128,
+        bonus: i128,
+        window_ledgers: u32,
+    ) -> u64 {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if spend_target <= 0 || bonus <= 0 {
+            panic!("Amount must be positive");
+        }
+        if window_ledgers == 0 {
+            panic!("Window must be positive");
+        }
+        let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand treasury not set"));
+        escrow(&env, &treasury, brand_id, bonus);
+
+        let mut promo_count: u64 = env.storage().instance().get(&PROMO_COUNT).unwrap_or(0);
+        promo_count += 1;
+
+        let promotion = Promotion {
+            promo_id: promo_count,
+            brand_id,
+            user: user.clone(),
+            bonus,
+            spend_target,
+            spent: 0,
+            expires_at: env.ledger().sequence() + window_ledgers,
+            status: PromotionStatus::Active,
+        };
+        save_promotion(&env, &promotion);
+        let mut promo_ids = active_promotions(&env, &user);
+        promo_ids.push_back(promo_count);
+        save_active_promotions(&env, &user, &promo_ids);
+        env.storage().instance().set(&PROMO_COUNT, &promo_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Promotion {} escrowed {} tokens of brand {}",
+            promo_count,
+            bonus,
+            brand_id
+        );
+        promo_count
+    }
+
+    /// Return the bonus of an expired promotion to the brand treasury;
+    /// anyone may call this
+    pub fn reclaim_promotion(env: Env, promo_id: u64) {
+        let promotion = load_promotion(&env, promo_id);
+        if promotion.status != PromotionStatus::Active {
+            panic!("Promotion is not active");
+        }
+        if env.ledger().sequence() <= promotion.expires_at {
+            panic!("Promotion has not expired");
+        }
+        resolve(&env, promotion, PromotionStatus::Reclaimed);
+        env.storage().instance().extend_ttl(100000, 100000);
+    }
+
+    /// View promotion details by promo_id
+    pub fn view_promotion(env: Env, promo_id: u64) -> Promotion {
+        load_promotion(&env, promo_id)
+    }
+
+    /// View the promotions still active for a user
+    pub fn view_active_promotions(env: Env, user: Address) -> Vec<Promotion> {
+        let mut promotions = Vec::new(&env);
+        for promo_id in active_promotions(&env, &user).iter() {
+            promotions.push_back(load_promotion(&env, promo_id));
+        }
+        promotions
+    }
+}
+
+// Count a redemption towards the user's promotions with the brand, releasing
+// those whose target is met and reclaiming those that expired
+pub(crate) fn progress_promotions(env: &Env, user: &Address, brand_id: u64, amount: i128) {
+    let sequence = env.ledger().sequence();
+    for promo_id in active_promotions(env, user).iter() {
+        let mut promotion = load_promotion(env, promo_id);
+        if promotion.brand_id != brand_id {
+            continue;
+        }
+        if sequence > promotion.expires_at {
+            resolve(env, promotion, PromotionStatus::Reclaimed);
+            continue;
+        }
+        promotion.spent += amount;
+        if promotion.spent >= promotion.spend_target {
+            resolve(env, promotion, PromotionStatus::Released);
+        } else {
+            save_promotion(env, &promotion);
+        }
+    }
+}
+
+// Pay out or return an escrowed bonus and close the promotion
+fn resolve(env: &Env, mut promotion: Promotion, status: PromotionStatus) {
+    match status {
+        PromotionStatus::Released => {
+            release(env, &promotion.user, promotion.brand_id, promotion.bonus);
+        }
+        _ => {
+            burn_escrow(env, promotion.brand_id, promotion.bonus);
+            deposit_treasury(
+                env,
+                promotion.brand_id,
+                TreasurySource::Promotion,
+                promotion.bonus,
+            );
+        }
+    }
+    promotion.status = status;
+    save_promotion(env, &promotion);
+
+    let mut promo_ids = active_promotions(env, &promotion.user);
+    if let Some(index) = promo_ids.first_index_of(promotion.promo_id) {
+        promo_ids.remove(index);
+    }
+    save_active_promotions(env, &promotion.user, &promo_ids);
+
+    PromotionResolved {
+        brand_id: promotion.brand_id,
+        user: promotion.user,
+        promo_id: promotion.promo_id,
+        released: status == PromotionStatus::Released,
+        bonus: promotion.bonus,
+    }
+    .publish(env);
+}
+
+fn active_promotions(env: &Env, user: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&PromotionBook::UserPromos(user.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_active_promotions(env: &Env, user: &Address, promo_ids: &Vec<u64>) {
+    let key = PromotionBook::UserPromos(user.clone());
+    if promo_ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, promo_ids);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    }
+}
+
+fn load_promotion(env: &Env, promo_id: u64) -> Promotion {
+    env.storage()
+        .persistent()
+        .get(&PromotionBook::Promotion(promo_id))
+        .unwrap_or_else(|| panic!("Promotion not found"))
+}
+
+fn save_promotion(env: &Env, promotion: &Promotion) {
+    let key = PromotionBook::Promotion(promotion.promo_id);
+    env.storage().persistent().set(&key, promotion);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, Address) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let brand_id = client.register_brand(&String::from_str(env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(env));
+        let treasury = Address::generate(env);
+        client.set_brand_treasury(&brand_id, &treasury);
+        client.issue_tokens(&treasury, &brand_id, &1000, &None);
+        (client, brand_id, treasury)
+    }
+
+    #[test]
+    fn test_bonus_released_when_spend_target_met() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, treasury) = setup(&env);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        let promo_id = client.create_promotion(&brand_id, &user, &300, &100, &1000);
+        assert_eq!(client.view_user_balance(&treasury, &brand_id), 900);
+
+        client.set_default_brand(&user, &brand_id);
+        client.quick_redeem(&user, &200, &None);
+        assert_eq!(client.view_promotion(&promo_id).spent, 200);
+        env.as_contract(&client.address, || {
+            let key = PromotionBook::UserPromos(user.clone());
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+        client.quick_redeem(&user, &100, &None);
+
+        let promotion = client.view_promotion(&promo_id);
+        assert_eq!(promotion.status, PromotionStatus::Released);
+        assert_eq!(client.view_user_balance(&user, &brand_id), 300);
+        assert_eq!(client.view_active_promotions(&user).len(), 0);
+    }
+
+    #[test]
+    fn test_bonus_reclaimed_after_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_id, _) = setup(&env);
+
+        let user = Address::generate(&env);
+        let promo_id = client.create_promotion(&brand_id, &user, &300, &100, &1000);
+        assert!(client.try_reclaim_promotion(&promo_id).is_err());
+
+        env.ledger().set_sequence_number(1001);
+        client.reclaim_promotion(&promo_id);
+        assert_eq!(
+            client.view_promotion(&promo_id).status,
+            PromotionStatus::Reclaimed
+        );
+        assert_eq!(client.view_treasury_reserve(&brand_id), 100);
+        assert!(client.try_reclaim_promotion(&promo_id).is_err());
+    }
+}
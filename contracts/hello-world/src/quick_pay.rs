@@ -0,0 +1,119 @@
+use crate::*;
+
+// Mapping for users' preferred brands: User -> Brand, kept in persistent storage
+#[contracttype]
+pub enum QuickPayBook {
+    DefaultBrand(Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the brand that `quick_redeem` spends from
+    pub fn set_default_brand(env: Env, user: Address, brand_id: u64) {
+        require_user(&env, &user);
+        if !load_brand(&env, brand_id).is_active {
+            panic!("Brand is not active");
+        }
+
+        let key = QuickPayBook::DefaultBrand(user);
+        env.storage().persistent().set(&key, &brand_id);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Default brand set to {}", brand_id);
+    }
+
+    /// View a user's default brand, if set
+    pub fn view_default_brand(env: Env, user: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&QuickPayBook::DefaultBrand(user))
+    }
+
+    /// Spend `amount` points of the user's default brand, e.g. at a kiosk
+    /// that only knows the user's address
+    /// Returns the op_id of the recorded redemption
+    pub fn quick_redeem(env: Env, user: Address, amount: i128, memo: Option<BytesN<32>>) -> u64 {
+        require_user(&env, &user);
+
+        let brand_id = Self::view_default_brand(env.clone(), user.clone())
+            .unwrap_or_else(|| panic!("Default brand not set"));
+        redeem_points(&env, user, brand_id, amount, memo)
+    }
+}
+
+// Validate and debit a direct spend of points, record it and announce it
+pub(crate) fn redeem_points(
+    env: &Env,
+    user: Address,
+    brand_id: u64,
+    amount: i128,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    if !is_brand_active(env, brand_id) {
+        CheckFailure::BrandInactive.fail();
+    }
+    if amount <= 0 {
+        CheckFailure::InvalidAmount.fail();
+    }
+
+    debit(env, &user, brand_id, amount);
+
+    let mut record = OperationRecord::new(env, OperationKind::Redeem, &user, brand_id, amount);
+    record.memo = memo.clone();
+    save_operation(env, &record);
+    record_reputation_redemption(env, brand_id);
+    track_redeemed(env, brand_id, amount);
+    progress_promotions(env, &user, brand_id, amount);
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    PointsRedeemed {
+        brand_id,
+        user,
+        amount,
+        op_id: record.op_id,
+        memo,
+    }
+    .publish(env);
+
+    log!(env, "✅ Redeemed {} tokens of brand {}", amount, brand_id);
+    record.op_id
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+    #[test]
+    fn test_quick_redeem_uses_default_brand() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+        client.issue_tokens(&user, &cafe, &200, &None);
+
+        assert!(client.try_quick_redeem(&user, &50, &None).is_err());
+
+        client.set_default_brand(&user, &cafe);
+        assert_eq!(client.view_default_brand(&user), Some(cafe));
+        env.as_contract(&contract_id, || {
+            let key = QuickPayBook::DefaultBrand(user.clone());
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+
+        let receipt = BytesN::from_array(&env, &[7; 32]);
+        let op_id = client.quick_redeem(&user, &150, &Some(receipt.clone()));
+        assert_eq!(client.view_user_balance(&user, &cafe), 50);
+        assert_eq!(client.view_user_balance(&user, &hotel), 500);
+        assert_eq!(client.find_operation(&user, &cafe, &receipt).op_id, op_id);
+
+        assert!(client.try_quick_redeem(&user, &100, &None).is_err());
+    }
+}
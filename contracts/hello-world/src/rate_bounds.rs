@@ -0,0 +1,106 @@
+use crate::*;
+
+// Structure to store the range every exchange rate must fall in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateBounds {
+    pub min_rate: u32,
+    pub max_rate: u32,
+}
+
+// Platform-wide rate bounds, DEFAULT_RATE_BOUNDS when unset
+const RATE_BOUNDS: Symbol = symbol_short!("RT_BOUND");
+
+// Rates stay within 100x of parity unless the platform admin says otherwise
+const DEFAULT_RATE_BOUNDS: RateBounds = RateBounds {
+    min_rate: (RATE_DENOMINATOR / 100) as u32,
+    max_rate: (RATE_DENOMINATOR * 100) as u32,
+};
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the range configured and derived exchange rates must fall in
+    /// (platform admin only)
+    /// Rate changes outside the range are rejected when scheduled, and
+    /// exchanges at an out-of-range rate fail
+    pub fn set_rate_bounds(env: Env, min_rate: u32, max_rate: u32) {
+        let admin = require_platform_admin(&env);
+        if min_rate == 0 || min_rate > max_rate {
+            panic!("Invalid rate bounds");
+        }
+        log_admin_action(
+            &env,
+            &admin,
+            AdminAction::RateBoundsSet,
+            PLATFORM_BRAND_ID,
+            Some(Self::view_rate_bounds(env.clone()).max_rate as i128),
+            Some(max_rate as i128),
+        );
+
+        env.storage()
+            .instance()
+            .set(&RATE_BOUNDS, &RateBounds { min_rate, max_rate });
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Rate bounds set to {} - {}", min_rate, max_rate);
+    }
+
+    /// View the range exchange rates must fall in
+    pub fn view_rate_bounds(env: Env) -> RateBounds {
+        env.storage()
+            .instance()
+            .get(&RATE_BOUNDS)
+            .unwrap_or(DEFAULT_RATE_BOUNDS)
+    }
+}
+
+// Reject a rate outside the platform bounds
+pub(crate) fn check_rate_bounds(env: &Env, rate: u32) {
+    let bounds = LoyaltyTokenExchange::view_rate_bounds(env.clone());
+    if rate < bounds.min_rate || rate > bounds.max_rate {
+        panic!("Rate outside bounds");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, Env, String};
+
+    #[test]
+    fn test_rates_outside_bounds_are_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        assert!(client
+            .try_schedule_change(&Param::RouteRate(hotel, airline), &2_000_000)
+            .is_err());
+        client.schedule_change(&Param::RouteRate(hotel, airline), &30_000);
+
+        // Coalition pegs far apart derive a rate beyond the bounds
+        let council = Address::generate(&env);
+        let coalition_id = client.create_coalition(
+            &String::from_str(&env, "Travel"),
+            &symbol_short!("MILE"),
+            &vec![&env, council.clone()],
+        );
+        for (brand_id, peg) in [(hotel, 1), (airline, 1_000)] {
+            client.set_brand_admin(&brand_id, &Address::generate(&env));
+            client.request_join_coalition(&coalition_id, &brand_id, &peg);
+            client.approve_coalition_member(&council, &brand_id);
+        }
+        assert!(client.try_quote_exchange(&airline, &hotel, &100).is_err());
+
+        client.set_rate_bounds(&1, &10_000_000);
+        assert_eq!(
+            client.quote_exchange(&airline, &hotel, &100).amount_out,
+            100_000
+        );
+        assert!(client.try_set_rate_bounds(&5, &4).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure recording the rate of a route at a given ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateSnapshot {
+    pub ledger: u32,
+    pub rate: u32,
+}
+
+// Mapping for rate history: (From, To) -> Snapshots, oldest first
+#[contracttype]
+pub enum RateHistoryBook {
+    Route(u64, u64),
+}
+
+// Snapshots kept per route; the oldest is dropped beyond this
+pub const RATE_HISTORY_SIZE: u32 = 64;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View up to `limit` of the latest rate snapshots of a route, newest first
+    pub fn rate_history(env: Env, from_brand: u64, to_brand: u64, limit: u32) -> Vec<RateSnapshot> {
+        let snapshots = route_snapshots(&env, from_brand, to_brand);
+        let mut latest = Vec::new(&env);
+        for snapshot in snapshots.iter().rev().take(limit as usize) {
+            latest.push_back(snapshot);
+        }
+        latest
+    }
+}
+
+// Record the rate a route was set to or settled at
+pub(crate) fn record_rate_snapshot(env: &Env, from_brand: u64, to_brand: u64, rate: u32) {
+    let mut snapshots = route_snapshots(env, from_brand, to_brand);
+    snapshots.push_back(RateSnapshot {
+        ledger: env.ledger().sequence(),
+        rate,
+    });
+    if snapshots.len() > RATE_HISTORY_SIZE {
+        snapshots.pop_front();
+    }
+    env.storage()
+        .instance()
+        .set(&RateHistoryBook::Route(from_brand, to_brand), &snapshots);
+}
+
+fn route_snapshots(env: &Env, from_brand: u64, to_brand: u64) -> Vec<RateSnapshot> {
+    env.storage()
+        .instance()
+        .get(&RateHistoryBook::Route(from_brand, to_brand))
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_rate_history_records_changes_and_uses() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &10_000, &None);
+
+        env.ledger().set_sequence_number(10);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+
+        let change_id = client.schedule_change(&Param::RouteRate(hotel, airline), &15000);
+        env.ledger().set_sequence_number(10 + TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+
+        let history = client.rate_history(&hotel, &airline, &10);
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            history.get(2).unwrap(),
+            RateSnapshot {
+                ledger: 10,
+                rate: 10000
+            }
+        );
+        assert_eq!(history.get(0).unwrap().rate, 15000);
+        assert_eq!(client.rate_history(&hotel, &airline, &1).len(), 1);
+        assert_eq!(client.rate_history(&airline, &hotel, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_rate_history_is_bounded() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &10_000, &None);
+
+        for ledger in 0..RATE_HISTORY_SIZE + 5 {
+            env.ledger().set_sequence_number(ledger);
+            client.exchange_tokens(&user, &hotel, &airline, &10);
+        }
+        let history = client.rate_history(&hotel, &airline, &1000);
+        assert_eq!(history.len(), RATE_HISTORY_SIZE);
+        assert_eq!(history.get(0).unwrap().ledger, RATE_HISTORY_SIZE + 4);
+    }
+}
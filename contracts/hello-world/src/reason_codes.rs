@@ -0,0 +1,135 @@
+use crate::*;
+
+// Structure explaining a rejected operation: the standard failure plus the
+// responsible brand's own reason code, if it registered one
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectionInfo {
+    pub failure: CheckFailure,
+    pub brand_id: Option<u64>,
+    pub reason_code: Option<Symbol>,
+}
+
+// Mapping for reason codes: (Brand, Failure) -> Code shown to end users
+#[contracttype]
+pub enum ReasonBook {
+    Code(u64, CheckFailure),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Register a short code surfaced with a failure caused by the brand,
+    /// e.g. REGN_LOCK; `None` removes it (brand admin only)
+    pub fn set_reason_code(env: Env, brand_id: u64, failure: CheckFailure, code: Option<Symbol>) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let key = ReasonBook::Code(brand_id, failure);
+        match code {
+            Some(code) => env.storage().instance().set(&key, &code),
+            None => env.storage().instance().remove(&key),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Reason code updated for brand {}", brand_id);
+    }
+
+    /// View the code a brand registered for a failure, if any
+    pub fn view_reason_code(env: Env, brand_id: u64, failure: CheckFailure) -> Option<Symbol> {
+        env.storage()
+            .instance()
+            .get(&ReasonBook::Code(brand_id, failure))
+    }
+
+    /// Dry-run `issue_tokens`, explaining the first check that would fail
+    pub fn explain_issue(env: Env, brand_id: u64, amount: i128) -> Option<RejectionInfo> {
+        let failure = check_issue(&env, brand_id, amount).err()?;
+        Some(rejection(&env, failure, Some(brand_id)))
+    }
+
+    /// Dry-run `exchange_tokens`, explaining the first check that would fail
+    pub fn explain_exchange(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+    ) -> Option<RejectionInfo> {
+        let failure = check_exchange(&env, &user, from_brand, to_brand, amount).err()?;
+        let to_side = match failure {
+            CheckFailure::ToBrandNotFound
+            | CheckFailure::ToBrandInactive
+            | CheckFailure::WindingDown => true,
+            CheckFailure::Blackout => !Self::is_in_blackout(env.clone(), from_brand),
+            _ => false,
+        };
+        let brand_id = if to_side { to_brand } else { from_brand };
+        Some(rejection(&env, failure, Some(brand_id)))
+    }
+
+    /// Dry-run `redeem_reward`, explaining the first check that would fail
+    pub fn explain_redeem(env: Env, user: Address, reward_id: u64) -> Option<RejectionInfo> {
+        let failure = check_redeem(&env, &user, reward_id).err()?;
+        let brand_id = env
+            .storage()
+            .instance()
+            .get::<_, Reward>(&RewardBook::Reward(reward_id))
+            .map(|reward| reward.brand_id);
+        Some(rejection(&env, failure, brand_id))
+    }
+}
+
+fn rejection(env: &Env, failure: CheckFailure, brand_id: Option<u64>) -> RejectionInfo {
+    RejectionInfo {
+        failure,
+        brand_id,
+        reason_code: brand_id.and_then(|brand_id| {
+            LoyaltyTokenExchange::view_reason_code(env.clone(), brand_id, failure)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_rejections_carry_brand_reason_code() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let shoes = client.register_brand(&String::from_str(&env, "Shoes"));
+        let socks = client.register_brand(&String::from_str(&env, "Socks"));
+        client.set_brand_admin(&socks, &Address::generate(&env));
+        client.set_reason_code(
+            &socks,
+            &CheckFailure::Blackout,
+            &Some(symbol_short!("ACCT_CLS")),
+        );
+        client.add_blackout_window(
+            &socks,
+            &BlackoutWindow {
+                start: 0,
+                period: 1000,
+                duration: 500,
+            },
+        );
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &shoes, &50, &None);
+
+        let info = client.explain_exchange(&user, &shoes, &socks, &10).unwrap();
+        assert_eq!(info.failure, CheckFailure::Blackout);
+        assert_eq!(info.brand_id, Some(socks));
+        assert_eq!(info.reason_code, Some(symbol_short!("ACCT_CLS")));
+
+        let info = client.explain_issue(&shoes, &0).unwrap();
+        assert_eq!(info.failure, CheckFailure::InvalidAmount);
+        assert_eq!(info.reason_code, None);
+        assert_eq!(client.explain_redeem(&user, &42).unwrap().brand_id, None);
+        assert_eq!(client.explain_issue(&shoes, &10), None);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::*;
+
+// Mapping for closed-loop programs that restrict who can receive points
+#[contracttype]
+pub enum WhitelistBook {
+    // Brand -> Whether transfers are limited to whitelisted receivers
+    Enabled(u64),
+    // (Brand, Receiver) -> Approved
+    Receiver(u64, Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Turn receiver whitelist mode on or off for a brand (brand admin only)
+    /// While on, points can only be transferred to whitelisted addresses,
+    /// e.g. the approved merchants of a corporate program
+    pub fn set_receiver_whitelist_mode(env: Env, brand_id: u64, enabled: bool) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .set(&WhitelistBook::Enabled(brand_id), &enabled);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Receiver whitelist for brand {} set to {}",
+            brand_id,
+            enabled
+        );
+    }
+
+    /// Approve an address to receive a brand's points (brand admin only)
+    pub fn add_whitelisted_receiver(env: Env, brand_id: u64, receiver: Address) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .set(&WhitelistBook::Receiver(brand_id, receiver), &true);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Receiver whitelisted for brand {}", brand_id);
+    }
+
+    /// Withdraw an address's approval to receive a brand's points (brand admin only)
+    pub fn remove_whitelisted_receiver(env: Env, brand_id: u64, receiver: Address) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .remove(&WhitelistBook::Receiver(brand_id, receiver));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Receiver removed from brand {} whitelist",
+            brand_id
+        );
+    }
+
+    /// Whether a brand limits transfers to whitelisted receivers
+    pub fn view_receiver_whitelist_mode(env: Env, brand_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&WhitelistBook::Enabled(brand_id))
+            .unwrap_or(false)
+    }
+
+    /// Whether an address may receive a brand's points
+    pub fn can_receive(env: Env, brand_id: u64, receiver: Address) -> bool {
+        !Self::view_receiver_whitelist_mode(env.clone(), brand_id)
+            || env
+                .storage()
+                .instance()
+                .has(&WhitelistBook::Receiver(brand_id, receiver))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_whitelist_mode_limits_transfer_receivers() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Canteen"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let employee = Address::generate(&env);
+        let merchant = Address::generate(&env);
+        let colleague = Address::generate(&env);
+        client.issue_tokens(&employee, &brand_id, &300, &None);
+
+        client.set_receiver_whitelist_mode(&brand_id, &true);
+        client.add_whitelisted_receiver(&brand_id, &merchant);
+        assert!(client.can_receive(&brand_id, &merchant));
+        assert!(!client.can_receive(&brand_id, &colleague));
+
+        client.transfer_tokens(&employee, &merchant, &brand_id, &100, &None);
+        assert!(client
+            .try_transfer_tokens(&employee, &colleague, &brand_id, &100, &None)
+            .is_err());
+
+        client.remove_whitelisted_receiver(&brand_id, &merchant);
+        assert!(client
+            .try_transfer_tokens(&employee, &merchant, &brand_id, &100, &None)
+            .is_err());
+
+        client.set_receiver_whitelist_mode(&brand_id, &false);
+        client.transfer_tokens(&employee, &colleague, &brand_id, &100, &None);
+        assert_eq!(client.view_user_balance(&employee, &brand_id), 100);
+    }
+}
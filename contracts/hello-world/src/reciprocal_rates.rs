@@ -0,0 +1,115 @@
+use crate::*;
+
+// Mapping for routes whose rate is never derived from the opposite route:
+// (From, To) -> Whether the route is managed on its own
+#[contracttype]
+pub enum ReciprocalBook {
+    Asymmetric(u64, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Schedule deriving B→A as the inverse of A→B, less `spread_bps`, whenever
+    /// a route rate change executes; `None` stops deriving; it takes effect
+    /// through `execute_change` after the timelock (platform admin only)
+    /// Returns the change_id of the queued change
+    pub fn set_reciprocal_spread(env: Env, spread_bps: Option<u32>) -> u64 {
+        let value = spread_bps.map_or(PARAM_UNSET, |spread_bps| spread_bps as i128);
+        Self::schedule_change(env, Param::ReciprocalSpread, value)
+    }
+
+    /// View the spread taken off derived reverse rates, if rates are derived
+    pub fn view_reciprocal_spread(env: Env) -> Option<u32> {
+        param_value(&env, &Param::ReciprocalSpread).map(|spread_bps| spread_bps as u32)
+    }
+
+    /// Keep a route's rate from being derived from the opposite route, for
+    /// routes that are deliberately asymmetric (platform admin only)
+    pub fn set_asymmetric_route(env: Env, from_brand: u64, to_brand: u64, asymmetric: bool) {
+        require_platform_admin(&env);
+
+        let key = ReciprocalBook::Asymmetric(from_brand, to_brand);
+        if asymmetric {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Route {} → {} asymmetry updated",
+            from_brand,
+            to_brand
+        );
+    }
+
+    /// View whether a route's rate is managed on its own
+    pub fn is_asymmetric_route(env: Env, from_brand: u64, to_brand: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&ReciprocalBook::Asymmetric(from_brand, to_brand))
+            .unwrap_or(false)
+    }
+}
+
+// Set the reverse of a route whose rate just changed, unless rates are not
+// derived or the reverse route is asymmetric
+pub(crate) fn derive_reciprocal_rate(env: &Env, from_brand: u64, to_brand: u64, rate: u32) {
+    let Some(spread_bps) = LoyaltyTokenExchange::view_reciprocal_spread(env.clone()) else {
+        return;
+    };
+    if LoyaltyTokenExchange::is_asymmetric_route(env.clone(), to_brand, from_brand) {
+        return;
+    }
+
+    let denominator = RATE_DENOMINATOR as u64;
+    let inverse = denominator * denominator / rate as u64;
+    let reverse = (inverse * (denominator - spread_bps as u64) / denominator).max(1);
+    let reverse = u32::try_from(reverse).unwrap_or_else(|_| panic!("Rate out of range"));
+    check_rate_bounds(env, reverse);
+
+    env.storage().instance().set(
+        &ParamBook::Value(Param::RouteRate(to_brand, from_brand)),
+        &(reverse as i128),
+    );
+    record_rate_snapshot(env, to_brand, from_brand, reverse);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_reverse_rate_derived_with_spread_unless_asymmetric() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let spread = client.set_reciprocal_spread(&Some(100));
+        client.set_asymmetric_route(&cafe, &hotel, &true);
+
+        let to_airline = client.schedule_change(&Param::RouteRate(hotel, airline), &20_000);
+        let to_cafe = client.schedule_change(&Param::RouteRate(hotel, cafe), &20_000);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&spread);
+        client.execute_change(&to_airline);
+        client.execute_change(&to_cafe);
+
+        // Half the forward rate, less a 1% spread
+        assert_eq!(
+            client.view_param(&Param::RouteRate(airline, hotel)),
+            Some(4_950)
+        );
+        assert_eq!(client.view_param(&Param::RouteRate(cafe, hotel)), None);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store what is needed to undo a recent exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExchangeReceipt {
+    pub op_id: u64,
+    pub user: Address,
+    pub from_brand: u64,
+    pub to_brand: u64,
+    // Destination points the user must give back
+    pub amount_out: i128,
+    // Source points returned: the output converted back at the same rate,
+    // so burns and fees are not refunded
+    pub refund: i128,
+    pub expires_at: u32,
+}
+
+// Mapping for regret windows and undoable exchanges
+#[contracttype]
+pub enum RegretBook {
+    // Brand -> Ledgers during which exchanges touching the brand can be undone
+    RegretWindow(u64),
+    // Exchange op_id -> Receipt, removed once reverted or expired, kept in
+    // persistent storage
+    Receipt(u64),
+    // op_ids of receipts not yet removed, pruned as new receipts are kept,
+    // kept in persistent storage
+    OpenReceipts,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Let users undo exchanges into or out of a brand for `window_ledgers`
+    /// (brand admin only); 0 turns it off
+    /// An exchange is undoable only while both brands' windows allow it
+    pub fn set_regret_window(env: Env, brand_id: u64, window_ledgers: u32) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        if window_ledgers == 0 {
+            env.storage()
+                .instance()
+                .remove(&RegretBook::RegretWindow(brand_id));
+        } else {
+            env.storage()
+                .instance()
+                .set(&RegretBook::RegretWindow(brand_id), &window_ledgers);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Regret window for brand {} set to {}",
+            brand_id,
+            window_ledgers
+        );
+    }
+
+    /// Undo an exchange within its regret window, as long as the destination
+    /// points have not been spent
+    /// Returns the op_id of the reversing exchange
+    pub fn revert_exchange(env: Env, user: Address, op_id: u64) -> u64 {
+        require_user(&env, &user);
+
+        let receipt = Self::view_exchange_receipt(env.clone(), op_id)
+            .unwrap_or_else(|| panic!("Exchange cannot be reverted"));
+        if receipt.user != user {
+            panic!("Not the exchanging user");
+        }
+        if env.ledger().sequence() > receipt.expires_at {
+            panic!("Regret window has passed");
+        }
+        if !is_brand_active(&env, receipt.from_brand) || !is_brand_active(&env, receipt.to_brand) {
+            panic!("One or both brands are not active");
+        }
+        if read_balance(&env, &user, receipt.to_brand) < receipt.amount_out {
+            panic!("Destination points already spent");
+        }
+
+        debit(&env, &user, receipt.to_brand, receipt.amount_out);
+        credit(&env, &user, receipt.from_brand, receipt.refund);
+        env.storage()
+            .persistent()
+            .remove(&RegretBook::Receipt(op_id));
+        prune_receipts(&env);
+
+        let mut record = OperationRecord::new(
+            &env,
+            OperationKind::Exchange,
+            &user,
+            receipt.to_brand,
+            receipt.amount_out,
+        );
+        record.to_brand = Some(receipt.from_brand);
+        save_operation(&env, &record);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Exchange {} reverted", op_id);
+        record.op_id
+    }
+
+    /// View the regret window of a brand, if set
+    pub fn view_regret_window(env: Env, brand_id: u64) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&RegretBook::RegretWindow(brand_id))
+    }
+
+    /// View the receipt of an exchange that can still be reverted, if any
+    pub fn view_exchange_receipt(env: Env, op_id: u64) -> Option<ExchangeReceipt> {
+        let receipt: Option<ExchangeReceipt> =
+            env.storage().persistent().get(&RegretBook::Receipt(op_id));
+        receipt.filter(|receipt| env.ledger().sequence() <= receipt.expires_at)
+    }
+}
+
+// Keep a receipt of an exchange when both brands offer a regret window
+pub(crate) fn record_exchange_receipt(
+    env: &Env,
+    op_id: u64,
+    user: &Address,
+    quote: &ExchangeQuote,
+) {
+    if quote.rate == 0 {
+        return;
+    }
+
+    let converted_back = quote.amount_out * RATE_DENOMINATOR / quote.rate as i128;
+    record_receipt(
+        env,
+        op_id,
+        user,
+        quote.from_brand,
+        quote.to_brand,
+        quote.amount_out,
+        converted_back.min(quote.amount_in - quote.burned),
+    );
+}
+
+// Keep a receipt of a multi-hop exchange when both end brands offer a regret
+// window; the output is converted back through every hop at its own rate
+pub(crate) fn record_route_receipt(
+    env: &Env,
+    op_id: u64,
+    user: &Address,
+    hops: &Vec<HopSettlement>,
+    rates: &Vec<u32>,
+) {
+    let mut converted_back = hops.get_unchecked(hops.len() - 1).amount_out;
+    for rate in rates.iter().rev() {
+        if rate == 0 {
+            return;
+        }
+        converted_back = converted_back * RATE_DENOMINATOR / rate as i128;
+    }
+
+    let first = hops.get_unchecked(0);
+    let last = hops.get_unchecked(hops.len() - 1);
+    record_receipt(
+        env,
+        op_id,
+        user,
+        first.from_brand,
+        last.to_brand,
+        last.amount_out,
+        converted_back.min(first.amount_in - first.burned),
+    );
+}
+
+fn record_receipt(
+    env: &Env,
+    op_id: u64,
+    user: &Address,
+    from_brand: u64,
+    to_brand: u64,
+    amount_out: i128,
+    refund: i128,
+) {
+    let from_window = LoyaltyTokenExchange::view_regret_window(env.clone(), from_brand);
+    let to_window = LoyaltyTokenExchange::view_regret_window(env.clone(), to_brand);
+    let (Some(from_window), Some(to_window)) = (from_window, to_window) else {
+        return;
+    };
+
+    let receipt = ExchangeReceipt {
+        op_id,
+        user: user.clone(),
+        from_brand,
+        to_brand,
+        amount_out,
+        refund,
+        expires_at: env.ledger().sequence() + from_window.min(to_window),
+    };
+    let key = RegretBook::Receipt(op_id);
+    env.storage().persistent().set(&key, &receipt);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+
+    prune_receipts(env);
+    let mut open = open_receipts(env);
+    open.push_back(op_id);
+    save_open_receipts(env, &open);
+}
+
+// Drop receipts that were reverted or whose regret window has passed
+fn prune_receipts(env: &Env) {
+    let mut open = Vec::new(env);
+    for op_id in open_receipts(env).iter() {
+        if LoyaltyTokenExchange::view_exchange_receipt(env.clone(), op_id).is_some() {
+            open.push_back(op_id);
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&RegretBook::Receipt(op_id));
+        }
+    }
+    save_open_receipts(env, &open);
+}
+
+fn open_receipts(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&RegretBook::OpenReceipts)
+        .unwrap_or(Vec::new(env))
+}
+
+fn save_open_receipts(env: &Env, open: &Vec<u64>) {
+    if open.is_empty() {
+        env.storage().persistent().remove(&RegretBook::OpenReceipts);
+    } else {
+        env.storage()
+            .persistent()
+            .set(&RegretBook::OpenReceipts, open);
+        env.storage()
+            .persistent()
+            .extend_ttl(&RegretBook::OpenReceipts, 100000, 100000);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, u64, u64) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+        client.set_platform_admin(&Address::generate(env));
+
+        let hotel = client.register_brand(&String::from_str(env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(env, "Airline"));
+        client.set_brand_admin(&hotel, &Address::generate(env));
+        client.set_brand_admin(&airline, &Address::generate(env));
+        client.set_regret_window(&hotel, &100);
+        client.set_regret_window(&airline, &50);
+        (client, hotel, airline)
+    }
+
+    #[test]
+    fn test_exchange_reverted_within_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, airline) = setup(&env);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+        let op_id = client.exchange_tokens_v2(&user, &hotel, &airline, &300, &0, &u32::MAX, &None);
+        assert_eq!(client.view_exchange_receipt(&op_id).unwrap().expires_at, 50);
+        env.as_contract(&client.address, || {
+            for key in [RegretBook::Receipt(op_id), RegretBook::OpenReceipts] {
+                assert!(env.storage().persistent().has(&key));
+                assert!(!env.storage().instance().has(&key));
+            }
+        });
+
+        assert!(client
+            .try_revert_exchange(&Address::generate(&env), &op_id)
+            .is_err());
+        client.revert_exchange(&user, &op_id);
+        assert_eq!(client.view_user_balance(&user, &hotel), 500);
+        assert_eq!(client.view_user_balance(&user, &airline), 0);
+        assert!(client.try_revert_exchange(&user, &op_id).is_err());
+    }
+
+    #[test]
+    fn test_revert_rejected_when_spent_or_late() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, hotel, airline) = setup(&env);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+        let spent = client.exchange_tokens_v2(&user, &hotel, &airline, &200, &0, &u32::MAX, &None);
+        client.transfer_tokens(&user, &Address::generate(&env), &airline, &150, &None);
+        assert!(client.try_revert_exchange(&user, &spent).is_err());
+
+        let late = client.exchange_tokens_v2(&user, &hotel, &airline, &100, &0, &u32::MAX, &None);
+        env.ledger().set_sequence_number(51);
+        assert!(client.try_revert_exchange(&user, &late).is_err());
+        assert_eq!(client.view_exchange_receipt(&late), None);
+
+        // Expired receipts are removed once the next one is kept
+        client.exchange_tokens_v2(&user, &hotel, &airline, &50, &0, &u32::MAX, &None);
+        env.as_contract(&client.address, || {
+            assert!(!env.storage().persistent().has(&RegretBook::Receipt(late)));
+            assert!(!env.storage().persistent().has(&RegretBook::Receipt(spent)));
+        });
+    }
+}
@@ -0,0 +1,186 @@
+use crate::*;
+
+// Structure tracking a brand's on-chain behavior and the score derived from it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrandReputation {
+    pub brand_id: u64,
+    pub registered_at: u32,
+    // Activity periods in which the brand issued or honored points
+    pub active_periods: u32,
+    pub last_active_period: Option<u32>,
+    pub redemptions: u32,
+    pub disputes: u32,
+    // Disputes resolved as fulfilled or unfulfilled by the brand
+    pub fulfilled: u32,
+    pub unfulfilled: u32,
+    // Score in basis points, 10000 being a perfect record
+    pub score: u32,
+}
+
+// Mapping for brand reputations
+#[contracttype]
+pub enum ReputationBook {
+    Reputation(u64),
+}
+
+// Length of an activity period used for uptime (about one day)
+pub const ACTIVITY_PERIOD_LEDGERS: u32 = 17_280;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View the reputation of a brand, with the score brought up to the current ledger
+    pub fn view_brand_reputation(env: Env, brand_id: u64) -> BrandReputation {
+        load_brand(&env, brand_id);
+        let mut reputation = load_reputation(&env, brand_id);
+        reputation.score = reputation_score(&env, &reputation);
+        reputation
+    }
+}
+
+// Start tracking a newly registered brand
+pub(crate) fn init_reputation(env: &Env, brand_id: u64) {
+    let reputation = new_reputation(env, brand_id);
+    save_reputation(env, reputation);
+}
+
+// Count the current period as active for the brand
+pub(crate) fn record_brand_activity(env: &Env, brand_id: u64) {
+    let mut reputation = load_reputation(env, brand_id);
+    mark_active(env, &mut reputation);
+    save_reputation(env, reputation);
+}
+
+// Count a redemption honored by the brand
+pub(crate) fn record_reputation_redemption(env: &Env, brand_id: u64) {
+    let mut reputation = load_reputation(env, brand_id);
+    mark_active(env, &mut reputation);
+    reputation.redemptions += 1;
+    save_reputation(env, reputation);
+}
+
+pub(crate) fn record_reputation_dispute(env: &Env, brand_id: u64) {
+    let mut reputation = load_reputation(env, brand_id);
+    reputation.disputes += 1;
+    save_reputation(env, reputation);
+}
+
+pub(crate) fn record_reputation_resolution(env: &Env, brand_id: u64, fulfilled: bool) {
+    let mut reputation = load_reputation(env, brand_id);
+    if fulfilled {
+        reputation.fulfilled += 1;
+    } else {
+        reputation.unfulfilled += 1;
+    }
+    save_reputation(env, reputation);
+}
+
+fn new_reputation(env: &Env, brand_id: u64) -> BrandReputation {
+    BrandReputation {
+        brand_id,
+        registered_at: env.ledger().sequence(),
+        active_periods: 0,
+        last_active_period: None,
+        redemptions: 0,
+        disputes: 0,
+        fulfilled: 0,
+        unfulfilled: 0,
+        score: RATE_DENOMINATOR as u32,
+    }
+}
+
+fn load_reputation(env: &Env, brand_id: u64) -> BrandReputation {
+    env.storage()
+        .instance()
+        .get(&ReputationBook::Reputation(brand_id))
+        .unwrap_or_else(|| new_reputation(env, brand_id))
+}
+
+fn save_reputation(env: &Env, mut reputation: BrandReputation) {
+    reputation.score = reputation_score(env, &reputation);
+    env.storage().instance().set(
+        &ReputationBook::Reputation(reputation.brand_id),
+        &reputation,
+    );
+}
+
+fn mark_active(env: &Env, reputation: &mut BrandReputation) {
+    let period = env.ledger().sequence() / ACTIVITY_PERIOD_LEDGERS;
+    if reputation.last_active_period != Some(period) {
+        reputation.active_periods += 1;
+        reputation.last_active_period = Some(period);
+    }
+}
+
+// Weighted score: 40% uptime, 30% share of undisputed redemptions,
+// 30% share of resolved disputes the brand had actually fulfilled
+fn reputation_score(env: &Env, reputation: &BrandReputation) -> u32 {
+    let full = RATE_DENOMINATOR as u64;
+
+    let elapsed_periods = (env.ledger().sequence() / ACTIVITY_PERIOD_LEDGERS)
+        - (reputation.registered_at / ACTIVITY_PERIOD_LEDGERS)
+        + 1;
+    let uptime = (reputation.active_periods as u64 * full / elapsed_periods as u64).min(full);
+
+    let undisputed = if reputation.redemptions == 0 {
+        full
+    } else {
+        full - (reputation.disputes as u64 * full / reputation.redemptions as u64).min(full)
+    };
+
+    let resolved = reputation.fulfilled + reputation.unfulfilled;
+    let fulfillment = if resolved == 0 {
+        full
+    } else {
+        reputation.fulfilled as u64 * full / resolved as u64
+    };
+
+    ((4 * uptime + 3 * undisputed + 3 * fulfillment) / 10) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_reputation_tracks_uptime_and_disputes() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Latte"), &100, &10);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &1000, &None);
+        assert_eq!(client.view_brand_reputation(&brand_id).score, 10000);
+
+        // Second period passes without activity: uptime drops to 1/2
+        env.ledger().set_sequence_number(ACTIVITY_PERIOD_LEDGERS);
+        assert_eq!(client.view_brand_reputation(&brand_id).score, 8000);
+
+        // Redeeming in a third period makes the brand active 2 of 3 periods
+        env.ledger()
+            .set_sequence_number(2 * ACTIVITY_PERIOD_LEDGERS);
+        let op1 = client.redeem_reward(&user, &reward_id, &None);
+        client.redeem_reward(&user, &reward_id, &None);
+        let reputation = client.view_brand_reputation(&brand_id);
+        assert_eq!(reputation.active_periods, 2);
+        assert_eq!(reputation.redemptions, 2);
+        assert_eq!(reputation.score, 8666);
+
+        // One of two redemptions disputed and found unfulfilled
+        let dispute_id = client.open_dispute(&user, &op1);
+        client.resolve_dispute(&dispute_id, &false);
+        let reputation = client.view_brand_reputation(&brand_id);
+        assert_eq!(reputation.disputes, 1);
+        assert_eq!(reputation.unfulfilled, 1);
+        assert_eq!(reputation.score, 4166);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::*;
+use soroban_sdk::BytesN;
+
+// Structure to store a reward offered in a brand's catalog
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reward {
+    pub reward_id: u64,
+    pub brand_id: u64,
+    pub name: String,
+    pub cost: i128,
+    pub stock: u32,
+    pub is_active: bool,
+}
+
+// Mapping for rewards
+#[contracttype]
+pub enum RewardBook {
+    Reward(u64),
+}
+
+// Counter for rewards
+const REWARD_COUNT: Symbol = symbol_short!("R_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Add a reward to a brand's catalog (brand admin only)
+    /// Returns the reward_id of the new reward
+    pub fn add_reward(env: Env, brand_id: u64, name: String, cost: i128, stock: u32) -> u64 {
+        let brand = load_brand(&env, brand_id);
+        if !brand.is_active {
+            panic!("Brand is not active");
+        }
+        require_brand_admin(&env, brand_id);
+        if cost <= 0 {
+            panic!("Cost must be positive");
+        }
+
+        let mut reward_count: u64 = env.storage().instance().get(&REWARD_COUNT).unwrap_or(0);
+        reward_count += 1;
+
+        let reward = Reward {
+            reward_id: reward_count,
+            brand_id,
+            name,
+            cost,
+            stock,
+            is_active: true,
+        };
+        save_reward(&env, &reward);
+        env.storage().instance().set(&REWARD_COUNT, &reward_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Reward {} added to brand {}",
+            reward_count,
+            brand_id
+        );
+        reward_count
+    }
+
+    /// Add stock to an existing reward (brand admin only)
+    pub fn restock_reward(env: Env, reward_id: u64, quantity: u32) {
+        let mut reward = load_reward(&env, reward_id);
+        require_brand_admin(&env, reward.brand_id);
+
+        reward.stock += quantity;
+        save_reward(&env, &reward);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Reward {} restocked by {}", reward_id, quantity);
+    }
+
+    /// Change the point cost of a reward (brand admin only)
+    pub fn set_reward_cost(env: Env, reward_id: u64, cost: i128) {
+        let mut reward = load_reward(&env, reward_id);
+        require_brand_admin(&env, reward.brand_id);
+        if cost <= 0 {
+            panic!("Cost must be positive");
+        }
+
+        reward.cost = cost;
+        save_reward(&env, &reward);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Reward {} repriced to {}", reward_id, cost);
+    }
+
+    /// Redeem a reward by burning its cost from the user's balance
+    /// Deprecated: forwards to `redeem_reward_v2` with no sequence number
+    pub fn redeem_reward(env: Env, user: Address, reward_id: u64, memo: Option<BytesN<32>>) -> u64 {
+        Self::redeem_reward_v2(env, user, reward_id, memo, None)
+    }
+
+    /// Redeem a reward by burning its cost from the user's balance
+    /// `sequence`, if given, must equal the user's next sequence number
+    /// Returns the op_id of the recorded operation
+    pub fn redeem_reward_v2(
+        env: Env,
+        user: Address,
+        reward_id: u64,
+        memo: Option<BytesN<32>>,
+        sequence: Option<u64>,
+    ) -> u64 {
+        user.require_auth();
+        consume_sequence(&env, &user, sequence);
+
+        if let Err(failure) = check_redeem(&env, &user, reward_id) {
+            failure.fail();
+        }
+
+        let mut reward = load_reward(&env, reward_id);
+        let op_id = fulfill_redemption(&env, &user, &mut reward, memo);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Reward {} redeemed for {} tokens",
+            reward_id,
+            reward.cost
+        );
+        op_id
+    }
+
+    /// View reward details by reward_id
+    pub fn view_reward(env: Env, reward_id: u64) -> Reward {
+        load_reward(&env, reward_id)
+    }
+}
+
+// Number of rewards ever added, across all brands
+pub(crate) fn reward_count(env: &Env) -> u64 {
+    env.storage().instance().get(&REWARD_COUNT).unwrap_or(0)
+}
+
+pub(crate) fn load_reward(env: &Env, reward_id: u64) -> Reward {
+    env.storage()
+        .instance()
+        .get(&RewardBook::Reward(reward_id))
+        .unwrap_or_else(|| panic!("Reward not found"))
+}
+
+pub(crate) fn save_reward(env: &Env, reward: &Reward) {
+    env.storage()
+        .instance()
+        .set(&RewardBook::Reward(reward.reward_id), reward);
+}
+
+// Burn the reward's cost from the user and hand out one unit of stock
+pub(crate) fn fulfill_redemption(
+    env: &Env,
+    user: &Address,
+    reward: &mut Reward,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    debit(env, user, reward.brand_id, reward.cost);
+    reward.stock -= 1;
+    save_reward(env, reward);
+    record_redemption(env, user, reward, reward.cost, memo)
+}
+
+// Record a redemption in the history and announce it
+pub(crate) fn record_redemption(
+    env: &Env,
+    user: &Address,
+    reward: &Reward,
+    cost: i128,
+    memo: Option<BytesN<32>>,
+) -> u64 {
+    let mut record = OperationRecord::new(env, OperationKind::Redeem, user, reward.brand_id, cost);
+    record.memo = memo.clone();
+    save_operation(env, &record);
+    open_fulfillment(env, record.op_id, user, reward, cost);
+    record_reputation_redemption(env, reward.brand_id);
+    track_redeemed(env, reward.brand_id, cost);
+    progress_promotions(env, user, reward.brand_id, cost);
+    track_experiment_redemption(env, user, reward.brand_id, cost);
+
+    emit_operation(env, reward.brand_id, user, record.op_id, || {
+        RewardRedeemed {
+            brand_id: reward.brand_id,
+            user: user.clone(),
+            reward_id: reward.reward_id,
+            cost,
+            op_id: record.op_id,
+            memo,
+        }
+        .publish(env)
+    });
+    record.op_id
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_redeem_reward() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cinema"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Popcorn"), &150, &1);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &200, &None);
+        client.redeem_reward(&user, &reward_id, &None);
+
+        assert_eq!(client.view_user_balance(&user, &brand_id), 50);
+        assert_eq!(client.view_brand_supply(&brand_id), 50);
+        assert_eq!(client.view_reward(&reward_id).stock, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reward is out of stock")]
+    fn test_redeem_out_of_stock_reward() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cinema"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let reward_id = client.add_reward(&brand_id, &String::from_str(&env, "Poster"), &10, &0);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &brand_id, &100, &None);
+        client.redeem_reward(&user, &reward_id, &None);
+    }
+}
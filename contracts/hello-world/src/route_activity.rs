@@ -0,0 +1,167 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure reporting when a route was last used
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteActivity {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub last_used: u32,
+}
+
+// Mapping for route activity: (From, To) -> Last ledger an exchange used the route
+#[contracttype]
+pub enum ActivityBook {
+    LastUsed(u64, u64),
+}
+
+// Every route ever used, in order of first use
+const USED_ROUTES: Symbol = symbol_short!("USED_RTS");
+
+// Ledgers without exchanges after which a route is paused for review
+const STALE_THRESHOLD: Symbol = symbol_short!("STALE_THR");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// List routes not used for more than `threshold` ledgers, including
+    /// paused ones
+    pub fn stale_routes(env: Env, threshold: u32) -> Vec<RouteActivity> {
+        let sequence = env.ledger().sequence();
+        let mut stale = Vec::new(&env);
+        for route in used_routes(&env).iter() {
+            if sequence.saturating_sub(route.last_used) > threshold {
+                stale.push_back(route);
+            }
+        }
+        stale
+    }
+
+    /// Set how long a route may go unused before `pause_stale_routes` pauses
+    /// it; `None` turns auto-pausing off (platform admin only)
+    pub fn set_stale_route_threshold(env: Env, threshold: Option<u32>) {
+        require_platform_admin(&env);
+
+        match threshold {
+            Some(threshold) => env.storage().instance().set(&STALE_THRESHOLD, &threshold),
+            None => env.storage().instance().remove(&STALE_THRESHOLD),
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Stale route threshold updated");
+    }
+
+    /// View the stale route threshold, if auto-pausing is on
+    pub fn view_stale_route_threshold(env: Env) -> Option<u32> {
+        env.storage().instance().get(&STALE_THRESHOLD)
+    }
+
+    /// Pause every route unused beyond the stale threshold, pending admin
+    /// review through `unpause_route`; anyone may call this
+    /// Returns the number of routes paused
+    pub fn pause_stale_routes(env: Env) -> u32 {
+        let threshold = Self::view_stale_route_threshold(env.clone())
+            .unwrap_or_else(|| panic!("Stale route threshold not set"));
+
+        let mut paused: u32 = 0;
+        for route in Self::stale_routes(env.clone(), threshold).iter() {
+            if !is_route_paused(&env, route.from_brand, route.to_brand) {
+                pause(
+                    &env,
+                    route.from_brand,
+                    route.to_brand,
+                    env.current_contract_address(),
+                );
+                paused += 1;
+            }
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ {} stale routes paused", paused);
+        paused
+    }
+}
+
+// Note an exchange on a route
+pub(crate) fn record_route_use(env: &Env, from_brand: u64, to_brand: u64) {
+    let key = ActivityBook::LastUsed(from_brand, to_brand);
+    if !env.storage().instance().has(&key) {
+        let mut routes: Vec<(u64, u64)> = env
+            .storage()
+            .instance()
+            .get(&USED_ROUTES)
+            .unwrap_or(Vec::new(env));
+        routes.push_back((from_brand, to_brand));
+        env.storage().instance().set(&USED_ROUTES, &routes);
+    }
+    env.storage().instance().set(&key, &env.ledger().sequence());
+}
+
+fn used_routes(env: &Env) -> Vec<RouteActivity> {
+    let routes: Vec<(u64, u64)> = env
+        .storage()
+        .instance()
+        .get(&USED_ROUTES)
+        .unwrap_or(Vec::new(env));
+    let mut activity = Vec::new(env);
+    for (from_brand, to_brand) in routes.iter() {
+        activity.push_back(RouteActivity {
+            from_brand,
+            to_brand,
+            last_used: env
+                .storage()
+                .instance()
+                .get(&ActivityBook::LastUsed(from_brand, to_brand))
+                .unwrap_or(0),
+        });
+    }
+    activity
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_unused_routes_are_reported_and_paused() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.set_platform_admin(&admin);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        env.ledger().set_sequence_number(100);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        env.ledger().set_sequence_number(500);
+        client.exchange_tokens(&user, &hotel, &cafe, &100);
+
+        env.ledger().set_sequence_number(700);
+        let stale = client.stale_routes(&300);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale.get_unchecked(0).to_brand, airline);
+        assert_eq!(stale.get_unchecked(0).last_used, 100);
+
+        assert!(client.try_pause_stale_routes().is_err());
+        client.set_stale_route_threshold(&Some(300));
+        assert_eq!(client.pause_stale_routes(), 1);
+        assert!(client
+            .try_exchange_tokens(&user, &hotel, &airline, &100)
+            .is_err());
+        assert_eq!(client.pause_stale_routes(), 0);
+
+        client.unpause_route(&admin, &hotel, &airline);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        assert!(client.stale_routes(&300).is_empty());
+    }
+}
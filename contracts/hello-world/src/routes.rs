@@ -0,0 +1,505 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure describing a one-hop exchange route out of a brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteInfo {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub to_brand_name: String,
+    // Destination points per RATE_DENOMINATOR source points
+    pub rate: u32,
+    pub fee_bps: u32,
+    pub fee_payer: FeePayer,
+    // Share of the input burned by the source brand
+    pub burn_bps: u32,
+    pub paused: bool,
+}
+
+// Who bears the exchange fee on a route
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeePayer {
+    // Fee taken from the source points before conversion
+    Input,
+    // Fee taken from the destination points after conversion
+    Output,
+    // Fee debited in source points from the source brand's treasury
+    Treasury,
+}
+
+// Structure recording a paused exchange route
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PausedRoute {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub paused_by: Address,
+    pub paused_at: u32,
+}
+
+// Mapping for exchange fees collected by the platform
+#[contracttype]
+pub enum FeeBook {
+    // Brand -> Fees collected in the brand's points
+    Collected(u64),
+    // (From, To) -> Who pays the fee; Input when unset
+    Payer(u64, u64),
+}
+
+// List of currently paused routes
+const PAUSED_ROUTES: Symbol = symbol_short!("P_ROUTES");
+
+// Structure describing how an exchange amount is settled
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExchangeQuote {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub amount_in: i128,
+    // Source points destroyed by the source brand's burn policy
+    pub burned: i128,
+    // Exchange fee, in destination points for FeePayer::Output and source points otherwise
+    pub fee: i128,
+    pub fee_payer: FeePayer,
+    // Part of the fee paid to the source brand's treasury, in source points;
+    // netted off the fee instead when the source brand's treasury pays it
+    pub partner_share: i128,
+    pub rate: u32,
+    // Destination points withheld for tax, already taken out of amount_out
+    pub withheld: i128,
+    pub amount_out: i128,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View the rate and fee currently applied to a one-hop exchange route
+    /// Listing every route out of a brand is left to the loyalty-query contract
+    pub fn view_route(env: Env, from_brand: u64, to_brand: u64) -> RouteInfo {
+        load_brand(&env, from_brand);
+        let target = load_brand(&env, to_brand);
+
+        RouteInfo {
+            from_brand,
+            to_brand,
+            to_brand_name: target.brand_name,
+            rate: route_rate(&env, from_brand, to_brand),
+            fee_bps: route_fee_bps(&env, from_brand, to_brand),
+            fee_payer: route_fee_payer(&env, from_brand, to_brand),
+            burn_bps: exchange_burn_bps(&env, from_brand),
+            paused: is_route_paused(&env, from_brand, to_brand),
+        }
+    }
+
+    /// Quote an exchange of `amount` source points without executing it
+    pub fn quote_exchange(env: Env, from_brand: u64, to_brand: u64, amount: i128) -> ExchangeQuote {
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        quote(&env, None, from_brand, to_brand, amount)
+    }
+
+    /// Quote an exchange for a specific user, including their fee discount
+    pub fn quote_exchange_for(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+    ) -> ExchangeQuote {
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        quote(&env, Some(&user), from_brand, to_brand, amount)
+    }
+
+    /// Pause exchanges from `from_brand` to `to_brand` without pausing either brand
+    /// Callable by the platform admin or the admin of either brand
+    pub fn pause_route(env: Env, caller: Address, from_brand: u64, to_brand: u64) {
+        require_route_authority(&env, &caller, from_brand, to_brand);
+        if is_route_paused(&env, from_brand, to_brand) {
+            panic!("Route already paused");
+        }
+        pause(&env, from_brand, to_brand, caller);
+        env.storage().instance().extend_ttl(100000, 100000);
+    }
+
+    /// Resume exchanges on a paused route (same authority as `pause_route`)
+    pub fn unpause_route(env: Env, caller: Address, from_brand: u64, to_brand: u64) {
+        require_route_authority(&env, &caller, from_brand, to_brand);
+
+        let mut paused = Self::view_paused_routes(env.clone());
+        let index = paused
+            .iter()
+            .position(|route| route.from_brand == from_brand && route.to_brand == to_brand)
+            .unwrap_or_else(|| panic!("Route is not paused"));
+        paused.remove(index as u32);
+        env.storage().instance().set(&PAUSED_ROUTES, &paused);
+        log_admin_action(
+            &env,
+            &caller,
+            AdminAction::RouteResumed,
+            from_brand,
+            None,
+            None,
+        );
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        RoutePauseChanged {
+            from_brand,
+            to_brand,
+            paused: false,
+            caller,
+        }
+        .publish(&env);
+        log!(&env, "✅ Route {} -> {} resumed", from_brand, to_brand);
+    }
+
+    /// View every currently paused route
+    pub fn view_paused_routes(env: Env) -> Vec<PausedRoute> {
+        env.storage()
+            .instance()
+            .get(&PAUSED_ROUTES)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Choose who pays the exchange fee on routes out of a brand (brand admin only)
+    pub fn set_route_fee_payer(env: Env, from_brand: u64, to_brand: u64, payer: FeePayer) {
+        load_brand(&env, from_brand);
+        load_brand(&env, to_brand);
+        require_brand_admin(&env, from_brand);
+        if payer == FeePayer::Treasury
+            && Self::view_brand_treasury(env.clone(), from_brand).is_none()
+        {
+            panic!("Brand treasury not set");
+        }
+
+        env.storage()
+            .instance()
+            .set(&FeeBook::Payer(from_brand, to_brand), &payer);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Fee payer set for route {} -> {}",
+            from_brand,
+            to_brand
+        );
+    }
+
+    /// View the exchange fees collected in a brand's points
+    pub fn view_collected_fees(env: Env, brand_id: u64) -> i128 {
+        env.storage()
+            .instance()
+            .get(&FeeBook::Collected(brand_id))
+            .unwrap_or(0)
+    }
+}
+
+// Split an exchange amount into burn, fee and converted output according to
+// the route's fee payer
+// The fee is discounted by the user's volume tier when a user is given
+pub(crate) fn quote(
+    env: &Env,
+    user: Option<&Address>,
+    from_brand: u64,
+    to_brand: u64,
+    amount: i128,
+) -> ExchangeQuote {
+    let burned = apply_bps(amount, exchange_burn_bps(env, from_brand));
+    let mut fee_bps = route_fee_bps(env, from_brand, to_brand);
+    if let Some(user) = user {
+        fee_bps = discounted_fee_bps(env, user, fee_bps);
+    }
+    let rate = route_rate(env, from_brand, to_brand);
+    check_rate_bounds(env, rate);
+    let fee_payer = route_fee_payer(env, from_brand, to_brand);
+
+    let (mut fee, amount_out) = match fee_payer {
+        FeePayer::Input => {
+            let fee = apply_bps(amount - burned, fee_bps);
+            (fee, apply_bps(amount - burned - fee, rate))
+        }
+        FeePayer::Output => {
+            let converted = apply_bps(amount - burned, rate);
+            let fee = apply_bps(converted, fee_bps);
+            (fee, converted - fee)
+        }
+        FeePayer::Treasury => (
+            apply_bps(amount - burned, fee_bps),
+            apply_bps(amount - burned, rate),
+        ),
+    };
+    let mut partner_share = apply_bps(
+        apply_bps(amount - burned, fee_bps),
+        LoyaltyTokenExchange::view_revenue_share(env.clone(), from_brand, to_brand),
+    );
+    // A treasury paying its own brand's fee is charged only the platform's part
+    if fee_payer == FeePayer::Treasury {
+        fee -= partner_share;
+        partner_share = 0;
+    }
+    let withheld = withholding_due(env, to_brand, amount_out);
+
+    ExchangeQuote {
+        from_brand,
+        to_brand,
+        amount_in: amount,
+        burned,
+        fee,
+        fee_payer,
+        partner_share,
+        rate,
+        withheld,
+        amount_out: amount_out - withheld,
+    }
+}
+
+// Scale an amount by a basis-point ratio, rounding down
+pub(crate) fn apply_bps(amount: i128, bps: u32) -> i128 {
+    amount * bps as i128 / RATE_DENOMINATOR
+}
+
+// Rate applied when exchanging between two brands
+// Members of a coalition settling through pegs always convert at their pegs;
+// otherwise a rate set for the route takes precedence, then other members of
+// the same coalition convert through the coalition unit; every other route
+// settles at parity
+pub(crate) fn route_rate(env: &Env, from_brand: u64, to_brand: u64) -> u32 {
+    if let Some(rate) = pegged_rate(env, from_brand, to_brand) {
+        return rate;
+    }
+    if let Some(rate) = inherited_route(env, from_brand, to_brand, |from, to| {
+        param_value(env, &Param::RouteRate(from, to))
+    }) {
+        return rate as u32;
+    }
+    match coalition_rate(env, from_brand, to_brand) {
+        Some(rate) => rate,
+        None => RATE_DENOMINATOR as u32,
+    }
+}
+
+// Fee in basis points charged on a route
+pub(crate) fn route_fee_bps(env: &Env, from_brand: u64, _to_brand: u64) -> u32 {
+    program_fee_bps(env, from_brand)
+        .unwrap_or_else(|| param_value(env, &Param::ExchangeFeeBps).unwrap_or(0) as u32)
+}
+
+pub(crate) fn route_fee_payer(env: &Env, from_brand: u64, to_brand: u64) -> FeePayer {
+    let payer = inherited_route(env, from_brand, to_brand, |from, to| {
+        env.storage().instance().get(&FeeBook::Payer(from, to))
+    })
+    .unwrap_or(FeePayer::Input);
+    // A sub-brand without a treasury of its own cannot pay its parent's way
+    if payer == FeePayer::Treasury
+        && LoyaltyTokenExchange::view_brand_treasury(env.clone(), from_brand).is_none()
+    {
+        return FeePayer::Input;
+    }
+    payer
+}
+
+// Collect a quoted fee from whoever bears it on the route
+// The platform keeps the fee less the source brand's partner share, which is
+// paid into the source brand's treasury reserve
+pub(crate) fn settle_fee(env: &Env, quote: &ExchangeQuote) {
+    match quote.fee_payer {
+        FeePayer::Input => {
+            record_fee(env, quote.from_brand, quote.fee - quote.partner_share);
+        }
+        FeePayer::Output => {
+            let share_out = apply_bps(quote.partner_share, quote.rate);
+            record_fee(env, quote.to_brand, (quote.fee - share_out).max(0));
+        }
+        FeePayer::Treasury => {
+            if quote.fee > 0 {
+                let treasury =
+                    LoyaltyTokenExchange::view_brand_treasury(env.clone(), quote.from_brand)
+                        .unwrap_or_else(|| panic!("Brand treasury not set"));
+                debit(env, &treasury, quote.from_brand, quote.fee);
+            }
+            record_fee(env, quote.from_brand, quote.fee);
+        }
+    }
+    share_revenue(env, quote.from_brand, quote.partner_share);
+}
+
+// Add a route to the paused list and announce it
+pub(crate) fn pause(env: &Env, from_brand: u64, to_brand: u64, paused_by: Address) {
+    let mut paused = LoyaltyTokenExchange::view_paused_routes(env.clone());
+    paused.push_back(PausedRoute {
+        from_brand,
+        to_brand,
+        paused_by: paused_by.clone(),
+        paused_at: env.ledger().sequence(),
+    });
+    env.storage().instance().set(&PAUSED_ROUTES, &paused);
+    log_admin_action(
+        env,
+        &paused_by,
+        AdminAction::RoutePaused,
+        from_brand,
+        None,
+        None,
+    );
+
+    RoutePauseChanged {
+        from_brand,
+        to_brand,
+        paused: true,
+        caller: paused_by,
+    }
+    .publish(env);
+    forward_alert(env, AlertKind::RoutePaused, from_brand, None, 0);
+    log!(env, "✅ Route {} -> {} paused", from_brand, to_brand);
+}
+
+pub(crate) fn is_route_paused(env: &Env, from_brand: u64, to_brand: u64) -> bool {
+    let paused: Vec<PausedRoute> = env
+        .storage()
+        .instance()
+        .get(&PAUSED_ROUTES)
+        .unwrap_or(Vec::new(env));
+    paused
+        .iter()
+        .any(|route| route.from_brand == from_brand && route.to_brand == to_brand)
+}
+
+// Require `caller` to be the platform admin or an admin of either brand on the route
+fn require_route_authority(env: &Env, caller: &Address, from_brand: u64, to_brand: u64) {
+    caller.require_auth();
+    load_brand(env, from_brand);
+    load_brand(env, to_brand);
+
+    let platform_admin: Option<Address> = env.storage().instance().get(&PLATFORM_ADMIN);
+    let authorized = platform_admin.as_ref() == Some(caller)
+        || brand_admin(env, from_brand).as_ref() == Some(caller)
+        || brand_admin(env, to_brand).as_ref() == Some(caller);
+    if !authorized {
+        panic!("Not authorized to pause this route");
+    }
+}
+
+// Add exchange fees, in the given brand's points, to the platform's collected
+// total, or to the program's for brands hosted by a program
+fn record_fee(env: &Env, brand_id: u64, fee: i128) {
+    if fee == 0 || record_program_fee(env, brand_id, fee) {
+        return;
+    }
+    let key = FeeBook::Collected(brand_id);
+    let collected: i128 = env.storage().instance().get(&key).unwrap_or(0);
+    env.storage().instance().set(&key, &(collected + fee));
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_paused_route_blocks_only_that_direction() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let airline_admin = Address::generate(&env);
+        client.set_brand_admin(&airline, &airline_admin);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+        client.issue_tokens(&user, &airline, &500, &None);
+
+        assert!(client
+            .try_pause_route(&Address::generate(&env), &hotel, &airline)
+            .is_err());
+        client.pause_route(&airline_admin, &hotel, &airline);
+        assert_eq!(client.view_paused_routes().len(), 1);
+        assert!(client.view_route(&hotel, &airline).paused);
+        assert!(!client.view_route(&airline, &hotel).paused);
+        assert_eq!(
+            client.can_exchange(&user, &hotel, &airline, &100),
+            Some(CheckFailure::RoutePaused)
+        );
+
+        // The reverse route and the brands themselves keep working
+        client.exchange_tokens(&user, &airline, &hotel, &100);
+        client.issue_tokens(&user, &hotel, &100, &None);
+
+        client.unpause_route(&airline_admin, &hotel, &airline);
+        assert_eq!(client.view_paused_routes().len(), 0);
+        client.exchange_tokens(&user, &hotel, &airline, &100);
+        assert_eq!(client.view_user_balance(&user, &airline), 500);
+    }
+
+    #[test]
+    fn test_fee_payer_modes() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+
+        // 1% fee on a 2:1 route
+        let fee_change = client.schedule_change(&Param::ExchangeFeeBps, &100);
+        let rate_change = client.schedule_change(&Param::RouteRate(hotel, airline), &20000);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&fee_change);
+        client.execute_change(&rate_change);
+
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!(quote.fee_payer, FeePayer::Input);
+        assert_eq!((quote.fee, quote.amount_out), (10, 1980));
+
+        client.set_route_fee_payer(&hotel, &airline, &FeePayer::Output);
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!((quote.fee, quote.amount_out), (20, 1980));
+
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&hotel, &treasury);
+        client.set_route_fee_payer(&hotel, &airline, &FeePayer::Treasury);
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!((quote.fee, quote.amount_out), (10, 2000));
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        assert!(client
+            .try_exchange_tokens(&user, &hotel, &airline, &1000)
+            .is_err());
+
+        client.issue_tokens(&treasury, &hotel, &50, &None);
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_user_balance(&user, &airline), 2000);
+        assert_eq!(client.view_user_balance(&treasury, &hotel), 40);
+        assert_eq!(client.view_collected_fees(&hotel), 10);
+    }
+
+    #[test]
+    fn test_quote_exchange_at_parity() {
+        let env = Env::default();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+
+        let quote = client.quote_exchange(&hotel, &airline, &1000);
+        assert_eq!(quote.burned, 0);
+        assert_eq!(quote.fee, 0);
+        assert_eq!(quote.amount_out, 1000);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::*;
+
+// Mapping for user sequence numbers: User -> Sequence the next call must carry
+// Kept in persistent storage, one entry per user
+#[contracttype]
+pub enum SequenceBook {
+    Next(Address),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// View the sequence number a user's next sequenced transfer, exchange or
+    /// redemption must carry
+    /// Every state-changing call the user authorizes advances it
+    pub fn view_user_sequence(env: Env, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&SequenceBook::Next(user))
+            .unwrap_or(0)
+    }
+}
+
+// Check the sequence number a user's call carries, if any, and advance the
+// user's sequence; unsequenced calls advance it too, so a sequenced call
+// submitted alongside them applies only in the order it was signed
+pub(crate) fn consume_sequence(env: &Env, user: &Address, sequence: Option<u64>) {
+    let next = LoyaltyTokenExchange::view_user_sequence(env.clone(), user.clone());
+    if let Some(sequence) = sequence {
+        if sequence != next {
+            panic!("Out-of-order sequence number");
+        }
+    }
+    let key = SequenceBook::Next(user.clone());
+    env.storage().persistent().set(&key, &(next + 1));
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+}
+
+// Require a user's authorization for a state-changing call without a
+// sequence number, advancing their sequence
+pub(crate) fn require_user(env: &Env, user: &Address) {
+    user.require_auth();
+    consume_sequence(env, user, None);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_sequenced_calls_apply_in_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        let friend = Address::generate(&env);
+        // Unsequenced calls the user authorizes advance the sequence too
+        client.issue_tokens(&user, &hotel, &1000, &None);
+        assert_eq!(client.view_user_sequence(&user), 1);
+
+        client.transfer_tokens_v2(&user, &friend, &hotel, &100, &None, &Some(1));
+        assert!(client
+            .try_transfer_tokens_v2(&user, &friend, &hotel, &100, &None, &Some(1))
+            .is_err());
+        assert!(client
+            .try_exchange_tokens_v3(
+                &user,
+                &hotel,
+                &airline,
+                &100,
+                &0,
+                &u32::MAX,
+                &None,
+                &Some(3)
+            )
+            .is_err());
+
+        client.exchange_tokens_v3(
+            &user,
+            &hotel,
+            &airline,
+            &100,
+            &0,
+            &u32::MAX,
+            &None,
+            &Some(2),
+        );
+        client.transfer_tokens(&user, &friend, &hotel, &100, &None);
+        client.send_gift(&user, &friend, &hotel, &100, &None, &None);
+        assert_eq!(client.view_user_sequence(&user), 5);
+        assert_eq!(client.view_user_sequence(&friend), 0);
+        assert_eq!(client.view_user_balance(&user, &hotel), 600);
+        env.as_contract(&contract_id, || {
+            let key = SequenceBook::Next(user.clone());
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+    }
+}
@@ -0,0 +1,164 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a point in time at which a brand's balances are frozen
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceSnapshot {
+    pub snapshot_id: u64,
+    pub brand_id: u64,
+    pub ledger: u32,
+}
+
+// Balance a holder had at every snapshot up to `snapshot_id` not covered by
+// an earlier entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldBalance {
+    pub snapshot_id: u64,
+    pub balance: i128,
+}
+
+// Mapping for balance snapshots
+// Balances are copied on write: a holder's balance is only stored when it
+// first changes after a snapshot, and read from the live balance until then
+#[contracttype]
+pub enum SnapshotBook {
+    Snapshot(u64),
+    // Brand -> Latest snapshot_id of the brand
+    Latest(u64),
+    // (Holder, Brand) -> Held balances, oldest first, kept in persistent storage
+    Held(Address, u64),
+}
+
+// Counter for snapshots
+const SNAPSHOT_COUNT: Symbol = symbol_short!("SN_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Freeze the current balances of a brand's holders (brand admin only)
+    /// Returns the snapshot_id
+    pub fn take_snapshot(env: Env, brand_id: u64) -> u64 {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let snapshot_id = snapshot(&env, brand_id);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Snapshot {} taken of brand {}",
+            snapshot_id,
+            brand_id
+        );
+        snapshot_id
+    }
+
+    /// View a snapshot by snapshot_id
+    pub fn view_snapshot(env: Env, snapshot_id: u64) -> BalanceSnapshot {
+        env.storage()
+            .instance()
+            .get(&SnapshotBook::Snapshot(snapshot_id))
+            .unwrap_or_else(|| panic!("Snapshot not found"))
+    }
+
+    /// View the balance a user held of the snapshot's brand when it was taken
+    /// Decay pending at that point is not deducted
+    pub fn balance_at_snapshot(env: Env, user: Address, snapshot_id: u64) -> i128 {
+        let snapshot = Self::view_snapshot(env.clone(), snapshot_id);
+        for held in held_balances(&env, &user, snapshot.brand_id).iter() {
+            if held.snapshot_id >= snapshot_id {
+                return held.balance;
+            }
+        }
+        stored_balance(&env, &user, snapshot.brand_id)
+    }
+}
+
+pub(crate) fn snapshot(env: &Env, brand_id: u64) -> u64 {
+    let mut snapshot_count: u64 = env.storage().instance().get(&SNAPSHOT_COUNT).unwrap_or(0);
+    snapshot_count += 1;
+
+    let snapshot = BalanceSnapshot {
+        snapshot_id: snapshot_count,
+        brand_id,
+        ledger: env.ledger().sequence(),
+    };
+    env.storage()
+        .instance()
+        .set(&SnapshotBook::Snapshot(snapshot_count), &snapshot);
+    env.storage()
+        .instance()
+        .set(&SnapshotBook::Latest(brand_id), &snapshot_count);
+    env.storage()
+        .instance()
+        .set(&SNAPSHOT_COUNT, &snapshot_count);
+    snapshot_count
+}
+
+// Keep a holder's balance for the snapshots taken since it last changed,
+// before it changes again
+pub(crate) fn preserve_snapshot_balance(env: &Env, user: &Address, brand_id: u64) {
+    let latest: Option<u64> = env
+        .storage()
+        .instance()
+        .get(&SnapshotBook::Latest(brand_id));
+    let Some(latest) = latest else {
+        return;
+    };
+    let mut held = held_balances(env, user, brand_id);
+    if held.last().is_some_and(|last| last.snapshot_id >= latest) {
+        return;
+    }
+
+    held.push_back(HeldBalance {
+        snapshot_id: latest,
+        balance: stored_balance(env, user, brand_id),
+    });
+    let key = SnapshotBook::Held(user.clone(), brand_id);
+    env.storage().persistent().set(&key, &held);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+}
+
+fn held_balances(env: &Env, user: &Address, brand_id: u64) -> Vec<HeldBalance> {
+    env.storage()
+        .persistent()
+        .get(&SnapshotBook::Held(user.clone(), brand_id))
+        .unwrap_or(Vec::new(env))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_snapshot_keeps_balances_at_the_time_it_was_taken() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        client.issue_tokens(&alice, &brand_id, &100, &None);
+
+        let first = client.take_snapshot(&brand_id);
+        client.issue_tokens(&alice, &brand_id, &50, &None);
+        let second = client.take_snapshot(&brand_id);
+        client.transfer_tokens(&alice, &bob, &brand_id, &150, &None);
+
+        assert_eq!(client.balance_at_snapshot(&alice, &first), 100);
+        assert_eq!(client.balance_at_snapshot(&alice, &second), 150);
+        assert_eq!(client.balance_at_snapshot(&bob, &second), 0);
+        assert_eq!(client.view_user_balance(&bob, &brand_id), 150);
+        env.as_contract(&contract_id, || {
+            let key = SnapshotBook::Held(alice.clone(), brand_id);
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+    }
+}
@@ -0,0 +1,258 @@
+use crate::*;
+
+// Structure to store a recurring exchange between two brands
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingOrder {
+    pub order_id: u64,
+    pub user: Address,
+    pub from_brand: u64,
+    pub to_brand: u64,
+    // Source points exchanged each interval
+    pub amount: i128,
+    pub interval_ledgers: u32,
+    pub next_due: u32,
+    pub executions: u32,
+    // Intervals skipped because the exchange could not be made
+    pub skipped: u32,
+    pub is_paused: bool,
+}
+
+// Mapping for standing orders
+#[contracttype]
+pub enum StandingOrderBook {
+    Order(u64),
+}
+
+// Counter for standing orders
+const ORDER_COUNT: Symbol = symbol_short!("SO_COUNT");
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Create a standing order exchanging `amount` points of `from_brand` into
+    /// `to_brand` every `interval_ledgers`; the first exchange is due immediately
+    /// Returns the order_id of the new order
+    pub fn create_standing_order(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+        interval_ledgers: u32,
+    ) -> u64 {
+        require_user(&env, &user);
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        if from_brand == to_brand {
+            panic!("Cannot exchange to the same brand");
+        }
+        if interval_ledgers == 0 {
+            panic!("Interval must be positive");
+        }
+        load_brand(&env, from_brand);
+        load_brand(&env, to_brand);
+
+        let mut order_count: u64 = env.storage().instance().get(&ORDER_COUNT).unwrap_or(0);
+        order_count += 1;
+
+        let order = StandingOrder {
+            order_id: order_count,
+            user,
+            from_brand,
+            to_brand,
+            amount,
+            interval_ledgers,
+            next_due: env.ledger().sequence(),
+            executions: 0,
+            skipped: 0,
+            is_paused: false,
+        };
+        save_order(&env, &order);
+        env.storage().instance().set(&ORDER_COUNT, &order_count);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Standing order {} created: brand {} → brand {}",
+            order_count,
+            from_brand,
+            to_brand
+        );
+        order_count
+    }
+
+    /// Execute a due standing order; anyone may call this
+    /// When the exchange cannot be made (e.g. insufficient balance) the
+    /// interval is skipped
+    /// Returns whether the exchange was made
+    pub fn tick(env: Env, order_id: u64) -> bool {
+        let mut order = load_order(&env, order_id);
+        if order.is_paused {
+            panic!("Standing order is paused");
+        }
+        let sequence = env.ledger().sequence();
+        if sequence < order.next_due {
+            panic!("Standing order is not due");
+        }
+
+        let executable = check_exchange(
+            &env,
+            &order.user,
+            order.from_brand,
+            order.to_brand,
+            order.amount,
+        )
+        .is_ok();
+        if executable {
+            exchange(
+                &env,
+                &order.user,
+                order.from_brand,
+                order.to_brand,
+                order.amount,
+                0,
+                None,
+            );
+            order.executions += 1;
+        } else {
+            order.skipped += 1;
+            log!(&env, "⚠️ Standing order {} skipped", order_id);
+        }
+
+        order.next_due = sequence + order.interval_ledgers;
+        save_order(&env, &order);
+        env.storage().instance().extend_ttl(100000, 100000);
+        executable
+    }
+
+    /// Pause a standing order (owner only)
+    pub fn pause_standing_order(env: Env, user: Address, order_id: u64) {
+        let mut order = load_owned_order(&env, &user, order_id);
+        order.is_paused = true;
+        save_order(&env, &order);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Standing order {} paused", order_id);
+    }
+
+    /// Resume a paused standing order (owner only); the next exchange is due immediately
+    pub fn resume_standing_order(env: Env, user: Address, order_id: u64) {
+        let mut order = load_owned_order(&env, &user, order_id);
+        if !order.is_paused {
+            panic!("Standing order is not paused");
+        }
+        order.is_paused = false;
+        order.next_due = order.next_due.min(env.ledger().sequence());
+        save_order(&env, &order);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Standing order {} resumed", order_id);
+    }
+
+    /// Cancel a standing order (owner only)
+    pub fn cancel_standing_order(env: Env, user: Address, order_id: u64) {
+        load_owned_order(&env, &user, order_id);
+        env.storage()
+            .instance()
+            .remove(&StandingOrderBook::Order(order_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Standing order {} cancelled", order_id);
+    }
+
+    /// View standing order details by order_id
+    pub fn view_standing_order(env: Env, order_id: u64) -> StandingOrder {
+        load_order(&env, order_id)
+    }
+}
+
+fn load_order(env: &Env, order_id: u64) -> StandingOrder {
+    env.storage()
+        .instance()
+        .get(&StandingOrderBook::Order(order_id))
+        .unwrap_or_else(|| panic!("Standing order not found"))
+}
+
+fn load_owned_order(env: &Env, user: &Address, order_id: u64) -> StandingOrder {
+    require_user(env, user);
+    let order = load_order(env, order_id);
+    if order.user != *user {
+        panic!("Not the order owner");
+    }
+    order
+}
+
+fn save_order(env: &Env, order: &StandingOrder) {
+    env.storage()
+        .instance()
+        .set(&StandingOrderBook::Order(order.order_id), order);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_standing_order_exchanges_each_interval() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &250, &None);
+
+        let order_id = client.create_standing_order(&user, &hotel, &airline, &100, &50);
+        assert!(client.tick(&order_id));
+        assert!(client.try_tick(&order_id).is_err());
+
+        env.ledger().set_sequence_number(50);
+        assert!(client.tick(&order_id));
+
+        // Only 50 hotel points left: the interval is skipped
+        env.ledger().set_sequence_number(100);
+        assert!(!client.tick(&order_id));
+
+        let order = client.view_standing_order(&order_id);
+        assert_eq!(order.executions, 2);
+        assert_eq!(order.skipped, 1);
+        assert_eq!(order.next_due, 150);
+        assert_eq!(client.view_user_balance(&user, &airline), 200);
+    }
+
+    #[test]
+    fn test_paused_and_cancelled_orders_do_not_execute() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &500, &None);
+
+        let order_id = client.create_standing_order(&user, &hotel, &airline, &100, &50);
+        assert!(client
+            .try_pause_standing_order(&Address::generate(&env), &order_id)
+            .is_err());
+        client.pause_standing_order(&user, &order_id);
+        assert!(client.try_tick(&order_id).is_err());
+
+        client.resume_standing_order(&user, &order_id);
+        assert!(client.tick(&order_id));
+
+        client.cancel_standing_order(&user, &order_id);
+        env.ledger().set_sequence_number(50);
+        assert!(client.try_tick(&order_id).is_err());
+        assert_eq!(client.view_user_balance(&user, &hotel), 400);
+    }
+}
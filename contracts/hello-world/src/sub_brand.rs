@@ -0,0 +1,226 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Mapping for brand hierarchy
+#[contracttype]
+pub enum SubBrandBook {
+    // Sub-brand -> Parent brand
+    Parent(u64),
+    // Parent brand -> Sub-brands
+    Children(u64),
+}
+
+// Structure reporting supply aggregated over a brand and its sub-brands
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramSupply {
+    pub parent_id: u64,
+    pub parent_supply: i128,
+    pub sub_brand_count: u32,
+    pub total_supply: i128,
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Create a regional sub-program under `parent_id` (parent brand admin only)
+    /// The sub-brand joins the parent's program, if it belongs to one
+    /// Until the sub-brand is given its own, it inherits the parent's admin,
+    /// coalition membership, and the rate, fee payer and revenue share of the
+    /// parent's routes
+    /// Returns the brand_id of the new sub-brand
+    pub fn create_sub_brand(env: Env, parent_id: u64, name: String) -> u64 {
+        Self::create_sub_brand_v2(env, parent_id, name, None)
+    }
+
+    /// Create a sub-brand through the registration gates: `code` redeems an
+    /// invitation while registration is invite-only, and the parent's admin
+    /// posts the deposit while one is required
+    /// Returns the brand_id of the new sub-brand
+    pub fn create_sub_brand_v2(env: Env, parent_id: u64, name: String, code: Option<Bytes>) -> u64 {
+        let parent = load_brand(&env, parent_id);
+        if !parent.is_active {
+            panic!("Brand is not active");
+        }
+        if env
+            .storage()
+            .instance()
+            .has(&SubBrandBook::Parent(parent_id))
+        {
+            panic!("Sub-brands cannot have sub-brands");
+        }
+        let admin = require_brand_admin(&env, parent_id);
+
+        let sub_brand_id = create_brand(&env, name);
+        admit_brand(&env, sub_brand_id, &admin, code);
+        env.storage()
+            .instance()
+            .set(&SubBrandBook::Parent(sub_brand_id), &parent_id);
+
+        let mut children = Self::view_sub_brands(env.clone(), parent_id);
+        children.push_back(sub_brand_id);
+        env.storage()
+            .instance()
+            .set(&SubBrandBook::Children(parent_id), &children);
+        if let Some(program_id) = Self::view_brand_program(env.clone(), parent_id) {
+            join_program(&env, program_id, sub_brand_id);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Sub-brand {} created under brand {}",
+            sub_brand_id,
+            parent_id
+        );
+        sub_brand_id
+    }
+
+    /// View the sub-brands of a brand
+    pub fn view_sub_brands(env: Env, parent_id: u64) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&SubBrandBook::Children(parent_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// View the parent of a sub-brand, if any
+    pub fn view_parent_brand(env: Env, brand_id: u64) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&SubBrandBook::Parent(brand_id))
+    }
+
+    /// View the supply of a brand aggregated with all of its sub-brands
+    pub fn view_program_supply(env: Env, parent_id: u64) -> ProgramSupply {
+        let parent_supply = Self::view_brand_supply(env.clone(), parent_id);
+        let children = Self::view_sub_brands(env.clone(), parent_id);
+
+        let mut total_supply = parent_supply;
+        for sub_brand_id in children.iter() {
+            total_supply += Self::view_brand_supply(env.clone(), sub_brand_id);
+        }
+
+        ProgramSupply {
+            parent_id,
+            parent_supply,
+            sub_brand_count: children.len(),
+            total_supply,
+        }
+    }
+}
+
+// Look up a brand setting, falling back to the parent's for a sub-brand that
+// has not set its own
+pub(crate) fn inherited<T>(
+    env: &Env,
+    brand_id: u64,
+    lookup: impl Fn(u64) -> Option<T>,
+) -> Option<T> {
+    lookup(brand_id)
+        .or_else(|| LoyaltyTokenExchange::view_parent_brand(env.clone(), brand_id).and_then(lookup))
+}
+
+// Look up a route setting, falling back to the route between the parents of
+// sub-brands when the route has not set its own
+pub(crate) fn inherited_route<T>(
+    env: &Env,
+    from_brand: u64,
+    to_brand: u64,
+    lookup: impl Fn(u64, u64) -> Option<T>,
+) -> Option<T> {
+    lookup(from_brand, to_brand).or_else(|| {
+        let parent_of = |brand_id| {
+            LoyaltyTokenExchange::view_parent_brand(env.clone(), brand_id).unwrap_or(brand_id)
+        };
+        let (from_parent, to_parent) = (parent_of(from_brand), parent_of(to_brand));
+        if (from_parent, to_parent) == (from_brand, to_brand) || from_parent == to_parent {
+            return None;
+        }
+        lookup(from_parent, to_parent)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        vec, Address, Env, String,
+    };
+
+    fn setup(env: &Env) -> (LoyaltyTokenExchangeClient<'_>, Address, u64) {
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(env, &contract_id);
+
+        let platform_admin = Address::generate(env);
+        let brand_admin = Address::generate(env);
+        client.set_platform_admin(&platform_admin);
+
+        let parent_id = client.register_brand(&String::from_str(env, "Coffee Co"));
+        client.set_brand_admin(&parent_id, &brand_admin);
+        (client, brand_admin, parent_id)
+    }
+
+    #[test]
+    fn test_sub_brand_inherits_admin_and_rolls_up_supply() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, brand_admin, parent_id) = setup(&env);
+
+        let east = client.create_sub_brand(&parent_id, &String::from_str(&env, "Coffee Co East"));
+        let west = client.create_sub_brand(&parent_id, &String::from_str(&env, "Coffee Co West"));
+        assert_eq!(client.view_sub_brands(&parent_id), vec![&env, east, west]);
+        assert_eq!(client.view_parent_brand(&east), Some(parent_id));
+        assert_eq!(client.get_brand_admin(&west), Some(brand_admin));
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &parent_id, &100, &None);
+        client.issue_tokens(&user, &east, &200, &None);
+        client.issue_tokens(&user, &west, &300, &None);
+
+        let supply = client.view_program_supply(&parent_id);
+        assert_eq!(supply.parent_supply, 100);
+        assert_eq!(supply.sub_brand_count, 2);
+        assert_eq!(supply.total_supply, 600);
+    }
+
+    #[test]
+    fn test_sub_brand_inherits_route_settings_until_overridden() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, parent_id) = setup(&env);
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+
+        let rate_change = client.schedule_change(&Param::RouteRate(parent_id, airline), &20_000);
+        let share_change = client.set_revenue_share(&parent_id, &airline, &2_500);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&rate_change);
+        client.execute_change(&share_change);
+        client.set_route_fee_payer(&parent_id, &airline, &FeePayer::Output);
+
+        let east = client.create_sub_brand(&parent_id, &String::from_str(&env, "East"));
+        let quote = client.quote_exchange(&east, &airline, &100);
+        assert_eq!(quote.rate, 20_000);
+        assert_eq!(quote.fee_payer, FeePayer::Output);
+        assert_eq!(client.view_revenue_share(&east, &airline), 2_500);
+
+        // The sub-brand's own route settings take precedence
+        client.set_route_fee_payer(&east, &airline, &FeePayer::Input);
+        assert_eq!(
+            client.quote_exchange(&east, &airline, &100).fee_payer,
+            FeePayer::Input
+        );
+        assert_eq!(client.quote_exchange(&east, &parent_id, &100).rate, 10_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sub-brands cannot have sub-brands")]
+    fn test_nested_sub_brand_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, parent_id) = setup(&env);
+
+        let east = client.create_sub_brand(&parent_id, &String::from_str(&env, "East"));
+        client.create_sub_brand(&east, &String::from_str(&env, "North East"));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a brand's withholding on points converted into it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxWithholding {
+    pub rate_bps: u32,
+    // Receives the withheld points
+    pub tax_address: Address,
+}
+
+// Structure reporting the points withheld from a user by one brand in a year
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WithholdingTotal {
+    pub brand_id: u64,
+    pub amount: i128,
+}
+
+// Mapping for tax withholding
+#[contracttype]
+pub enum TaxBook {
+    // Brand -> Withholding on exchanges into the brand
+    Withholding(u64),
+    // Change -> Withholding awaiting the timelock
+    QueuedWithholding(u64),
+    // (User, Year, Brand) -> Points withheld, kept in persistent storage
+    Withheld(Address, u32, u64),
+    // (User, Year) -> Brands that withheld from the user, in order of first
+    // withholding, kept in persistent storage
+    Brands(Address, u32),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Schedule withholding `rate_bps` of the points converted into a brand
+    /// and paying them to the tax address; `None` stops withholding
+    /// It takes effect through `execute_change` after the timelock (brand
+    /// admin only)
+    /// Returns the change_id of the queued change
+    pub fn set_tax_withholding(
+        env: Env,
+        brand_id: u64,
+        withholding: Option<TaxWithholding>,
+    ) -> u64 {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        let value = match &withholding {
+            Some(withholding) => withholding.rate_bps as i128,
+            None => PARAM_UNSET,
+        };
+        let change_id = queue_change(&env, Param::TaxWithholding(brand_id), value);
+        if let Some(withholding) = withholding {
+            env.storage()
+                .instance()
+                .set(&TaxBook::QueuedWithholding(change_id), &withholding);
+        }
+        env.storage().instance().extend_ttl(100000, 100000);
+        change_id
+    }
+
+    /// Withdraw a queued withholding change (brand admin only)
+    pub fn cancel_tax_withholding(env: Env, change_id: u64) {
+        let Param::TaxWithholding(brand_id) = load_change(&env, change_id).param else {
+            panic!("Not a tax withholding change");
+        };
+        require_brand_admin(&env, brand_id);
+
+        drop_change(&env, change_id);
+    }
+
+    /// View a brand's withholding on exchanges into it, if any
+    pub fn view_tax_withholding(env: Env, brand_id: u64) -> Option<TaxWithholding> {
+        env.storage()
+            .instance()
+            .get(&TaxBook::Withholding(brand_id))
+    }
+
+    /// View the points a brand withheld from a user in a calendar year
+    pub fn view_withheld(env: Env, user: Address, brand_id: u64, year: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&TaxBook::Withheld(user, year, brand_id))
+            .unwrap_or(0)
+    }
+
+    /// View the points withheld from a user in a calendar year, per brand
+    pub fn view_annual_withholding(env: Env, user: Address, year: u32) -> Vec<WithholdingTotal> {
+        let brands: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&TaxBook::Brands(user.clone(), year))
+            .unwrap_or(Vec::new(&env));
+        let mut totals = Vec::new(&env);
+        for brand_id in brands.iter() {
+            totals.push_back(WithholdingTotal {
+                brand_id,
+                amount: Self::view_withheld(env.clone(), user.clone(), brand_id, year),
+            });
+        }
+        totals
+    }
+
+    /// View the calendar year (UTC) of the current ledger
+    pub fn current_tax_year(env: Env) -> u32 {
+        tax_year(env.ledger().timestamp())
+    }
+}
+
+// Put a withholding change that passed the timelock in place
+pub(crate) fn apply_tax_withholding(env: &Env, brand_id: u64, change_id: u64) {
+    let queued_key = TaxBook::QueuedWithholding(change_id);
+    let key = TaxBook::Withholding(brand_id);
+    match env
+        .storage()
+        .instance()
+        .get::<_, TaxWithholding>(&queued_key)
+    {
+        Some(withholding) => env.storage().instance().set(&key, &withholding),
+        None => env.storage().instance().remove(&key),
+    }
+    env.storage().instance().remove(&queued_key);
+}
+
+// Forget the withholding of a withdrawn change
+pub(crate) fn drop_tax_withholding(env: &Env, change_id: u64) {
+    env.storage()
+        .instance()
+        .remove(&TaxBook::QueuedWithholding(change_id));
+}
+
+// Points withheld from an exchange paying out `amount_out` of the brand
+pub(crate) fn withholding_due(env: &Env, to_brand: u64, amount_out: i128) -> i128 {
+    match LoyaltyTokenExchange::view_tax_withholding(env.clone(), to_brand) {
+        Some(withholding) => apply_bps(amount_out, withholding.rate_bps),
+        None => 0,
+    }
+}
+
+// Pay an exchange's withheld points to the tax address and add them to the
+// user's total for the year
+pub(crate) fn withhold_tax(env: &Env, user: &Address, quote: &ExchangeQuote) {
+    if quote.withheld == 0 {
+        return;
+    }
+    let withholding = LoyaltyTokenExchange::view_tax_withholding(env.clone(), quote.to_brand)
+        .unwrap_or_else(|| panic!("Tax withholding not set"));
+    credit(
+        env,
+        &withholding.tax_address,
+        quote.to_brand,
+        quote.withheld,
+    );
+
+    let year = LoyaltyTokenExchange::current_tax_year(env.clone());
+    let total =
+        LoyaltyTokenExchange::view_withheld(env.clone(), user.clone(), quote.to_brand, year);
+    if total == 0 {
+        let key = TaxBook::Brands(user.clone(), year);
+        let mut brands: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        brands.push_back(quote.to_brand);
+        env.storage().persistent().set(&key, &brands);
+        env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    }
+    let key = TaxBook::Withheld(user.clone(), year, quote.to_brand);
+    env.storage()
+        .persistent()
+        .set(&key, &(total + quote.withheld));
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+
+    TaxWithheld {
+        brand_id: quote.to_brand,
+        user: user.clone(),
+        amount: quote.withheld,
+        tax_address: withholding.tax_address,
+        year,
+    }
+    .publish(env);
+}
+
+// Calendar year (UTC) of a unix timestamp
+fn tax_year(timestamp: u64) -> u32 {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of each year
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    // January and February belong to the next calendar year
+    let year = era * 400 + year_of_era + if month_index >= 10 { 1 } else { 0 };
+    year as u32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_exchange_withholding_reported_per_year() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&airline, &Address::generate(&env));
+        let tax_address = Address::generate(&env);
+        let change_id = client.set_tax_withholding(
+            &airline,
+            &Some(TaxWithholding {
+                rate_bps: 1_000,
+                tax_address: tax_address.clone(),
+            }),
+        );
+        assert_eq!(client.view_tax_withholding(&airline), None);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        // 2024-12-31 23:59:59 UTC, then 2025-01-01 00:00:00 UTC
+        env.ledger().set_timestamp(1_735_689_599);
+        assert_eq!(client.current_tax_year(), 2024);
+        assert_eq!(client.quote_exchange(&hotel, &airline, &500).withheld, 50);
+        client.exchange_tokens(&user, &hotel, &airline, &500);
+        env.ledger().set_timestamp(1_735_689_600);
+        client.exchange_tokens(&user, &hotel, &airline, &200);
+
+        assert_eq!(client.view_user_balance(&user, &airline), 630);
+        assert_eq!(client.view_user_balance(&tax_address, &airline), 70);
+        assert_eq!(client.view_withheld(&user, &airline, &2024), 50);
+        env.as_contract(&contract_id, || {
+            let key = TaxBook::Withheld(user.clone(), 2024, airline);
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+        let totals = client.view_annual_withholding(&user, &2025);
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals.get_unchecked(0).brand_id, airline);
+        assert_eq!(totals.get_unchecked(0).amount, 20);
+
+        // Converting back into the hotel withholds nothing
+        client.exchange_tokens(&user, &airline, &hotel, &100);
+        assert_eq!(client.view_annual_withholding(&user, &2025).len(), 1);
+    }
+
+    #[test]
+    fn test_withholding_changes_wait_for_timelock() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&airline, &Address::generate(&env));
+        let withholding = TaxWithholding {
+            rate_bps: 500,
+            tax_address: Address::generate(&env),
+        };
+        assert!(client
+            .try_schedule_change(&Param::TaxWithholding(airline), &500)
+            .is_err());
+
+        let change_id = client.set_tax_withholding(&airline, &Some(withholding.clone()));
+        assert_eq!(client.quote_exchange(&hotel, &airline, &1000).withheld, 0);
+        assert!(client.try_execute_change(&change_id).is_err());
+
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change_id);
+        assert_eq!(client.view_tax_withholding(&airline), Some(withholding));
+        assert_eq!(client.quote_exchange(&hotel, &airline, &1000).withheld, 50);
+
+        // Stopping withholding waits for the timelock too, and a cancelled
+        // change leaves the current withholding in place
+        let stop = client.set_tax_withholding(&airline, &None);
+        let raise = client.set_tax_withholding(
+            &airline,
+            &Some(TaxWithholding {
+                rate_bps: 9_999,
+                tax_address: Address::generate(&env),
+            }),
+        );
+        assert!(client.try_cancel_change(&raise).is_err());
+        client.cancel_tax_withholding(&raise);
+        env.ledger().set_sequence_number(2 * TIMELOCK_LEDGERS);
+        assert!(client.try_execute_change(&raise).is_err());
+        assert_eq!(client.quote_exchange(&hotel, &airline, &1000).withheld, 50);
+        client.execute_change(&stop);
+        assert_eq!(client.view_tax_withholding(&airline), None);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::*;
+
+// Where points deposited into a brand treasury came from
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TreasurySource {
+    // The brand's share of exchange fees on conversions out of it
+    RevenueShare,
+    // Points spent on subscription redemptions
+    Subscription,
+    // Expired claimable balances taken back from users
+    Clawback,
+    // Escrowed promotion bonuses whose condition was not met
+    Promotion,
+}
+
+// Mapping for brand treasuries
+#[contracttype]
+pub enum TreasuryBook {
+    // Brand -> Address receiving withdrawals and paying Treasury-mode fees
+    Address(u64),
+    // Brand -> Points held by the treasury, out of circulation
+    Reserve(u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the treasury address of a brand (brand admin only)
+    /// The treasury is kept apart from the admin key: the admin authorizes
+    /// withdrawals, the treasury address receives them
+    pub fn set_brand_treasury(env: Env, brand_id: u64, treasury: Address) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .set(&TreasuryBook::Address(brand_id), &treasury);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        TreasurySet { brand_id, treasury }.publish(&env);
+        log!(&env, "✅ Treasury set for brand {}", brand_id);
+    }
+
+    /// Pay points out of a brand's treasury reserve to its treasury address
+    /// (brand admin only)
+    /// Returns the remaining reserve
+    pub fn withdraw_treasury(env: Env, brand_id: u64, amount: i128) -> i128 {
+        require_brand_admin(&env, brand_id);
+        let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand treasury not set"));
+
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let reserve = Self::view_treasury_reserve(env.clone(), brand_id);
+        if reserve < amount {
+            panic!("Insufficient treasury reserve");
+        }
+
+        let remaining = reserve - amount;
+        env.storage()
+            .instance()
+            .set(&TreasuryBook::Reserve(brand_id), &remaining);
+        credit(&env, &treasury, brand_id, amount);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        TreasuryWithdrawn {
+            brand_id,
+            treasury,
+            amount,
+            reserve: remaining,
+        }
+        .publish(&env);
+        log!(
+            &env,
+            "✅ Withdrew {} tokens from treasury of brand {}",
+            amount,
+            brand_id
+        );
+        remaining
+    }
+
+    /// View the treasury address of a brand, if set
+    pub fn view_brand_treasury(env: Env, brand_id: u64) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&TreasuryBook::Address(brand_id))
+    }
+
+    /// View the points held in a brand's treasury reserve
+    pub fn view_treasury_reserve(env: Env, brand_id: u64) -> i128 {
+        env.storage()
+            .instance()
+            .get(&TreasuryBook::Reserve(brand_id))
+            .unwrap_or(0)
+    }
+}
+
+// Add points taken out of circulation to a brand's treasury reserve
+pub(crate) fn deposit_treasury(env: &Env, brand_id: u64, source: TreasurySource, amount: i128) {
+    if amount <= 0 {
+        return;
+    }
+    let reserve = LoyaltyTokenExchange::view_treasury_reserve(env.clone(), brand_id) + amount;
+    env.storage()
+        .instance()
+        .set(&TreasuryBook::Reserve(brand_id), &reserve);
+
+    TreasuryDeposited {
+        brand_id,
+        source,
+        amount,
+        reserve,
+    }
+    .publish(env);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_fees_and_subscriptions_accumulate_in_treasury() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = client.register_brand(&String::from_str(&env, "Airline"));
+        client.set_brand_admin(&hotel, &Address::generate(&env));
+        let change = client.schedule_change(&Param::ExchangeFeeBps, &100);
+        let share_change = client.set_revenue_share(&hotel, &airline, &10000);
+        env.ledger().set_sequence_number(TIMELOCK_LEDGERS);
+        client.execute_change(&change);
+        client.execute_change(&share_change);
+
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &hotel, &1300, &None);
+        client.exchange_tokens(&user, &hotel, &airline, &1000);
+        assert_eq!(client.view_treasury_reserve(&hotel), 10);
+
+        let reward_id = client.add_reward(&hotel, &String::from_str(&env, "Night"), &200, &5);
+        let sub_id = client.subscribe(&user, &hotel, &reward_id, &100);
+        client.process_due(&sub_id);
+        assert_eq!(client.view_treasury_reserve(&hotel), 210);
+        assert_eq!(client.view_brand_supply(&hotel), 100);
+
+        // Withdrawals need a treasury address and cannot exceed the reserve
+        assert!(client.try_withdraw_treasury(&hotel, &10).is_err());
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&hotel, &treasury);
+        assert!(client.try_withdraw_treasury(&hotel, &211).is_err());
+
+        assert_eq!(client.withdraw_treasury(&hotel, &150), 60);
+        assert_eq!(client.view_user_balance(&treasury, &hotel), 150);
+        assert_eq!(client.view_brand_supply(&hotel), 250);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::*;
+
+// Reasons an operation would be rejected
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CheckFailure {
+    InvalidAmount,
+    BrandNotFound,
+    BrandInactive,
+    SupplyCapExceeded,
+    SameBrand,
+    FromBrandNotFound,
+    ToBrandNotFound,
+    FromBrandInactive,
+    ToBrandInactive,
+    RoutePaused,
+    InsufficientBalance,
+    RewardNotFound,
+    RewardInactive,
+    RewardOutOfStock,
+    MissingReference,
+    CrossProgram,
+    Blackout,
+    WindingDown,
+    BrandCapReached,
+    AdminQuotaReached,
+    IssuanceLimitExceeded,
+}
+
+impl CheckFailure {
+    // Abort the current invocation with the failure's error message
+    pub(crate) fn fail(self) -> ! {
+        match self {
+            CheckFailure::InvalidAmount => panic!("Amount must be positive"),
+            CheckFailure::BrandNotFound
+            | CheckFailure::FromBrandNotFound
+            | CheckFailure::ToBrandNotFound => panic!("Brand not found"),
+            CheckFailure::BrandInactive => panic!("Brand is not active"),
+            CheckFailure::SupplyCapExceeded => panic!("Supply cap exceeded"),
+            CheckFailure::SameBrand => panic!("Cannot exchange to the same brand"),
+            CheckFailure::FromBrandInactive | CheckFailure::ToBrandInactive => {
+                panic!("One or both brands are not active")
+            }
+            CheckFailure::RoutePaused => panic!("Exchange route is paused"),
+            CheckFailure::InsufficientBalance => panic!("Insufficient balance"),
+            CheckFailure::RewardNotFound => panic!("Reward not found"),
+            CheckFailure::RewardInactive => panic!("Reward is not active"),
+            CheckFailure::RewardOutOfStock => panic!("Reward is out of stock"),
+            CheckFailure::MissingReference => panic!("Issuance reference required"),
+            CheckFailure::CrossProgram => panic!("Brands belong to different programs"),
+            CheckFailure::Blackout => panic!("Brand is in an exchange blackout"),
+            CheckFailure::WindingDown => panic!("Brand is winding down"),
+            CheckFailure::BrandCapReached => panic!("Brand limit reached"),
+            CheckFailure::AdminQuotaReached => panic!("Admin brand quota reached"),
+            CheckFailure::IssuanceLimitExceeded => panic!("Issuance over velocity limit"),
+        }
+    }
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Dry-run `issue_tokens`; returns the first check that would fail, if any
+    pub fn can_issue(env: Env, brand_id: u64, amount: i128) -> Option<CheckFailure> {
+        check_issue(&env, brand_id, amount).err()
+    }
+
+    /// Dry-run `exchange_tokens`; returns the first check that would fail, if any
+    pub fn can_exchange(
+        env: Env,
+        user: Address,
+        from_brand: u64,
+        to_brand: u64,
+        amount: i128,
+    ) -> Option<CheckFailure> {
+        check_exchange(&env, &user, from_brand, to_brand, amount).err()
+    }
+
+    /// Dry-run `redeem_reward`; returns the first check that would fail, if any
+    pub fn can_redeem(env: Env, user: Address, reward_id: u64) -> Option<CheckFailure> {
+        check_redeem(&env, &user, reward_id).err()
+    }
+}
+
+pub(crate) fn check_issue(env: &Env, brand_id: u64, amount: i128) -> Result<(), CheckFailure> {
+    if !brand_exists(env, brand_id) {
+        return Err(CheckFailure::BrandNotFound);
+    }
+    if !is_brand_active(env, brand_id) {
+        return Err(CheckFailure::BrandInactive);
+    }
+    if is_winding_down(env, brand_id) {
+        return Err(CheckFailure::WindingDown);
+    }
+    if amount <= 0 {
+        return Err(CheckFailure::InvalidAmount);
+    }
+    if let Some(cap) = param_value(env, &Param::SupplyCap(brand_id)) {
+        if brand_supply(env, brand_id).saturating_add(Points::from(amount)) > Points::from(cap) {
+            return Err(CheckFailure::SupplyCapExceeded);
+        }
+    }
+    let max_single_issuance = LoyaltyTokenExchange::view_velocity_rules(env.clone(), brand_id)
+        .and_then(|rules| rules.max_single_issuance);
+    if max_single_issuance.is_some_and(|max| amount > max) {
+        return Err(CheckFailure::IssuanceLimitExceeded);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_exchange(
+    env: &Env,
+    user: &Address,
+    from_brand: u64,
+    to_brand: u64,
+    amount: i128,
+) -> Result<(), CheckFailure> {
+    if amount <= 0 {
+        return Err(CheckFailure::InvalidAmount);
+    }
+    if from_brand == to_brand {
+        return Err(CheckFailure::SameBrand);
+    }
+    if !brand_exists(env, from_brand) {
+        return Err(CheckFailure::FromBrandNotFound);
+    }
+    if !brand_exists(env, to_brand) {
+        return Err(CheckFailure::ToBrandNotFound);
+    }
+    if !is_brand_active(env, from_brand) {
+        return Err(CheckFailure::FromBrandInactive);
+    }
+    if !is_brand_active(env, to_brand) {
+        return Err(CheckFailure::ToBrandInactive);
+    }
+    if is_winding_down(env, to_brand) {
+        return Err(CheckFailure::WindingDown);
+    }
+    if !same_program(env, from_brand, to_brand) {
+        return Err(CheckFailure::CrossProgram);
+    }
+    if LoyaltyTokenExchange::is_in_blackout(env.clone(), from_brand)
+        || LoyaltyTokenExchange::is_in_blackout(env.clone(), to_brand)
+    {
+        return Err(CheckFailure::Blackout);
+    }
+    if is_route_paused(env, from_brand, to_brand) {
+        return Err(CheckFailure::RoutePaused);
+    }
+    if read_balance(env, user, from_brand) < amount {
+        return Err(CheckFailure::InsufficientBalance);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_redeem(env: &Env, user: &Address, reward_id: u64) -> Result<(), CheckFailure> {
+    let reward: Reward = env
+        .storage()
+        .instance()
+        .get(&RewardBook::Reward(reward_id))
+        .ok_or(CheckFailure::RewardNotFound)?;
+    if !reward.is_active {
+        return Err(CheckFailure::RewardInactive);
+    }
+    if available_stock(env, &reward) == 0 {
+        return Err(CheckFailure::RewardOutOfStock);
+    }
+    if !is_brand_active(env, reward.brand_id) {
+        return Err(CheckFailure::BrandInactive);
+    }
+    if read_balance(env, user, reward.brand_id) < reward.cost {
+        return Err(CheckFailure::InsufficientBalance);
+    }
+    Ok(())
+}
+
+pub(crate) fn brand_exists(env: &Env, brand_id: u64) -> bool {
+    env.storage().instance().has(&BrandBook::Brand(brand_id))
+}
+
+// Unregistered brands count as inactive
+pub(crate) fn is_brand_active(env: &Env, brand_id: u64) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, Brand>(&BrandBook::Brand(brand_id))
+        .map(|brand| brand.is_active)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_dry_run_reports_failing_check() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let user = Address::generate(&env);
+        let shoes = client.register_brand(&String::from_str(&env, "Shoes"));
+        let socks = client.register_brand(&String::from_str(&env, "Socks"));
+        client.set_brand_admin(&shoes, &Address::generate(&env));
+        let reward_id = client.add_reward(&shoes, &String::from_str(&env, "Laces"), &80, &1);
+        client.issue_tokens(&user, &shoes, &50, &None);
+
+        assert_eq!(client.can_issue(&shoes, &10), None);
+        assert_eq!(
+            client.can_issue(&shoes, &0),
+            Some(CheckFailure::InvalidAmount)
+        );
+        assert_eq!(
+            client.can_issue(&99, &10),
+            Some(CheckFailure::BrandNotFound)
+        );
+
+        assert_eq!(client.can_exchange(&user, &shoes, &socks, &50), None);
+        assert_eq!(
+            client.can_exchange(&user, &shoes, &shoes, &50),
+            Some(CheckFailure::SameBrand)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &shoes, &99, &50),
+            Some(CheckFailure::ToBrandNotFound)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &shoes, &socks, &51),
+            Some(CheckFailure::InsufficientBalance)
+        );
+
+        assert_eq!(
+            client.can_redeem(&user, &reward_id),
+            Some(CheckFailure::InsufficientBalance)
+        );
+        client.issue_tokens(&user, &shoes, &30, &None);
+        assert_eq!(client.can_redeem(&user, &reward_id), None);
+        assert_eq!(
+            client.can_redeem(&user, &42),
+            Some(CheckFailure::RewardNotFound)
+        );
+    }
+
+    #[test]
+    fn test_missing_and_inactive_brands_rejected_distinctly() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let user = Address::generate(&env);
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&cafe, &Address::generate(&env));
+        client.begin_wind_down(&cafe);
+        client.complete_wind_down(&cafe);
+        client.issue_tokens(&user, &hotel, &100, &None);
+
+        assert_eq!(
+            client.can_exchange(&user, &99, &hotel, &50),
+            Some(CheckFailure::FromBrandNotFound)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &hotel, &99, &50),
+            Some(CheckFailure::ToBrandNotFound)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &cafe, &hotel, &50),
+            Some(CheckFailure::FromBrandInactive)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &hotel, &cafe, &50),
+            Some(CheckFailure::ToBrandInactive)
+        );
+        assert_eq!(
+            client.can_issue(&cafe, &10),
+            Some(CheckFailure::BrandInactive)
+        );
+
+        assert!(client.try_exchange_tokens(&user, &hotel, &99, &50).is_err());
+        assert!(client
+            .try_transfer_tokens(&user, &Address::generate(&env), &99, &50, &None)
+            .is_err());
+        assert_eq!(client.view_user_balance(&user, &hotel), 100);
+    }
+}
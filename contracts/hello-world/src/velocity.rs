@@ -0,0 +1,262 @@
+use crate::*;
+use soroban_sdk::Vec;
+
+// Structure to store a brand's velocity limits; unset limits are not enforced
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VelocityRules {
+    pub max_ops_per_hour: Option<u32>,
+    pub max_counterparties_per_day: Option<u32>,
+    // Larger issuances are rejected rather than frozen
+    pub max_single_issuance: Option<i128>,
+}
+
+// Velocity limit a user broke
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VelocityRule {
+    OpsPerHour,
+    CounterpartiesPerDay,
+}
+
+// Structure recording a (user, brand) pair frozen for review
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FrozenPair {
+    pub user: Address,
+    pub brand_id: u64,
+    pub rule: VelocityRule,
+    pub frozen_at: u32,
+}
+
+// Structure counting a user's recent activity with a brand
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VelocityActivity {
+    pub hour_start: u32,
+    pub ops: u32,
+    pub day_start: u32,
+    pub counterparties: Vec<Address>,
+}
+
+// Mapping for velocity rules and the pairs they track
+// Per-pair entries live in persistent storage, one entry per (user, brand)
+#[contracttype]
+pub enum VelocityBook {
+    // Brand -> Rules
+    Rules(u64),
+    // (User, Brand) -> Activity in the current windows
+    Activity(Address, u64),
+    // (User, Brand) -> Freeze pending review
+    Frozen(Address, u64),
+}
+
+// Length of the hourly window (5-second ledgers)
+pub const HOUR_LEDGERS: u32 = 720;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set a brand's velocity rules (brand admin only)
+    /// Issuances over `max_single_issuance` are rejected; every issuance and
+    /// every spend of the brand's points counts against the other limits
+    pub fn set_velocity_rules(env: Env, brand_id: u64, rules: VelocityRules) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .set(&VelocityBook::Rules(brand_id), &rules);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Velocity rules set for brand {}", brand_id);
+    }
+
+    /// Stop checking a brand's velocity rules (brand admin only)
+    pub fn remove_velocity_rules(env: Env, brand_id: u64) {
+        require_brand_admin(&env, brand_id);
+
+        env.storage()
+            .instance()
+            .remove(&VelocityBook::Rules(brand_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Velocity rules removed for brand {}", brand_id);
+    }
+
+    /// View a brand's velocity rules, if any
+    pub fn view_velocity_rules(env: Env, brand_id: u64) -> Option<VelocityRules> {
+        env.storage().instance().get(&VelocityBook::Rules(brand_id))
+    }
+
+    /// View the freeze on a user's activity with a brand, if any
+    pub fn view_frozen_pair(env: Env, user: Address, brand_id: u64) -> Option<FrozenPair> {
+        env.storage()
+            .persistent()
+            .get(&VelocityBook::Frozen(user, brand_id))
+    }
+
+    /// Lift a freeze after review and restart the user's activity windows
+    /// (brand admin only)
+    pub fn unfreeze_pair(env: Env, user: Address, brand_id: u64) {
+        require_brand_admin(&env, brand_id);
+
+        let key = VelocityBook::Frozen(user.clone(), brand_id);
+        if !env.storage().persistent().has(&key) {
+            panic!("Pair is not frozen");
+        }
+        env.storage().persistent().remove(&key);
+        env.storage()
+            .persistent()
+            .remove(&VelocityBook::Activity(user, brand_id));
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Pair unfrozen for brand {}", brand_id);
+    }
+}
+
+// Reject an operation of a frozen pair, then count it against the brand's
+// velocity rules
+// A violation freezes the pair but lets the operation through, since
+// rejecting it would also roll the freeze back
+pub(crate) fn check_velocity(
+    env: &Env,
+    user: &Address,
+    brand_id: u64,
+    counterparty: Option<&Address>,
+) {
+    if LoyaltyTokenExchange::view_frozen_pair(env.clone(), user.clone(), brand_id).is_some() {
+        panic!("Frozen pending review");
+    }
+    let Some(rules) = LoyaltyTokenExchange::view_velocity_rules(env.clone(), brand_id) else {
+        return;
+    };
+
+    let sequence = env.ledger().sequence();
+    let key = VelocityBook::Activity(user.clone(), brand_id);
+    let mut activity = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(VelocityActivity {
+            hour_start: sequence,
+            ops: 0,
+            day_start: sequence,
+            counterparties: Vec::new(env),
+        });
+    if sequence - activity.hour_start >= HOUR_LEDGERS {
+        activity.hour_start = sequence;
+        activity.ops = 0;
+    }
+    if sequence - activity.day_start >= EPOCH_LEDGERS {
+        activity.day_start = sequence;
+        activity.counterparties = Vec::new(env);
+    }
+    activity.ops += 1;
+    if let Some(counterparty) = counterparty {
+        if !activity.counterparties.contains(counterparty) {
+            activity.counterparties.push_back(counterparty.clone());
+        }
+    }
+    env.storage().persistent().set(&key, &activity);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+
+    let broken = if rules.max_ops_per_hour.is_some_and(|max| activity.ops > max) {
+        Some(VelocityRule::OpsPerHour)
+    } else if rules
+        .max_counterparties_per_day
+        .is_some_and(|max| activity.counterparties.len() > max)
+    {
+        Some(VelocityRule::CounterpartiesPerDay)
+    } else {
+        None
+    };
+    if let Some(rule) = broken {
+        let frozen = FrozenPair {
+            user: user.clone(),
+            brand_id,
+            rule,
+            frozen_at: sequence,
+        };
+        let frozen_key = VelocityBook::Frozen(user.clone(), brand_id);
+        env.storage().persistent().set(&frozen_key, &frozen);
+        env.storage()
+            .persistent()
+            .extend_ttl(&frozen_key, 100000, 100000);
+        PairFrozen {
+            brand_id,
+            user: user.clone(),
+            rule,
+        }
+        .publish(env);
+        forward_alert(env, AlertKind::PairFrozen, brand_id, Some(user), 0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_velocity_violations_freeze_pair_until_review() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        client.set_velocity_rules(
+            &brand_id,
+            &VelocityRules {
+                max_ops_per_hour: Some(3),
+                max_counterparties_per_day: Some(1),
+                max_single_issuance: Some(500),
+            },
+        );
+
+        // Spread out, ops stay under the hourly limit
+        let user = Address::generate(&env);
+        for hour in 0..4 {
+            env.ledger().set_sequence_number(hour * HOUR_LEDGERS);
+            client.issue_tokens(&user, &brand_id, &100, &None);
+        }
+        assert_eq!(client.view_frozen_pair(&user, &brand_id), None);
+
+        // A second recipient in a day freezes the sender
+        client.transfer_tokens(&user, &Address::generate(&env), &brand_id, &10, &None);
+        client.transfer_tokens(&user, &Address::generate(&env), &brand_id, &10, &None);
+        let frozen = client.view_frozen_pair(&user, &brand_id).unwrap();
+        assert_eq!(frozen.rule, VelocityRule::CounterpartiesPerDay);
+        assert!(client
+            .try_issue_tokens(&user, &brand_id, &10, &None)
+            .is_err());
+
+        // Oversized issuances are rejected before minting
+        client.unfreeze_pair(&user, &brand_id);
+        assert_eq!(
+            client.can_issue(&brand_id, &600),
+            Some(CheckFailure::IssuanceLimitExceeded)
+        );
+        assert!(client
+            .try_issue_tokens(&user, &brand_id, &600, &None)
+            .is_err());
+        assert_eq!(client.view_user_balance(&user, &brand_id), 380);
+
+        // Spending points through any feature counts as an operation
+        client.issue_tokens(&user, &brand_id, &500, &None);
+        let friend = Address::generate(&env);
+        client.send_gift(&user, &friend, &brand_id, &10, &None, &None);
+        client.send_gift(&user, &friend, &brand_id, &10, &None, &None);
+        assert_eq!(client.view_frozen_pair(&user, &brand_id), None);
+        client.send_gift(&user, &friend, &brand_id, &10, &None, &None);
+        assert_eq!(
+            client.view_frozen_pair(&user, &brand_id).unwrap().rule,
+            VelocityRule::OpsPerHour
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use crate::*;
+use soroban_sdk::{IntoVal, Val};
+
+// Structure recording voting power from a given ledger onwards
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub votes: i128,
+}
+
+// Mapping for delegated voting power
+// Delegations and checkpoints live in persistent storage, one entry per
+// checkpoint, so balance changes of delegators never grow the instance
+#[contracttype]
+pub enum VotesBook {
+    // (Holder, Brand) -> Delegate
+    Delegate(Address, u64),
+    // (Delegate, Brand, index) -> Checkpoint, indexed oldest first
+    Checkpoint(Address, u64, u32),
+    // (Delegate, Brand) -> Number of checkpoints
+    CheckpointCount(Address, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Delegate the voting power of a user's brand balance to `delegate_to`
+    /// Balances only count as votes once delegated; delegate to yourself to vote directly
+    pub fn delegate(env: Env, user: Address, brand_id: u64, delegate_to: Address) {
+        require_user(&env, &user);
+        load_brand(&env, brand_id);
+
+        let balance = read_balance(&env, &user, brand_id);
+        if let Some(previous) = Self::view_delegate(env.clone(), user.clone(), brand_id) {
+            move_votes(&env, &previous, brand_id, -balance);
+        }
+        move_votes(&env, &delegate_to, brand_id, balance);
+
+        write_entry(&env, &VotesBook::Delegate(user, brand_id), &delegate_to);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Voting power for brand {} delegated", brand_id);
+    }
+
+    /// View who a user's voting power is delegated to, if anyone
+    pub fn view_delegate(env: Env, user: Address, brand_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&VotesBook::Delegate(user, brand_id))
+    }
+
+    /// Current voting power of an address for a brand
+    pub fn get_votes(env: Env, account: Address, brand_id: u64) -> i128 {
+        let count = checkpoint_count(&env, &account, brand_id);
+        if count == 0 {
+            return 0;
+        }
+        checkpoint(&env, &account, brand_id, count - 1).votes
+    }
+
+    /// Voting power of an address for a brand as of the end of `ledger`
+    pub fn get_past_votes(env: Env, account: Address, brand_id: u64, ledger: u32) -> i128 {
+        // Binary search for the first checkpoint after `ledger`
+        let mut low = 0;
+        let mut high = checkpoint_count(&env, &account, brand_id);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if checkpoint(&env, &account, brand_id, middle).ledger > ledger {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        if low == 0 {
+            return 0;
+        }
+        checkpoint(&env, &account, brand_id, low - 1).votes
+    }
+}
+
+// Move voting power along with a balance change of a delegating holder
+pub(crate) fn track_votes(env: &Env, user: &Address, brand_id: u64, delta: i128) {
+    if let Some(delegate) = LoyaltyTokenExchange::view_delegate(env.clone(), user.clone(), brand_id)
+    {
+        move_votes(env, &delegate, brand_id, delta);
+    }
+}
+
+fn checkpoint_count(env: &Env, account: &Address, brand_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&VotesBook::CheckpointCount(account.clone(), brand_id))
+        .unwrap_or(0)
+}
+
+fn checkpoint(env: &Env, account: &Address, brand_id: u64, index: u32) -> Checkpoint {
+    env.storage()
+        .persistent()
+        .get(&VotesBook::Checkpoint(account.clone(), brand_id, index))
+        .unwrap_or_else(|| panic!("Checkpoint not found"))
+}
+
+fn move_votes(env: &Env, account: &Address, brand_id: u64, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+
+    let ledger = env.ledger().sequence();
+    let count = checkpoint_count(env, account, brand_id);
+    let latest = (count > 0).then(|| checkpoint(env, account, brand_id, count - 1));
+    let votes = latest
+        .as_ref()
+        .map(|checkpoint| checkpoint.votes)
+        .unwrap_or(0)
+        + delta;
+
+    // Several changes within one ledger share a checkpoint
+    let index = match latest {
+        Some(checkpoint) if checkpoint.ledger == ledger => count - 1,
+        _ => {
+            write_entry(
+                env,
+                &VotesBook::CheckpointCount(account.clone(), brand_id),
+                &(count + 1),
+            );
+            count
+        }
+    };
+    write_entry(
+        env,
+        &VotesBook::Checkpoint(account.clone(), brand_id, index),
+        &Checkpoint { ledger, votes },
+    );
+}
+
+fn write_entry<V: IntoVal<Env, Val>>(env: &Env, key: &VotesBook, value: &V) {
+    env.storage().persistent().set(key, value);
+    env.storage().persistent().extend_ttl(key, 100000, 100000);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_delegated_votes_follow_balances() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let brand_id = client.register_brand(&String::from_str(&env, "Club"));
+        client.issue_tokens(&alice, &brand_id, &100, &None);
+        client.issue_tokens(&bob, &brand_id, &50, &None);
+
+        // Undelegated balances carry no votes
+        assert_eq!(client.get_votes(&bob, &brand_id), 0);
+
+        client.delegate(&alice, &brand_id, &carol);
+        client.delegate(&bob, &brand_id, &bob);
+        assert_eq!(client.get_votes(&carol, &brand_id), 100);
+        assert_eq!(client.get_votes(&bob, &brand_id), 50);
+
+        env.ledger().set_sequence_number(10);
+        client.transfer_tokens(&alice, &bob, &brand_id, &30, &None);
+        assert_eq!(client.get_votes(&carol, &brand_id), 70);
+        assert_eq!(client.get_votes(&bob, &brand_id), 80);
+
+        env.ledger().set_sequence_number(20);
+        client.delegate(&alice, &brand_id, &bob);
+        assert_eq!(client.get_votes(&carol, &brand_id), 0);
+        assert_eq!(client.get_votes(&bob, &brand_id), 150);
+
+        assert_eq!(client.get_past_votes(&carol, &brand_id, &5), 100);
+        assert_eq!(client.get_past_votes(&carol, &brand_id, &15), 70);
+        assert_eq!(client.get_past_votes(&bob, &brand_id, &15), 80);
+        assert_eq!(client.get_past_votes(&bob, &brand_id, &25), 150);
+        assert_eq!(client.get_past_votes(&carol, &brand_id, &20), 0);
+
+        // Checkpoints are kept out of the contract instance
+        env.as_contract(&contract_id, || {
+            let key = VotesBook::Checkpoint(carol.clone(), brand_id, 0);
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+    }
+}
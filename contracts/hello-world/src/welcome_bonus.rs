@@ -0,0 +1,193 @@
+use crate::*;
+
+// Structure to store a brand's welcome bonus and the budget funding it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WelcomeBonus {
+    pub bonus: i128,
+    // Points set aside from the brand treasury, left to grant
+    pub budget: i128,
+    pub granted: u32,
+}
+
+// Mapping for welcome bonuses
+#[contracttype]
+pub enum WelcomeBook {
+    // Brand -> Welcome bonus
+    Bonus(u64),
+    // (User, Brand) -> Whether the user enrolled with the brand, kept in
+    // persistent storage
+    Enrolled(Address, u64),
+}
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Set the bonus credited to users enrolling with a brand (brand admin only)
+    /// A bonus of 0 turns it off and returns the remaining budget to the
+    /// brand treasury
+    pub fn set_welcome_bonus(env: Env, brand_id: u64, bonus: i128) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if bonus < 0 {
+            panic!("Bonus cannot be negative");
+        }
+
+        let mut welcome = load_welcome(&env, brand_id);
+        if bonus == 0 && welcome.budget > 0 {
+            let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+                .unwrap_or_else(|| panic!("Brand treasury not set"));
+            release(&env, &treasury, brand_id, welcome.budget);
+            welcome.budget = 0;
+        }
+        welcome.bonus = bonus;
+        env.storage()
+            .instance()
+            .set(&WelcomeBook::Bonus(brand_id), &welcome);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Welcome bonus for brand {} set to {}",
+            brand_id,
+            bonus
+        );
+    }
+
+    /// Move points from the brand treasury into the welcome bonus budget
+    /// (brand admin only)
+    pub fn fund_welcome_budget(env: Env, brand_id: u64, amount: i128) {
+        load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if amount <= 0 {
+            panic!("Amount must be positive");
+        }
+        let treasury = Self::view_brand_treasury(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand treasury not set"));
+        escrow(&env, &treasury, brand_id, amount);
+
+        let mut welcome = load_welcome(&env, brand_id);
+        welcome.budget += amount;
+        env.storage()
+            .instance()
+            .set(&WelcomeBook::Bonus(brand_id), &welcome);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(&env, "✅ Welcome budget of brand {} funded", brand_id);
+    }
+
+    /// Enroll a user with a brand, crediting the welcome bonus while the
+    /// budget lasts
+    /// Returns the bonus credited, 0 when none was
+    pub fn enroll(env: Env, user: Address, brand_id: u64) -> i128 {
+        require_user(&env, &user);
+        let bonus = load_welcome(&env, brand_id).bonus;
+        enroll_user(&env, user, brand_id, bonus)
+    }
+
+    /// View whether a user enrolled with a brand
+    pub fn is_enrolled(env: Env, user: Address, brand_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&WelcomeBook::Enrolled(user, brand_id))
+            .unwrap_or(false)
+    }
+
+    /// View a brand's welcome bonus and its remaining budget
+    pub fn view_welcome_bonus(env: Env, brand_id: u64) -> WelcomeBonus {
+        load_welcome(&env, brand_id)
+    }
+}
+
+// Enroll a user with a brand, crediting `bonus` from the welcome budget while
+// it lasts
+pub(crate) fn enroll_user(env: &Env, user: Address, brand_id: u64, bonus: i128) -> i128 {
+    if !is_brand_active(env, brand_id) {
+        panic!("Brand is not active");
+    }
+    if LoyaltyTokenExchange::is_enrolled(env.clone(), user.clone(), brand_id) {
+        panic!("Already enrolled");
+    }
+
+    let key = WelcomeBook::Enrolled(user.clone(), brand_id);
+    env.storage().persistent().set(&key, &true);
+    env.storage().persistent().extend_ttl(&key, 100000, 100000);
+    let mut welcome = load_welcome(env, brand_id);
+    let bonus = if bonus > 0 && welcome.budget >= bonus {
+        bonus
+    } else {
+        0
+    };
+    if bonus > 0 {
+        release(env, &user, brand_id, bonus);
+        welcome.budget -= bonus;
+        welcome.granted += 1;
+        env.storage()
+            .instance()
+            .set(&WelcomeBook::Bonus(brand_id), &welcome);
+
+        WelcomeBonusGranted {
+            brand_id,
+            user,
+            bonus,
+            budget_left: welcome.budget,
+        }
+        .publish(env);
+    }
+    env.storage().instance().extend_ttl(100000, 100000);
+
+    log!(env, "✅ Enrolled with brand {}", brand_id);
+    bonus
+}
+
+fn load_welcome(env: &Env, brand_id: u64) -> WelcomeBonus {
+    env.storage()
+        .instance()
+        .get(&WelcomeBook::Bonus(brand_id))
+        .unwrap_or(WelcomeBonus {
+            bonus: 0,
+            budget: 0,
+            granted: 0,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_welcome_bonus_granted_once_while_budget_lasts() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let brand_id = client.register_brand(&String::from_str(&env, "Cafe"));
+        client.set_brand_admin(&brand_id, &Address::generate(&env));
+        let treasury = Address::generate(&env);
+        client.set_brand_treasury(&brand_id, &treasury);
+        client.issue_tokens(&treasury, &brand_id, &250, &None);
+        client.set_welcome_bonus(&brand_id, &100);
+        client.fund_welcome_budget(&brand_id, &250);
+
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let third = Address::generate(&env);
+        assert_eq!(client.enroll(&first, &brand_id), 100);
+        assert!(client.try_enroll(&first, &brand_id).is_err());
+        assert_eq!(client.enroll(&second, &brand_id), 100);
+        assert_eq!(client.enroll(&third, &brand_id), 0);
+        assert!(client.is_enrolled(&third, &brand_id));
+        env.as_contract(&contract_id, || {
+            let key = WelcomeBook::Enrolled(third.clone(), brand_id);
+            assert!(env.storage().persistent().has(&key));
+            assert!(!env.storage().instance().has(&key));
+        });
+        assert_eq!(client.view_user_balance(&first, &brand_id), 100);
+
+        client.set_welcome_bonus(&brand_id, &0);
+        assert_eq!(client.view_user_balance(&treasury, &brand_id), 50);
+        assert_eq!(client.view_welcome_bonus(&brand_id).granted, 2);
+    }
+}
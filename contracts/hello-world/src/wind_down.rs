@@ -0,0 +1,176 @@
+use crate::*;
+
+// Structure to store a brand's exit: the liabilities outstanding when it
+// began and how long holders have to redeem
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WindDown {
+    pub brand_id: u64,
+    // Circulating supply plus points held in escrow when the wind-down began
+    pub liabilities: i128,
+    pub started_at: u32,
+    // Last ledger of the redemption window
+    pub redeem_until: u32,
+    // Set once the brand is deactivated
+    pub completed_at: Option<u32>,
+    // Liabilities still outstanding at completion, written off
+    pub written_off: i128,
+}
+
+// Mapping for wind-downs: Brand -> Wind-down
+#[contracttype]
+pub enum WindDownBook {
+    WindDown(u64),
+}
+
+// Ledgers holders have to redeem once a wind-down begins (about 90 days)
+pub const WIND_DOWN_LEDGERS: u32 = 90 * EPOCH_LEDGERS;
+
+// Share of the snapshot liabilities, in basis points, below which a wind-down
+// can complete before the window ends
+pub const WIND_DOWN_THRESHOLD_BPS: u32 = 100;
+
+#[contractimpl]
+impl LoyaltyTokenExchange {
+    /// Begin winding a brand down (brand admin only)
+    /// Snapshots the outstanding liabilities and stops issuance and exchanges
+    /// into the brand; holders can still redeem and exchange out until the
+    /// window ends
+    pub fn begin_wind_down(env: Env, brand_id: u64) -> WindDown {
+        let brand = load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        if !brand.is_active {
+            panic!("Brand is not active");
+        }
+        if is_winding_down(&env, brand_id) {
+            panic!("Brand is already winding down");
+        }
+
+        let sequence = env.ledger().sequence();
+        let wind_down = WindDown {
+            brand_id,
+            liabilities: Self::view_brand_liabilities(env.clone(), brand_id),
+            started_at: sequence,
+            redeem_until: sequence + WIND_DOWN_LEDGERS,
+            completed_at: None,
+            written_off: 0,
+        };
+        env.storage()
+            .instance()
+            .set(&WindDownBook::WindDown(brand_id), &wind_down);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} winding down with {} outstanding",
+            brand_id,
+            wind_down.liabilities
+        );
+        wind_down
+    }
+
+    /// Deactivate a winding-down brand once its liabilities fall below
+    /// WIND_DOWN_THRESHOLD_BPS of the snapshot or the redemption window ends
+    /// (brand admin only)
+    /// Returns the liabilities written off
+    pub fn complete_wind_down(env: Env, brand_id: u64) -> i128 {
+        let mut brand = load_brand(&env, brand_id);
+        require_brand_admin(&env, brand_id);
+        let mut wind_down = Self::view_wind_down(env.clone(), brand_id)
+            .unwrap_or_else(|| panic!("Brand is not winding down"));
+        if wind_down.completed_at.is_some() {
+            panic!("Wind-down already completed");
+        }
+
+        let sequence = env.ledger().sequence();
+        let outstanding = Self::view_brand_liabilities(env.clone(), brand_id);
+        let threshold = wind_down.liabilities * WIND_DOWN_THRESHOLD_BPS as i128 / RATE_DENOMINATOR;
+        if outstanding > threshold && sequence <= wind_down.redeem_until {
+            panic!("Liabilities still outstanding");
+        }
+
+        brand.is_active = false;
+        env.storage()
+            .instance()
+            .set(&BrandBook::Brand(brand_id), &brand);
+        wind_down.completed_at = Some(sequence);
+        wind_down.written_off = outstanding;
+        env.storage()
+            .instance()
+            .set(&WindDownBook::WindDown(brand_id), &wind_down);
+        env.storage().instance().extend_ttl(100000, 100000);
+
+        log!(
+            &env,
+            "✅ Brand {} wound down, {} written off",
+            brand_id,
+            outstanding
+        );
+        outstanding
+    }
+
+    /// View a brand's wind-down, if one began
+    pub fn view_wind_down(env: Env, brand_id: u64) -> Option<WindDown> {
+        env.storage()
+            .instance()
+            .get(&WindDownBook::WindDown(brand_id))
+    }
+}
+
+// Whether a brand began winding down; issuance and exchanges into it are closed
+pub(crate) fn is_winding_down(env: &Env, brand_id: u64) -> bool {
+    env.storage()
+        .instance()
+        .has(&WindDownBook::WindDown(brand_id))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger as _},
+        Address, Env, String,
+    };
+
+    #[test]
+    fn test_wind_down_closes_issuance_until_liabilities_clear() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(LoyaltyTokenExchange, ());
+        let client = LoyaltyTokenExchangeClient::new(&env, &contract_id);
+        client.set_platform_admin(&Address::generate(&env));
+
+        let cafe = client.register_brand(&String::from_str(&env, "Cafe"));
+        let hotel = client.register_brand(&String::from_str(&env, "Hotel"));
+        client.set_brand_admin(&cafe, &Address::generate(&env));
+        let user = Address::generate(&env);
+        client.issue_tokens(&user, &cafe, &1000, &None);
+        client.issue_tokens(&user, &hotel, &1000, &None);
+
+        // Points held in escrow are still owed
+        let friend = Address::generate(&env);
+        let gift_id = client.send_gift(&user, &friend, &cafe, &200, &Some(100), &None);
+        let wind_down = client.begin_wind_down(&cafe);
+        assert_eq!(wind_down.liabilities, 1000);
+        assert!(client.try_begin_wind_down(&cafe).is_err());
+        assert_eq!(
+            client.can_issue(&cafe, &100),
+            Some(CheckFailure::WindingDown)
+        );
+        assert_eq!(
+            client.can_exchange(&user, &hotel, &cafe, &100),
+            Some(CheckFailure::WindingDown)
+        );
+        assert!(client.try_complete_wind_down(&cafe).is_err());
+
+        client.exchange_tokens(&user, &cafe, &hotel, &795);
+        assert!(client.try_complete_wind_down(&cafe).is_err());
+
+        env.ledger().set_sequence_number(100);
+        client.claim_gift(&friend, &gift_id);
+        client.exchange_tokens(&friend, &cafe, &hotel, &200);
+        assert_eq!(client.complete_wind_down(&cafe), 5);
+        assert!(!client.view_brand(&cafe).is_active);
+        assert_eq!(client.view_wind_down(&cafe).unwrap().written_off, 5);
+    }
+}
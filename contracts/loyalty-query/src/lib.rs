@@ -0,0 +1,359 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
+    Symbol, Vec,
+};
+
+// Read-only companion of the loyalty exchange contract
+// Aggregations that walk every brand live here and read the exchange through
+// its views, so the exchange itself stays small
+
+// Brand information as returned by the exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Brand {
+    pub brand_id: u64,
+    pub brand_name: String,
+    pub is_active: bool,
+}
+
+// Supply figures of a brand as returned by the exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupplyStats {
+    pub brand_id: u64,
+    pub circulating: i128,
+    pub burned: i128,
+    pub burn_bps: u32,
+}
+
+// Activity totals of an epoch as returned by the exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochStats {
+    pub issued: i128,
+    pub redeemed: i128,
+    pub exchanged_out: i128,
+    pub exchanged_in: i128,
+}
+
+// Who bears the exchange fee on a route, as returned by the exchange
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeePayer {
+    Input,
+    Output,
+    Treasury,
+}
+
+// One-hop exchange route as returned by the exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RouteInfo {
+    pub from_brand: u64,
+    pub to_brand: u64,
+    pub to_brand_name: String,
+    pub rate: u32,
+    pub fee_bps: u32,
+    pub fee_payer: FeePayer,
+    pub burn_bps: u32,
+    pub paused: bool,
+}
+
+// Franchise as returned by the exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Franchise {
+    pub franchise_id: u64,
+    pub parent_id: u64,
+    pub name: String,
+    pub owner: Address,
+    pub budget: i128,
+    pub issued: i128,
+}
+
+// Supply of a brand and its sub-brands as returned by the exchange
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramSupply {
+    pub parent_id: u64,
+    pub parent_supply: i128,
+    pub sub_brand_count: u32,
+    pub total_supply: i128,
+}
+
+// Views of the exchange the query contract reads from
+#[contractclient(name = "ExchangeClient")]
+pub trait ExchangeViews {
+    fn get_brand_count(env: Env) -> u64;
+    fn view_brand(env: Env, brand_id: u64) -> Brand;
+    fn view_brand_program(env: Env, brand_id: u64) -> Option<u64>;
+    fn view_balances(env: Env, user: Address) -> Map<u64, i128>;
+    fn view_reference_value(env: Env, brand_id: u64) -> Option<i128>;
+    fn view_supply_stats(env: Env, brand_id: u64) -> SupplyStats;
+    fn epoch_stats(env: Env, brand_id: u64, epoch: u32) -> EpochStats;
+    fn view_route(env: Env, from_brand: u64, to_brand: u64) -> RouteInfo;
+    fn view_franchises(env: Env, parent_id: u64) -> Vec<u64>;
+    fn view_franchise(env: Env, franchise_id: u64) -> Franchise;
+    fn view_franchise_locations(env: Env, franchise_id: u64) -> Vec<Address>;
+    fn view_program_supply(env: Env, parent_id: u64) -> ProgramSupply;
+}
+
+// One brand holding of a user
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortfolioEntry {
+    pub brand_id: u64,
+    pub brand_name: String,
+    pub balance: i128,
+    // Points per unit of reference value, if the brand publishes one
+    pub reference_value: Option<i128>,
+}
+
+// Structure reporting a franchisor's activity rolled up over its franchises
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FranchiseStats {
+    pub parent_id: u64,
+    pub franchise_count: u32,
+    pub location_count: u32,
+    // Points issued by all franchise locations
+    pub franchise_issued: i128,
+    // Supply of the franchisor and its sub-brands
+    pub total_supply: i128,
+}
+
+// Address of the exchange contract read from
+const EXCHANGE: Symbol = symbol_short!("EXCHANGE");
+
+#[contract]
+pub struct LoyaltyQuery;
+
+#[contractimpl]
+impl LoyaltyQuery {
+    pub fn __constructor(env: Env, exchange: Address) {
+        env.storage().instance().set(&EXCHANGE, &exchange);
+    }
+
+    /// View the exchange contract this contract reads from
+    pub fn view_exchange(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&EXCHANGE)
+            .unwrap_or_else(|| panic!("Exchange not set"))
+    }
+
+    /// View every brand holding of a user with the brand's name and reference value
+    pub fn portfolio(env: Env, user: Address) -> Vec<PortfolioEntry> {
+        let exchange = exchange_client(&env);
+        let mut entries = Vec::new(&env);
+        for (brand_id, balance) in exchange.view_balances(&user).iter() {
+            entries.push_back(PortfolioEntry {
+                brand_id,
+                brand_name: exchange.view_brand(&brand_id).brand_name,
+                balance,
+                reference_value: exchange.view_reference_value(&brand_id),
+            });
+        }
+        entries
+    }
+
+    /// View the `limit` active brands with the largest circulating supply,
+    /// largest first
+    pub fn supply_leaderboard(env: Env, limit: u32) -> Vec<SupplyStats> {
+        let exchange = exchange_client(&env);
+        let mut leaders: Vec<SupplyStats> = Vec::new(&env);
+        for brand_id in 1..=exchange.get_brand_count() {
+            if !exchange.view_brand(&brand_id).is_active {
+                continue;
+            }
+            let stats = exchange.view_supply_stats(&brand_id);
+            let mut index = leaders.len();
+            while index > 0 && leaders.get_unchecked(index - 1).circulating < stats.circulating {
+                index -= 1;
+            }
+            if index < limit {
+                leaders.insert(index, stats);
+                if leaders.len() > limit {
+                    leaders.pop_back();
+                }
+            }
+        }
+        leaders
+    }
+
+    /// List every brand reachable from `brand_id` in a single exchange,
+    /// with the rate and fee currently applied to the route
+    pub fn routes_from(env: Env, brand_id: u64) -> Vec<RouteInfo> {
+        let exchange = exchange_client(&env);
+        let mut routes = Vec::new(&env);
+        if !exchange.view_brand(&brand_id).is_active {
+            return routes;
+        }
+
+        let program = exchange.view_brand_program(&brand_id);
+        for to_brand in 1..=exchange.get_brand_count() {
+            if to_brand == brand_id
+                || exchange.view_brand_program(&to_brand) != program
+                || !exchange.view_brand(&to_brand).is_active
+            {
+                continue;
+            }
+            routes.push_back(exchange.view_route(&brand_id, &to_brand));
+        }
+        routes
+    }
+
+    /// View a franchisor's issuance and supply rolled up over its franchises
+    pub fn franchise_stats(env: Env, parent_id: u64) -> FranchiseStats {
+        let exchange = exchange_client(&env);
+        let franchises = exchange.view_franchises(&parent_id);
+
+        let mut location_count = 0;
+        let mut franchise_issued = 0;
+        for franchise_id in franchises.iter() {
+            location_count += exchange.view_franchise_locations(&franchise_id).len();
+            franchise_issued += exchange.view_franchise(&franchise_id).issued;
+        }
+
+        FranchiseStats {
+            parent_id,
+            franchise_count: franchises.len(),
+            location_count,
+            franchise_issued,
+            total_supply: exchange.view_program_supply(&parent_id).total_supply,
+        }
+    }
+
+    /// View the activity totals of an epoch summed over every brand
+    pub fn epoch_totals(env: Env, epoch: u32) -> EpochStats {
+        let exchange = exchange_client(&env);
+        let mut totals = EpochStats {
+            issued: 0,
+            redeemed: 0,
+            exchanged_out: 0,
+            exchanged_in: 0,
+        };
+        for brand_id in 1..=exchange.get_brand_count() {
+            let stats = exchange.epoch_stats(&brand_id, &epoch);
+            totals.issued += stats.issued;
+            totals.redeemed += stats.redeemed;
+            totals.exchanged_out += stats.exchanged_out;
+            totals.exchanged_in += stats.exchanged_in;
+        }
+        totals
+    }
+}
+
+fn exchange_client(env: &Env) -> ExchangeClient<'_> {
+    ExchangeClient::new(env, &LoyaltyQuery::view_exchange(env.clone()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::*;
+    use hello_world::{LoyaltyTokenExchange, LoyaltyTokenExchangeClient};
+    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+    #[test]
+    fn test_aggregates_read_from_exchange() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let exchange_id = env.register(LoyaltyTokenExchange, ());
+        let exchange = LoyaltyTokenExchangeClient::new(&env, &exchange_id);
+        let query_id = env.register(LoyaltyQuery, (&exchange_id,));
+        let query = LoyaltyQueryClient::new(&env, &query_id);
+
+        let hotel = exchange.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = exchange.register_brand(&String::from_str(&env, "Airline"));
+        let cafe = exchange.register_brand(&String::from_str(&env, "Cafe"));
+        let user = Address::generate(&env);
+        exchange.issue_tokens(&user, &hotel, &100, &None);
+        exchange.issue_tokens(&user, &airline, &300, &None);
+        exchange.issue_tokens(&Address::generate(&env), &cafe, &200, &None);
+
+        let portfolio = query.portfolio(&user);
+        assert_eq!(portfolio.len(), 2);
+        assert_eq!(
+            portfolio.get(1).unwrap().brand_name,
+            String::from_str(&env, "Airline")
+        );
+        assert_eq!(portfolio.get(1).unwrap().balance, 300);
+
+        let leaders = query.supply_leaderboard(&2);
+        assert_eq!(leaders.len(), 2);
+        assert_eq!(leaders.get(0).unwrap().brand_id, airline);
+        assert_eq!(leaders.get(1).unwrap().brand_id, cafe);
+
+        assert_eq!(query.epoch_totals(&0).issued, 600);
+    }
+
+    #[test]
+    fn test_routes_skip_self_and_inactive_brands() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let exchange_id = env.register(LoyaltyTokenExchange, ());
+        let exchange = LoyaltyTokenExchangeClient::new(&env, &exchange_id);
+        let query_id = env.register(LoyaltyQuery, (&exchange_id,));
+        let query = LoyaltyQueryClient::new(&env, &query_id);
+        exchange.set_platform_admin(&Address::generate(&env));
+
+        let hotel = exchange.register_brand(&String::from_str(&env, "Hotel"));
+        let airline = exchange.register_brand(&String::from_str(&env, "Airline"));
+        let old_airline = exchange.register_brand(&String::from_str(&env, "Old Airline"));
+        exchange.merge_brands(&old_airline, &airline, &10000);
+
+        let routes = query.routes_from(&hotel);
+        assert_eq!(routes.len(), 1);
+
+        let route = routes.get(0).unwrap();
+        assert_eq!(route.to_brand, airline);
+        assert_eq!(route.to_brand_name, String::from_str(&env, "Airline"));
+        assert_eq!(route.rate, 10000);
+        assert_eq!(route.fee_bps, 0);
+        assert_eq!(route.fee_payer, FeePayer::Input);
+
+        assert_eq!(query.routes_from(&old_airline).len(), 0);
+    }
+
+    #[test]
+    fn test_franchise_stats_roll_up_to_parent() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let exchange_id = env.register(LoyaltyTokenExchange, ());
+        let exchange = LoyaltyTokenExchangeClient::new(&env, &exchange_id);
+        let query_id = env.register(LoyaltyQuery, (&exchange_id,));
+        let query = LoyaltyQueryClient::new(&env, &query_id);
+        exchange.set_platform_admin(&Address::generate(&env));
+
+        let parent_id = exchange.register_brand(&String::from_str(&env, "Burger Co"));
+        exchange.set_brand_admin(&parent_id, &Address::generate(&env));
+        let downtown = exchange.add_franchise(
+            &parent_id,
+            &String::from_str(&env, "Downtown"),
+            &Address::generate(&env),
+            &500,
+        );
+        let airport = exchange.add_franchise(
+            &parent_id,
+            &String::from_str(&env, "Airport"),
+            &Address::generate(&env),
+            &500,
+        );
+        let till = Address::generate(&env);
+        let kiosk = Address::generate(&env);
+        exchange.set_franchise_location(&downtown, &till, &400);
+        exchange.set_franchise_location(&downtown, &kiosk, &400);
+        exchange.set_franchise_location(&airport, &kiosk, &100);
+
+        let user = Address::generate(&env);
+        exchange.issue_as_franchise(&till, &downtown, &user, &300, &None);
+        exchange.issue_as_franchise(&kiosk, &airport, &user, &100, &None);
+
+        let stats = query.franchise_stats(&parent_id);
+        assert_eq!(stats.franchise_count, 2);
+        assert_eq!(stats.location_count, 3);
+        assert_eq!(stats.franchise_issued, 400);
+        assert_eq!(stats.total_supply, 400);
+    }
+}